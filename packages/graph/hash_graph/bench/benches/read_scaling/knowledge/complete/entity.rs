@@ -170,6 +170,7 @@ pub fn bench_get_entity_by_id(
                             Some(TimespanBound::Unbounded),
                         ),
                     }),
+                    draft_id: None,
                 })
                 .await
                 .expect("failed to read entity from store");
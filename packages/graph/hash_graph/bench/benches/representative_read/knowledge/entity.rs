@@ -57,6 +57,7 @@ pub fn bench_get_entity_by_id(
                             Some(TimespanBound::Unbounded),
                         ),
                     }),
+                    draft_id: None,
                 })
                 .await
                 .expect("failed to read entity from store");
@@ -91,6 +92,7 @@ pub fn bench_get_entities_by_property(
                         Some(TimespanBound::Unbounded),
                     ),
                 }),
+                draft_id: None,
             })
             .await
             .expect("failed to read entity from store");
@@ -125,6 +127,7 @@ pub fn bench_get_link_by_target_by_property(
                         Some(TimespanBound::Unbounded),
                     ),
                 }),
+                draft_id: None,
             })
             .await
             .expect("failed to read entity from store");
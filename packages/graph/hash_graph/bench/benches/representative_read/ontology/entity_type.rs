@@ -39,6 +39,7 @@ pub fn bench_get_entity_type_by_id(
                             Some(TimespanBound::Unbounded),
                         ),
                     }),
+                    draft_id: None,
                 })
                 .await
                 .expect("failed to read entity type from store");
@@ -1,6 +1,13 @@
-use clap::{Args as _, Command, Parser};
+use std::path::PathBuf;
+
+use clap::{Args as _, Command, Parser, Subcommand, ValueEnum};
 use clap_complete::Shell;
-use graph::{logging::LoggingArgs, store::DatabaseConnectionInfo};
+use graph::{
+    codegen::Language,
+    identifier::time::Timestamp,
+    logging::LoggingArgs,
+    store::{webhook::WebhookRetryPolicy, DatabaseConnectionInfo, QueryLimits, ReadReplicaConfig},
+};
 use regex::Regex;
 
 /// Arguments passed to the program.
@@ -10,6 +17,18 @@ pub struct Args {
     #[clap(flatten)]
     pub db_info: DatabaseConnectionInfo,
 
+    #[clap(flatten)]
+    pub read_replica_config: ReadReplicaConfig,
+
+    #[clap(flatten)]
+    pub query_limits: QueryLimits,
+
+    /// The maximum number of ontology types per kind kept in the in-process ontology cache.
+    ///
+    /// A value of `0` disables the cache.
+    #[clap(long, default_value_t = 10_000, env = "HASH_GRAPH_ONTOLOGY_CACHE_SIZE")]
+    pub ontology_cache_size: usize,
+
     #[clap(flatten)]
     pub log_config: LoggingArgs,
 
@@ -41,6 +60,27 @@ pub struct Args {
     )]
     pub allowed_url_domain: Regex,
 
+    /// A domain from which ontology types referenced by new types are fetched if they are not in
+    /// the store yet. Subdomains are allowed as well. Can be passed multiple times.
+    ///
+    /// If no domain is provided, missing types are not fetched.
+    #[clap(
+        long = "type-fetcher-allowed-domain",
+        env = "HASH_GRAPH_TYPE_FETCHER_ALLOWED_DOMAINS",
+        value_delimiter = ','
+    )]
+    pub type_fetcher_allowed_domains: Vec<String>,
+
+    #[clap(flatten)]
+    pub webhook_retry_policy: WebhookRetryPolicy,
+
+    /// Deliver webhooks to targets resolving to loopback, private or link-local addresses.
+    ///
+    /// This should only be enabled if every webhook receiver is trusted, e.g. for local
+    /// development.
+    #[clap(long, env = "HASH_GRAPH_WEBHOOK_ALLOW_PRIVATE_TARGETS")]
+    pub webhook_allow_private_targets: bool,
+
     /// The OpenTelemetry protocol endpoint for sending traces.
     #[clap(long, default_value = None, env = "HASH_GRAPH_OTLP_ENDPOINT")]
     pub otlp_endpoint: Option<String>,
@@ -48,6 +88,116 @@ pub struct Args {
     /// Generate a completion script for the given shell and outputs it to stdout.
     #[clap(long, value_enum, exclusive = true)]
     generate_completion: Option<Shell>,
+
+    #[clap(subcommand)]
+    pub command: Option<GraphCommand>,
+}
+
+/// Commands, which are run instead of starting the server.
+#[derive(Debug, Subcommand)]
+pub enum GraphCommand {
+    /// Generate typed bindings for entity types.
+    Codegen(CodegenArgs),
+    /// Query the graph, either through a running server or directly from the database.
+    Query(QueryArgs),
+}
+
+#[derive(Debug, clap::Args)]
+pub struct CodegenArgs {
+    /// The URI of an entity type to generate bindings for. Can be passed multiple times.
+    #[clap(long = "entity-type", required = true)]
+    pub entity_type_ids: Vec<String>,
+
+    /// The language of the generated bindings.
+    #[clap(long, value_enum)]
+    pub language: Language,
+
+    /// A JSON file containing an array of ontology type schemas to read the types from.
+    ///
+    /// If not provided, the types are read from the database.
+    #[clap(long)]
+    pub snapshot: Option<PathBuf>,
+
+    /// The file to write the bindings to. If not provided, the bindings are written to stdout.
+    #[clap(long)]
+    pub output: Option<PathBuf>,
+}
+
+/// The kind of records to query.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum QueryKind {
+    Entities,
+    EntityTypes,
+    PropertyTypes,
+    DataTypes,
+}
+
+/// The time axis, which is not pinned when querying.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum VariableAxis {
+    DecisionTime,
+    TransactionTime,
+}
+
+/// The format the result of a query is printed in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// One row per root of the subgraph.
+    Table,
+    /// The subgraph as returned by the REST API.
+    Json,
+    /// The subgraph as a Graphviz `digraph`.
+    Graphviz,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct QueryArgs {
+    /// The kind of records to query.
+    #[clap(value_enum)]
+    pub kind: QueryKind,
+
+    /// The filter the records have to match, e.g.
+    /// `type.baseUri = "https://example.com/@alice/types/entity-type/person/" and archived = false`.
+    ///
+    /// Comparisons are written as `path = value` or `path != value` and can be combined with
+    /// `and`, `or`, `not`, and parentheses. If not provided, all records are returned.
+    #[clap(long)]
+    pub filter: Option<String>,
+
+    /// How many ontology edges (e.g. `inheritsFrom`, `isOfType`) are followed from the roots.
+    #[clap(long, default_value_t = 0)]
+    pub ontology_depth: u8,
+
+    /// How many link edges (`hasLeftEntity`, `hasRightEntity`) are followed from the roots in
+    /// each direction.
+    #[clap(long, default_value_t = 0)]
+    pub link_depth: u8,
+
+    /// The time axis, which is not pinned.
+    #[clap(long, value_enum, default_value_t = VariableAxis::DecisionTime)]
+    pub variable_axis: VariableAxis,
+
+    /// The point in time the pinned axis is set to. Defaults to now.
+    #[clap(long)]
+    pub pinned_time: Option<Timestamp<()>>,
+
+    /// The start of the interval on the variable axis. Unbounded if not provided.
+    #[clap(long)]
+    pub from: Option<Timestamp<()>>,
+
+    /// The end of the interval on the variable axis. Unbounded if not provided.
+    #[clap(long)]
+    pub to: Option<Timestamp<()>>,
+
+    /// The address of a running Graph server, e.g. `http://127.0.0.1:4000`.
+    ///
+    /// If not provided, the records are read from the database directly.
+    #[clap(long)]
+    pub server: Option<String>,
+
+    /// The format the result is printed in.
+    #[clap(long, value_enum, default_value_t = OutputFormat::Table)]
+    pub format: OutputFormat,
 }
 
 impl Args {
@@ -2,19 +2,31 @@
 #![forbid(unsafe_code)]
 
 mod args;
+mod query;
 
-use std::{collections::HashMap, fmt, net::SocketAddr, sync::Arc};
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    io::{self, Write},
+    net::SocketAddr,
+    str::FromStr,
+    sync::Arc,
+};
 
 use error_stack::{Context, IntoReport, Result, ResultExt};
+#[cfg(feature = "sqlite")]
+use graph::store::SqliteStorePool;
 use graph::{
     api::rest::rest_api_router,
+    codegen::OntologyTypeSet,
     identifier::account::AccountId,
     logging::init_logger,
     ontology::domain_validator::DomainValidator,
     provenance::{OwnedById, UpdatedById},
     store::{
-        AccountStore, BaseUriAlreadyExists, DataTypeStore, EntityTypeStore, PostgresStorePool,
-        StorePool,
+        fetcher::OntologyTypeFetcher, webhook::WebhookDispatcher, AccountStore,
+        BaseUriAlreadyExists, DataTypeStore, DatabaseConnectionInfo, DatabaseType, EntityTypeStore,
+        OntologyCache, PostgresStorePool, StorePool,
     },
 };
 use serde_json::json;
@@ -25,7 +37,7 @@ use type_system::{
 };
 use uuid::Uuid;
 
-use crate::args::Args;
+use crate::args::{Args, CodegenArgs, GraphCommand};
 
 #[derive(Debug)]
 pub struct GraphError;
@@ -42,7 +54,11 @@ impl fmt::Display for GraphError {
 /// This will include things that are mocks or stubs to make up for missing pieces of infrastructure
 /// that haven't been created yet.
 #[expect(clippy::too_many_lines, reason = "temporary solution")]
-async fn stop_gap_setup(pool: &PostgresStorePool<NoTls>) -> Result<(), GraphError> {
+async fn stop_gap_setup<P>(pool: &P) -> Result<(), GraphError>
+where
+    P: StorePool,
+    P::Error: Context,
+{
     // TODO: how do we make these URIs compliant
     let text = DataType::new(
         VersionedUri::new(
@@ -214,6 +230,76 @@ async fn stop_gap_setup(pool: &PostgresStorePool<NoTls>) -> Result<(), GraphErro
     Ok(())
 }
 
+/// Generates bindings for the entity types specified in `args`.
+async fn codegen(db_info: &DatabaseConnectionInfo, args: &CodegenArgs) -> Result<(), GraphError> {
+    let entity_type_ids = args
+        .entity_type_ids
+        .iter()
+        .map(|uri| {
+            VersionedUri::from_str(uri)
+                .into_report()
+                .change_context(GraphError)
+                .attach_printable_lazy(|| uri.clone())
+        })
+        .collect::<Result<Vec<_>, GraphError>>()?;
+
+    let ontology_types = if let Some(snapshot) = &args.snapshot {
+        let snapshot_content = fs::read_to_string(snapshot)
+            .into_report()
+            .change_context(GraphError)
+            .attach_printable_lazy(|| snapshot.display().to_string())?;
+        let schemas: Vec<serde_json::Value> = serde_json::from_str(&snapshot_content)
+            .into_report()
+            .change_context(GraphError)
+            .attach_printable_lazy(|| snapshot.display().to_string())?;
+
+        let mut ontology_types = OntologyTypeSet::new();
+        for schema in schemas {
+            ontology_types
+                .insert_schema(schema)
+                .change_context(GraphError)?;
+        }
+        ontology_types
+    } else {
+        match db_info.database_type() {
+            DatabaseType::Postgres => {
+                let pool = PostgresStorePool::new(db_info, NoTls)
+                    .await
+                    .change_context(GraphError)?;
+                let store = pool.acquire_read().await.change_context(GraphError)?;
+                OntologyTypeSet::from_store(&store, &entity_type_ids)
+                    .await
+                    .change_context(GraphError)?
+            }
+            #[cfg(feature = "sqlite")]
+            DatabaseType::Sqlite => {
+                let pool = SqliteStorePool::new(db_info)
+                    .await
+                    .change_context(GraphError)?;
+                let store = pool.acquire_read().await.change_context(GraphError)?;
+                OntologyTypeSet::from_store(&store, &entity_type_ids)
+                    .await
+                    .change_context(GraphError)?
+            }
+        }
+    };
+
+    let code = ontology_types
+        .generate(&entity_type_ids, args.language)
+        .change_context(GraphError)?;
+
+    match &args.output {
+        Some(output) => fs::write(output, code)
+            .into_report()
+            .change_context(GraphError)
+            .attach_printable_lazy(|| output.display().to_string()),
+        None => io::stdout()
+            .write_all(code.as_bytes())
+            .into_report()
+            .change_context(GraphError),
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), GraphError> {
     let args = Args::parse_args();
@@ -225,19 +311,65 @@ async fn main() -> Result<(), GraphError> {
         args.otlp_endpoint.as_deref(),
     );
 
-    let pool = PostgresStorePool::new(&args.db_info, NoTls)
-        .await
-        .change_context(GraphError)
-        .map_err(|err| {
-            tracing::error!("{err:?}");
-            err
-        })?;
+    match &args.command {
+        Some(GraphCommand::Codegen(codegen_args)) => {
+            return codegen(&args.db_info, codegen_args).await;
+        }
+        Some(GraphCommand::Query(query_args)) => {
+            return query::query(&args.db_info, query_args).await;
+        }
+        None => {}
+    }
+
+    match args.db_info.database_type() {
+        DatabaseType::Postgres => {
+            let pool = PostgresStorePool::with_config(
+                &args.db_info,
+                &args.read_replica_config,
+                args.query_limits,
+                OntologyCache::new(args.ontology_cache_size),
+                NoTls,
+            )
+            .await
+            .change_context(GraphError)
+            .map_err(|err| {
+                tracing::error!("{err:?}");
+                err
+            })?;
+
+            serve(pool, args).await
+        }
+        #[cfg(feature = "sqlite")]
+        DatabaseType::Sqlite => {
+            let pool = SqliteStorePool::with_query_limits(&args.db_info, args.query_limits)
+                .await
+                .change_context(GraphError)
+                .map_err(|err| {
+                    tracing::error!("{err:?}");
+                    err
+                })?;
+
+            serve(pool, args).await
+        }
+    }
+}
 
+/// Seeds the store behind `pool` and serves the REST API until the server is shut down.
+async fn serve<P>(pool: P, args: Args) -> Result<(), GraphError>
+where
+    P: StorePool + Send + 'static,
+    P::Error: Context,
+{
     stop_gap_setup(&pool).await?;
 
+    let type_fetcher = (!args.type_fetcher_allowed_domains.is_empty())
+        .then(|| OntologyTypeFetcher::new(args.type_fetcher_allowed_domains));
     let rest_router = rest_api_router(
         Arc::new(pool),
         DomainValidator::new(args.allowed_url_domain),
+        type_fetcher,
+        WebhookDispatcher::new(args.webhook_retry_policy)
+            .allow_private_targets(args.webhook_allow_private_targets),
     );
     let api_address = format!("{}:{}", args.api_host, args.api_port);
     let addr: SocketAddr = api_address
@@ -0,0 +1,416 @@
+//! Implementation of the `query` subcommand.
+
+use std::{
+    fmt::Write as _,
+    io::{self, Write},
+};
+
+use error_stack::{ensure, IntoReport, Report, Result, ResultExt};
+use graph::{
+    api::rest::utoipa_typedef::subgraph::Subgraph,
+    identifier::time::{
+        TimespanBound, Timestamp, UnresolvedImage, UnresolvedKernel, UnresolvedProjection,
+        UnresolvedTimeProjection,
+    },
+    knowledge::Entity,
+    ontology::{DataTypeWithMetadata, EntityTypeWithMetadata, PropertyTypeWithMetadata},
+    store::{
+        query::parse_filter, DatabaseConnectionInfo, DatabaseType, PostgresStorePool, Store,
+        StorePool,
+    },
+    subgraph::{
+        edges::{EdgeResolveDepths, GraphResolveDepths, OutgoingEdgeResolveDepth},
+        query::StructuralQuery,
+    },
+};
+use hyper::{body, header::CONTENT_TYPE, Body, Client, Request};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio_postgres::NoTls;
+
+use crate::{
+    args::{OutputFormat, QueryArgs, QueryKind, VariableAxis},
+    GraphError,
+};
+
+impl QueryKind {
+    /// The path of the REST resource for this kind, e.g. `entity-types`.
+    const fn resource(self) -> &'static str {
+        match self {
+            Self::Entities => "entities",
+            Self::EntityTypes => "entity-types",
+            Self::PropertyTypes => "property-types",
+            Self::DataTypes => "data-types",
+        }
+    }
+}
+
+fn graph_resolve_depths(args: &QueryArgs) -> GraphResolveDepths {
+    let ontology_depth = OutgoingEdgeResolveDepth {
+        outgoing: args.ontology_depth,
+        ..OutgoingEdgeResolveDepth::default()
+    };
+    let link_depth = EdgeResolveDepths {
+        incoming: args.link_depth,
+        outgoing: args.link_depth,
+    };
+
+    GraphResolveDepths {
+        inherits_from: ontology_depth,
+        constrains_values_on: ontology_depth,
+        constrains_properties_on: ontology_depth,
+        constrains_links_on: ontology_depth,
+        constrains_link_destinations_on: ontology_depth,
+        is_of_type: ontology_depth,
+        has_left_entity: link_depth,
+        has_right_entity: link_depth,
+    }
+}
+
+fn time_projection(args: &QueryArgs) -> UnresolvedTimeProjection {
+    fn bound<A>(timestamp: Option<Timestamp<()>>) -> Option<TimespanBound<A>> {
+        Some(timestamp.map_or(TimespanBound::Unbounded, |timestamp| {
+            TimespanBound::Included(timestamp.cast())
+        }))
+    }
+
+    match args.variable_axis {
+        VariableAxis::DecisionTime => {
+            UnresolvedTimeProjection::DecisionTime(UnresolvedProjection {
+                kernel: UnresolvedKernel::new(args.pinned_time.map(Timestamp::cast)),
+                image: UnresolvedImage::new(bound(args.from), bound(args.to)),
+            })
+        }
+        VariableAxis::TransactionTime => {
+            UnresolvedTimeProjection::TransactionTime(UnresolvedProjection {
+                kernel: UnresolvedKernel::new(args.pinned_time.map(Timestamp::cast)),
+                image: UnresolvedImage::new(bound(args.from), bound(args.to)),
+            })
+        }
+    }
+}
+
+/// Builds the body of a structural query as accepted by the `/<kind>/query` endpoints.
+fn query_body(args: &QueryArgs) -> Result<Value, GraphError> {
+    let filter = match &args.filter {
+        Some(filter) => parse_filter(filter).change_context(GraphError)?,
+        None => json!({ "all": [] }),
+    };
+
+    Ok(json!({
+        "filter": filter,
+        "graphResolveDepths": graph_resolve_depths(args),
+        "timeProjection": time_projection(args),
+    }))
+}
+
+/// Sends the query to a running Graph server and returns the resulting subgraph.
+async fn query_server(server: &str, kind: QueryKind, body: &Value) -> Result<Value, GraphError> {
+    let uri = format!("{}/{}/query", server.trim_end_matches('/'), kind.resource());
+    let request = Request::post(&uri)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .into_report()
+        .change_context(GraphError)
+        .attach_printable_lazy(|| uri.clone())?;
+
+    let response = Client::new()
+        .request(request)
+        .await
+        .into_report()
+        .change_context(GraphError)
+        .attach_printable_lazy(|| uri.clone())?;
+    let status = response.status();
+    let bytes = body::to_bytes(response.into_body())
+        .await
+        .into_report()
+        .change_context(GraphError)?;
+
+    ensure!(
+        status.is_success(),
+        Report::new(GraphError)
+            .attach_printable(format!("the server responded with {status}"))
+            .attach_printable(String::from_utf8_lossy(&bytes).into_owned())
+    );
+
+    serde_json::from_slice(&bytes)
+        .into_report()
+        .change_context(GraphError)
+}
+
+/// Reads the subgraph directly from the database.
+async fn query_store(
+    db_info: &DatabaseConnectionInfo,
+    kind: QueryKind,
+    body: &Value,
+) -> Result<Value, GraphError> {
+    match db_info.database_type() {
+        DatabaseType::Postgres => {
+            let pool = PostgresStorePool::new(db_info, NoTls)
+                .await
+                .change_context(GraphError)?;
+            let store = pool.acquire_read().await.change_context(GraphError)?;
+            read_subgraph(&store, kind, body).await
+        }
+        #[cfg(feature = "sqlite")]
+        DatabaseType::Sqlite => {
+            let pool = graph::store::SqliteStorePool::new(db_info)
+                .await
+                .change_context(GraphError)?;
+            let store = pool.acquire_read().await.change_context(GraphError)?;
+            read_subgraph(&store, kind, body).await
+        }
+    }
+}
+
+/// Reads the subgraph of the `kind` described by `body` from `store`.
+async fn read_subgraph(
+    store: &impl Store,
+    kind: QueryKind,
+    body: &Value,
+) -> Result<Value, GraphError> {
+    let subgraph = match kind {
+        QueryKind::Entities => {
+            let mut query = StructuralQuery::<Entity>::deserialize(body)
+                .into_report()
+                .change_context(GraphError)?;
+            query
+                .filter
+                .convert_parameters()
+                .change_context(GraphError)?;
+            store.get_entity(&query).await
+        }
+        QueryKind::EntityTypes => {
+            let mut query = StructuralQuery::<EntityTypeWithMetadata>::deserialize(body)
+                .into_report()
+                .change_context(GraphError)?;
+            query
+                .filter
+                .convert_parameters()
+                .change_context(GraphError)?;
+            store.get_entity_type(&query).await
+        }
+        QueryKind::PropertyTypes => {
+            let mut query = StructuralQuery::<PropertyTypeWithMetadata>::deserialize(body)
+                .into_report()
+                .change_context(GraphError)?;
+            query
+                .filter
+                .convert_parameters()
+                .change_context(GraphError)?;
+            store.get_property_type(&query).await
+        }
+        QueryKind::DataTypes => {
+            let mut query = StructuralQuery::<DataTypeWithMetadata>::deserialize(body)
+                .into_report()
+                .change_context(GraphError)?;
+            query
+                .filter
+                .convert_parameters()
+                .change_context(GraphError)?;
+            store.get_data_type(&query).await
+        }
+    }
+    .change_context(GraphError)?;
+
+    serde_json::to_value(Subgraph::from(subgraph))
+        .into_report()
+        .change_context(GraphError)
+}
+
+/// Returns the key used for `version` in the `vertices` and `edges` maps of a subgraph.
+fn version_key(version: &Value) -> String {
+    match version {
+        Value::String(version) => version.clone(),
+        version => version.to_string(),
+    }
+}
+
+/// Returns the vertex of the subgraph identified by `base_id` and `version`.
+fn vertex<'v>(subgraph: &'v Value, base_id: &str, version: &Value) -> &'v Value {
+    &subgraph["vertices"][base_id][version_key(version)]
+}
+
+fn compact(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+fn render_table(subgraph: &Value, kind: QueryKind) -> String {
+    let headers: &[&str] = match kind {
+        QueryKind::Entities => &["ENTITY ID", "ENTITY TYPE", "ARCHIVED", "PROPERTIES"],
+        QueryKind::EntityTypes | QueryKind::PropertyTypes | QueryKind::DataTypes => {
+            &["ID", "TITLE"]
+        }
+    };
+
+    let rows = subgraph["roots"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|root| {
+            let base_id = root["baseId"].as_str().unwrap_or_default();
+            let inner = &vertex(subgraph, base_id, &root["version"])["inner"];
+            match kind {
+                QueryKind::Entities => vec![
+                    base_id.to_owned(),
+                    compact(&inner["metadata"]["entityTypeId"]),
+                    compact(&inner["metadata"]["archived"]),
+                    compact(&inner["properties"]),
+                ],
+                QueryKind::EntityTypes | QueryKind::PropertyTypes | QueryKind::DataTypes => vec![
+                    compact(&inner["schema"]["$id"]),
+                    compact(&inner["schema"]["title"]),
+                ],
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let widths = headers
+        .iter()
+        .enumerate()
+        .map(|(column, header)| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain([header.len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+
+    let header = headers
+        .iter()
+        .map(|header| (*header).to_owned())
+        .collect::<Vec<_>>();
+
+    let mut table = String::new();
+    for row in [&header].into_iter().chain(&rows) {
+        let line = row
+            .iter()
+            .zip(widths.iter().copied())
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        table.push_str(line.trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+/// Returns the Graphviz node id of a vertex.
+///
+/// Entities are identified by their entity id only, as edges between entities refer to the
+/// entity at a point in time rather than a specific edition.
+fn node_id(base_id: &str, version: &Value) -> String {
+    match version {
+        Value::Number(version) => format!("{base_id}v/{version}"),
+        _ => base_id.to_owned(),
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn render_graphviz(subgraph: &Value) -> String {
+    let mut graph = String::from("digraph subgraph {\n");
+
+    let roots = subgraph["roots"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|root| {
+            node_id(
+                root["baseId"].as_str().unwrap_or_default(),
+                &root["version"],
+            )
+        })
+        .collect::<Vec<_>>();
+
+    for (base_id, editions) in subgraph["vertices"].as_object().into_iter().flatten() {
+        for (version, vertex) in editions.as_object().into_iter().flatten() {
+            let (id, label) = if vertex["kind"] == "entity" {
+                (base_id.clone(), base_id.clone())
+            } else {
+                (
+                    format!("{base_id}v/{version}"),
+                    compact(&vertex["inner"]["schema"]["title"]),
+                )
+            };
+            let style = if roots.contains(&id) {
+                ", style=bold"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                graph,
+                r#"    "{}" [label="{}", shape=box{style}];"#,
+                escape(&id),
+                escape(&label)
+            );
+        }
+    }
+
+    for (base_id, editions) in subgraph["edges"].as_object().into_iter().flatten() {
+        for (version, edges) in editions.as_object().into_iter().flatten() {
+            let source = match version.parse::<u32>() {
+                Ok(version) => node_id(base_id, &json!(version)),
+                Err(_) => base_id.clone(),
+            };
+            for edge in edges.as_array().map(Vec::as_slice).unwrap_or_default() {
+                let endpoint = &edge["rightEndpoint"];
+                let target = node_id(
+                    endpoint["baseId"].as_str().unwrap_or_default(),
+                    &endpoint["version"],
+                );
+                let (from, to) = if edge["reversed"] == true {
+                    (&target, &source)
+                } else {
+                    (&source, &target)
+                };
+                let _ = writeln!(
+                    graph,
+                    r#"    "{}" -> "{}" [label="{}"];"#,
+                    escape(from),
+                    escape(to),
+                    escape(&compact(&edge["kind"]))
+                );
+            }
+        }
+    }
+
+    graph.push_str("}\n");
+    graph
+}
+
+/// Runs the query described by `args` and prints the result to stdout.
+pub async fn query(db_info: &DatabaseConnectionInfo, args: &QueryArgs) -> Result<(), GraphError> {
+    let body = query_body(args)?;
+
+    let subgraph = match &args.server {
+        Some(server) => query_server(server, args.kind, &body).await?,
+        None => query_store(db_info, args.kind, &body).await?,
+    };
+
+    let output = match args.format {
+        OutputFormat::Table => render_table(&subgraph, args.kind),
+        OutputFormat::Json => {
+            let mut json = serde_json::to_string_pretty(&subgraph)
+                .into_report()
+                .change_context(GraphError)?;
+            json.push('\n');
+            json
+        }
+        OutputFormat::Graphviz => render_graphviz(&subgraph),
+    };
+
+    io::stdout()
+        .write_all(output.as_bytes())
+        .into_report()
+        .change_context(GraphError)
+}
@@ -9,6 +9,7 @@ use uuid::Uuid;
 use super::api_resource::RoutedResource;
 use crate::{
     identifier::account::AccountId,
+    metrics,
     store::{AccountStore, StorePool},
 };
 
@@ -61,6 +62,7 @@ async fn create_account_id<P: StorePool + Send>(
         .await
         .map_err(|report| {
             tracing::error!(error=?report, "Could not create account id");
+            metrics::record_error(&report);
 
             // Insertion/update errors are considered internal server errors.
             StatusCode::INTERNAL_SERVER_ERROR
@@ -12,9 +12,11 @@ use utoipa::{OpenApi, ToSchema};
 use super::api_resource::RoutedResource;
 use crate::{
     api::rest::{report_to_status_code, utoipa_typedef::subgraph::Subgraph},
+    metrics,
     ontology::{
         domain_validator::{DomainValidator, ValidateOntologyType},
-        patch_id_and_parse, DataTypeQueryToken, DataTypeWithMetadata, OntologyElementMetadata,
+        patch_id_and_parse, DataTypeQueryToken, DataTypeWithMetadata, InvalidDataTypeConstraint,
+        OntologyElementMetadata,
     },
     provenance::{OwnedById, UpdatedById},
     store::{BaseUriAlreadyExists, BaseUriDoesNotExist, DataTypeStore, StorePool},
@@ -115,10 +117,14 @@ async fn create_data_type<P: StorePool + Send>(
         .map_err(|report| {
             // TODO: consider adding the data type, or at least its URI in the trace
             tracing::error!(error=?report, "Could not create data type");
+            metrics::record_error(&report);
 
             if report.contains::<BaseUriAlreadyExists>() {
                 return StatusCode::CONFLICT;
             }
+            if report.contains::<InvalidDataTypeConstraint>() {
+                return StatusCode::UNPROCESSABLE_ENTITY;
+            }
 
             // Insertion/update errors are considered internal server errors.
             StatusCode::INTERNAL_SERVER_ERROR
@@ -135,6 +141,8 @@ async fn create_data_type<P: StorePool + Send>(
         (status = 200, content_type = "application/json", body = Subgraph, description = "Gets a subgraph rooted at all data types that satisfy the given query, each resolved to the requested depth."),
 
         (status = 422, content_type = "text/plain", description = "Provided query is invalid"),
+
+        (status = 400, content_type = "text/plain", description = "Query exceeds the configured query limits"),
         (status = 500, description = "Store error occurred"),
     )
 )]
@@ -143,7 +151,7 @@ async fn get_data_types_by_query<P: StorePool + Send>(
     pool: Extension<Arc<P>>,
     Json(query): Json<serde_json::Value>,
 ) -> Result<Json<Subgraph>, StatusCode> {
-    pool.acquire()
+    pool.acquire_read()
         .map_err(|error| {
             tracing::error!(?error, "Could not acquire access to the store");
             StatusCode::INTERNAL_SERVER_ERROR
@@ -222,10 +230,14 @@ async fn update_data_type<P: StorePool + Send>(
         .await
         .map_err(|report| {
             tracing::error!(error=?report, "Could not update data type");
+            metrics::record_error(&report);
 
             if report.contains::<BaseUriDoesNotExist>() {
                 return StatusCode::NOT_FOUND;
             }
+            if report.contains::<InvalidDataTypeConstraint>() {
+                return StatusCode::UNPROCESSABLE_ENTITY;
+            }
 
             // Insertion/update errors are considered internal server errors.
             StatusCode::INTERNAL_SERVER_ERROR
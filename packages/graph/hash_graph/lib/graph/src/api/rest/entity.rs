@@ -14,14 +14,24 @@ use crate::{
         report_to_status_code,
         utoipa_typedef::{subgraph::Subgraph, EntityIdAndTimestamp},
     },
-    identifier::knowledge::{EntityEditionId, EntityId, EntityRecordId, EntityVersion},
+    identifier::{
+        knowledge::{EntityEditionId, EntityId, EntityRecordId, EntityVersion},
+        time::{Timestamp, TransactionTime},
+    },
     knowledge::{
-        Entity, EntityLinkOrder, EntityMetadata, EntityProperties, EntityQueryToken, EntityUuid,
-        LinkData, LinkOrder,
+        DraftId, Entity, EntityDiff, EntityEditionUpdate, EntityLinkOrder, EntityMetadata,
+        EntityProperties, EntityPropertyProvenance, EntityQueryToken, EntityUuid, LinkData,
+        LinkOrder, PropertyProvenanceField,
     },
+    metrics,
+    ontology::ConstraintViolation,
     provenance::{OwnedById, UpdatedById},
     store::{
-        error::{EntityDoesNotExist, RaceConditionOnUpdate},
+        error::{
+            DraftDoesNotExist, DraftPublishConflict, EntityDoesNotExist, RaceConditionOnUpdate,
+        },
+        query::Filter,
+        webhook::WebhookDispatcher,
         EntityStore, StorePool,
     },
     subgraph::query::{EntityStructuralQuery, StructuralQuery},
@@ -33,11 +43,20 @@ use crate::{
         create_entity,
         get_entities_by_query,
         update_entity,
+        diff_entities,
+        create_draft,
+        publish_draft,
+        discard_draft,
     ),
     components(
         schemas(
             CreateEntityRequest,
             UpdateEntityRequest,
+            DiffEntitiesRequest,
+            CreateDraftRequest,
+            PublishDraftRequest,
+            DiscardDraftRequest,
+            DraftId,
             EntityQueryToken,
             EntityStructuralQuery,
 
@@ -49,8 +68,12 @@ use crate::{
             EntityMetadata,
             EntityLinkOrder,
             EntityProperties,
+            EntityPropertyProvenance,
+            PropertyProvenanceField,
             EntityRecordId,
             EntityVersion,
+            EntityDiff,
+            EntityEditionUpdate,
             EntityStructuralQuery,
             EntityQueryToken,
             LinkData,
@@ -71,7 +94,15 @@ impl RoutedResource for EntityResource {
             "/entities",
             Router::new()
                 .route("/", post(create_entity::<P>).put(update_entity::<P>))
-                .route("/query", post(get_entities_by_query::<P>)),
+                .route("/query", post(get_entities_by_query::<P>))
+                .route("/diff", post(diff_entities::<P>))
+                .nest(
+                    "/drafts",
+                    Router::new()
+                        .route("/", post(create_draft::<P>))
+                        .route("/publish", post(publish_draft::<P>))
+                        .route("/discard", post(discard_draft::<P>)),
+                ),
         )
     }
 }
@@ -85,10 +116,16 @@ struct CreateEntityRequest {
     owned_by_id: OwnedById,
     entity_uuid: Option<EntityUuid>,
     actor_id: UpdatedById,
+    /// Where the property values were obtained from, recorded in the provenance of each property.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    property_source: Option<String>,
     // TODO: this could break invariants if we don't move to fractional indexing
     //  https://app.asana.com/0/1201095311341924/1202085856561975/f
     #[serde(default, skip_serializing_if = "Option::is_none")]
     link_data: Option<LinkData>,
+    /// The draft the entity is created in. If not provided, the entity is created live.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    draft_id: Option<DraftId>,
 }
 
 #[utoipa::path(
@@ -104,9 +141,10 @@ struct CreateEntityRequest {
         (status = 500, description = "Store error occurred"),
     ),
 )]
-#[tracing::instrument(level = "info", skip(pool))]
-async fn create_entity<P: StorePool + Send>(
+#[tracing::instrument(level = "info", skip(pool, webhook_dispatcher))]
+async fn create_entity<P: StorePool + Send + 'static>(
     pool: Extension<Arc<P>>,
+    webhook_dispatcher: Extension<WebhookDispatcher>,
     body: Json<CreateEntityRequest>,
 ) -> Result<Json<EntityMetadata>, StatusCode> {
     let Json(CreateEntityRequest {
@@ -115,7 +153,9 @@ async fn create_entity<P: StorePool + Send>(
         owned_by_id,
         entity_uuid,
         actor_id,
+        property_source,
         link_data,
+        draft_id,
     }) = body;
 
     let mut store = pool.acquire().await.map_err(|report| {
@@ -123,7 +163,7 @@ async fn create_entity<P: StorePool + Send>(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
-    store
+    let metadata = store
         .create_entity(
             owned_by_id,
             entity_uuid,
@@ -132,16 +172,28 @@ async fn create_entity<P: StorePool + Send>(
             false,
             entity_type_id,
             properties,
+            property_source,
             link_data,
+            draft_id,
         )
         .await
         .map_err(|report| {
             tracing::error!(error=?report, "Could not create entity");
+            metrics::record_error(&report);
+
+            if report.contains::<ConstraintViolation>() {
+                return StatusCode::UNPROCESSABLE_ENTITY;
+            }
 
             // Insertion/update errors are considered internal server errors.
             StatusCode::INTERNAL_SERVER_ERROR
-        })
-        .map(Json)
+        })?;
+
+    // Drafts are not visible to webhooks until they are published.
+    if draft_id.is_none() {
+        dispatch_to_webhooks(&pool, webhook_dispatcher.0, metadata.edition_id());
+    }
+    Ok(Json(metadata))
 }
 
 #[utoipa::path(
@@ -152,6 +204,7 @@ async fn create_entity<P: StorePool + Send>(
     responses(
         (status = 200, content_type = "application/json", body = Subgraph, description = "A subgraph rooted at entities that satisfy the given query, each resolved to the requested depth."),
         (status = 422, content_type = "text/plain", description = "Provided query is invalid"),
+        (status = 400, content_type = "text/plain", description = "Query exceeds the configured query limits"),
         (status = 500, description = "Store error occurred"),
     )
 )]
@@ -160,7 +213,7 @@ async fn get_entities_by_query<P: StorePool + Send>(
     pool: Extension<Arc<P>>,
     Json(query): Json<serde_json::Value>,
 ) -> Result<Json<Subgraph>, StatusCode> {
-    pool.acquire()
+    pool.acquire_read()
         .map_err(|error| {
             tracing::error!(?error, "Could not acquire access to the store");
             StatusCode::INTERNAL_SERVER_ERROR
@@ -191,9 +244,16 @@ struct UpdateEntityRequest {
     #[schema(value_type = String)]
     entity_type_id: VersionedUri,
     actor_id: UpdatedById,
+    /// Where the changed property values were obtained from, recorded in the provenance of each
+    /// changed property.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    property_source: Option<String>,
     #[serde(flatten)]
     order: EntityLinkOrder,
     archived: bool,
+    /// The draft the update is applied to. If not provided, the live entity is updated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    draft_id: Option<DraftId>,
 }
 
 #[utoipa::path(
@@ -210,9 +270,10 @@ struct UpdateEntityRequest {
     ),
     request_body = UpdateEntityRequest,
 )]
-#[tracing::instrument(level = "info", skip(pool))]
-async fn update_entity<P: StorePool + Send>(
+#[tracing::instrument(level = "info", skip(pool, webhook_dispatcher))]
+async fn update_entity<P: StorePool + Send + 'static>(
     pool: Extension<Arc<P>>,
+    webhook_dispatcher: Extension<WebhookDispatcher>,
     body: Json<UpdateEntityRequest>,
 ) -> Result<Json<EntityMetadata>, StatusCode> {
     let Json(UpdateEntityRequest {
@@ -220,8 +281,10 @@ async fn update_entity<P: StorePool + Send>(
         entity_id,
         entity_type_id,
         actor_id,
+        property_source,
         order,
         archived,
+        draft_id,
     }) = body;
 
     let mut store = pool.acquire().await.map_err(|report| {
@@ -229,7 +292,7 @@ async fn update_entity<P: StorePool + Send>(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
-    store
+    let metadata = store
         .update_entity(
             entity_id,
             None,
@@ -237,20 +300,246 @@ async fn update_entity<P: StorePool + Send>(
             archived,
             entity_type_id,
             properties,
+            property_source,
             order,
+            draft_id,
         )
         .await
         .map_err(|report| {
             tracing::error!(error=?report, "Could not update entity");
+            metrics::record_error(&report);
 
             if report.contains::<EntityDoesNotExist>() {
                 StatusCode::NOT_FOUND
             } else if report.contains::<RaceConditionOnUpdate>() {
                 StatusCode::LOCKED
+            } else if report.contains::<ConstraintViolation>() {
+                StatusCode::UNPROCESSABLE_ENTITY
             } else {
                 // Insertion/update errors are considered internal server errors.
                 StatusCode::INTERNAL_SERVER_ERROR
             }
+        })?;
+
+    // Drafts are not visible to webhooks until they are published.
+    if draft_id.is_none() {
+        dispatch_to_webhooks(&pool, webhook_dispatcher.0, metadata.edition_id());
+    }
+    Ok(Json(metadata))
+}
+
+/// Delivers the entity edition to the matching webhooks in the background.
+///
+/// The response to the request does not wait for the delivery, failures are only logged. No store
+/// is held while the payloads are sent.
+fn dispatch_to_webhooks<P: StorePool + Send + 'static>(
+    pool: &Arc<P>,
+    webhook_dispatcher: WebhookDispatcher,
+    edition_id: EntityEditionId,
+) {
+    let pool = Arc::clone(pool);
+    tokio::spawn(async move {
+        if let Err(report) = webhook_dispatcher
+            .dispatch_from_pool(pool.as_ref(), edition_id)
+            .await
+        {
+            tracing::error!(error=?report, "Could not dispatch entity to webhooks");
+            metrics::record_error(&report);
+        }
+    });
+}
+
+#[derive(Debug, ToSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct DiffEntitiesRequest {
+    from: Timestamp<TransactionTime>,
+    to: Timestamp<TransactionTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    filter: Option<serde_json::Value>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/entities/diff",
+    request_body = DiffEntitiesRequest,
+    tag = "Entity",
+    responses(
+        (status = 200, content_type = "application/json", body = EntityDiff, description = "The entities, which were created, updated, or archived between the two transaction times"),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+        (status = 500, description = "Store error occurred"),
+    )
+)]
+#[tracing::instrument(level = "info", skip(pool))]
+async fn diff_entities<P: StorePool + Send>(
+    pool: Extension<Arc<P>>,
+    Json(request): Json<DiffEntitiesRequest>,
+) -> Result<Json<EntityDiff>, StatusCode> {
+    let DiffEntitiesRequest { from, to, filter } = request;
+
+    let filter = filter
+        .as_ref()
+        .map(|filter| {
+            let mut filter = Filter::<Entity>::deserialize(filter).map_err(|error| {
+                tracing::error!(?error, "Could not deserialize filter");
+                StatusCode::UNPROCESSABLE_ENTITY
+            })?;
+            filter.convert_parameters().map_err(|error| {
+                tracing::error!(?error, "Could not validate filter");
+                StatusCode::UNPROCESSABLE_ENTITY
+            })?;
+            Ok(filter)
+        })
+        .transpose()?;
+
+    let store = pool.acquire_read().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .diff_entities(from, to, filter.as_ref())
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not diff entities");
+            report_to_status_code(&report)
         })
         .map(Json)
 }
+
+#[derive(Debug, ToSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct CreateDraftRequest {
+    owned_by_id: OwnedById,
+}
+
+#[utoipa::path(
+    post,
+    path = "/entities/drafts",
+    request_body = CreateDraftRequest,
+    tag = "Entity",
+    responses(
+        (status = 201, content_type = "application/json", description = "The id of the created draft", body = DraftId),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 500, description = "Store error occurred"),
+    ),
+)]
+#[tracing::instrument(level = "info", skip(pool))]
+async fn create_draft<P: StorePool + Send>(
+    pool: Extension<Arc<P>>,
+    Json(request): Json<CreateDraftRequest>,
+) -> Result<Json<DraftId>, StatusCode> {
+    let mut store = pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .create_draft(request.owned_by_id)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not create draft");
+            metrics::record_error(&report);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+        .map(Json)
+}
+
+#[derive(Debug, ToSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct PublishDraftRequest {
+    draft_id: DraftId,
+}
+
+#[utoipa::path(
+    post,
+    path = "/entities/drafts/publish",
+    request_body = PublishDraftRequest,
+    tag = "Entity",
+    responses(
+        (status = 200, content_type = "application/json", description = "The editions, which were published", body = [EntityEditionId]),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 404, description = "Draft was not found"),
+        (status = 409, description = "An entity in the draft was updated since it was changed in the draft"),
+        (status = 500, description = "Store error occurred"),
+    ),
+)]
+#[tracing::instrument(level = "info", skip(pool, webhook_dispatcher))]
+async fn publish_draft<P: StorePool + Send + 'static>(
+    pool: Extension<Arc<P>>,
+    webhook_dispatcher: Extension<WebhookDispatcher>,
+    Json(request): Json<PublishDraftRequest>,
+) -> Result<Json<Vec<EntityEditionId>>, StatusCode> {
+    let mut store = pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let edition_ids = store
+        .publish_draft(request.draft_id)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not publish draft");
+            metrics::record_error(&report);
+
+            if report.contains::<DraftDoesNotExist>() {
+                StatusCode::NOT_FOUND
+            } else if report.contains::<DraftPublishConflict>() {
+                StatusCode::CONFLICT
+            } else {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        })?;
+
+    for edition_id in &edition_ids {
+        dispatch_to_webhooks(&pool, webhook_dispatcher.0.clone(), *edition_id);
+    }
+    Ok(Json(edition_ids))
+}
+
+#[derive(Debug, ToSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct DiscardDraftRequest {
+    draft_id: DraftId,
+}
+
+#[utoipa::path(
+    post,
+    path = "/entities/drafts/discard",
+    request_body = DiscardDraftRequest,
+    tag = "Entity",
+    responses(
+        (status = 204, description = "The draft was discarded"),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 404, description = "Draft was not found"),
+        (status = 500, description = "Store error occurred"),
+    ),
+)]
+#[tracing::instrument(level = "info", skip(pool))]
+async fn discard_draft<P: StorePool + Send>(
+    pool: Extension<Arc<P>>,
+    Json(request): Json<DiscardDraftRequest>,
+) -> Result<StatusCode, StatusCode> {
+    let mut store = pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .discard_draft(request.draft_id)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not discard draft");
+            metrics::record_error(&report);
+
+            if report.contains::<DraftDoesNotExist>() {
+                StatusCode::NOT_FOUND
+            } else {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        })
+        .map(|()| StatusCode::NO_CONTENT)
+}
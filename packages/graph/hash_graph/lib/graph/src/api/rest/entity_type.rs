@@ -13,6 +13,7 @@ use crate::{
     api::rest::{
         api_resource::RoutedResource, report_to_status_code, utoipa_typedef::subgraph::Subgraph,
     },
+    metrics,
     ontology::{
         domain_validator::{DomainValidator, ValidateOntologyType},
         patch_id_and_parse, EntityTypeQueryToken, EntityTypeWithMetadata, OntologyElementMetadata,
@@ -20,6 +21,7 @@ use crate::{
     provenance::{OwnedById, UpdatedById},
     store::{
         error::{BaseUriAlreadyExists, BaseUriDoesNotExist},
+        fetcher::{OntologyTypeFetcher, OntologyTypeReference},
         EntityTypeStore, StorePool,
     },
     subgraph::query::{EntityTypeStructuralQuery, StructuralQuery},
@@ -80,17 +82,18 @@ struct CreateEntityTypeRequest {
     tag = "EntityType",
     responses(
         (status = 201, content_type = "application/json", description = "The metadata of the created entity type", body = OntologyElementMetadata),
-        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid or a referenced type could not be fetched"),
 
         (status = 409, description = "Unable to create entity type in the datastore as the base entity type ID already exists"),
         (status = 500, description = "Store error occurred"),
     ),
     request_body = CreateEntityTypeRequest,
 )]
-#[tracing::instrument(level = "info", skip(pool, domain_validator))]
+#[tracing::instrument(level = "info", skip(pool, domain_validator, type_fetcher))]
 async fn create_entity_type<P: StorePool + Send>(
     pool: Extension<Arc<P>>,
     domain_validator: Extension<DomainValidator>,
+    type_fetcher: Extension<Option<OntologyTypeFetcher>>,
     body: Json<CreateEntityTypeRequest>,
 ) -> Result<Json<OntologyElementMetadata>, StatusCode> {
     let Json(CreateEntityTypeRequest {
@@ -117,11 +120,27 @@ async fn create_entity_type<P: StorePool + Send>(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
+    if let Some(type_fetcher) = &*type_fetcher {
+        type_fetcher
+            .import_missing(
+                &mut store,
+                OntologyTypeReference::of_entity_type(&entity_type),
+                owned_by_id,
+                actor_id,
+            )
+            .await
+            .map_err(|report| {
+                tracing::error!(error=?report, "Could not import referenced types");
+                StatusCode::UNPROCESSABLE_ENTITY
+            })?;
+    }
+
     store
         .create_entity_type(entity_type, owned_by_id, actor_id)
         .await
         .map_err(|report| {
             tracing::error!(error=?report, "Could not create entity type");
+            metrics::record_error(&report);
 
             if report.contains::<BaseUriAlreadyExists>() {
                 return StatusCode::CONFLICT;
@@ -141,6 +160,7 @@ async fn create_entity_type<P: StorePool + Send>(
     responses(
         (status = 200, content_type = "application/json", body = Subgraph, description = "A subgraph rooted at entity types that satisfy the given query, each resolved to the requested depth."),
         (status = 422, content_type = "text/plain", description = "Provided query is invalid"),
+        (status = 400, content_type = "text/plain", description = "Query exceeds the configured query limits"),
         (status = 500, description = "Store error occurred"),
     )
 )]
@@ -149,7 +169,7 @@ async fn get_entity_types_by_query<P: StorePool + Send>(
     pool: Extension<Arc<P>>,
     Json(query): Json<serde_json::Value>,
 ) -> Result<Json<Subgraph>, StatusCode> {
-    pool.acquire()
+    pool.acquire_read()
         .map_err(|error| {
             tracing::error!(?error, "Could not acquire access to the store");
             StatusCode::INTERNAL_SERVER_ERROR
@@ -232,6 +252,7 @@ async fn update_entity_type<P: StorePool + Send>(
         .await
         .map_err(|report| {
             tracing::error!(error=?report, "Could not update entity type");
+            metrics::record_error(&report);
 
             if report.contains::<BaseUriDoesNotExist>() {
                 return StatusCode::NOT_FOUND;
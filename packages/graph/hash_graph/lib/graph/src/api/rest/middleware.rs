@@ -1,4 +1,8 @@
-use std::{borrow::Cow, net::SocketAddr, time::Duration};
+use std::{
+    borrow::Cow,
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
 
 use axum::{
     body::{Body, Bytes, HttpBody},
@@ -19,6 +23,8 @@ use tower_http::{
 };
 use tracing::{enabled, field::Empty, Level};
 
+use crate::metrics;
+
 // *Heavily* inspired by
 // https://github.com/tokio-rs/axum/blob/main/examples/print-request-response/src/main.rs
 
@@ -144,6 +150,42 @@ fn parse_x_forwarded_for(headers: &http::HeaderMap) -> Option<Cow<'_, str>> {
     Some(ips.next()?.trim().into())
 }
 
+/// Returns the route of the request, which is used to identify the request in traces and metrics.
+fn matched_route(request: &Request<Body>) -> String {
+    // Because of https://github.com/tokio-rs/axum/issues/1441 and our usage of nested routes we
+    // need to fall back to using the `OriginalUri`.
+    // As we are to remove any routes that have URI path parameters, we shouldn't run into
+    // high-cardinality name problems in the OpenTelemetry traces and metrics we send.
+    request.extensions().get::<MatchedPath>().map_or_else(
+        || {
+            request.extensions().get::<OriginalUri>().map_or_else(
+                || request.uri().path().to_owned(),
+                |uri| uri.0.path().to_owned(),
+            )
+        },
+        |matched_path| matched_path.as_str().to_owned(),
+    )
+}
+
+/// Records the latency of each request by its route in the [`metrics`].
+///
+/// [`metrics`]: crate::metrics
+pub(super) async fn record_request_metrics(request: Request<Body>, next: Next<Body>) -> Response {
+    let method = request.method().clone();
+    let route = matched_route(&request);
+
+    let start = Instant::now();
+    let response = next.run(request).await;
+    metrics::record_http_request(
+        method.as_str(),
+        &route,
+        response.status().as_u16(),
+        start.elapsed(),
+    );
+
+    response
+}
+
 // Based on https://github.com/tokio-rs/axum/pull/769
 fn span_maker(request: &Request<Body>) -> tracing::Span {
     let target = request.uri();
@@ -161,20 +203,7 @@ fn span_maker(request: &Request<Body>) -> tracing::Span {
     );
 
     let method = request.method();
-
-    // Because of https://github.com/tokio-rs/axum/issues/1441 and our usage of nested routes we
-    // need to fall back to using the `OriginalUri`.
-    // As we are to remove any routes that have URI path parameters, we shouldn't run into
-    // high-cardinality name problems in the OpenTelemetry traces we send.
-    let route = request.extensions().get::<MatchedPath>().map_or_else(
-        || {
-            request.extensions().get::<OriginalUri>().map_or_else(
-                || request.uri().path().to_owned(),
-                |uri| uri.0.path().to_owned(),
-            )
-        },
-        |matched_path| matched_path.as_str().to_owned(),
-    );
+    let route = matched_route(request);
 
     let user_agent = request
         .headers()
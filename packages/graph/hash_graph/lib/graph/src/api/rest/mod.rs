@@ -10,7 +10,9 @@ mod data_type;
 mod entity;
 mod entity_type;
 mod property_type;
-mod utoipa_typedef;
+mod status;
+pub mod utoipa_typedef;
+mod webhook;
 
 use std::sync::Arc;
 
@@ -31,7 +33,10 @@ use utoipa::{
     Modify, OpenApi, ToSchema,
 };
 
-use self::{api_resource::RoutedResource, middleware::span_trace_layer};
+use self::{
+    api_resource::RoutedResource,
+    middleware::{record_request_metrics, span_trace_layer},
+};
 use crate::{
     api::rest::{
         middleware::log_request_and_response,
@@ -53,9 +58,13 @@ use crate::{
         },
         EntityVertexId, GraphElementId, GraphElementVertexId,
     },
+    metrics,
     ontology::{domain_validator::DomainValidator, OntologyElementMetadata, Selector},
-    provenance::{OwnedById, ProvenanceMetadata, UpdatedById},
-    store::{QueryError, StorePool},
+    provenance::{OwnedById, PropertyProvenance, ProvenanceMetadata, UpdatedById},
+    store::{
+        fetcher::OntologyTypeFetcher, webhook::WebhookDispatcher, QueryError, QueryLimitExceeded,
+        StorePool,
+    },
     subgraph::edges::{
         EdgeResolveDepths, GraphResolveDepths, KnowledgeGraphEdgeKind, OntologyEdgeKind,
         OntologyOutwardEdges, OutgoingEdgeResolveDepth, SharedEdgeKind,
@@ -71,6 +80,7 @@ fn api_resources<P: StorePool + Send + 'static>() -> Vec<Router> {
         property_type::PropertyTypeResource::routes::<P>(),
         entity_type::EntityTypeResource::routes::<P>(),
         entity::EntityResource::routes::<P>(),
+        webhook::WebhookResource::routes::<P>(),
     ]
 }
 
@@ -81,22 +91,31 @@ fn api_documentation() -> Vec<openapi::OpenApi> {
         property_type::PropertyTypeResource::documentation(),
         entity_type::EntityTypeResource::documentation(),
         entity::EntityResource::documentation(),
+        webhook::WebhookResource::documentation(),
     ]
 }
 
 fn report_to_status_code<C>(report: &Report<C>) -> StatusCode {
+    metrics::record_error(report);
+
     let mut status_code = StatusCode::INTERNAL_SERVER_ERROR;
 
     if let Some(error) = report.downcast_ref::<QueryError>() {
         tracing::error!(%error, "Unable to query from data store");
         status_code = StatusCode::UNPROCESSABLE_ENTITY;
     }
+    if let Some(error) = report.downcast_ref::<QueryLimitExceeded>() {
+        tracing::warn!(%error, "Query was rejected");
+        status_code = StatusCode::BAD_REQUEST;
+    }
     status_code
 }
 
 pub fn rest_api_router<P: StorePool + Send + 'static>(
     store: Arc<P>,
     domain_regex: DomainValidator,
+    type_fetcher: Option<OntologyTypeFetcher>,
+    webhook_dispatcher: WebhookDispatcher,
 ) -> Router {
     // All api resources are merged together into a super-router.
     let merged_routes = api_resources::<P>()
@@ -109,11 +128,16 @@ pub fn rest_api_router<P: StorePool + Send + 'static>(
     // super-router can then be used as any other router.
     // Make sure extensions are added at the end so they are made available to merged routers.
     // The `/api-doc` endpoints are nested as we don't want any layers or handlers for the api-doc
+    // The status routes are merged after the tracing layers to avoid logging every probe.
     merged_routes
-        .layer(Extension(store))
+        .layer(Extension(Arc::clone(&store)))
         .layer(Extension(domain_regex))
+        .layer(Extension(type_fetcher))
+        .layer(Extension(webhook_dispatcher))
         .layer(axum::middleware::from_fn(log_request_and_response))
         .layer(span_trace_layer())
+        .layer(axum::middleware::from_fn(record_request_metrics))
+        .merge(status::routes::<P>().layer(Extension(store)))
         .nest(
             "/api-doc",
             Router::new()
@@ -160,6 +184,7 @@ async fn serve_static_schema(Path(path): Path<String>) -> Result<Response, Statu
             OwnedById,
             UpdatedById,
             ProvenanceMetadata,
+            PropertyProvenance,
             OntologyTypeEditionId,
             OntologyElementMetadata,
             EntityVertexId,
@@ -12,13 +12,17 @@ use utoipa::{OpenApi, ToSchema};
 use super::api_resource::RoutedResource;
 use crate::{
     api::rest::{report_to_status_code, utoipa_typedef::subgraph::Subgraph},
+    metrics,
     ontology::{
         domain_validator::{DomainValidator, ValidateOntologyType},
         patch_id_and_parse, OntologyElementMetadata, PropertyTypeQueryToken,
         PropertyTypeWithMetadata,
     },
     provenance::{OwnedById, UpdatedById},
-    store::{BaseUriAlreadyExists, BaseUriDoesNotExist, PropertyTypeStore, StorePool},
+    store::{
+        fetcher::{OntologyTypeFetcher, OntologyTypeReference},
+        BaseUriAlreadyExists, BaseUriDoesNotExist, PropertyTypeStore, StorePool,
+    },
     subgraph::query::{PropertyTypeStructuralQuery, StructuralQuery},
 };
 
@@ -77,17 +81,18 @@ struct CreatePropertyTypeRequest {
     tag = "PropertyType",
     responses(
         (status = 201, content_type = "application/json", description = "The metadata of the created property type", body = OntologyElementMetadata),
-        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid or a referenced type could not be fetched"),
 
         (status = 409, description = "Unable to create property type in the store as the base property type ID already exists"),
         (status = 500, description = "Store error occurred"),
     ),
     request_body = CreatePropertyTypeRequest,
 )]
-#[tracing::instrument(level = "info", skip(pool, domain_validator))]
+#[tracing::instrument(level = "info", skip(pool, domain_validator, type_fetcher))]
 async fn create_property_type<P: StorePool + Send>(
     pool: Extension<Arc<P>>,
     domain_validator: Extension<DomainValidator>,
+    type_fetcher: Extension<Option<OntologyTypeFetcher>>,
     body: Json<CreatePropertyTypeRequest>,
 ) -> Result<Json<OntologyElementMetadata>, StatusCode> {
     let Json(CreatePropertyTypeRequest {
@@ -115,11 +120,27 @@ async fn create_property_type<P: StorePool + Send>(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
+    if let Some(type_fetcher) = &*type_fetcher {
+        type_fetcher
+            .import_missing(
+                &mut store,
+                OntologyTypeReference::of_property_type(&property_type),
+                owned_by_id,
+                actor_id,
+            )
+            .await
+            .map_err(|report| {
+                tracing::error!(error=?report, "Could not import referenced types");
+                StatusCode::UNPROCESSABLE_ENTITY
+            })?;
+    }
+
     store
         .create_property_type(property_type, owned_by_id, actor_id)
         .await
         .map_err(|report| {
             tracing::error!(error=?report, "Could not create property type");
+            metrics::record_error(&report);
 
             if report.contains::<BaseUriAlreadyExists>() {
                 return StatusCode::CONFLICT;
@@ -140,6 +161,8 @@ async fn create_property_type<P: StorePool + Send>(
         (status = 200, content_type = "application/json", body = Subgraph, description = "A subgraph rooted at property types that satisfy the given query, each resolved to the requested depth."),
 
         (status = 422, content_type = "text/plain", description = "Provided query is invalid"),
+
+        (status = 400, content_type = "text/plain", description = "Query exceeds the configured query limits"),
         (status = 500, description = "Store error occurred"),
     )
 )]
@@ -148,7 +171,7 @@ async fn get_property_types_by_query<P: StorePool + Send>(
     pool: Extension<Arc<P>>,
     Json(query): Json<serde_json::Value>,
 ) -> Result<Json<Subgraph>, StatusCode> {
-    pool.acquire()
+    pool.acquire_read()
         .map_err(|error| {
             tracing::error!(?error, "Could not acquire access to the store");
             StatusCode::INTERNAL_SERVER_ERROR
@@ -230,6 +253,7 @@ async fn update_property_type<P: StorePool + Send>(
         .await
         .map_err(|report| {
             tracing::error!(error=?report, "Could not update property type");
+            metrics::record_error(&report);
 
             if report.contains::<BaseUriDoesNotExist>() {
                 return StatusCode::NOT_FOUND;
@@ -0,0 +1,65 @@
+//! Web routes for probing the status of the Graph.
+//!
+//! These routes are intended to be used by orchestrators and monitoring systems and are therefore
+//! not part of the OpenAPI specification.
+
+use std::sync::Arc;
+
+use axum::{
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Extension, Router,
+};
+
+use crate::{
+    metrics,
+    store::{Store, StorePool},
+};
+
+/// Create routes for the health, readiness, and metrics probes.
+pub(super) fn routes<P: StorePool + Send + 'static>() -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/ready", get(ready::<P>))
+        .route("/metrics", get(render_metrics))
+}
+
+#[allow(
+    clippy::unused_async,
+    reason = "This route does not need async capabilities, but axum requires it in trait bounds."
+)]
+async fn health() -> StatusCode {
+    StatusCode::OK
+}
+
+async fn ready<P: StorePool + Send>(pool: Extension<Arc<P>>) -> StatusCode {
+    let store = match pool.acquire_read().await {
+        Ok(store) => store,
+        Err(report) => {
+            tracing::warn!(error=?report, "Could not acquire store");
+            return StatusCode::SERVICE_UNAVAILABLE;
+        }
+    };
+
+    match store.check_readiness().await {
+        Ok(()) => StatusCode::OK,
+        Err(report) => {
+            tracing::warn!(error=?report, "Store is not ready");
+            StatusCode::SERVICE_UNAVAILABLE
+        }
+    }
+}
+
+#[allow(
+    clippy::unused_async,
+    reason = "This route does not need async capabilities, but axum requires it in trait bounds."
+)]
+async fn render_metrics() -> Result<impl IntoResponse, StatusCode> {
+    let metrics = metrics::encode().map_err(|report| {
+        tracing::error!(error=?report, "Could not encode metrics");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(([(header::CONTENT_TYPE, metrics::CONTENT_TYPE)], metrics))
+}
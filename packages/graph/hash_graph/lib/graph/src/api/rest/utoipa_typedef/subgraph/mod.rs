@@ -16,6 +16,7 @@ use crate::{
         time::{TimeProjection, UnresolvedTimeProjection},
         GraphElementVertexId,
     },
+    knowledge::DraftId,
     subgraph::edges::GraphResolveDepths,
 };
 
@@ -28,6 +29,9 @@ pub struct Subgraph {
     depths: GraphResolveDepths,
     time_projection: UnresolvedTimeProjection,
     resolved_time_projection: TimeProjection,
+    incomplete: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    draft_id: Option<DraftId>,
 }
 
 impl From<crate::subgraph::Subgraph> for Subgraph {
@@ -45,6 +49,8 @@ impl From<crate::subgraph::Subgraph> for Subgraph {
             depths: subgraph.depths,
             time_projection: subgraph.time_projection,
             resolved_time_projection: subgraph.resolved_time_projection,
+            incomplete: subgraph.incomplete,
+            draft_id: subgraph.draft_id,
         }
     }
 }
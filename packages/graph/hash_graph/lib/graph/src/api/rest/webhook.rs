@@ -0,0 +1,110 @@
+//! Web routes for registering webhooks.
+
+use std::sync::Arc;
+
+use axum::{http::StatusCode, routing::post, Extension, Json, Router};
+use serde::Deserialize;
+use utoipa::{OpenApi, ToSchema};
+use uuid::Uuid;
+
+use super::api_resource::RoutedResource;
+use crate::{
+    metrics,
+    provenance::OwnedById,
+    store::{
+        webhook::{InvalidWebhookTargetUrl, Webhook, WebhookId, WebhookStore},
+        StorePool,
+    },
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        create_webhook,
+    ),
+    components(
+        schemas(CreateWebhookRequest, Webhook, WebhookId),
+    ),
+    tags(
+        (name = "Webhook", description = "Webhook management API")
+    )
+)]
+pub struct WebhookResource;
+
+impl RoutedResource for WebhookResource {
+    /// Create routes for interacting with webhooks.
+    fn routes<P: StorePool + Send + 'static>() -> Router {
+        Router::new().nest(
+            "/webhooks",
+            Router::new().route("/", post(create_webhook::<P>)),
+        )
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct CreateWebhookRequest {
+    owned_by_id: OwnedById,
+    target_url: String,
+    /// The secret used to sign the payloads sent to the webhook.
+    secret: String,
+    /// The filter an entity has to match to be sent to the webhook.
+    #[schema(value_type = Object)]
+    filter: serde_json::Value,
+}
+
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    request_body = CreateWebhookRequest,
+    tag = "Webhook",
+    responses(
+        (status = 201, content_type = "application/json", description = "The registered webhook", body = Webhook),
+        (status = 422, content_type = "text/plain", description = "Provided filter or target URL is invalid"),
+
+        (status = 500, description = "Store error occurred"),
+    )
+)]
+#[tracing::instrument(level = "info", skip(pool, body))]
+async fn create_webhook<P: StorePool + Send>(
+    pool: Extension<Arc<P>>,
+    body: Json<CreateWebhookRequest>,
+) -> Result<Json<Webhook>, StatusCode> {
+    let Json(CreateWebhookRequest {
+        owned_by_id,
+        target_url,
+        secret,
+        filter,
+    }) = body;
+
+    let webhook = Webhook::new(
+        WebhookId::new(Uuid::new_v4()),
+        owned_by_id,
+        target_url,
+        secret,
+        filter,
+    )
+    .map_err(|report| {
+        tracing::error!(error=?report, "Invalid webhook filter");
+        StatusCode::UNPROCESSABLE_ENTITY
+    })?;
+
+    let mut store = pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store.create_webhook(&webhook).await.map_err(|report| {
+        tracing::error!(error=?report, "Could not create webhook");
+        metrics::record_error(&report);
+
+        if report.contains::<InvalidWebhookTargetUrl>() {
+            StatusCode::UNPROCESSABLE_ENTITY
+        } else {
+            // Insertion/update errors are considered internal server errors.
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    })?;
+
+    Ok(Json(webhook))
+}
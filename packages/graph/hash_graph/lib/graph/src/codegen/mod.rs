@@ -0,0 +1,682 @@
+//! Generation of typed bindings from entity types.
+//!
+//! The entry point is the [`OntologyTypeSet`], which holds the ontology types code is generated
+//! from. It can either be filled from a [`Store`] by [`OntologyTypeSet::from_store`] or from a
+//! snapshot of ontology type schemas by [`OntologyTypeSet::insert_schema`].
+//!
+//! For every requested entity type and all types it depends on, code is generated:
+//!
+//! - data types become type aliases of the primitive type they describe,
+//! - property types become type aliases, structs (for property objects), or untagged enums (if a
+//!   property type allows more than one value),
+//! - entity types become structs whose fields are keyed by the [`BaseUri`] of the property type, so
+//!   they (de)serialize to the same JSON as [`EntityProperties`], and
+//! - the links of an entity type become a `<Name>Link` enum in Rust and a `<Name>Links` interface
+//!   in TypeScript.
+//!
+//! [`Store`]: crate::store::Store
+//! [`BaseUri`]: type_system::uri::BaseUri
+//! [`EntityProperties`]: crate::knowledge::EntityProperties
+
+mod rust;
+mod typescript;
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt,
+};
+
+use error_stack::{Context, IntoReport, Report, Result, ResultExt};
+use type_system::{repr, uri::VersionedUri, DataType, EntityType, PropertyType};
+
+use crate::{
+    identifier::time::{
+        TimespanBound, UnresolvedImage, UnresolvedKernel, UnresolvedProjection,
+        UnresolvedTimeProjection,
+    },
+    ontology::schema::{
+        DataTypeSchema, EntityTypeSchema, OntologyTypeSchema, PropertyTypeSchema, PropertyValues,
+        ValueOrArray,
+    },
+    store::{query::Filter, EntityTypeStore},
+    subgraph::{
+        edges::{GraphResolveDepths, OutgoingEdgeResolveDepth},
+        query::StructuralQuery,
+        Subgraph,
+    },
+};
+
+#[derive(Debug)]
+pub struct CodegenError;
+
+impl Context for CodegenError {}
+
+impl fmt::Display for CodegenError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("could not generate code from the ontology types")
+    }
+}
+
+/// The language to generate bindings for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Language {
+    /// Rust structs and enums, which are (de)serializable with `serde`.
+    Rust,
+    /// TypeScript interfaces and type aliases.
+    TypeScript,
+}
+
+/// A collection of ontology types to generate code from.
+#[derive(Debug, Default)]
+pub struct OntologyTypeSet {
+    data_types: BTreeMap<String, DataTypeSchema>,
+    property_types: BTreeMap<String, PropertyTypeSchema>,
+    entity_types: BTreeMap<String, EntityTypeSchema>,
+}
+
+impl OntologyTypeSet {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the entity types identified by `entity_type_ids` from the `store` and resolves all
+    /// ontology types they depend on.
+    ///
+    /// # Errors
+    ///
+    /// - [`CodegenError`], if reading from the `store` failed
+    pub async fn from_store<S>(
+        store: &S,
+        entity_type_ids: &[VersionedUri],
+    ) -> Result<Self, CodegenError>
+    where
+        S: EntityTypeStore + Sync,
+    {
+        let mut set = Self::new();
+        for entity_type_id in entity_type_ids {
+            let subgraph = store
+                .get_entity_type(&StructuralQuery {
+                    filter: Filter::for_versioned_uri(entity_type_id),
+                    graph_resolve_depths: dependency_resolve_depths(),
+                    time_projection: UnresolvedTimeProjection::DecisionTime(UnresolvedProjection {
+                        kernel: UnresolvedKernel::new(None),
+                        image: UnresolvedImage::new(
+                            Some(TimespanBound::Unbounded),
+                            Some(TimespanBound::Unbounded),
+                        ),
+                    }),
+                    draft_id: None,
+                })
+                .await
+                .change_context(CodegenError)
+                .attach_printable_lazy(|| entity_type_id.clone())?;
+            set.extend_from_subgraph(subgraph)?;
+        }
+        Ok(set)
+    }
+
+    /// Adds all ontology types contained in the vertices of the `subgraph`.
+    ///
+    /// # Errors
+    ///
+    /// - [`CodegenError`], if an ontology type could not be converted
+    pub fn extend_from_subgraph(&mut self, subgraph: Subgraph) -> Result<(), CodegenError> {
+        for data_type in subgraph.vertices.data_types.into_values() {
+            self.insert_data_type(data_type.inner().clone())?;
+        }
+        for property_type in subgraph.vertices.property_types.into_values() {
+            self.insert_property_type(property_type.inner().clone())?;
+        }
+        for entity_type in subgraph.vertices.entity_types.into_values() {
+            self.insert_entity_type(entity_type.inner().clone())?;
+        }
+        Ok(())
+    }
+
+    /// Adds a [`DataType`] to the set.
+    ///
+    /// # Errors
+    ///
+    /// - [`CodegenError`], if the [`DataType`] could not be converted
+    pub fn insert_data_type(&mut self, data_type: DataType) -> Result<(), CodegenError> {
+        self.insert_schema(
+            serde_json::to_value(repr::DataType::from(data_type))
+                .into_report()
+                .change_context(CodegenError)?,
+        )
+    }
+
+    /// Adds a [`PropertyType`] to the set.
+    ///
+    /// # Errors
+    ///
+    /// - [`CodegenError`], if the [`PropertyType`] could not be converted
+    pub fn insert_property_type(
+        &mut self,
+        property_type: PropertyType,
+    ) -> Result<(), CodegenError> {
+        self.insert_schema(
+            serde_json::to_value(repr::PropertyType::from(property_type))
+                .into_report()
+                .change_context(CodegenError)?,
+        )
+    }
+
+    /// Adds an [`EntityType`] to the set.
+    ///
+    /// # Errors
+    ///
+    /// - [`CodegenError`], if the [`EntityType`] could not be converted
+    pub fn insert_entity_type(&mut self, entity_type: EntityType) -> Result<(), CodegenError> {
+        self.insert_schema(
+            serde_json::to_value(repr::EntityType::from(entity_type))
+                .into_report()
+                .change_context(CodegenError)?,
+        )
+    }
+
+    /// Adds the JSON schema of an ontology type to the set.
+    ///
+    /// The kind of the ontology type is determined by the `"kind"` field of the schema.
+    ///
+    /// # Errors
+    ///
+    /// - [`CodegenError`], if the `schema` is not a valid ontology type schema
+    pub fn insert_schema(&mut self, schema: serde_json::Value) -> Result<(), CodegenError> {
+        let schema: OntologyTypeSchema = serde_json::from_value(schema)
+            .into_report()
+            .change_context(CodegenError)
+            .attach_printable("not a valid ontology type schema")?;
+
+        match schema {
+            OntologyTypeSchema::DataType(data_type) => {
+                self.data_types.insert(data_type.id.to_string(), data_type);
+            }
+            OntologyTypeSchema::PropertyType(property_type) => {
+                self.property_types
+                    .insert(property_type.id.to_string(), property_type);
+            }
+            OntologyTypeSchema::EntityType(entity_type) => {
+                self.entity_types
+                    .insert(entity_type.id.to_string(), entity_type);
+            }
+        }
+        Ok(())
+    }
+
+    /// Generates bindings in the given `language` for the entity types identified by
+    /// `entity_type_ids` and all ontology types they depend on.
+    ///
+    /// # Errors
+    ///
+    /// - [`CodegenError`], if an entity type or one of its dependencies is not part of the set
+    pub fn generate(
+        &self,
+        entity_type_ids: &[VersionedUri],
+        language: Language,
+    ) -> Result<String, CodegenError> {
+        let selection = Selection::new(self, entity_type_ids)?;
+        match language {
+            Language::Rust => rust::generate(&selection),
+            Language::TypeScript => typescript::generate(&selection),
+        }
+        .into_report()
+        .change_context(CodegenError)
+    }
+}
+
+/// The depths required to resolve every ontology type an entity type depends on.
+fn dependency_resolve_depths() -> GraphResolveDepths {
+    let depth = OutgoingEdgeResolveDepth {
+        outgoing: u8::MAX,
+        incoming: 0,
+    };
+    GraphResolveDepths {
+        inherits_from: depth,
+        constrains_values_on: depth,
+        constrains_properties_on: depth,
+        constrains_links_on: depth,
+        constrains_link_destinations_on: depth,
+        ..GraphResolveDepths::default()
+    }
+}
+
+/// The ontology types, which code is generated for, alongside the names of the generated types.
+struct Selection<'s> {
+    set: &'s OntologyTypeSet,
+    data_types: Vec<&'s DataTypeSchema>,
+    property_types: Vec<&'s PropertyTypeSchema>,
+    entity_types: Vec<&'s EntityTypeSchema>,
+    names: HashMap<String, String>,
+}
+
+impl<'s> Selection<'s> {
+    fn new(
+        set: &'s OntologyTypeSet,
+        entity_type_ids: &[VersionedUri],
+    ) -> Result<Self, CodegenError> {
+        let mut data_type_ids = BTreeSet::new();
+        let mut property_type_ids = BTreeSet::new();
+        let mut entity_type_ids = entity_type_ids
+            .iter()
+            .map(ToString::to_string)
+            .collect::<BTreeSet<_>>();
+
+        let mut entity_types_to_visit = entity_type_ids.iter().cloned().collect::<Vec<_>>();
+        let mut property_types_to_visit = Vec::new();
+        while let Some(entity_type_id) = entity_types_to_visit.pop() {
+            let entity_type = lookup(&set.entity_types, &entity_type_id)?;
+
+            let mut referenced_entity_types = entity_type
+                .all_of
+                .iter()
+                .map(|reference| reference.uri.to_string())
+                .collect::<Vec<_>>();
+            for (link_type_id, link) in &entity_type.links {
+                referenced_entity_types.push(link_type_id.clone());
+                referenced_entity_types.extend(
+                    link.items
+                        .one_of
+                        .iter()
+                        .map(|reference| reference.uri.to_string()),
+                );
+            }
+            for referenced_entity_type in referenced_entity_types {
+                if entity_type_ids.insert(referenced_entity_type.clone()) {
+                    entity_types_to_visit.push(referenced_entity_type);
+                }
+            }
+
+            for property in entity_type.properties.values() {
+                let property_type_id = property_reference(property).uri.to_string();
+                if property_type_ids.insert(property_type_id.clone()) {
+                    property_types_to_visit.push(property_type_id);
+                }
+            }
+        }
+
+        while let Some(property_type_id) = property_types_to_visit.pop() {
+            let property_type = lookup(&set.property_types, &property_type_id)?;
+
+            let mut references = References::default();
+            for values in &property_type.one_of {
+                references.collect(values);
+            }
+            for referenced_property_type in references.property_types {
+                if property_type_ids.insert(referenced_property_type.clone()) {
+                    property_types_to_visit.push(referenced_property_type);
+                }
+            }
+            data_type_ids.extend(references.data_types);
+        }
+
+        let selection = Self {
+            set,
+            data_types: data_type_ids
+                .iter()
+                .map(|id| lookup(&set.data_types, id))
+                .collect::<Result<_, CodegenError>>()?,
+            property_types: property_type_ids
+                .iter()
+                .map(|id| lookup(&set.property_types, id))
+                .collect::<Result<_, CodegenError>>()?,
+            entity_types: entity_type_ids
+                .iter()
+                .map(|id| lookup(&set.entity_types, id))
+                .collect::<Result<_, CodegenError>>()?,
+            names: HashMap::new(),
+        };
+
+        Ok(selection.with_names())
+    }
+
+    /// Assigns a unique type name to every selected ontology type.
+    ///
+    /// Entity types are named first, so they are least likely to receive a suffixed name.
+    fn with_names(mut self) -> Self {
+        let mut used_names = HashSet::new();
+        let types = self
+            .entity_types
+            .iter()
+            .map(|entity_type| (&entity_type.id, &entity_type.title, ""))
+            .chain(
+                self.property_types
+                    .iter()
+                    .map(|property_type| (&property_type.id, &property_type.title, "PropertyType")),
+            )
+            .chain(
+                self.data_types
+                    .iter()
+                    .map(|data_type| (&data_type.id, &data_type.title, "DataType")),
+            );
+
+        for (id, title, kind_suffix) in types {
+            let base_name = pascal_case(title);
+            let name = [
+                base_name.clone(),
+                format!("{base_name}{kind_suffix}"),
+                format!("{base_name}{kind_suffix}V{}", id.version()),
+            ]
+            .into_iter()
+            .chain((2..).map(|n| format!("{base_name}{kind_suffix}V{}_{n}", id.version())))
+            .find(|name| !used_names.contains(name))
+            .expect("an infinite iterator always yields a name");
+
+            used_names.insert(name.clone());
+            self.names.insert(id.to_string(), name);
+        }
+
+        self
+    }
+
+    /// Returns the name of the type generated for the ontology type identified by `id`.
+    fn name(&self, id: impl fmt::Display) -> &str {
+        self.names
+            .get(&id.to_string())
+            .expect("all referenced ontology types are part of the selection")
+    }
+
+    fn property_type(&self, id: impl fmt::Display) -> &'s PropertyTypeSchema {
+        self.set
+            .property_types
+            .get(&id.to_string())
+            .expect("all referenced property types are part of the selection")
+    }
+}
+
+fn lookup<'s, T>(types: &'s BTreeMap<String, T>, id: &str) -> Result<&'s T, CodegenError> {
+    types.get(id).ok_or_else(|| {
+        Report::new(CodegenError).attach_printable(format!("ontology type `{id}` is unknown"))
+    })
+}
+
+const fn property_reference<T>(property: &ValueOrArray<T>) -> &T {
+    match property {
+        ValueOrArray::Value(value) => value,
+        ValueOrArray::Array(array) => &array.items,
+    }
+}
+
+/// The ontology types referenced by property values.
+#[derive(Default)]
+struct References {
+    data_types: Vec<String>,
+    property_types: Vec<String>,
+}
+
+impl References {
+    fn collect(&mut self, values: &PropertyValues) {
+        match values {
+            PropertyValues::DataTypeReference(reference) => {
+                self.data_types.push(reference.uri.to_string());
+            }
+            PropertyValues::PropertyTypeObject(object) => {
+                self.property_types.extend(
+                    object
+                        .properties
+                        .values()
+                        .map(|property| property_reference(property).uri.to_string()),
+                );
+            }
+            PropertyValues::ArrayOfPropertyValues(array) => {
+                for values in &array.items.one_of {
+                    self.collect(values);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the lines of the documentation of a generated type.
+fn doc_lines(title: &str, description: Option<&str>, id: &VersionedUri) -> Vec<String> {
+    let mut lines = vec![title.to_owned(), String::new()];
+    if let Some(description) = description {
+        lines.extend(description.lines().map(str::to_owned));
+        lines.push(String::new());
+    }
+    lines.push(format!("Generated from `{id}`."));
+    lines
+}
+
+fn words(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(|character: char| !character.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+}
+
+/// Converts `value` into `PascalCase`, which is a valid identifier in Rust and TypeScript.
+fn pascal_case(value: &str) -> String {
+    let mut name = String::new();
+    for word in words(value) {
+        let mut characters = word.chars();
+        if let Some(first) = characters.next() {
+            name.push(first.to_ascii_uppercase());
+            name.extend(characters);
+        }
+    }
+
+    if name.is_empty() || name.starts_with(|character: char| character.is_ascii_digit()) {
+        name.insert(0, 'T');
+    }
+    name
+}
+
+/// Converts `value` into `snake_case`.
+fn snake_case(value: &str) -> String {
+    words(value)
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Converts `value` into `camelCase`.
+fn camel_case(value: &str) -> String {
+    let mut name = pascal_case(value);
+    if let Some(first) = name.get_mut(0..1) {
+        first.make_ascii_lowercase();
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn ontology_type_set() -> OntologyTypeSet {
+        let mut set = OntologyTypeSet::new();
+        for schema in [
+            graph_test_data::data_type::TEXT_V1,
+            graph_test_data::data_type::NUMBER_V1,
+            graph_test_data::property_type::NAME_V1,
+            graph_test_data::property_type::EMAIL_V1,
+            graph_test_data::property_type::PHONE_NUMBER_V1,
+            graph_test_data::property_type::CONTACT_INFORMATION_V1,
+            graph_test_data::property_type::CONTRIVED_PROPERTY_V1,
+            graph_test_data::property_type::TEXT_V1,
+            graph_test_data::entity_type::LINK_V1,
+            graph_test_data::entity_type::link::FRIEND_OF_V1,
+            graph_test_data::entity_type::PERSON_V1,
+        ] {
+            set.insert_schema(serde_json::from_str(schema).expect("invalid JSON"))
+                .expect("invalid ontology type schema");
+        }
+        set
+    }
+
+    fn person_type_id() -> VersionedUri {
+        VersionedUri::from_str("https://blockprotocol.org/@alice/types/entity-type/person/v/1")
+            .expect("invalid URI")
+    }
+
+    #[test]
+    fn case_conversion() {
+        assert_eq!(pascal_case("Friend of"), "FriendOf");
+        assert_eq!(pascal_case("address-line 1"), "AddressLine1");
+        assert_eq!(pascal_case("1st place"), "T1stPlace");
+        assert_eq!(snake_case("Address Line 1"), "address_line_1");
+        assert_eq!(camel_case("Friend of"), "friendOf");
+    }
+
+    #[test]
+    fn selects_dependencies() {
+        let set = ontology_type_set();
+        let selection = Selection::new(&set, &[person_type_id()]).expect("could not select types");
+
+        let mut names = selection.names.values().cloned().collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["FriendOf", "Link", "Name", "Person", "Text"]);
+    }
+
+    #[test]
+    fn names_are_unique() {
+        let mut set = ontology_type_set();
+        set.insert_schema(serde_json::json!({
+            "kind": "entityType",
+            "$id": "https://example.com/@alice/types/entity-type/text/v/1",
+            "type": "object",
+            "title": "Text",
+            "properties": {
+                "https://blockprotocol.org/@alice/types/property-type/text/": {
+                    "$ref": "https://blockprotocol.org/@alice/types/property-type/text/v/1"
+                }
+            }
+        }))
+        .expect("invalid ontology type schema");
+
+        let selection = Selection::new(&set, &[VersionedUri::from_str(
+            "https://example.com/@alice/types/entity-type/text/v/1",
+        )
+        .expect("invalid URI")])
+        .expect("could not select types");
+
+        let mut names = selection.names.values().cloned().collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["Text", "TextDataType", "TextPropertyType"]);
+    }
+
+    #[test]
+    fn unknown_types_fail() {
+        let mut set = OntologyTypeSet::new();
+        set.insert_schema(
+            serde_json::from_str(graph_test_data::entity_type::PERSON_V1).expect("invalid JSON"),
+        )
+        .expect("invalid ontology type schema");
+
+        let report = set
+            .generate(&[person_type_id()], Language::Rust)
+            .expect_err("dependencies are missing");
+        assert!(report.contains::<CodegenError>());
+    }
+
+    #[test]
+    fn generates_rust() {
+        let code = ontology_type_set()
+            .generate(&[person_type_id()], Language::Rust)
+            .expect("could not generate code");
+
+        assert!(code.contains("pub type Text = String;"), "{code}");
+        assert!(code.contains("pub type Name = Text;"), "{code}");
+        assert!(code.contains("pub struct Person {"), "{code}");
+        assert!(
+            code.contains(
+                "#[serde(rename = \"https://blockprotocol.org/@alice/types/property-type/name/\", \
+                 default, skip_serializing_if = \"Option::is_none\")]"
+            ),
+            "{code}"
+        );
+        assert!(code.contains("pub name: Option<Name>,"), "{code}");
+        assert!(
+            code.contains("pub enum PersonLink {\n    /// Friend of\n    FriendOf,"),
+            "{code}"
+        );
+        assert!(
+            code.contains("#[serde(flatten)]\n    pub link: Link,"),
+            "{code}"
+        );
+    }
+
+    #[test]
+    fn generates_typescript() {
+        let code = ontology_type_set()
+            .generate(&[person_type_id()], Language::TypeScript)
+            .expect("could not generate code");
+
+        assert!(code.contains("export type Text = string;"), "{code}");
+        assert!(code.contains("export type Name = Text;"), "{code}");
+        assert!(
+            code.contains(
+                "export interface Person {\n  \"https://blockprotocol.org/@alice/types/property-type/name/\"?: Name;\n}"
+            ),
+            "{code}"
+        );
+        assert!(
+            code.contains("export interface FriendOf extends Link {"),
+            "{code}"
+        );
+        assert!(code.contains("export interface PersonLinks {"), "{code}");
+        assert!(
+            code.contains(
+                "friendOf: \"https://blockprotocol.org/@alice/types/entity-type/friend-of/v/1\","
+            ),
+            "{code}"
+        );
+    }
+
+    #[test]
+    fn generates_property_values() {
+        let mut set = ontology_type_set();
+        set.insert_schema(serde_json::json!({
+            "kind": "entityType",
+            "$id": "https://example.com/@alice/types/entity-type/contact/v/1",
+            "type": "object",
+            "title": "Contact",
+            "properties": {
+                "https://blockprotocol.org/@alice/types/property-type/contact-information/": {
+                    "type": "array",
+                    "items": {
+                        "$ref": "https://blockprotocol.org/@alice/types/property-type/contact-information/v/1"
+                    }
+                },
+                "https://blockprotocol.org/@alice/types/property-type/contrived-property/": {
+                    "$ref": "https://blockprotocol.org/@alice/types/property-type/contrived-property/v/1"
+                }
+            },
+            "required": ["https://blockprotocol.org/@alice/types/property-type/contact-information/"]
+        }))
+        .expect("invalid ontology type schema");
+        let contact_type_id =
+            VersionedUri::from_str("https://example.com/@alice/types/entity-type/contact/v/1")
+                .expect("invalid URI");
+
+        let rust = set
+            .generate(&[contact_type_id.clone()], Language::Rust)
+            .expect("could not generate code");
+        assert!(
+            rust.contains("pub contact_information: Vec<ContactInformation>,"),
+            "{rust}"
+        );
+        assert!(rust.contains("pub struct ContactInformation {"), "{rust}");
+        assert!(rust.contains("pub email: Email,"), "{rust}");
+        assert!(
+            rust.contains("#[serde(untagged)]\npub enum ContrivedProperty {"),
+            "{rust}"
+        );
+        assert!(rust.contains("    Array(Vec<Number>),"), "{rust}");
+
+        let typescript = set
+            .generate(&[contact_type_id], Language::TypeScript)
+            .expect("could not generate code");
+        assert!(
+            typescript.contains("export type ContrivedProperty = Number | Number[];"),
+            "{typescript}"
+        );
+        assert!(
+            typescript.contains("export interface ContactInformation {"),
+            "{typescript}"
+        );
+    }
+}
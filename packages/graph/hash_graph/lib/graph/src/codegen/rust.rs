@@ -0,0 +1,428 @@
+//! Generation of Rust bindings.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::{self, Write},
+};
+
+use super::{doc_lines, snake_case, Selection};
+use crate::ontology::schema::{
+    DataTypeSchema, EntityTypeSchema, PropertyTypeSchema, PropertyValues, Reference, ValueOrArray,
+};
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while",
+];
+
+const DERIVES: &str = "#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]";
+
+pub(super) fn generate(selection: &Selection) -> Result<String, fmt::Error> {
+    let mut generator = Generator {
+        selection,
+        code: String::from(
+            "// This file was generated from the HASH ontology. Do not edit it manually.\n",
+        ),
+    };
+
+    for entity_type in &selection.entity_types {
+        generator.entity_type(entity_type)?;
+    }
+    for property_type in &selection.property_types {
+        generator.property_type(property_type)?;
+    }
+    for data_type in &selection.data_types {
+        generator.data_type(data_type)?;
+    }
+
+    Ok(generator.code)
+}
+
+/// Returns `value` as a string literal.
+fn literal(value: &str) -> String {
+    format!("\"{}\"", value.escape_default())
+}
+
+struct Field<'a> {
+    key: &'a str,
+    ident: String,
+    ty: String,
+    required: bool,
+}
+
+struct Generator<'s> {
+    selection: &'s Selection<'s>,
+    code: String,
+}
+
+impl Generator<'_> {
+    fn docs(&mut self, indent: &str, lines: &[String]) -> fmt::Result {
+        for line in lines {
+            if line.is_empty() {
+                writeln!(self.code, "{indent}///")?;
+            } else {
+                writeln!(self.code, "{indent}/// {line}")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn data_type(&mut self, data_type: &DataTypeSchema) -> fmt::Result {
+        let primitive = match data_type.json_type.as_str() {
+            "string" => "String",
+            "number" => "f64",
+            "boolean" => "bool",
+            "null" => "()",
+            "object" => "serde_json::Map<String, serde_json::Value>",
+            "array" => "Vec<serde_json::Value>",
+            _ => "serde_json::Value",
+        };
+
+        writeln!(self.code)?;
+        self.docs(
+            "",
+            &doc_lines(
+                &data_type.title,
+                data_type.description.as_deref(),
+                &data_type.id,
+            ),
+        )?;
+        writeln!(
+            self.code,
+            "pub type {} = {primitive};",
+            self.selection.name(&data_type.id)
+        )
+    }
+
+    fn property_type(&mut self, property_type: &PropertyTypeSchema) -> fmt::Result {
+        let name = self.selection.name(&property_type.id);
+        let docs = doc_lines(
+            &property_type.title,
+            property_type.description.as_deref(),
+            &property_type.id,
+        );
+
+        let ty = self.property_values(name, &property_type.one_of, &docs)?;
+        if ty != name {
+            writeln!(self.code)?;
+            self.docs("", &docs)?;
+            writeln!(self.code, "pub type {name} = {ty};")?;
+        }
+        Ok(())
+    }
+
+    /// Returns the type of the `values`.
+    ///
+    /// If the values require a dedicated type definition, it's emitted with the given `name` and
+    /// `docs`.
+    fn property_values(
+        &mut self,
+        name: &str,
+        values: &[PropertyValues],
+        docs: &[String],
+    ) -> Result<String, fmt::Error> {
+        if let [value] = values {
+            return self.property_value(name, value, docs);
+        }
+
+        let mut variant_names = HashSet::new();
+        let mut variants = Vec::with_capacity(values.len());
+        for (index, value) in values.iter().enumerate() {
+            let base_name = match value {
+                PropertyValues::DataTypeReference(reference) => {
+                    self.selection.name(&reference.uri).to_owned()
+                }
+                PropertyValues::PropertyTypeObject(_) => "Object".to_owned(),
+                PropertyValues::ArrayOfPropertyValues(_) => "Array".to_owned(),
+            };
+            let variant_name = if variant_names.insert(base_name.clone()) {
+                base_name
+            } else {
+                format!("{base_name}{index}")
+            };
+            let ty = self.property_value(&format!("{name}{variant_name}"), value, &[])?;
+            variants.push((variant_name, ty));
+        }
+
+        writeln!(self.code)?;
+        self.docs("", docs)?;
+        writeln!(self.code, "{DERIVES}")?;
+        writeln!(self.code, "#[serde(untagged)]")?;
+        writeln!(self.code, "pub enum {name} {{")?;
+        for (variant_name, ty) in variants {
+            writeln!(self.code, "    {variant_name}({ty}),")?;
+        }
+        writeln!(self.code, "}}")?;
+        Ok(name.to_owned())
+    }
+
+    fn property_value(
+        &mut self,
+        name: &str,
+        value: &PropertyValues,
+        docs: &[String],
+    ) -> Result<String, fmt::Error> {
+        match value {
+            PropertyValues::DataTypeReference(reference) => {
+                Ok(self.selection.name(&reference.uri).to_owned())
+            }
+            PropertyValues::PropertyTypeObject(object) => {
+                let fields = self.fields(&object.properties, &object.required);
+                self.write_struct(name, docs, &[], &fields)?;
+                Ok(name.to_owned())
+            }
+            PropertyValues::ArrayOfPropertyValues(array) => {
+                let items =
+                    self.property_values(&format!("{name}Item"), &array.items.one_of, &[])?;
+                Ok(format!("Vec<{items}>"))
+            }
+        }
+    }
+
+    fn fields<'a>(
+        &self,
+        properties: &'a BTreeMap<String, ValueOrArray<Reference>>,
+        required: &[String],
+    ) -> Vec<Field<'a>> {
+        let mut idents = HashSet::new();
+        properties
+            .iter()
+            .map(|(key, property)| {
+                let (reference, is_array) = match property {
+                    ValueOrArray::Value(reference) => (reference, false),
+                    ValueOrArray::Array(array) => (&array.items, true),
+                };
+                let property_type = self.selection.property_type(&reference.uri);
+                let ty = self.selection.name(&reference.uri);
+
+                Field {
+                    key,
+                    ident: unique_ident(&mut idents, &property_type.title),
+                    ty: if is_array {
+                        format!("Vec<{ty}>")
+                    } else {
+                        ty.to_owned()
+                    },
+                    required: required.contains(key),
+                }
+            })
+            .collect()
+    }
+
+    fn write_struct(
+        &mut self,
+        name: &str,
+        docs: &[String],
+        parents: &[&str],
+        fields: &[Field],
+    ) -> fmt::Result {
+        let mut idents = fields
+            .iter()
+            .map(|field| field.ident.clone())
+            .collect::<HashSet<_>>();
+
+        writeln!(self.code)?;
+        self.docs("", docs)?;
+        writeln!(self.code, "{DERIVES}")?;
+        writeln!(self.code, "pub struct {name} {{")?;
+        for parent in parents {
+            writeln!(self.code, "    #[serde(flatten)]")?;
+            writeln!(
+                self.code,
+                "    pub {}: {parent},",
+                unique_ident(&mut idents, parent)
+            )?;
+        }
+        for field in fields {
+            if field.required {
+                writeln!(self.code, "    #[serde(rename = {})]", literal(field.key))?;
+                writeln!(self.code, "    pub {}: {},", field.ident, field.ty)?;
+            } else {
+                writeln!(
+                    self.code,
+                    "    #[serde(rename = {}, default, skip_serializing_if = \"Option::is_none\")]",
+                    literal(field.key)
+                )?;
+                writeln!(self.code, "    pub {}: Option<{}>,", field.ident, field.ty)?;
+            }
+        }
+        writeln!(self.code, "}}")
+    }
+
+    fn entity_type(&mut self, entity_type: &EntityTypeSchema) -> fmt::Result {
+        let name = self.selection.name(&entity_type.id);
+        let parents = entity_type
+            .all_of
+            .iter()
+            .map(|reference| self.selection.name(&reference.uri))
+            .collect::<Vec<_>>();
+        let fields = self.fields(&entity_type.properties, &entity_type.required);
+
+        self.write_struct(
+            name,
+            &doc_lines(
+                &entity_type.title,
+                entity_type.description.as_deref(),
+                &entity_type.id,
+            ),
+            &parents,
+            &fields,
+        )?;
+
+        writeln!(self.code)?;
+        writeln!(self.code, "impl {name} {{")?;
+        writeln!(
+            self.code,
+            "    /// The URI of the entity type this struct was generated from."
+        )?;
+        writeln!(
+            self.code,
+            "    pub const ENTITY_TYPE_ID: &'static str = {};",
+            literal(&entity_type.id.to_string())
+        )?;
+        writeln!(self.code, "}}")?;
+
+        if !entity_type.links.is_empty() {
+            self.links(name, entity_type)?;
+        }
+        Ok(())
+    }
+
+    fn links(&mut self, name: &str, entity_type: &EntityTypeSchema) -> fmt::Result {
+        let links = entity_type
+            .links
+            .iter()
+            .map(|(link_type_id, link)| {
+                let link_type = self
+                    .selection
+                    .entity_types
+                    .iter()
+                    .find(|link_type| link_type.id.to_string() == *link_type_id)
+                    .expect("all link types are part of the selection");
+                (
+                    self.selection.name(link_type_id),
+                    link_type,
+                    link_type_id,
+                    link,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        writeln!(self.code)?;
+        writeln!(
+            self.code,
+            "/// The links, which can be created from an entity of type [`{name}`]."
+        )?;
+        writeln!(
+            self.code,
+            "#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]"
+        )?;
+        writeln!(self.code, "pub enum {name}Link {{")?;
+        for (variant, link_type, ..) in &links {
+            writeln!(self.code, "    /// {}", link_type.title)?;
+            writeln!(self.code, "    {variant},")?;
+        }
+        writeln!(self.code, "}}")?;
+
+        writeln!(self.code)?;
+        writeln!(self.code, "impl {name}Link {{")?;
+        writeln!(self.code, "    /// All links of [`{name}`].")?;
+        write!(self.code, "    pub const ALL: &'static [Self] = &[")?;
+        for (index, (variant, ..)) in links.iter().enumerate() {
+            if index > 0 {
+                write!(self.code, ", ")?;
+            }
+            write!(self.code, "Self::{variant}")?;
+        }
+        writeln!(self.code, "];")?;
+
+        writeln!(self.code)?;
+        writeln!(
+            self.code,
+            "    /// Returns the URI of the link entity type."
+        )?;
+        writeln!(self.code, "    #[must_use]")?;
+        writeln!(
+            self.code,
+            "    pub const fn link_entity_type_id(self) -> &'static str {{"
+        )?;
+        writeln!(self.code, "        match self {{")?;
+        for (variant, _, link_type_id, _) in &links {
+            writeln!(
+                self.code,
+                "            Self::{variant} => {},",
+                literal(link_type_id)
+            )?;
+        }
+        writeln!(self.code, "        }}")?;
+        writeln!(self.code, "    }}")?;
+
+        writeln!(self.code)?;
+        writeln!(
+            self.code,
+            "    /// Returns the URIs of the entity types the link may point to."
+        )?;
+        writeln!(self.code, "    #[must_use]")?;
+        writeln!(
+            self.code,
+            "    pub const fn destination_entity_type_ids(self) -> &'static [&'static str] {{"
+        )?;
+        writeln!(self.code, "        match self {{")?;
+        for (variant, _, _, link) in &links {
+            let destinations = link
+                .items
+                .one_of
+                .iter()
+                .map(|reference| literal(&reference.uri.to_string()))
+                .collect::<Vec<_>>();
+            writeln!(
+                self.code,
+                "            Self::{variant} => &[{}],",
+                destinations.join(", ")
+            )?;
+        }
+        writeln!(self.code, "        }}")?;
+        writeln!(self.code, "    }}")?;
+
+        writeln!(self.code)?;
+        writeln!(
+            self.code,
+            "    /// Returns if the links of this kind are ordered."
+        )?;
+        writeln!(self.code, "    #[must_use]")?;
+        writeln!(self.code, "    pub const fn is_ordered(self) -> bool {{")?;
+        writeln!(self.code, "        match self {{")?;
+        for (variant, _, _, link) in &links {
+            writeln!(
+                self.code,
+                "            Self::{variant} => {},",
+                link.ordered
+            )?;
+        }
+        writeln!(self.code, "        }}")?;
+        writeln!(self.code, "    }}")?;
+        writeln!(self.code, "}}")
+    }
+}
+
+/// Creates a field identifier from `title`, which is not contained in `idents`.
+fn unique_ident(idents: &mut HashSet<String>, title: &str) -> String {
+    let mut ident = snake_case(title);
+    if ident.is_empty() {
+        ident = "property".to_owned();
+    } else if ident.starts_with(|character: char| character.is_ascii_digit()) {
+        ident.insert(0, '_');
+    } else if KEYWORDS.contains(&ident.as_str()) {
+        ident.push('_');
+    }
+
+    let mut unique_ident = ident.clone();
+    let mut counter = 2;
+    while !idents.insert(unique_ident.clone()) {
+        unique_ident = format!("{ident}_{counter}");
+        counter += 1;
+    }
+    unique_ident
+}
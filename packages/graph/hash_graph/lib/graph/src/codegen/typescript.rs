@@ -0,0 +1,235 @@
+//! Generation of TypeScript bindings.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+};
+
+use super::{camel_case, doc_lines, Selection};
+use crate::ontology::schema::{
+    DataTypeSchema, EntityTypeSchema, PropertyTypeSchema, PropertyValues, Reference, ValueOrArray,
+};
+
+pub(super) fn generate(selection: &Selection) -> Result<String, fmt::Error> {
+    let mut generator = Generator {
+        selection,
+        code: String::from(
+            "// This file was generated from the HASH ontology. Do not edit it manually.\n",
+        ),
+    };
+
+    for entity_type in &selection.entity_types {
+        generator.entity_type(entity_type)?;
+    }
+    for property_type in &selection.property_types {
+        generator.property_type(property_type)?;
+    }
+    for data_type in &selection.data_types {
+        generator.data_type(data_type)?;
+    }
+
+    Ok(generator.code)
+}
+
+/// Returns `value` as a string literal.
+fn literal(value: &str) -> String {
+    serde_json::Value::from(value).to_string()
+}
+
+struct Generator<'s> {
+    selection: &'s Selection<'s>,
+    code: String,
+}
+
+impl Generator<'_> {
+    fn docs(&mut self, lines: &[String]) -> fmt::Result {
+        writeln!(self.code, "/**")?;
+        for line in lines {
+            let line = line.replace("*/", "*\\/");
+            if line.is_empty() {
+                writeln!(self.code, " *")?;
+            } else {
+                writeln!(self.code, " * {line}")?;
+            }
+        }
+        writeln!(self.code, " */")
+    }
+
+    fn data_type(&mut self, data_type: &DataTypeSchema) -> fmt::Result {
+        let primitive = match data_type.json_type.as_str() {
+            "string" => "string",
+            "number" => "number",
+            "boolean" => "boolean",
+            "null" => "null",
+            "object" => "Record<string, unknown>",
+            "array" => "unknown[]",
+            _ => "unknown",
+        };
+
+        writeln!(self.code)?;
+        self.docs(&doc_lines(
+            &data_type.title,
+            data_type.description.as_deref(),
+            &data_type.id,
+        ))?;
+        writeln!(
+            self.code,
+            "export type {} = {primitive};",
+            self.selection.name(&data_type.id)
+        )
+    }
+
+    fn property_type(&mut self, property_type: &PropertyTypeSchema) -> fmt::Result {
+        let name = self.selection.name(&property_type.id);
+
+        writeln!(self.code)?;
+        self.docs(&doc_lines(
+            &property_type.title,
+            property_type.description.as_deref(),
+            &property_type.id,
+        ))?;
+        if let [PropertyValues::PropertyTypeObject(object)] = property_type.one_of.as_slice() {
+            writeln!(self.code, "export interface {name} {{")?;
+            self.properties(&object.properties, &object.required)?;
+            writeln!(self.code, "}}")
+        } else {
+            let ty = self.property_values(&property_type.one_of);
+            writeln!(self.code, "export type {name} = {ty};")
+        }
+    }
+
+    /// Returns the type of the `values` as inline type expression.
+    fn property_values(&self, values: &[PropertyValues]) -> String {
+        values
+            .iter()
+            .map(|value| match value {
+                PropertyValues::DataTypeReference(reference) => {
+                    self.selection.name(&reference.uri).to_owned()
+                }
+                PropertyValues::PropertyTypeObject(object) => {
+                    let properties = object
+                        .properties
+                        .iter()
+                        .map(|(key, property)| {
+                            let optional = if object.required.contains(key) {
+                                ""
+                            } else {
+                                "?"
+                            };
+                            format!("{}{optional}: {}", literal(key), self.property(property))
+                        })
+                        .collect::<Vec<_>>();
+                    format!("{{ {} }}", properties.join("; "))
+                }
+                PropertyValues::ArrayOfPropertyValues(array) => {
+                    if let [_] = array.items.one_of.as_slice() {
+                        format!("{}[]", self.property_values(&array.items.one_of))
+                    } else {
+                        format!("({})[]", self.property_values(&array.items.one_of))
+                    }
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    fn property(&self, property: &ValueOrArray<Reference>) -> String {
+        match property {
+            ValueOrArray::Value(reference) => self.selection.name(&reference.uri).to_owned(),
+            ValueOrArray::Array(array) => format!("{}[]", self.selection.name(&array.items.uri)),
+        }
+    }
+
+    fn properties(
+        &mut self,
+        properties: &BTreeMap<String, ValueOrArray<Reference>>,
+        required: &[String],
+    ) -> fmt::Result {
+        for (key, property) in properties {
+            let optional = if required.contains(key) { "" } else { "?" };
+            writeln!(
+                self.code,
+                "  {}{optional}: {};",
+                literal(key),
+                self.property(property)
+            )?;
+        }
+        Ok(())
+    }
+
+    fn entity_type(&mut self, entity_type: &EntityTypeSchema) -> fmt::Result {
+        let name = self.selection.name(&entity_type.id);
+        let parents = entity_type
+            .all_of
+            .iter()
+            .map(|reference| self.selection.name(&reference.uri))
+            .collect::<Vec<_>>();
+
+        writeln!(self.code)?;
+        self.docs(&doc_lines(
+            &entity_type.title,
+            entity_type.description.as_deref(),
+            &entity_type.id,
+        ))?;
+        if parents.is_empty() {
+            writeln!(self.code, "export interface {name} {{")?;
+        } else {
+            writeln!(
+                self.code,
+                "export interface {name} extends {} {{",
+                parents.join(", ")
+            )?;
+        }
+        self.properties(&entity_type.properties, &entity_type.required)?;
+        writeln!(self.code, "}}")?;
+
+        writeln!(self.code)?;
+        writeln!(
+            self.code,
+            "export const {name}EntityTypeId = {};",
+            literal(&entity_type.id.to_string())
+        )?;
+
+        if !entity_type.links.is_empty() {
+            self.links(name, entity_type)?;
+        }
+        Ok(())
+    }
+
+    fn links(&mut self, name: &str, entity_type: &EntityTypeSchema) -> fmt::Result {
+        writeln!(self.code)?;
+        writeln!(
+            self.code,
+            "/** The links, which can be created from an entity of type `{name}`. */"
+        )?;
+        writeln!(self.code, "export interface {name}Links {{")?;
+        for (link_type_id, link) in &entity_type.links {
+            let link_entity = self.selection.name(link_type_id);
+            let destinations = link
+                .items
+                .one_of
+                .iter()
+                .map(|reference| self.selection.name(&reference.uri))
+                .collect::<Vec<_>>();
+
+            writeln!(self.code, "  {}: {{", literal(link_type_id))?;
+            writeln!(self.code, "    linkEntity: {link_entity};")?;
+            writeln!(self.code, "    destination: {};", destinations.join(" | "))?;
+            writeln!(self.code, "    ordered: {};", link.ordered)?;
+            writeln!(self.code, "  }};")?;
+        }
+        writeln!(self.code, "}}")?;
+
+        writeln!(self.code)?;
+        writeln!(self.code, "export const {name}LinkTypeIds = {{")?;
+        for link_type_id in entity_type.links.keys() {
+            writeln!(
+                self.code,
+                "  {}: {},",
+                camel_case(self.selection.name(link_type_id)),
+                literal(link_type_id)
+            )?;
+        }
+        writeln!(self.code, "}} as const;")
+    }
+}
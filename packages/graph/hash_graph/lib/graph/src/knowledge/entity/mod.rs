@@ -8,14 +8,16 @@ use type_system::uri::{BaseUri, VersionedUri};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-pub use self::query::{EntityQueryPath, EntityQueryPathVisitor, EntityQueryToken};
+pub use self::query::{
+    EntityQueryPath, EntityQueryPathVisitor, EntityQueryToken, PropertyProvenanceField,
+};
 use crate::{
     identifier::{
         knowledge::{EntityEditionId, EntityId, EntityVersion},
         time::TimeAxis,
         EntityVertexId,
     },
-    provenance::ProvenanceMetadata,
+    provenance::{PropertyProvenance, ProvenanceMetadata},
     store::{query::Filter, Record},
 };
 
@@ -55,6 +57,45 @@ impl fmt::Display for EntityUuid {
     }
 }
 
+/// Identifies a draft, in which entities can be created and updated without being visible to
+/// queries, which don't include the draft.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    ToSchema,
+    FromSql,
+    ToSql,
+)]
+#[postgres(transparent)]
+#[repr(transparent)]
+pub struct DraftId(Uuid);
+
+impl DraftId {
+    #[must_use]
+    pub const fn new(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+
+    #[must_use]
+    pub const fn as_uuid(self) -> Uuid {
+        self.0
+    }
+}
+
+impl fmt::Display for DraftId {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{}", &self.0)
+    }
+}
+
 #[derive(
     Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, FromSql, ToSql,
 )]
@@ -67,6 +108,11 @@ impl LinkOrder {
     pub const fn new(order: i32) -> Self {
         Self(order)
     }
+
+    #[must_use]
+    pub const fn as_i32(self) -> i32 {
+        self.0
+    }
 }
 
 /// The properties of an entity.
@@ -90,6 +136,23 @@ impl EntityProperties {
     }
 }
 
+/// The [`PropertyProvenance`] of each property of an entity, keyed by the property's base URI.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(value_type = Object)]
+pub struct EntityPropertyProvenance(HashMap<BaseUri, PropertyProvenance>);
+
+impl EntityPropertyProvenance {
+    #[must_use]
+    pub const fn properties(&self) -> &HashMap<BaseUri, PropertyProvenance> {
+        &self.0
+    }
+
+    #[must_use]
+    pub fn get(&self, property: &BaseUri) -> Option<&PropertyProvenance> {
+        self.0.get(property)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct EntityLinkOrder {
@@ -180,6 +243,8 @@ pub struct EntityMetadata {
     #[serde(rename = "provenance")]
     provenance_metadata: ProvenanceMetadata,
     archived: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    draft_id: Option<DraftId>,
 }
 
 impl EntityMetadata {
@@ -190,6 +255,7 @@ impl EntityMetadata {
         entity_type_id: VersionedUri,
         provenance_metadata: ProvenanceMetadata,
         archived: bool,
+        draft_id: Option<DraftId>,
     ) -> Self {
         Self {
             edition_id,
@@ -197,6 +263,7 @@ impl EntityMetadata {
             entity_type_id,
             provenance_metadata,
             archived,
+            draft_id,
         }
     }
 
@@ -224,6 +291,12 @@ impl EntityMetadata {
     pub const fn archived(&self) -> bool {
         self.archived
     }
+
+    /// Returns the draft this edition belongs to, or `None` if the edition is live.
+    #[must_use]
+    pub const fn draft_id(&self) -> Option<DraftId> {
+        self.draft_id
+    }
 }
 
 /// A record of an [`Entity`] that has been persisted in the datastore, with its associated
@@ -232,6 +305,7 @@ impl EntityMetadata {
 #[serde(rename_all = "camelCase")]
 pub struct Entity {
     properties: EntityProperties,
+    property_provenance: EntityPropertyProvenance,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     link_data: Option<LinkData>,
     metadata: EntityMetadata,
@@ -239,17 +313,21 @@ pub struct Entity {
 
 impl Entity {
     #[must_use]
+    #[expect(clippy::too_many_arguments)]
     pub const fn new(
         properties: EntityProperties,
+        property_provenance: EntityPropertyProvenance,
         link_data: Option<LinkData>,
         identifier: EntityEditionId,
         version: EntityVersion,
         entity_type_id: VersionedUri,
         provenance_metadata: ProvenanceMetadata,
         archived: bool,
+        draft_id: Option<DraftId>,
     ) -> Self {
         Self {
             properties,
+            property_provenance,
             link_data,
             metadata: EntityMetadata::new(
                 identifier,
@@ -257,6 +335,7 @@ impl Entity {
                 entity_type_id,
                 provenance_metadata,
                 archived,
+                draft_id,
             ),
         }
     }
@@ -266,6 +345,14 @@ impl Entity {
         &self.properties
     }
 
+    /// Returns who set each of the [`properties`] and when.
+    ///
+    /// [`properties`]: Self::properties
+    #[must_use]
+    pub const fn property_provenance(&self) -> &EntityPropertyProvenance {
+        &self.property_provenance
+    }
+
     #[must_use]
     pub const fn link_data(&self) -> Option<LinkData> {
         self.link_data
@@ -299,6 +386,105 @@ impl Record for Entity {
     }
 }
 
+/// An [`Entity`] which was updated between two points in [`TransactionTime`].
+///
+/// [`TransactionTime`]: crate::identifier::time::TransactionTime
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityEditionUpdate {
+    old_edition_id: EntityEditionId,
+    new_edition_id: EntityEditionId,
+}
+
+impl EntityEditionUpdate {
+    #[must_use]
+    pub const fn new(old_edition_id: EntityEditionId, new_edition_id: EntityEditionId) -> Self {
+        Self {
+            old_edition_id,
+            new_edition_id,
+        }
+    }
+
+    #[must_use]
+    pub const fn old_edition_id(&self) -> EntityEditionId {
+        self.old_edition_id
+    }
+
+    #[must_use]
+    pub const fn new_edition_id(&self) -> EntityEditionId {
+        self.new_edition_id
+    }
+}
+
+/// The changes of [`Entities`] between two points in [`TransactionTime`].
+///
+/// Every entity is contained in at most one of the lists. An entity, which was archived in the
+/// window, is only reported as archived, even if it was created or updated in the same window.
+///
+/// [`Entities`]: Entity
+/// [`TransactionTime`]: crate::identifier::time::TransactionTime
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityDiff {
+    created: Vec<EntityEditionId>,
+    updated: Vec<EntityEditionUpdate>,
+    archived: Vec<EntityEditionUpdate>,
+}
+
+impl EntityDiff {
+    /// Classifies the change of an entity from its edition at the start of the window, `old`, to
+    /// its edition at the end of the window, `new`.
+    pub(crate) fn record(&mut self, old: Option<&EntityMetadata>, new: &EntityMetadata) {
+        match old {
+            Some(old) if old.archived() => {
+                if !new.archived() && old.edition_id() != new.edition_id() {
+                    self.updated
+                        .push(EntityEditionUpdate::new(old.edition_id(), new.edition_id()));
+                }
+            }
+            Some(old) if new.archived() => self
+                .archived
+                .push(EntityEditionUpdate::new(old.edition_id(), new.edition_id())),
+            Some(old) => {
+                if old.edition_id() != new.edition_id() {
+                    self.updated
+                        .push(EntityEditionUpdate::new(old.edition_id(), new.edition_id()));
+                }
+            }
+            None if new.archived() => self
+                .archived
+                .push(EntityEditionUpdate::new(new.edition_id(), new.edition_id())),
+            None => self.created.push(new.edition_id()),
+        }
+    }
+
+    /// Entities, which did not exist at the start of the window.
+    #[must_use]
+    pub fn created(&self) -> &[EntityEditionId] {
+        &self.created
+    }
+
+    /// Entities, which existed at both ends of the window but were changed in between.
+    #[must_use]
+    pub fn updated(&self) -> &[EntityEditionUpdate] {
+        &self.updated
+    }
+
+    /// Entities, which were archived in the window.
+    ///
+    /// If the entity was created in the window as well, the old edition id is the same as the new
+    /// one.
+    #[must_use]
+    pub fn archived(&self) -> &[EntityEditionUpdate] {
+        &self.archived
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty() && self.updated.is_empty() && self.archived.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
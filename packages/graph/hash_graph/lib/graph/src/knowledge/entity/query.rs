@@ -11,7 +11,7 @@ use crate::{
     store::query::{ParameterType, QueryPath},
 };
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EntityQueryPath<'p> {
     /// The [`EntityUuid`] of the [`EntityId`] belonging to the [`Entity`].
     ///
@@ -92,6 +92,24 @@ pub enum EntityQueryPath<'p> {
     ///
     /// [`Entity`]: crate::knowledge::Entity
     Archived,
+    /// The [`DraftId`] of the draft the edition of the [`Entity`] belongs to.
+    ///
+    /// Only editions of the draft included in the [`StructuralQuery`] are returned, so this is
+    /// `null` for all live editions.
+    ///
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// # use serde_json::json;
+    /// # use graph::knowledge::EntityQueryPath;
+    /// let path = EntityQueryPath::deserialize(json!(["draftId"]))?;
+    /// assert_eq!(path, EntityQueryPath::DraftId);
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    ///
+    /// [`DraftId`]: crate::knowledge::DraftId
+    /// [`StructuralQuery`]: crate::shared::subgraph::query::StructuralQuery
+    /// [`Entity`]: crate::knowledge::Entity
+    DraftId,
     /// The [`UpdatedById`] of the [`ProvenanceMetadata`] belonging to the [`Entity`].
     ///
     /// ```rust
@@ -252,6 +270,60 @@ pub enum EntityQueryPath<'p> {
     /// [`Entity`]: crate::knowledge::Entity
     /// [`Entity::properties()`]: crate::knowledge::Entity::properties
     Properties(Option<Cow<'p, str>>),
+    /// Corresponds to [`Entity::property_provenance()`].
+    ///
+    /// Deserializes from `["propertyProvenance", ...]` where `...` is a property URI of an
+    /// [`Entity`] followed by a field of its [`PropertyProvenance`].
+    ///
+    /// ```rust
+    /// # use std::borrow::Cow;
+    /// # use serde::Deserialize;
+    /// # use serde_json::json;
+    /// # use graph::knowledge::{EntityQueryPath, PropertyProvenanceField};
+    /// let path = EntityQueryPath::deserialize(json!([
+    ///     "propertyProvenance",
+    ///     "https://blockprotocol.org/@blockprotocol/types/property-type/name/",
+    ///     "updatedById"
+    /// ]))?;
+    /// assert_eq!(
+    ///     path,
+    ///     EntityQueryPath::PropertyProvenance(Some((
+    ///         Cow::Borrowed("https://blockprotocol.org/@blockprotocol/types/property-type/name/"),
+    ///         PropertyProvenanceField::UpdatedById
+    ///     )))
+    /// );
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    ///
+    /// [`Entity`]: crate::knowledge::Entity
+    /// [`Entity::property_provenance()`]: crate::knowledge::Entity::property_provenance
+    /// [`PropertyProvenance`]: crate::provenance::PropertyProvenance
+    PropertyProvenance(Option<(Cow<'p, str>, PropertyProvenanceField)>),
+}
+
+/// A field of the [`PropertyProvenance`] of a single property.
+///
+/// [`PropertyProvenance`]: crate::provenance::PropertyProvenance
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum PropertyProvenanceField {
+    UpdatedById,
+    UpdatedAt,
+    Source,
+}
+
+impl PropertyProvenanceField {
+    /// The key of the field in the serialized [`PropertyProvenance`].
+    ///
+    /// [`PropertyProvenance`]: crate::provenance::PropertyProvenance
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::UpdatedById => "updatedById",
+            Self::UpdatedAt => "updatedAt",
+            Self::Source => "source",
+        }
+    }
 }
 
 impl fmt::Display for EntityQueryPath<'_> {
@@ -265,9 +337,14 @@ impl fmt::Display for EntityQueryPath<'_> {
             Self::TransactionTime => fmt.write_str("transactionTime"),
             Self::ProjectedTime => fmt.write_str("projectedTime"),
             Self::Archived => fmt.write_str("archived"),
+            Self::DraftId => fmt.write_str("draftId"),
             Self::Type(path) => write!(fmt, "type.{path}"),
             Self::Properties(Some(property)) => write!(fmt, "properties.{property}"),
             Self::Properties(None) => fmt.write_str("properties"),
+            Self::PropertyProvenance(Some((property, field))) => {
+                write!(fmt, "propertyProvenance.{property}.{}", field.as_str())
+            }
+            Self::PropertyProvenance(None) => fmt.write_str("propertyProvenance"),
             Self::IncomingLinks(link) => write!(fmt, "incomingLinks.{link}"),
             Self::OutgoingLinks(link) => write!(fmt, "outgoingLinks.{link}"),
             Self::LeftEntity(path) => write!(fmt, "leftEntityUuid.{path}"),
@@ -281,7 +358,7 @@ impl fmt::Display for EntityQueryPath<'_> {
 impl QueryPath for EntityQueryPath<'_> {
     fn expected_type(&self) -> ParameterType {
         match self {
-            Self::Uuid | Self::OwnedById | Self::UpdatedById => ParameterType::Uuid,
+            Self::Uuid | Self::OwnedById | Self::UpdatedById | Self::DraftId => ParameterType::Uuid,
             Self::RecordId => ParameterType::UnsignedInteger,
             Self::LeftEntity(path)
             | Self::RightEntity(path)
@@ -290,7 +367,9 @@ impl QueryPath for EntityQueryPath<'_> {
             Self::DecisionTime | Self::TransactionTime => ParameterType::Timespan,
             Self::ProjectedTime => ParameterType::Timestamp,
             Self::Type(path) => path.expected_type(),
-            Self::Properties(_) => ParameterType::Any,
+            Self::Properties(_) | Self::PropertyProvenance(None) => ParameterType::Any,
+            // The fields are compared as they are stored in the JSON object
+            Self::PropertyProvenance(Some(_)) => ParameterType::Text,
             Self::LeftToRightOrder | Self::RightToLeftOrder => ParameterType::Number,
             Self::Archived => ParameterType::Boolean,
         }
@@ -306,10 +385,12 @@ pub enum EntityQueryToken {
     Version,
     RecordId,
     Archived,
+    DraftId,
     OwnedById,
     UpdatedById,
     Type,
     Properties,
+    PropertyProvenance,
     IncomingLinks,
     OutgoingLinks,
     LeftEntity,
@@ -325,10 +406,10 @@ pub struct EntityQueryPathVisitor {
 }
 
 impl EntityQueryPathVisitor {
-    pub const EXPECTING: &'static str = "one of `uuid`, `version`, `recordId`, `archived`, \
-                                         `ownedById`, `updatedById`, `type`, `properties`, \
-                                         `incomingLinks`, `outgoingLinks`, `leftEntity`, \
-                                         `rightEntity`, `leftToRightOrder`, `rightToLeftOrder`";
+    pub const EXPECTING: &'static str =
+        "one of `uuid`, `version`, `recordId`, `archived`, `draftId`, `ownedById`, `updatedById`, \
+         `type`, `properties`, `propertyProvenance`, `incomingLinks`, `outgoingLinks`, \
+         `leftEntity`, `rightEntity`, `leftToRightOrder`, `rightToLeftOrder`";
 
     #[must_use]
     pub const fn new(position: usize) -> Self {
@@ -359,6 +440,7 @@ impl<'de> Visitor<'de> for EntityQueryPathVisitor {
             EntityQueryToken::UpdatedById => EntityQueryPath::UpdatedById,
             EntityQueryToken::Version => EntityQueryPath::ProjectedTime,
             EntityQueryToken::Archived => EntityQueryPath::Archived,
+            EntityQueryToken::DraftId => EntityQueryPath::DraftId,
             EntityQueryToken::Type => EntityQueryPath::Type(
                 EntityTypeQueryPathVisitor::new(self.position).visit_seq(seq)?,
             ),
@@ -368,6 +450,16 @@ impl<'de> Visitor<'de> for EntityQueryPathVisitor {
                     .ok_or_else(|| de::Error::invalid_length(self.position, &self))?;
                 EntityQueryPath::Properties(Some(property))
             }
+            EntityQueryToken::PropertyProvenance => {
+                let property = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(self.position, &self))?;
+                self.position += 1;
+                let field = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(self.position, &self))?;
+                EntityQueryPath::PropertyProvenance(Some((property, field)))
+            }
             EntityQueryToken::OutgoingLinks => {
                 EntityQueryPath::OutgoingLinks(Box::new(Self::new(self.position).visit_seq(seq)?))
             }
@@ -412,6 +504,7 @@ mod tests {
     fn deserialization() {
         assert_eq!(deserialize(["version"]), EntityQueryPath::ProjectedTime);
         assert_eq!(deserialize(["ownedById"]), EntityQueryPath::OwnedById);
+        assert_eq!(deserialize(["draftId"]), EntityQueryPath::DraftId);
         assert_eq!(
             deserialize(["type", "version"]),
             EntityQueryPath::Type(EntityTypeQueryPath::Version)
@@ -425,6 +518,17 @@ mod tests {
                 "https://blockprotocol.org/@alice/types/property-type/name/"
             )))
         );
+        assert_eq!(
+            deserialize([
+                "propertyProvenance",
+                "https://blockprotocol.org/@alice/types/property-type/name/",
+                "source"
+            ]),
+            EntityQueryPath::PropertyProvenance(Some((
+                Cow::Borrowed("https://blockprotocol.org/@alice/types/property-type/name/"),
+                PropertyProvenanceField::Source
+            )))
+        );
         assert_eq!(
             deserialize(["leftEntity", "uuid"]),
             EntityQueryPath::LeftEntity(Box::new(EntityQueryPath::Uuid))
@@ -4,6 +4,7 @@
 mod entity;
 
 pub use self::entity::{
-    Entity, EntityLinkOrder, EntityMetadata, EntityProperties, EntityQueryPath,
-    EntityQueryPathVisitor, EntityQueryToken, EntityUuid, LinkData, LinkOrder,
+    DraftId, Entity, EntityDiff, EntityEditionUpdate, EntityLinkOrder, EntityMetadata,
+    EntityProperties, EntityPropertyProvenance, EntityQueryPath, EntityQueryPathVisitor,
+    EntityQueryToken, EntityUuid, LinkData, LinkOrder, PropertyProvenanceField,
 };
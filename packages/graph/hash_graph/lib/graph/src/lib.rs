@@ -22,6 +22,8 @@
 
 pub mod api;
 
+pub mod codegen;
+
 pub mod knowledge;
 pub mod ontology;
 mod shared;
@@ -30,4 +32,6 @@ pub mod store;
 
 pub mod logging;
 
+pub mod metrics;
+
 pub use self::shared::*;
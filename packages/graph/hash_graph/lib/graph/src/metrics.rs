@@ -0,0 +1,219 @@
+//! Prometheus metrics collected by the Graph.
+//!
+//! The metrics are collected in a global registry and can be rendered in the Prometheus text
+//! exposition format by calling [`encode`].
+
+use std::{
+    any::type_name,
+    fmt,
+    sync::LazyLock,
+    time::{Duration, Instant},
+};
+
+use error_stack::{Context, IntoReport, Report, Result, ResultExt};
+use prometheus::{
+    exponential_buckets, Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+};
+
+#[derive(Debug)]
+pub struct MetricsError;
+
+impl Context for MetricsError {}
+
+impl fmt::Display for MetricsError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Could not encode metrics")
+    }
+}
+
+struct Metrics {
+    registry: Registry,
+    http_request_duration: HistogramVec,
+    store_operations: IntCounterVec,
+    store_operation_duration: HistogramVec,
+    store_pool_connections: IntGaugeVec,
+    ontology_cache_lookups: IntCounterVec,
+    errors: IntCounterVec,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some("graph".to_owned()), None)
+            .expect("metric registry should be valid");
+
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "Latency of HTTP requests by matched route",
+            )
+            .buckets(
+                exponential_buckets(0.001, 2.0, 15).expect("histogram buckets should be valid"),
+            ),
+            &["method", "route", "status"],
+        )
+        .expect("metric should be valid");
+
+        let store_operations = IntCounterVec::new(
+            Opts::new(
+                "store_operations_total",
+                "Number of operations executed on the store",
+            ),
+            &["operation", "record"],
+        )
+        .expect("metric should be valid");
+
+        let store_operation_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "store_operation_duration_seconds",
+                "Duration of operations executed on the store",
+            )
+            .buckets(
+                exponential_buckets(0.0005, 2.0, 15).expect("histogram buckets should be valid"),
+            ),
+            &["operation", "record"],
+        )
+        .expect("metric should be valid");
+
+        let store_pool_connections = IntGaugeVec::new(
+            Opts::new(
+                "store_pool_connections",
+                "Number of connections held by the store pool",
+            ),
+            &["pool", "state"],
+        )
+        .expect("metric should be valid");
+
+        let ontology_cache_lookups = IntCounterVec::new(
+            Opts::new(
+                "ontology_cache_lookups_total",
+                "Number of lookups in the ontology cache by result",
+            ),
+            &["record", "result"],
+        )
+        .expect("metric should be valid");
+
+        let errors = IntCounterVec::new(
+            Opts::new("errors_total", "Number of reported errors by context"),
+            &["context"],
+        )
+        .expect("metric should be valid");
+
+        for collector in [
+            Box::new(http_request_duration.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(store_operations.clone()),
+            Box::new(store_operation_duration.clone()),
+            Box::new(store_pool_connections.clone()),
+            Box::new(ontology_cache_lookups.clone()),
+            Box::new(errors.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("metric should only be registered once");
+        }
+
+        Self {
+            registry,
+            http_request_duration,
+            store_operations,
+            store_operation_duration,
+            store_pool_connections,
+            ontology_cache_lookups,
+            errors,
+        }
+    }
+}
+
+static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+/// Renders all collected metrics in the Prometheus text exposition format.
+///
+/// # Errors
+///
+/// - if encoding the metrics failed
+pub fn encode() -> Result<String, MetricsError> {
+    let mut buffer = Vec::new();
+    TextEncoder::new()
+        .encode(&METRICS.registry.gather(), &mut buffer)
+        .into_report()
+        .change_context(MetricsError)?;
+    String::from_utf8(buffer)
+        .into_report()
+        .change_context(MetricsError)
+}
+
+/// The content type of the output of [`encode`].
+pub const CONTENT_TYPE: &str = prometheus::TEXT_FORMAT;
+
+pub(crate) fn record_http_request(method: &str, route: &str, status: u16, latency: Duration) {
+    METRICS
+        .http_request_duration
+        .with_label_values(&[method, route, &status.to_string()])
+        .observe(latency.as_secs_f64());
+}
+
+/// Records the number of connections of a store pool.
+pub(crate) fn record_pool_state(pool: &str, connections: u32, idle_connections: u32) {
+    METRICS
+        .store_pool_connections
+        .with_label_values(&[pool, "active"])
+        .set(i64::from(connections.saturating_sub(idle_connections)));
+    METRICS
+        .store_pool_connections
+        .with_label_values(&[pool, "idle"])
+        .set(i64::from(idle_connections));
+}
+
+/// Counts a lookup of a `record` in the ontology cache.
+pub(crate) fn record_cache_lookup(record: &'static str, hit: bool) {
+    METRICS
+        .ontology_cache_lookups
+        .with_label_values(&[record, if hit { "hit" } else { "miss" }])
+        .inc();
+}
+
+/// Counts the error by the type name of the current context of the [`Report`].
+pub fn record_error<C>(_report: &Report<C>) {
+    let context = type_name::<C>();
+    // Strip the module path and generic parameters to keep the cardinality low
+    let context = context
+        .split_once('<')
+        .map_or(context, |(path, _)| path)
+        .rsplit("::")
+        .next()
+        .unwrap_or(context);
+
+    METRICS.errors.with_label_values(&[context]).inc();
+}
+
+/// Measures an operation on the store.
+///
+/// The operation is counted and its duration is recorded, when the timer is dropped.
+pub(crate) struct StoreOperationTimer {
+    operation: &'static str,
+    record: &'static str,
+    start: Instant,
+}
+
+impl Drop for StoreOperationTimer {
+    fn drop(&mut self) {
+        let labels = [self.operation, self.record];
+        METRICS.store_operations.with_label_values(&labels).inc();
+        METRICS
+            .store_operation_duration
+            .with_label_values(&labels)
+            .observe(self.start.elapsed().as_secs_f64());
+    }
+}
+
+/// Starts measuring an `operation` on the store for the specified `record` kind.
+pub(crate) fn store_operation(
+    operation: &'static str,
+    record: &'static str,
+) -> StoreOperationTimer {
+    StoreOperationTimer {
+        operation,
+        record,
+        start: Instant::now(),
+    }
+}
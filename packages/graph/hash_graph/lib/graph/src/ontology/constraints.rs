@@ -0,0 +1,429 @@
+//! Constraints refining the primitive type of a [`DataType`].
+//!
+//! [`DataType`]s store keywords beyond the primitive `type` as additional properties. The keywords
+//! listed in [`DataTypeConstraints`] are understood by the graph: they are checked when a
+//! [`DataType`] is created or updated and enforced for every entity property value, which refers
+//! to the [`DataType`] through a [`PropertyType`].
+//!
+//! [`DataType`]: type_system::DataType
+//! [`PropertyType`]: type_system::PropertyType
+
+use core::fmt;
+
+use error_stack::{Context, IntoReport, Report, Result, ResultExt};
+use regex::Regex;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use type_system::{repr, DataType};
+
+use crate::ontology::schema::DataTypeSchema;
+
+#[derive(Debug)]
+#[must_use]
+pub struct InvalidDataTypeConstraint;
+
+impl fmt::Display for InvalidDataTypeConstraint {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("The data type has an invalid constraint")
+    }
+}
+
+impl Context for InvalidDataTypeConstraint {}
+
+#[derive(Debug)]
+#[must_use]
+pub struct ConstraintViolation;
+
+impl fmt::Display for ConstraintViolation {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("The value does not satisfy the constraints of its type")
+    }
+}
+
+impl Context for ConstraintViolation {}
+
+/// A well-known string format.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Format {
+    /// An e-mail address, e.g. `alice@example.com`.
+    Email,
+    /// An absolute URI, e.g. `https://example.com/path`.
+    Uri,
+    /// A timestamp as specified in RFC 3339, e.g. `2023-01-01T12:00:00Z`.
+    DateTime,
+}
+
+impl Format {
+    fn matches(self, value: &str) -> bool {
+        match self {
+            Self::Email => value.split_once('@').map_or(false, |(local, domain)| {
+                !local.is_empty()
+                    && domain.contains('.')
+                    && !domain.starts_with('.')
+                    && !domain.ends_with('.')
+                    && !domain.contains('@')
+                    && !value.contains(char::is_whitespace)
+            }),
+            Self::Uri => value
+                .parse::<hyper::Uri>()
+                .map_or(false, |uri| uri.scheme().is_some()),
+            Self::DateTime => chrono::DateTime::parse_from_rfc3339(value).is_ok(),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Email => fmt.write_str("email"),
+            Self::Uri => fmt.write_str("uri"),
+            Self::DateTime => fmt.write_str("date-time"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Pattern(Regex);
+
+impl<'de> Deserialize<'de> for Pattern {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let pattern = String::deserialize(deserializer)?;
+        Regex::new(&pattern)
+            .map(Self)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// The constraints of a [`DataType`] in addition to its primitive type.
+///
+/// [`DataType`]: type_system::DataType
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataTypeConstraints {
+    #[serde(default)]
+    minimum: Option<f64>,
+    #[serde(default)]
+    maximum: Option<f64>,
+    #[serde(default)]
+    min_length: Option<usize>,
+    #[serde(default)]
+    max_length: Option<usize>,
+    #[serde(default)]
+    pattern: Option<Pattern>,
+    #[serde(default, rename = "enum")]
+    enum_values: Option<Vec<Value>>,
+    #[serde(default, rename = "const")]
+    const_value: Option<Value>,
+    #[serde(default)]
+    format: Option<Format>,
+    #[serde(default)]
+    unit: Option<String>,
+}
+
+impl DataTypeConstraints {
+    #[must_use]
+    pub const fn minimum(&self) -> Option<f64> {
+        self.minimum
+    }
+
+    #[must_use]
+    pub const fn maximum(&self) -> Option<f64> {
+        self.maximum
+    }
+
+    #[must_use]
+    pub const fn min_length(&self) -> Option<usize> {
+        self.min_length
+    }
+
+    #[must_use]
+    pub const fn max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+
+    #[must_use]
+    pub fn pattern(&self) -> Option<&str> {
+        self.pattern.as_ref().map(|pattern| pattern.0.as_str())
+    }
+
+    #[must_use]
+    pub fn enum_values(&self) -> Option<&[Value]> {
+        self.enum_values.as_deref()
+    }
+
+    #[must_use]
+    pub const fn const_value(&self) -> Option<&Value> {
+        self.const_value.as_ref()
+    }
+
+    #[must_use]
+    pub const fn format(&self) -> Option<Format> {
+        self.format
+    }
+
+    /// The unit of measurement of the values, e.g. `"kg"`.
+    ///
+    /// The unit is informational only and not enforced.
+    #[must_use]
+    pub fn unit(&self) -> Option<&str> {
+        self.unit.as_deref()
+    }
+
+    /// Reads the constraints of the [`DataType`] and checks that they are consistent.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidDataTypeConstraint`], if a constraint could not be parsed, does not apply to the
+    ///   primitive type of the [`DataType`], or contradicts another constraint
+    pub fn from_data_type(data_type: &DataType) -> Result<Self, InvalidDataTypeConstraint> {
+        let schema: DataTypeSchema = serde_json::to_value(repr::DataType::from(data_type.clone()))
+            .and_then(serde_json::from_value)
+            .into_report()
+            .change_context(InvalidDataTypeConstraint)?;
+        schema.constraints.check(&schema.json_type)?;
+        Ok(schema.constraints)
+    }
+
+    /// Checks that the constraints apply to `json_type` and are consistent with each other.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidDataTypeConstraint`], if a constraint does not apply to `json_type` or
+    ///   contradicts another constraint
+    pub(crate) fn check(&self, json_type: &str) -> Result<(), InvalidDataTypeConstraint> {
+        let is_numeric = matches!(json_type, "number" | "integer");
+        let is_string = json_type == "string";
+
+        if !is_numeric && (self.minimum.is_some() || self.maximum.is_some()) {
+            return Err(
+                Report::new(InvalidDataTypeConstraint).attach_printable(format!(
+                    "`minimum` and `maximum` only apply to numbers, but the type is `{json_type}`"
+                )),
+            );
+        }
+        if !is_string
+            && (self.min_length.is_some()
+                || self.max_length.is_some()
+                || self.pattern.is_some()
+                || self.format.is_some())
+        {
+            return Err(
+                Report::new(InvalidDataTypeConstraint).attach_printable(format!(
+                    "`minLength`, `maxLength`, `pattern`, and `format` only apply to strings, but \
+                     the type is `{json_type}`"
+                )),
+            );
+        }
+        if let (Some(minimum), Some(maximum)) = (self.minimum, self.maximum) {
+            if minimum > maximum {
+                return Err(
+                    Report::new(InvalidDataTypeConstraint).attach_printable(format!(
+                        "`minimum` ({minimum}) is greater than `maximum` ({maximum})"
+                    )),
+                );
+            }
+        }
+        if let (Some(min_length), Some(max_length)) = (self.min_length, self.max_length) {
+            if min_length > max_length {
+                return Err(
+                    Report::new(InvalidDataTypeConstraint).attach_printable(format!(
+                        "`minLength` ({min_length}) is greater than `maxLength` ({max_length})"
+                    )),
+                );
+            }
+        }
+        for value in self.enum_values.iter().flatten().chain(&self.const_value) {
+            if !matches_json_type(json_type, value) {
+                return Err(
+                    Report::new(InvalidDataTypeConstraint).attach_printable(format!(
+                        "`{value}` in `enum` or `const` is not of type `{json_type}`"
+                    )),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validates `value` against `json_type` and the constraints.
+    ///
+    /// # Errors
+    ///
+    /// - [`ConstraintViolation`], if `value` is not of type `json_type` or violates a constraint
+    pub(crate) fn validate(
+        &self,
+        json_type: &str,
+        value: &Value,
+    ) -> Result<(), ConstraintViolation> {
+        let violation =
+            |message: String| Err(Report::new(ConstraintViolation).attach_printable(message));
+
+        if !matches_json_type(json_type, value) {
+            return violation(format!("`{value}` is not of type `{json_type}`"));
+        }
+
+        if let Some(number) = value.as_f64() {
+            if let Some(minimum) = self.minimum {
+                if number < minimum {
+                    return violation(format!("`{number}` is less than the minimum of {minimum}"));
+                }
+            }
+            if let Some(maximum) = self.maximum {
+                if number > maximum {
+                    return violation(format!(
+                        "`{number}` is greater than the maximum of {maximum}"
+                    ));
+                }
+            }
+        }
+
+        if let Some(string) = value.as_str() {
+            let length = string.chars().count();
+            if let Some(min_length) = self.min_length {
+                if length < min_length {
+                    return violation(format!(
+                        "`{string}` is shorter than the minimum length of {min_length}"
+                    ));
+                }
+            }
+            if let Some(max_length) = self.max_length {
+                if length > max_length {
+                    return violation(format!(
+                        "`{string}` is longer than the maximum length of {max_length}"
+                    ));
+                }
+            }
+            if let Some(Pattern(pattern)) = &self.pattern {
+                if !pattern.is_match(string) {
+                    return violation(format!("`{string}` does not match the pattern `{pattern}`"));
+                }
+            }
+            if let Some(format) = self.format {
+                if !format.matches(string) {
+                    return violation(format!("`{string}` is not a valid `{format}`"));
+                }
+            }
+        }
+
+        if let Some(enum_values) = &self.enum_values {
+            if !enum_values.contains(value) {
+                return violation(format!("`{value}` is not one of the allowed values"));
+            }
+        }
+        if let Some(const_value) = &self.const_value {
+            if const_value != value {
+                return violation(format!("`{value}` is not equal to `{const_value}`"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn matches_json_type(json_type: &str, value: &Value) -> bool {
+    match json_type {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn constraints(json_type: &str, constraints: Value) -> DataTypeConstraints {
+        let constraints: DataTypeConstraints =
+            serde_json::from_value(constraints).expect("could not parse constraints");
+        constraints
+            .check(json_type)
+            .expect("constraints are inconsistent");
+        constraints
+    }
+
+    #[test]
+    fn numbers() {
+        let percentage = constraints("number", json!({ "minimum": 0, "maximum": 100 }));
+
+        percentage
+            .validate("number", &json!(42.5))
+            .expect("value should be valid");
+        assert!(percentage.validate("number", &json!(-1)).is_err());
+        assert!(percentage.validate("number", &json!(100.1)).is_err());
+        assert!(percentage.validate("number", &json!("50")).is_err());
+    }
+
+    #[test]
+    fn strings() {
+        let code = constraints(
+            "string",
+            json!({ "minLength": 2, "maxLength": 3, "pattern": "^[A-Z]+$" }),
+        );
+
+        code.validate("string", &json!("GBP"))
+            .expect("value should be valid");
+        assert!(code.validate("string", &json!("G")).is_err());
+        assert!(code.validate("string", &json!("GBPX")).is_err());
+        assert!(code.validate("string", &json!("gbp")).is_err());
+    }
+
+    #[test]
+    fn formats() {
+        let email = constraints("string", json!({ "format": "email" }));
+        email
+            .validate("string", &json!("alice@example.com"))
+            .expect("value should be valid");
+        assert!(email.validate("string", &json!("alice")).is_err());
+        assert!(email.validate("string", &json!("alice@example")).is_err());
+
+        let uri = constraints("string", json!({ "format": "uri" }));
+        uri.validate("string", &json!("https://example.com/path"))
+            .expect("value should be valid");
+        assert!(uri.validate("string", &json!("example")).is_err());
+
+        let date_time = constraints("string", json!({ "format": "date-time" }));
+        date_time
+            .validate("string", &json!("2023-01-01T12:00:00Z"))
+            .expect("value should be valid");
+        assert!(date_time.validate("string", &json!("2023-01-01")).is_err());
+    }
+
+    #[test]
+    fn enums() {
+        let unit = constraints("string", json!({ "enum": ["kg", "lb"] }));
+
+        unit.validate("string", &json!("kg"))
+            .expect("value should be valid");
+        assert!(unit.validate("string", &json!("g")).is_err());
+    }
+
+    #[test]
+    fn inconsistent() {
+        let parse = |json_type: &str, value: Value| {
+            serde_json::from_value::<DataTypeConstraints>(value)
+                .expect("could not parse constraints")
+                .check(json_type)
+        };
+
+        assert!(parse("number", json!({ "minimum": 10, "maximum": 0 })).is_err());
+        assert!(parse("string", json!({ "minLength": 10, "maxLength": 0 })).is_err());
+        assert!(parse("string", json!({ "minimum": 0 })).is_err());
+        assert!(parse("number", json!({ "pattern": "^[0-9]+$" })).is_err());
+        assert!(parse("string", json!({ "enum": [1, 2] })).is_err());
+        assert!(serde_json::from_value::<DataTypeConstraints>(json!({ "pattern": "(" })).is_err());
+        assert!(
+            serde_json::from_value::<DataTypeConstraints>(json!({ "format": "color" })).is_err()
+        );
+    }
+}
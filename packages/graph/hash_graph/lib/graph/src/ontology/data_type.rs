@@ -17,7 +17,7 @@ use crate::store::query::{OntologyQueryPath, ParameterType, QueryPath};
 /// [`DataType`]: type_system::DataType
 // TODO: Adjust enum and docs when adding non-primitive data types
 //   see https://app.asana.com/0/1200211978612931/1202464168422955/f
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DataTypeQueryPath {
     /// The [`BaseUri`] of the [`DataType`].
     ///
@@ -151,6 +151,8 @@ pub enum DataTypeQueryPath {
     /// Only used internally and not available for deserialization.
     VersionId,
     /// Only used internally and not available for deserialization.
+    External,
+    /// Only used internally and not available for deserialization.
     Schema,
 }
 
@@ -175,6 +177,10 @@ impl OntologyQueryPath for DataTypeQueryPath {
         Self::UpdatedById
     }
 
+    fn external() -> Self {
+        Self::External
+    }
+
     fn schema() -> Self {
         Self::Schema
     }
@@ -184,6 +190,7 @@ impl QueryPath for DataTypeQueryPath {
     fn expected_type(&self) -> ParameterType {
         match self {
             Self::VersionId | Self::OwnedById | Self::UpdatedById => ParameterType::Uuid,
+            Self::External => ParameterType::Boolean,
             Self::Schema => ParameterType::Any,
             Self::BaseUri => ParameterType::BaseUri,
             Self::VersionedUri => ParameterType::VersionedUri,
@@ -202,6 +209,7 @@ impl fmt::Display for DataTypeQueryPath {
             Self::VersionedUri => fmt.write_str("versionedUri"),
             Self::OwnedById => fmt.write_str("ownedById"),
             Self::UpdatedById => fmt.write_str("updatedById"),
+            Self::External => fmt.write_str("external"),
             Self::Schema => fmt.write_str("schema"),
             Self::Title => fmt.write_str("title"),
             Self::Description => fmt.write_str("description"),
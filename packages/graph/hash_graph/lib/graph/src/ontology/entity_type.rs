@@ -14,7 +14,7 @@ use crate::{
 /// A path to a [`EntityType`] field.
 ///
 /// [`EntityType`]: type_system::EntityType
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EntityTypeQueryPath {
     /// The [`BaseUri`] of the [`EntityType`].
     ///
@@ -259,6 +259,8 @@ pub enum EntityTypeQueryPath {
     /// Only used internally and not available for deserialization.
     VersionId,
     /// Only used internally and not available for deserialization.
+    External,
+    /// Only used internally and not available for deserialization.
     Schema,
 }
 
@@ -283,6 +285,10 @@ impl OntologyQueryPath for EntityTypeQueryPath {
         Self::UpdatedById
     }
 
+    fn external() -> Self {
+        Self::External
+    }
+
     fn schema() -> Self {
         Self::Schema
     }
@@ -292,6 +298,7 @@ impl QueryPath for EntityTypeQueryPath {
     fn expected_type(&self) -> ParameterType {
         match self {
             Self::VersionId | Self::OwnedById | Self::UpdatedById => ParameterType::Uuid,
+            Self::External => ParameterType::Boolean,
             Self::Schema => ParameterType::Any,
             Self::BaseUri => ParameterType::BaseUri,
             Self::VersionedUri => ParameterType::VersionedUri,
@@ -315,6 +322,7 @@ impl fmt::Display for EntityTypeQueryPath {
             Self::VersionedUri => fmt.write_str("versionedUri"),
             Self::OwnedById => fmt.write_str("ownedById"),
             Self::UpdatedById => fmt.write_str("updatedById"),
+            Self::External => fmt.write_str("external"),
             Self::Schema => fmt.write_str("schema"),
             Self::Title => fmt.write_str("title"),
             Self::Description => fmt.write_str("description"),
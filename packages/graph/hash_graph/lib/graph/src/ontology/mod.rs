@@ -1,9 +1,12 @@
 //! TODO: DOC
 
+mod constraints;
 mod data_type;
 pub mod domain_validator;
 mod entity_type;
 mod property_type;
+pub(crate) mod schema;
+pub(crate) mod validation;
 
 use core::fmt;
 
@@ -17,6 +20,7 @@ use type_system::{
 use utoipa::ToSchema;
 
 pub use self::{
+    constraints::{ConstraintViolation, DataTypeConstraints, Format, InvalidDataTypeConstraint},
     data_type::{DataTypeQueryPath, DataTypeQueryPathVisitor, DataTypeQueryToken},
     entity_type::{EntityTypeQueryPath, EntityTypeQueryPathVisitor, EntityTypeQueryToken},
     property_type::{PropertyTypeQueryPath, PropertyTypeQueryPathVisitor, PropertyTypeQueryToken},
@@ -188,7 +192,7 @@ impl OntologyElementMetadata {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Serialize, ToSchema)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
 pub struct DataTypeWithMetadata {
     #[schema(value_type = VAR_DATA_TYPE)]
     #[serde(rename = "schema", serialize_with = "serialize_ontology_type")]
@@ -233,7 +237,7 @@ impl OntologyTypeWithMetadata for DataTypeWithMetadata {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Serialize, ToSchema)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
 pub struct PropertyTypeWithMetadata {
     #[schema(value_type = VAR_PROPERTY_TYPE)]
     #[serde(rename = "schema", serialize_with = "serialize_ontology_type")]
@@ -278,7 +282,7 @@ impl OntologyTypeWithMetadata for PropertyTypeWithMetadata {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Serialize, ToSchema)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
 pub struct EntityTypeWithMetadata {
     #[schema(value_type = VAR_ENTITY_TYPE)]
     #[serde(rename = "schema", serialize_with = "serialize_ontology_type")]
@@ -14,7 +14,7 @@ use crate::{
 /// A path to a [`PropertyType`] field.
 ///
 /// [`PropertyType`]: type_system::PropertyType
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PropertyTypeQueryPath {
     /// The [`BaseUri`] of the [`PropertyType`].
     ///
@@ -167,6 +167,8 @@ pub enum PropertyTypeQueryPath {
     /// Only used internally and not available for deserialization.
     VersionId,
     /// Only used internally and not available for deserialization.
+    External,
+    /// Only used internally and not available for deserialization.
     Schema,
 }
 
@@ -191,6 +193,10 @@ impl OntologyQueryPath for PropertyTypeQueryPath {
         Self::UpdatedById
     }
 
+    fn external() -> Self {
+        Self::External
+    }
+
     fn schema() -> Self {
         Self::Schema
     }
@@ -200,6 +206,7 @@ impl QueryPath for PropertyTypeQueryPath {
     fn expected_type(&self) -> ParameterType {
         match self {
             Self::VersionId | Self::OwnedById | Self::UpdatedById => ParameterType::Uuid,
+            Self::External => ParameterType::Boolean,
             Self::Schema => ParameterType::Any,
             Self::BaseUri => ParameterType::BaseUri,
             Self::VersionedUri => ParameterType::VersionedUri,
@@ -220,6 +227,7 @@ impl fmt::Display for PropertyTypeQueryPath {
             Self::VersionedUri => fmt.write_str("versionedUri"),
             Self::OwnedById => fmt.write_str("ownedById"),
             Self::UpdatedById => fmt.write_str("updatedById"),
+            Self::External => fmt.write_str("external"),
             Self::Schema => fmt.write_str("schema"),
             Self::Title => fmt.write_str("title"),
             Self::Description => fmt.write_str("description"),
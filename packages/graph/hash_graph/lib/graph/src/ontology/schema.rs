@@ -0,0 +1,127 @@
+//! Deserializable representations of the ontology type schemas.
+//!
+//! The types in here mirror the JSON representation of the Block Protocol type system closely
+//! enough to generate code from them and to validate values against them. They intentionally
+//! ignore everything which affects neither the shape of the generated code nor the validation.
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use type_system::uri::VersionedUri;
+
+use crate::ontology::DataTypeConstraints;
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum OntologyTypeSchema {
+    DataType(DataTypeSchema),
+    PropertyType(PropertyTypeSchema),
+    EntityType(EntityTypeSchema),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Reference {
+    #[serde(rename = "$ref")]
+    pub uri: VersionedUri,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OneOf<T> {
+    pub one_of: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum ArrayTypeTag {
+    Array,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum ObjectTypeTag {
+    Object,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Array<T> {
+    #[serde(rename = "type")]
+    _type: ArrayTypeTag,
+    pub items: T,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Object<T> {
+    #[serde(rename = "type")]
+    _type: ObjectTypeTag,
+    #[serde(default)]
+    pub properties: BTreeMap<String, T>,
+    #[serde(default)]
+    pub required: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum ValueOrArray<T> {
+    Array(Array<T>),
+    Value(T),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataTypeSchema {
+    #[serde(rename = "$id")]
+    pub id: VersionedUri,
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(rename = "type")]
+    pub json_type: String,
+    #[serde(flatten)]
+    pub constraints: DataTypeConstraints,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum PropertyValues {
+    DataTypeReference(Reference),
+    PropertyTypeObject(Object<ValueOrArray<Reference>>),
+    ArrayOfPropertyValues(Array<OneOf<PropertyValues>>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyTypeSchema {
+    #[serde(rename = "$id")]
+    pub id: VersionedUri,
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub one_of: Vec<PropertyValues>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkSchema {
+    pub items: OneOf<Reference>,
+    #[serde(default)]
+    pub ordered: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityTypeSchema {
+    #[serde(rename = "$id")]
+    pub id: VersionedUri,
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub all_of: Vec<Reference>,
+    #[serde(default)]
+    pub properties: BTreeMap<String, ValueOrArray<Reference>>,
+    #[serde(default)]
+    pub required: Vec<String>,
+    #[serde(default)]
+    pub links: BTreeMap<String, LinkSchema>,
+}
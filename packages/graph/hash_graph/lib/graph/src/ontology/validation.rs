@@ -0,0 +1,303 @@
+//! Validation of entity properties against the constraints of their data types.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use error_stack::{IntoReport, Report, Result, ResultExt};
+use serde_json::Value;
+use type_system::{repr, uri::VersionedUri, DataType, EntityType, PropertyType};
+
+use crate::{
+    knowledge::EntityProperties,
+    ontology::{
+        schema::{
+            DataTypeSchema, EntityTypeSchema, PropertyTypeSchema, PropertyValues, Reference,
+            ValueOrArray,
+        },
+        ConstraintViolation,
+    },
+};
+
+/// Validates the properties of an entity against the [`DataTypeConstraints`] of the data types
+/// its property types refer to.
+///
+/// The validator has to be filled with every ontology type reachable from the entity type. The
+/// types which are still required are returned by [`missing_entity_types`],
+/// [`missing_property_types`], and [`missing_data_types`].
+///
+/// Only property values are validated, for which the entity type (or one of its parents) defines
+/// the property type. The presence of required properties is not checked.
+///
+/// [`DataTypeConstraints`]: crate::ontology::DataTypeConstraints
+/// [`missing_entity_types`]: Self::missing_entity_types
+/// [`missing_property_types`]: Self::missing_property_types
+/// [`missing_data_types`]: Self::missing_data_types
+#[derive(Debug)]
+pub(crate) struct PropertyValidator {
+    entity_type_id: VersionedUri,
+    entity_types: HashMap<VersionedUri, EntityTypeSchema>,
+    property_types: HashMap<VersionedUri, PropertyTypeSchema>,
+    data_types: HashMap<VersionedUri, DataTypeSchema>,
+}
+
+fn parse<T>(value: impl serde::Serialize) -> Result<T, ConstraintViolation>
+where
+    T: for<'de> serde::Deserialize<'de>,
+{
+    serde_json::to_value(value)
+        .and_then(serde_json::from_value)
+        .into_report()
+        .change_context(ConstraintViolation)
+}
+
+impl PropertyValidator {
+    pub(crate) fn new(entity_type_id: VersionedUri) -> Self {
+        Self {
+            entity_type_id,
+            entity_types: HashMap::new(),
+            property_types: HashMap::new(),
+            data_types: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn insert_entity_type(
+        &mut self,
+        entity_type: EntityType,
+    ) -> Result<(), ConstraintViolation> {
+        let schema: EntityTypeSchema = parse(repr::EntityType::from(entity_type))?;
+        self.entity_types.insert(schema.id.clone(), schema);
+        Ok(())
+    }
+
+    pub(crate) fn insert_property_type(
+        &mut self,
+        property_type: PropertyType,
+    ) -> Result<(), ConstraintViolation> {
+        let schema: PropertyTypeSchema = parse(repr::PropertyType::from(property_type))?;
+        self.property_types.insert(schema.id.clone(), schema);
+        Ok(())
+    }
+
+    pub(crate) fn insert_data_type(
+        &mut self,
+        data_type: DataType,
+    ) -> Result<(), ConstraintViolation> {
+        let schema: DataTypeSchema = parse(repr::DataType::from(data_type))?;
+        self.data_types.insert(schema.id.clone(), schema);
+        Ok(())
+    }
+
+    /// Returns the entity type and its parents, which are not yet in the validator.
+    pub(crate) fn missing_entity_types(&self) -> Vec<VersionedUri> {
+        let mut missing = Vec::new();
+        if !self.entity_types.contains_key(&self.entity_type_id) {
+            missing.push(self.entity_type_id.clone());
+        }
+        for entity_type in self.entity_types.values() {
+            for parent in &entity_type.all_of {
+                if !self.entity_types.contains_key(&parent.uri) {
+                    missing.push(parent.uri.clone());
+                }
+            }
+        }
+        missing
+    }
+
+    /// Returns the property types referenced by the known types, which are not yet in the
+    /// validator.
+    pub(crate) fn missing_property_types(&self) -> Vec<VersionedUri> {
+        let mut references = HashSet::new();
+        for entity_type in self.entity_types.values() {
+            for property in entity_type.properties.values() {
+                references.insert(value_or_array_reference(property));
+            }
+        }
+        for property_type in self.property_types.values() {
+            collect_references(&property_type.one_of, &mut references, &mut HashSet::new());
+        }
+
+        references
+            .into_iter()
+            .filter(|uri| !self.property_types.contains_key(uri))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the data types referenced by the known property types, which are not yet in the
+    /// validator.
+    pub(crate) fn missing_data_types(&self) -> Vec<VersionedUri> {
+        let mut references = HashSet::new();
+        for property_type in self.property_types.values() {
+            collect_references(&property_type.one_of, &mut HashSet::new(), &mut references);
+        }
+
+        references
+            .into_iter()
+            .filter(|uri| !self.data_types.contains_key(uri))
+            .cloned()
+            .collect()
+    }
+
+    /// Validates the `properties` of an entity.
+    ///
+    /// # Errors
+    ///
+    /// - [`ConstraintViolation`], if a property value does not satisfy its property type
+    pub(crate) fn validate(
+        &self,
+        properties: &EntityProperties,
+    ) -> Result<(), ConstraintViolation> {
+        let mut definitions = BTreeMap::new();
+        let mut visited = HashSet::new();
+        let mut entity_type_ids = vec![&self.entity_type_id];
+        while let Some(entity_type_id) = entity_type_ids.pop() {
+            if !visited.insert(entity_type_id) {
+                continue;
+            }
+            let entity_type = self.entity_type(entity_type_id)?;
+            for (base_uri, property) in &entity_type.properties {
+                definitions.entry(base_uri.as_str()).or_insert(property);
+            }
+            entity_type_ids.extend(entity_type.all_of.iter().map(|parent| &parent.uri));
+        }
+
+        for (base_uri, value) in properties.properties() {
+            if let Some(property) = definitions.get(base_uri.as_str()) {
+                self.validate_property(property, value)
+                    .attach_printable_lazy(|| format!("invalid value for property `{base_uri}`"))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn entity_type(&self, uri: &VersionedUri) -> Result<&EntityTypeSchema, ConstraintViolation> {
+        self.entity_types.get(uri).ok_or_else(|| {
+            Report::new(ConstraintViolation)
+                .attach_printable(format!("entity type `{uri}` is not known"))
+        })
+    }
+
+    fn validate_property(
+        &self,
+        property: &ValueOrArray<Reference>,
+        value: &Value,
+    ) -> Result<(), ConstraintViolation> {
+        match property {
+            ValueOrArray::Value(reference) => self.validate_property_type(&reference.uri, value),
+            ValueOrArray::Array(array) => {
+                let items = value.as_array().ok_or_else(|| {
+                    Report::new(ConstraintViolation)
+                        .attach_printable(format!("`{value}` is not an array"))
+                })?;
+                for item in items {
+                    self.validate_property_type(&array.items.uri, item)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn validate_property_type(
+        &self,
+        uri: &VersionedUri,
+        value: &Value,
+    ) -> Result<(), ConstraintViolation> {
+        let property_type = self.property_types.get(uri).ok_or_else(|| {
+            Report::new(ConstraintViolation)
+                .attach_printable(format!("property type `{uri}` is not known"))
+        })?;
+
+        self.validate_one_of(&property_type.one_of, value)
+            .attach_printable_lazy(|| format!("`{value}` does not satisfy property type `{uri}`"))
+    }
+
+    /// Succeeds if `value` satisfies at least one of the `values`.
+    fn validate_one_of(
+        &self,
+        values: &[PropertyValues],
+        value: &Value,
+    ) -> Result<(), ConstraintViolation> {
+        let mut error: Option<Report<ConstraintViolation>> = None;
+        for property_values in values {
+            match self.validate_property_values(property_values, value) {
+                Ok(()) => return Ok(()),
+                Err(report) => match &mut error {
+                    Some(error) => error.extend_one(report),
+                    None => error = Some(report),
+                },
+            }
+        }
+
+        Err(error.unwrap_or_else(|| Report::new(ConstraintViolation)))
+    }
+
+    fn validate_property_values(
+        &self,
+        property_values: &PropertyValues,
+        value: &Value,
+    ) -> Result<(), ConstraintViolation> {
+        match property_values {
+            PropertyValues::DataTypeReference(reference) => {
+                let data_type = self.data_types.get(&reference.uri).ok_or_else(|| {
+                    Report::new(ConstraintViolation)
+                        .attach_printable(format!("data type `{}` is not known", reference.uri))
+                })?;
+                data_type
+                    .constraints
+                    .validate(&data_type.json_type, value)
+                    .attach_printable_lazy(|| {
+                        format!("invalid value for data type `{}`", data_type.id)
+                    })
+            }
+            PropertyValues::PropertyTypeObject(object) => {
+                let object_value = value.as_object().ok_or_else(|| {
+                    Report::new(ConstraintViolation)
+                        .attach_printable(format!("`{value}` is not an object"))
+                })?;
+                for (base_uri, value) in object_value {
+                    if let Some(property) = object.properties.get(base_uri) {
+                        self.validate_property(property, value)?;
+                    }
+                }
+                Ok(())
+            }
+            PropertyValues::ArrayOfPropertyValues(array) => {
+                let items = value.as_array().ok_or_else(|| {
+                    Report::new(ConstraintViolation)
+                        .attach_printable(format!("`{value}` is not an array"))
+                })?;
+                for item in items {
+                    self.validate_one_of(&array.items.one_of, item)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn value_or_array_reference(property: &ValueOrArray<Reference>) -> &VersionedUri {
+    match property {
+        ValueOrArray::Value(reference) => &reference.uri,
+        ValueOrArray::Array(array) => &array.items.uri,
+    }
+}
+
+fn collect_references<'a>(
+    values: &'a [PropertyValues],
+    property_types: &mut HashSet<&'a VersionedUri>,
+    data_types: &mut HashSet<&'a VersionedUri>,
+) {
+    for property_values in values {
+        match property_values {
+            PropertyValues::DataTypeReference(reference) => {
+                data_types.insert(&reference.uri);
+            }
+            PropertyValues::PropertyTypeObject(object) => {
+                property_types.extend(object.properties.values().map(value_or_array_reference));
+            }
+            PropertyValues::ArrayOfPropertyValues(array) => {
+                collect_references(&array.items.one_of, property_types, data_types);
+            }
+        }
+    }
+}
@@ -354,6 +354,10 @@ impl ToSchema for TransactionTimeProjection {
 /// [`Kernel`] and the other axis is called the [`Image`] of a projection. The returned data will
 /// then only contain temporal data that is contained in the [`Timespan`] of the [`Image`], the
 /// [`ProjectedTime`], for the given [`Timestamp`] of the [`Kernel`].
+///
+/// Ontology types are only versioned along the [`TransactionTime`]. When projecting the
+/// [`DecisionTime`], only ontology types which were known at the [`Timestamp`] of the [`Kernel`]
+/// are returned.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum TimeProjection {
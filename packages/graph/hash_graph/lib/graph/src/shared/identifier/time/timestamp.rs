@@ -5,9 +5,13 @@ use std::{
 };
 
 use chrono::{DateTime, Utc};
+#[cfg(feature = "sqlite")]
+use chrono::{SecondsFormat, SubsecRound};
 use derivative::Derivative;
 use interval_ops::LowerBound;
 use postgres_types::{private::BytesMut, FromSql, ToSql, Type};
+#[cfg(feature = "sqlite")]
+use rusqlite::types::{FromSqlError, FromSqlResult, ToSqlOutput, ValueRef};
 use serde::{Deserialize, Serialize};
 use utoipa::{openapi, ToSchema};
 
@@ -100,6 +104,16 @@ impl<A> Timestamp<A> {
             time: time.time,
         }
     }
+
+    /// Truncates the timestamp to the microsecond precision, which is used by the stores.
+    #[cfg(feature = "sqlite")]
+    #[must_use]
+    pub(crate) fn truncate_to_microseconds(self) -> Self {
+        Self {
+            axis: PhantomData,
+            time: self.time.trunc_subsecs(6),
+        }
+    }
 }
 
 impl<A> FromStr for Timestamp<A> {
@@ -148,6 +162,29 @@ impl<A> ToSql for Timestamp<A> {
     }
 }
 
+// Timestamps are stored as RFC 3339 text with a fixed number of fractional digits, so they are
+// ordered chronologically when compared as text.
+#[cfg(feature = "sqlite")]
+impl<A> rusqlite::ToSql for Timestamp<A> {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(
+            self.time.to_rfc3339_opts(SecondsFormat::Micros, true),
+        ))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<A> rusqlite::types::FromSql for Timestamp<A> {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        Ok(Self {
+            axis: PhantomData,
+            time: DateTime::parse_from_rfc3339(value.as_str()?)
+                .map_err(|error| FromSqlError::Other(Box::new(error)))?
+                .with_timezone(&Utc),
+        })
+    }
+}
+
 impl<A> ToSchema for Timestamp<A> {
     fn schema() -> openapi::Schema {
         openapi::schema::ObjectBuilder::new()
@@ -5,7 +5,10 @@ use tokio_postgres::types::ToSql;
 use utoipa::{openapi::Schema, ToSchema};
 use uuid::Uuid;
 
-use crate::identifier::account::AccountId;
+use crate::identifier::{
+    account::AccountId,
+    time::{Timestamp, TransactionTime},
+};
 
 macro_rules! define_provenance_id {
     ($name:tt) => {
@@ -54,16 +57,82 @@ define_provenance_id!(UpdatedById);
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct ProvenanceMetadata {
     updated_by_id: UpdatedById,
+    /// Set if the element was fetched from a remote location instead of being created in this
+    /// graph.
+    #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+    external: bool,
 }
 
 impl ProvenanceMetadata {
     #[must_use]
     pub const fn new(updated_by_id: UpdatedById) -> Self {
-        Self { updated_by_id }
+        Self {
+            updated_by_id,
+            external: false,
+        }
+    }
+
+    /// Creates provenance metadata for an element which was fetched from a remote location.
+    #[must_use]
+    pub const fn new_external(updated_by_id: UpdatedById) -> Self {
+        Self {
+            updated_by_id,
+            external: true,
+        }
+    }
+
+    #[must_use]
+    pub const fn updated_by_id(&self) -> UpdatedById {
+        self.updated_by_id
+    }
+
+    #[must_use]
+    pub const fn is_external(&self) -> bool {
+        self.external
+    }
+}
+
+/// The provenance of a single property value of an entity.
+///
+/// The provenance is kept across editions of an entity as long as the value of the property does
+/// not change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct PropertyProvenance {
+    updated_by_id: UpdatedById,
+    /// The transaction time at which the value was set.
+    updated_at: Timestamp<TransactionTime>,
+    /// Where the value was obtained from, e.g. the URL of a website or the name of an integration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+}
+
+impl PropertyProvenance {
+    #[must_use]
+    pub const fn new(
+        updated_by_id: UpdatedById,
+        updated_at: Timestamp<TransactionTime>,
+        source: Option<String>,
+    ) -> Self {
+        Self {
+            updated_by_id,
+            updated_at,
+            source,
+        }
     }
 
     #[must_use]
     pub const fn updated_by_id(&self) -> UpdatedById {
         self.updated_by_id
     }
+
+    #[must_use]
+    pub const fn updated_at(&self) -> Timestamp<TransactionTime> {
+        self.updated_at
+    }
+
+    #[must_use]
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
 }
@@ -12,6 +12,7 @@ use error_stack::Result;
 
 use crate::{
     identifier::time::{TimeProjection, UnresolvedTimeProjection},
+    knowledge::DraftId,
     shared::identifier::GraphElementVertexId,
     store::{crud::Read, QueryError, Record},
     subgraph::{edges::GraphResolveDepths, vertices::Vertices},
@@ -29,6 +30,10 @@ pub struct Subgraph {
     pub depths: GraphResolveDepths,
     pub time_projection: UnresolvedTimeProjection,
     pub resolved_time_projection: TimeProjection,
+    /// Whether the subgraph was truncated because a query limit was reached.
+    pub incomplete: bool,
+    /// The draft, whose entity editions are included in the subgraph.
+    pub draft_id: Option<DraftId>,
 }
 
 impl Subgraph {
@@ -45,6 +50,8 @@ impl Subgraph {
             depths,
             time_projection,
             resolved_time_projection,
+            incomplete: false,
+            draft_id: None,
         }
     }
 
@@ -6,7 +6,7 @@ use utoipa::ToSchema;
 
 use crate::{
     identifier::time::UnresolvedTimeProjection,
-    knowledge::Entity,
+    knowledge::{DraftId, Entity},
     ontology::{DataTypeWithMetadata, EntityTypeWithMetadata, PropertyTypeWithMetadata},
     store::{query::Filter, Record},
     subgraph::edges::GraphResolveDepths,
@@ -43,6 +43,12 @@ use crate::{
 /// an in-depth explanation of the depth of a query, please see the documentation on
 /// [`GraphResolveDepths`].
 ///
+/// # Drafts
+///
+/// Entities, which were created or updated in a draft, are not visible to queries by default. When
+/// a `draftId` is specified, the draft's editions are returned in place of the live editions of the
+/// same entities. The draft only applies to entities, it's ignored when querying ontology types.
+///
 /// # Examples
 ///
 /// Typically, a structural will be deserialized from a JSON request. The following examples assume,
@@ -167,4 +173,6 @@ pub struct StructuralQuery<'p, R: Record> {
     pub graph_resolve_depths: GraphResolveDepths,
     #[serde(default)]
     pub time_projection: UnresolvedTimeProjection,
+    #[serde(default)]
+    pub draft_id: Option<DraftId>,
 }
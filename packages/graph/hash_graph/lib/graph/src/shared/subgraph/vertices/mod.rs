@@ -13,3 +13,19 @@ pub struct Vertices {
     pub entity_types: HashMap<OntologyTypeEditionId, EntityTypeWithMetadata>,
     pub entities: HashMap<EntityVertexId, Entity>,
 }
+
+impl Vertices {
+    /// Returns the number of vertices of all kinds.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data_types.len()
+            + self.property_types.len()
+            + self.entity_types.len()
+            + self.entities.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -37,6 +37,18 @@ impl fmt::Display for QueryError {
 
 impl Context for QueryError {}
 
+#[derive(Debug)]
+#[must_use]
+pub struct QueryLimitExceeded;
+
+impl fmt::Display for QueryLimitExceeded {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("The query exceeded the configured query limits")
+    }
+}
+
+impl Context for QueryLimitExceeded {}
+
 #[derive(Debug)]
 #[must_use]
 pub struct UpdateError;
@@ -85,6 +97,18 @@ impl fmt::Display for EntityDoesNotExist {
 
 impl Context for EntityDoesNotExist {}
 
+#[derive(Debug)]
+#[must_use]
+pub struct DraftDoesNotExist;
+
+impl fmt::Display for DraftDoesNotExist {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Draft does not exist")
+    }
+}
+
+impl Context for DraftDoesNotExist {}
+
 #[derive(Debug)]
 #[must_use]
 pub struct RaceConditionOnUpdate;
@@ -97,6 +121,18 @@ impl fmt::Display for RaceConditionOnUpdate {
 
 impl Context for RaceConditionOnUpdate {}
 
+#[derive(Debug)]
+#[must_use]
+pub struct DraftPublishConflict;
+
+impl fmt::Display for DraftPublishConflict {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("The entity was updated since it was changed in the draft")
+    }
+}
+
+impl Context for DraftPublishConflict {}
+
 #[derive(Debug)]
 #[must_use]
 pub struct VersionedUriAlreadyExists;
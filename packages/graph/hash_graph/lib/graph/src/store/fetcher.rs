@@ -0,0 +1,417 @@
+//! Resolution of ontology types which are hosted outside of the graph.
+//!
+//! When a type references another type which is not yet in the store, the [`OntologyTypeFetcher`]
+//! can be used to load the missing types over HTTP. Fetched types are validated and inserted
+//! recursively, so that all of their own references are satisfied as well. Inserted types are
+//! marked as external in their [`ProvenanceMetadata`].
+//!
+//! [`ProvenanceMetadata`]: crate::provenance::ProvenanceMetadata
+
+use std::{collections::HashSet, fmt};
+
+use error_stack::{ensure, IntoReport, Report, Result, ResultExt};
+use hyper::{
+    client::{connect::Connect, HttpConnector},
+    header::ACCEPT,
+    Body, Client, Request, Uri,
+};
+use type_system::{
+    uri::VersionedUri, DataType, DataTypeReference, EntityType, EntityTypeReference, PropertyType,
+    PropertyTypeReference,
+};
+
+use crate::{
+    identifier::time::UnresolvedTimeProjection,
+    ontology::{
+        DataTypeWithMetadata, EntityTypeWithMetadata, OntologyElementMetadata, OntologyType,
+        PropertyTypeWithMetadata,
+    },
+    provenance::{OwnedById, UpdatedById},
+    store::{crud::Read, query::Filter, DataTypeStore, EntityTypeStore, PropertyTypeStore},
+};
+
+#[derive(Debug)]
+#[must_use]
+pub struct FetchError;
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Could not fetch ontology type")
+    }
+}
+
+impl error_stack::Context for FetchError {}
+
+#[derive(Debug)]
+#[must_use]
+pub struct DomainNotAllowed;
+
+impl fmt::Display for DomainNotAllowed {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("The domain is not in the list of allowed domains")
+    }
+}
+
+impl error_stack::Context for DomainNotAllowed {}
+
+/// A reference to an ontology type of a known kind.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OntologyTypeReference {
+    DataType(VersionedUri),
+    PropertyType(VersionedUri),
+    EntityType(VersionedUri),
+}
+
+impl OntologyTypeReference {
+    #[must_use]
+    pub const fn uri(&self) -> &VersionedUri {
+        match self {
+            Self::DataType(uri) | Self::PropertyType(uri) | Self::EntityType(uri) => uri,
+        }
+    }
+
+    /// Returns all types directly referenced by the [`PropertyType`].
+    #[must_use]
+    pub fn of_property_type(property_type: &PropertyType) -> Vec<Self> {
+        property_type
+            .data_type_references()
+            .into_iter()
+            .map(DataTypeReference::uri)
+            .cloned()
+            .map(Self::DataType)
+            .chain(
+                property_type
+                    .property_type_references()
+                    .into_iter()
+                    .map(PropertyTypeReference::uri)
+                    .cloned()
+                    .map(Self::PropertyType),
+            )
+            .collect()
+    }
+
+    /// Returns all types directly referenced by the [`EntityType`].
+    ///
+    /// References of the [`EntityType`] to itself, e.g. as a link destination, are omitted.
+    #[must_use]
+    pub fn of_entity_type(entity_type: &EntityType) -> Vec<Self> {
+        entity_type
+            .property_type_references()
+            .into_iter()
+            .map(PropertyTypeReference::uri)
+            .cloned()
+            .map(Self::PropertyType)
+            .chain(
+                entity_type
+                    .link_mappings()
+                    .into_iter()
+                    .flat_map(|(link_entity_type, destinations)| {
+                        std::iter::once(link_entity_type).chain(destinations.into_iter().flatten())
+                    })
+                    .chain(entity_type.inherits_from().all_of())
+                    .map(EntityTypeReference::uri)
+                    .filter(|uri| *uri != entity_type.id())
+                    .cloned()
+                    .map(Self::EntityType),
+            )
+            .collect()
+    }
+}
+
+/// An ontology type which was fetched from a remote location.
+#[derive(Debug)]
+enum FetchedOntologyType {
+    DataType(DataType),
+    PropertyType(PropertyType),
+    EntityType(EntityType),
+}
+
+impl FetchedOntologyType {
+    fn references(&self) -> Vec<OntologyTypeReference> {
+        match self {
+            Self::DataType(_) => Vec::new(),
+            Self::PropertyType(property_type) => {
+                OntologyTypeReference::of_property_type(property_type)
+            }
+            Self::EntityType(entity_type) => OntologyTypeReference::of_entity_type(entity_type),
+        }
+    }
+
+    async fn import<S>(
+        self,
+        store: &mut S,
+        owned_by_id: OwnedById,
+        actor_id: UpdatedById,
+    ) -> Result<OntologyElementMetadata, FetchError>
+    where
+        S: DataTypeStore + PropertyTypeStore + EntityTypeStore + Send,
+    {
+        match self {
+            Self::DataType(data_type) => {
+                store
+                    .import_data_type(data_type, owned_by_id, actor_id)
+                    .await
+            }
+            Self::PropertyType(property_type) => {
+                store
+                    .import_property_type(property_type, owned_by_id, actor_id)
+                    .await
+            }
+            Self::EntityType(entity_type) => {
+                store
+                    .import_entity_type(entity_type, owned_by_id, actor_id)
+                    .await
+            }
+        }
+        .change_context(FetchError)
+    }
+}
+
+enum Step {
+    Resolve(OntologyTypeReference),
+    Import(FetchedOntologyType),
+}
+
+/// Fetches ontology types over HTTP from an allow-list of domains.
+///
+/// A domain in the allow-list also permits all of its subdomains. The fetcher uses a plain HTTP
+/// connector by default, a client with a different connector (e.g. one supporting TLS) can be
+/// provided with [`with_client`].
+///
+/// [`with_client`]: Self::with_client
+#[derive(Debug, Clone)]
+pub struct OntologyTypeFetcher<C = HttpConnector> {
+    client: Client<C>,
+    allowed_domains: Vec<String>,
+}
+
+impl OntologyTypeFetcher {
+    #[must_use]
+    pub fn new(allowed_domains: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self::with_client(Client::new(), allowed_domains)
+    }
+}
+
+impl<C> OntologyTypeFetcher<C>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
+    #[must_use]
+    pub fn with_client(
+        client: Client<C>,
+        allowed_domains: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            client,
+            allowed_domains: allowed_domains.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Returns if the host of `uri` is part of the allow-list.
+    #[must_use]
+    pub fn is_allowed(&self, uri: &VersionedUri) -> bool {
+        uri.to_string()
+            .parse::<Uri>()
+            .ok()
+            .and_then(|uri| uri.host().map(str::to_owned))
+            .map_or(false, |host| {
+                self.allowed_domains.iter().any(|domain| {
+                    host == *domain
+                        || host
+                            .strip_suffix(domain.as_str())
+                            .map_or(false, |subdomain| subdomain.ends_with('.'))
+                })
+            })
+    }
+
+    /// Fetches the ontology type identified by `uri` and validates it.
+    ///
+    /// # Errors
+    ///
+    /// - [`DomainNotAllowed`], if the domain of `uri` is not in the allow-list
+    /// - if the request failed or did not return a successful status code
+    /// - if the response is not a valid `T`
+    /// - if the `$id` of the returned type does not match `uri`
+    pub async fn fetch<T: OntologyType>(&self, uri: &VersionedUri) -> Result<T, FetchError> {
+        if !self.is_allowed(uri) {
+            return Err(Report::new(DomainNotAllowed)
+                .attach_printable(uri.clone())
+                .change_context(FetchError));
+        }
+
+        let request = Request::get(uri.to_string())
+            .header(ACCEPT, "application/json")
+            .body(Body::empty())
+            .into_report()
+            .change_context(FetchError)?;
+        let response = self
+            .client
+            .request(request)
+            .await
+            .into_report()
+            .change_context(FetchError)
+            .attach_printable_lazy(|| uri.clone())?;
+
+        let status = response.status();
+        ensure!(
+            status.is_success(),
+            Report::new(FetchError)
+                .attach_printable(format!("unexpected status code: {status}"))
+                .attach_printable(uri.clone())
+        );
+
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .into_report()
+            .change_context(FetchError)?;
+        let ontology_type_repr: T::Representation = serde_json::from_slice(&body)
+            .into_report()
+            .change_context(FetchError)
+            .attach_printable_lazy(|| uri.clone())?;
+        let ontology_type = T::try_from(ontology_type_repr)
+            .into_report()
+            .change_context(FetchError)
+            .attach_printable_lazy(|| uri.clone())?;
+
+        ensure!(
+            ontology_type.id() == uri,
+            Report::new(FetchError)
+                .attach_printable("the fetched type has a different `$id` than requested")
+                .attach_printable(ontology_type.id().clone())
+        );
+
+        Ok(ontology_type)
+    }
+
+    /// Fetches and imports all types in `references`, which are not yet in the `store`.
+    ///
+    /// The references of fetched types are resolved recursively, so a type is only imported
+    /// after all of its own references are available in the `store`. Returns the metadata of the
+    /// imported types in the order they were imported.
+    ///
+    /// # Errors
+    ///
+    /// - if a missing type could not be fetched, see [`fetch`]
+    /// - if importing a type into the store failed, e.g. because types reference each other
+    ///   cyclically
+    ///
+    /// [`fetch`]: Self::fetch
+    pub async fn import_missing<S>(
+        &self,
+        store: &mut S,
+        references: impl IntoIterator<Item = OntologyTypeReference> + Send,
+        owned_by_id: OwnedById,
+        actor_id: UpdatedById,
+    ) -> Result<Vec<OntologyElementMetadata>, FetchError>
+    where
+        S: DataTypeStore + PropertyTypeStore + EntityTypeStore + Send,
+    {
+        let mut stack = references
+            .into_iter()
+            .map(Step::Resolve)
+            .collect::<Vec<_>>();
+        stack.reverse();
+
+        let mut visited = HashSet::new();
+        let mut imported = Vec::new();
+        while let Some(step) = stack.pop() {
+            match step {
+                Step::Resolve(reference) => {
+                    if !visited.insert(reference.uri().clone())
+                        || contains(store, &reference).await?
+                    {
+                        continue;
+                    }
+
+                    let ontology_type = match &reference {
+                        OntologyTypeReference::DataType(uri) => {
+                            FetchedOntologyType::DataType(self.fetch(uri).await?)
+                        }
+                        OntologyTypeReference::PropertyType(uri) => {
+                            FetchedOntologyType::PropertyType(self.fetch(uri).await?)
+                        }
+                        OntologyTypeReference::EntityType(uri) => {
+                            FetchedOntologyType::EntityType(self.fetch(uri).await?)
+                        }
+                    };
+                    tracing::info!(uri = %reference.uri(), "fetched external ontology type");
+
+                    let mut dependencies = ontology_type.references();
+                    dependencies.reverse();
+                    stack.push(Step::Import(ontology_type));
+                    stack.extend(dependencies.into_iter().map(Step::Resolve));
+                }
+                Step::Import(ontology_type) => {
+                    imported.push(ontology_type.import(store, owned_by_id, actor_id).await?);
+                }
+            }
+        }
+
+        Ok(imported)
+    }
+}
+
+/// Returns if the type referred to by `reference` is already in the `store`.
+async fn contains<S>(store: &S, reference: &OntologyTypeReference) -> Result<bool, FetchError>
+where
+    S: DataTypeStore + PropertyTypeStore + EntityTypeStore + Send,
+{
+    let time_projection = UnresolvedTimeProjection::default().resolve();
+    let is_empty = match reference {
+        OntologyTypeReference::DataType(uri) => Read::<DataTypeWithMetadata>::read(
+            store,
+            &Filter::for_versioned_uri(uri),
+            &time_projection,
+        )
+        .await
+        .change_context(FetchError)?
+        .is_empty(),
+        OntologyTypeReference::PropertyType(uri) => Read::<PropertyTypeWithMetadata>::read(
+            store,
+            &Filter::for_versioned_uri(uri),
+            &time_projection,
+        )
+        .await
+        .change_context(FetchError)?
+        .is_empty(),
+        OntologyTypeReference::EntityType(uri) => Read::<EntityTypeWithMetadata>::read(
+            store,
+            &Filter::for_versioned_uri(uri),
+            &time_projection,
+        )
+        .await
+        .change_context(FetchError)?
+        .is_empty(),
+    };
+
+    Ok(!is_empty)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn uri(uri: &str) -> VersionedUri {
+        VersionedUri::from_str(uri).expect("invalid versioned URI")
+    }
+
+    #[test]
+    fn allow_list() {
+        let fetcher = OntologyTypeFetcher::new(["blockprotocol.org"]);
+
+        assert!(fetcher.is_allowed(&uri(
+            "https://blockprotocol.org/@blockprotocol/types/data-type/text/v/1"
+        )));
+        assert!(fetcher.is_allowed(&uri(
+            "https://types.blockprotocol.org/@blockprotocol/types/data-type/text/v/1"
+        )));
+        assert!(!fetcher.is_allowed(&uri(
+            "https://evilblockprotocol.org/@blockprotocol/types/data-type/text/v/1"
+        )));
+        assert!(!fetcher.is_allowed(&uri(
+            "https://blockprotocol.org.example.com/@blockprotocol/types/data-type/text/v/1"
+        )));
+    }
+}
@@ -1,15 +1,23 @@
+use std::{collections::HashMap, ops::RangeBounds};
+
 use async_trait::async_trait;
-use error_stack::Result;
+use error_stack::{ensure, Report, Result};
 use type_system::uri::VersionedUri;
 
 use crate::{
     identifier::{
-        knowledge::EntityId,
-        time::{DecisionTime, Timestamp},
+        knowledge::{EntityEditionId, EntityId},
+        time::{
+            DecisionTime, Image, Kernel, Projection, TimeProjection, Timespan, TimespanBound,
+            Timestamp, TransactionTime,
+        },
+    },
+    knowledge::{
+        DraftId, Entity, EntityDiff, EntityLinkOrder, EntityMetadata, EntityProperties, EntityUuid,
+        LinkData,
     },
-    knowledge::{Entity, EntityLinkOrder, EntityMetadata, EntityProperties, EntityUuid, LinkData},
     provenance::{OwnedById, UpdatedById},
-    store::{crud, InsertionError, QueryError, UpdateError},
+    store::{crud, query::Filter, InsertionError, QueryError, UpdateError},
     subgraph::{query::StructuralQuery, Subgraph},
 };
 
@@ -20,14 +28,22 @@ use crate::{
 pub trait EntityStore: crud::Read<Entity> {
     /// Creates a new [`Entity`].
     ///
+    /// Every property is attributed to `updated_by_id` in the [`PropertyProvenance`], optionally
+    /// with the `property_source` the values were obtained from.
+    ///
+    /// If a `draft_id` is passed, the entity is only created in that draft and becomes visible to
+    /// other queries once the draft is published.
+    ///
     /// # Errors:
     ///
     /// - if the [`EntityType`] doesn't exist
     /// - if the [`EntityProperties`] is not valid with respect to the specified [`EntityType`]
     /// - if the account referred to by `owned_by_id` does not exist
     /// - if an [`EntityUuid`] was supplied and already exists in the store
+    /// - if the draft referred to by `draft_id` does not exist
     ///
     /// [`EntityType`]: type_system::EntityType
+    /// [`PropertyProvenance`]: crate::provenance::PropertyProvenance
     #[expect(clippy::too_many_arguments)]
     async fn create_entity(
         &mut self,
@@ -38,7 +54,9 @@ pub trait EntityStore: crud::Read<Entity> {
         archived: bool,
         entity_type_id: VersionedUri,
         properties: EntityProperties,
+        property_source: Option<String>,
         link_data: Option<LinkData>,
+        draft_id: Option<DraftId>,
     ) -> Result<EntityMetadata, InsertionError>;
 
     /// Inserts the entities with the specified [`EntityType`] into the `Store`.
@@ -83,16 +101,121 @@ pub trait EntityStore: crud::Read<Entity> {
     /// - if the requested [`Entity`] doesn't exist
     async fn get_entity(&self, query: &StructuralQuery<Entity>) -> Result<Subgraph, QueryError>;
 
+    /// Returns the [`EntityId`]s of all entities, for which a version was recorded after `from` and
+    /// until `to` in [`TransactionTime`].
+    ///
+    /// Editions in drafts are not considered until the draft is published.
+    ///
+    /// # Errors
+    ///
+    /// - if reading the versions from the store fails
+    async fn changed_entity_ids(
+        &self,
+        from: Timestamp<TransactionTime>,
+        to: Timestamp<TransactionTime>,
+    ) -> Result<Vec<EntityId>, QueryError>;
+
+    /// Returns the [`Entities`] which were created, updated, or archived between the two points in
+    /// [`TransactionTime`] `from` and `to`.
+    ///
+    /// The entities are compared as they were known at `from` and at `to` respectively, each
+    /// projected onto the [`DecisionTime`] equal to the transaction time. If a `filter` is passed,
+    /// only entities matching the filter at either end of the window are considered.
+    ///
+    /// Only entities with a version recorded after `from` and until `to`, see
+    /// [`changed_entity_ids`], are read from the store.
+    ///
+    /// # Errors
+    ///
+    /// - if `from` is later than `to`
+    /// - if reading the entities from the store fails
+    ///
+    /// [`Entities`]: Entity
+    /// [`changed_entity_ids`]: Self::changed_entity_ids
+    async fn diff_entities(
+        &self,
+        from: Timestamp<TransactionTime>,
+        to: Timestamp<TransactionTime>,
+        filter: Option<&Filter<'_, Entity>>,
+    ) -> Result<EntityDiff, QueryError> {
+        ensure!(
+            from <= to,
+            Report::new(QueryError).attach_printable(format!(
+                "The start of the window ({from}) is later than its end ({to})"
+            ))
+        );
+
+        let changed_entity_ids = self.changed_entity_ids(from, to).await?;
+        if changed_entity_ids.is_empty() {
+            return Ok(EntityDiff::default());
+        }
+
+        let changed_filter = filter_for_entity_ids(changed_entity_ids);
+        let filter = match filter {
+            Some(filter) => Filter::All(vec![filter.clone(), changed_filter]),
+            None => changed_filter,
+        };
+        let from_projection = projection_at(from);
+        let to_projection = projection_at(to);
+
+        let mut old = entity_metadata_by_id(self.read(&filter, &from_projection).await?, from);
+        let mut new = entity_metadata_by_id(self.read(&filter, &to_projection).await?, to);
+
+        // An entity may only match the filter at one end of the window, e.g. if the properties,
+        // which are filtered on, changed. The edition at the other end is looked up unfiltered.
+        let missing_old = new
+            .keys()
+            .filter(|entity_id| !old.contains_key(entity_id))
+            .copied()
+            .collect::<Vec<_>>();
+        let missing_new = old
+            .keys()
+            .filter(|entity_id| !new.contains_key(entity_id))
+            .copied()
+            .collect::<Vec<_>>();
+        if !missing_old.is_empty() {
+            old.extend(entity_metadata_by_id(
+                self.read(&filter_for_entity_ids(missing_old), &from_projection)
+                    .await?,
+                from,
+            ));
+        }
+        if !missing_new.is_empty() {
+            new.extend(entity_metadata_by_id(
+                self.read(&filter_for_entity_ids(missing_new), &to_projection)
+                    .await?,
+                to,
+            ));
+        }
+
+        let mut new = new.into_iter().collect::<Vec<_>>();
+        new.sort_unstable_by_key(|(entity_id, _)| *entity_id);
+
+        let mut diff = EntityDiff::default();
+        for (entity_id, metadata) in &new {
+            diff.record(old.get(entity_id), metadata);
+        }
+        Ok(diff)
+    }
+
     /// Update an existing [`Entity`].
     ///
+    /// Properties, which keep their value, keep their [`PropertyProvenance`]. All other properties
+    /// are attributed to `updated_by_id`, optionally with the `property_source`.
+    ///
+    /// If a `draft_id` is passed, the update is only applied to that draft and the live edition of
+    /// the [`Entity`] stays untouched until the draft is published.
+    ///
     /// # Errors
     ///
-    /// - if the [`Entity`] doesn't exist
+    /// - if the [`Entity`] doesn't exist, or, if a `draft_id` is passed, exists neither in the
+    ///   draft nor live
     /// - if the [`EntityType`] doesn't exist
     /// - if the [`Entity`] is not valid with respect to its [`EntityType`]
     /// - if the account referred to by `actor_id` does not exist
     ///
     /// [`EntityType`]: type_system::EntityType
+    /// [`PropertyProvenance`]: crate::provenance::PropertyProvenance
     #[expect(clippy::too_many_arguments)]
     async fn update_entity(
         &mut self,
@@ -102,6 +225,96 @@ pub trait EntityStore: crud::Read<Entity> {
         archived: bool,
         entity_type_id: VersionedUri,
         properties: EntityProperties,
+        property_source: Option<String>,
         link_order: EntityLinkOrder,
+        draft_id: Option<DraftId>,
     ) -> Result<EntityMetadata, UpdateError>;
+
+    /// Creates a new, empty draft owned by `owned_by_id`.
+    ///
+    /// # Errors
+    ///
+    /// - if the account referred to by `owned_by_id` does not exist
+    async fn create_draft(&mut self, owned_by_id: OwnedById) -> Result<DraftId, InsertionError>;
+
+    /// Publishes all editions of the draft in a single transaction and removes the draft.
+    ///
+    /// Entities, which were created in the draft, become live, entities, which were updated in
+    /// the draft, are updated as if [`update_entity`] was called without a draft. Returns the
+    /// [`EntityEditionId`]s of the published editions.
+    ///
+    /// # Errors
+    ///
+    /// - if the draft does not exist
+    /// - [`DraftPublishConflict`], if the live edition of an entity changed since the entity was
+    ///   first changed in the draft
+    /// - if an edition of the draft could not be published, in which case nothing is published
+    ///
+    /// [`update_entity`]: Self::update_entity
+    /// [`DraftPublishConflict`]: crate::store::error::DraftPublishConflict
+    async fn publish_draft(
+        &mut self,
+        draft_id: DraftId,
+    ) -> Result<Vec<EntityEditionId>, UpdateError>;
+
+    /// Discards all editions of the draft and removes the draft.
+    ///
+    /// Entities, which were only created in the draft, are removed as well.
+    ///
+    /// # Errors
+    ///
+    /// - if the draft does not exist
+    async fn discard_draft(&mut self, draft_id: DraftId) -> Result<(), UpdateError>;
+}
+
+/// Returns the [`TimeProjection`] of the knowledge as it was known at `timestamp`.
+///
+/// The stores only support unbounded images, so the editions have to be narrowed down to the
+/// decision time `timestamp` with [`entity_metadata_by_id`].
+fn projection_at(timestamp: Timestamp<TransactionTime>) -> TimeProjection {
+    TimeProjection::DecisionTime(Projection {
+        kernel: Kernel {
+            axis: TransactionTime::default(),
+            timestamp,
+        },
+        image: Image {
+            axis: DecisionTime::default(),
+            span: Timespan {
+                start: TimespanBound::Unbounded,
+                end: TimespanBound::Unbounded,
+            },
+        },
+    })
+}
+
+/// Maps the editions of `entities`, which are valid at the decision time `timestamp`, by their
+/// [`EntityId`].
+fn entity_metadata_by_id(
+    entities: Vec<Entity>,
+    timestamp: Timestamp<TransactionTime>,
+) -> HashMap<EntityId, EntityMetadata> {
+    let decision_time = timestamp.cast::<DecisionTime>();
+    entities
+        .into_iter()
+        .filter(|entity| {
+            entity
+                .metadata()
+                .version()
+                .decision_time()
+                .contains(&decision_time)
+        })
+        .map(|entity| {
+            let metadata = entity.metadata().clone();
+            (metadata.edition_id().base_id(), metadata)
+        })
+        .collect()
+}
+
+fn filter_for_entity_ids(entity_ids: Vec<EntityId>) -> Filter<'static, Entity> {
+    Filter::Any(
+        entity_ids
+            .into_iter()
+            .map(Filter::for_entity_by_entity_id)
+            .collect(),
+    )
 }
@@ -1,6 +1,8 @@
 pub mod crud;
 pub mod error;
+pub mod fetcher;
 pub mod query;
+pub mod webhook;
 
 mod account;
 mod config;
@@ -9,22 +11,35 @@ mod ontology;
 mod pool;
 mod postgres;
 mod record;
+#[cfg(feature = "sqlite")]
+mod sqlite;
 
 use async_trait::async_trait;
 use error_stack::Result;
 
+#[cfg(feature = "sqlite")]
+pub use self::sqlite::{
+    AsConnection, SqliteConnection, SqliteConnectionManager, SqliteStore, SqliteStorePool,
+    SqliteTransaction,
+};
 pub use self::{
     account::AccountStore,
-    config::{DatabaseConnectionInfo, DatabaseType},
+    config::{
+        DatabaseConnectionInfo, DatabaseType, ParseDatabaseConnectionInfoError, QueryLimits,
+        ReadReplicaConfig,
+    },
     error::{
-        BaseUriAlreadyExists, BaseUriDoesNotExist, InsertionError, QueryError, StoreError,
-        UpdateError,
+        BaseUriAlreadyExists, BaseUriDoesNotExist, InsertionError, QueryError, QueryLimitExceeded,
+        StoreError, UpdateError,
     },
     knowledge::EntityStore,
     ontology::{DataTypeStore, EntityTypeStore, PropertyTypeStore},
     pool::StorePool,
-    postgres::{AsClient, PostgresStore, PostgresStorePool},
+    postgres::{
+        AsClient, OntologyCache, OntologyCacheStatistics, PostgresStore, PostgresStorePool,
+    },
     record::Record,
+    webhook::WebhookStore,
 };
 
 /// Describes the API of a store implementation.
@@ -35,13 +50,21 @@ pub use self::{
 /// raised depending on the implementation, e.g. connection issues.
 #[async_trait]
 pub trait Store:
-    AccountStore + DataTypeStore + PropertyTypeStore + EntityTypeStore + EntityStore
+    AccountStore + DataTypeStore + PropertyTypeStore + EntityTypeStore + EntityStore + WebhookStore
 {
     type Transaction<'t>: Transaction
     where
         Self: 't;
 
     async fn transaction(&mut self) -> Result<Self::Transaction<'_>, StoreError>;
+
+    /// Checks if the store is able to serve requests.
+    ///
+    /// # Errors
+    ///
+    /// - if the store is not reachable
+    /// - if the schema of the store is not up-to-date
+    async fn check_readiness(&self) -> Result<(), StoreError>;
 }
 
 #[async_trait]
@@ -21,8 +21,10 @@ pub trait DataTypeStore: crud::Read<DataTypeWithMetadata> {
     ///
     /// - if the account referred to by `owned_by_id` does not exist.
     /// - if the [`BaseUri`] of the `data_type` already exist.
+    /// - [`InvalidDataTypeConstraint`], if the constraints of the `data_type` are invalid.
     ///
     /// [`BaseUri`]: type_system::uri::BaseUri
+    /// [`InvalidDataTypeConstraint`]: crate::ontology::InvalidDataTypeConstraint
     async fn create_data_type(
         &mut self,
         data_type: DataType,
@@ -30,6 +32,28 @@ pub trait DataTypeStore: crud::Read<DataTypeWithMetadata> {
         actor_id: UpdatedById,
     ) -> Result<OntologyElementMetadata, InsertionError>;
 
+    /// Imports a [`DataType`] which was fetched from a remote location.
+    ///
+    /// This behaves like [`create_data_type`] but marks the [`DataType`] as external in its
+    /// [`ProvenanceMetadata`].
+    ///
+    /// # Errors:
+    ///
+    /// - if the account referred to by `owned_by_id` does not exist.
+    /// - if the [`BaseUri`] of the `data_type` already exist.
+    /// - [`InvalidDataTypeConstraint`], if the constraints of the `data_type` are invalid.
+    ///
+    /// [`create_data_type`]: Self::create_data_type
+    /// [`ProvenanceMetadata`]: crate::provenance::ProvenanceMetadata
+    /// [`BaseUri`]: type_system::uri::BaseUri
+    /// [`InvalidDataTypeConstraint`]: crate::ontology::InvalidDataTypeConstraint
+    async fn import_data_type(
+        &mut self,
+        data_type: DataType,
+        owned_by_id: OwnedById,
+        actor_id: UpdatedById,
+    ) -> Result<OntologyElementMetadata, InsertionError>;
+
     /// Get the [`Subgraph`] specified by the [`StructuralQuery`].
     ///
     /// # Errors
@@ -45,6 +69,9 @@ pub trait DataTypeStore: crud::Read<DataTypeWithMetadata> {
     /// # Errors
     ///
     /// - if the [`DataType`] doesn't exist.
+    /// - [`InvalidDataTypeConstraint`], if the constraints of the `data_type` are invalid.
+    ///
+    /// [`InvalidDataTypeConstraint`]: crate::ontology::InvalidDataTypeConstraint
     async fn update_data_type(
         &mut self,
         data_type: DataType,
@@ -70,6 +97,26 @@ pub trait PropertyTypeStore: crud::Read<PropertyTypeWithMetadata> {
         actor_id: UpdatedById,
     ) -> Result<OntologyElementMetadata, InsertionError>;
 
+    /// Imports a [`PropertyType`] which was fetched from a remote location.
+    ///
+    /// This behaves like [`create_property_type`] but marks the [`PropertyType`] as external in its
+    /// [`ProvenanceMetadata`].
+    ///
+    /// # Errors:
+    ///
+    /// - if the account referred to by `owned_by_id` does not exist.
+    /// - if the [`BaseUri`] of the `property_type` already exist.
+    ///
+    /// [`create_property_type`]: Self::create_property_type
+    /// [`ProvenanceMetadata`]: crate::provenance::ProvenanceMetadata
+    /// [`BaseUri`]: type_system::uri::BaseUri
+    async fn import_property_type(
+        &mut self,
+        property_type: PropertyType,
+        owned_by_id: OwnedById,
+        actor_id: UpdatedById,
+    ) -> Result<OntologyElementMetadata, InsertionError>;
+
     /// Get the [`Subgraph`] specified by the [`StructuralQuery`].
     ///
     /// # Errors
@@ -110,6 +157,26 @@ pub trait EntityTypeStore: crud::Read<EntityTypeWithMetadata> {
         actor_id: UpdatedById,
     ) -> Result<OntologyElementMetadata, InsertionError>;
 
+    /// Imports an [`EntityType`] which was fetched from a remote location.
+    ///
+    /// This behaves like [`create_entity_type`] but marks the [`EntityType`] as external in its
+    /// [`ProvenanceMetadata`].
+    ///
+    /// # Errors:
+    ///
+    /// - if the account referred to by `owned_by_id` does not exist.
+    /// - if the [`BaseUri`] of the `entity_type` already exist.
+    ///
+    /// [`create_entity_type`]: Self::create_entity_type
+    /// [`ProvenanceMetadata`]: crate::provenance::ProvenanceMetadata
+    /// [`BaseUri`]: type_system::uri::BaseUri
+    async fn import_entity_type(
+        &mut self,
+        entity_type: EntityType,
+        owned_by_id: OwnedById,
+        actor_id: UpdatedById,
+    ) -> Result<OntologyElementMetadata, InsertionError>;
+
     /// Get the [`Subgraph`]s specified by the [`StructuralQuery`].
     ///
     /// # Errors
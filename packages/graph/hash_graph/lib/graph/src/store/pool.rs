@@ -21,4 +21,13 @@ pub trait StorePool: Sync {
     /// [`StorePool::acquire`] (which stores a lifetime-bound reference to the `StorePool`) should
     /// be preferred whenever possible.
     async fn acquire_owned(&self) -> Result<Self::Store<'static>, Self::Error>;
+
+    /// Retrieves a [`Store`] from the pool, which is only used for reading.
+    ///
+    /// Implementations may hand out a [`Store`] connected to a read replica, so changes made
+    /// through a [`Store`] returned by [`StorePool::acquire`] are not necessarily visible yet. By
+    /// default, this is the same as [`StorePool::acquire`].
+    async fn acquire_read(&self) -> Result<Self::Store<'_>, Self::Error> {
+        self.acquire().await
+    }
 }
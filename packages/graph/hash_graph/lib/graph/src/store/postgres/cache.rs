@@ -0,0 +1,568 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    mem,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+};
+
+use type_system::uri::{BaseUri, VersionedUri};
+
+use crate::{
+    identifier::time::{TimeProjection, TimespanBound, Timestamp, TransactionTime},
+    metrics,
+    ontology::{
+        DataTypeWithMetadata, EntityTypeWithMetadata, OntologyTypeWithMetadata,
+        PropertyTypeWithMetadata,
+    },
+    store::postgres::version_id::VersionId,
+};
+
+/// A map holding at most `capacity` entries.
+///
+/// When the map is full, the entry which was inserted first is evicted.
+struct BoundedMap<K, V> {
+    capacity: usize,
+    entries: HashMap<K, V>,
+    insertion_order: VecDeque<K>,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> BoundedMap<K, V> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            insertion_order: VecDeque::new(),
+        }
+    }
+
+    fn get(&self, key: &K) -> Option<V> {
+        self.entries.get(key).cloned()
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.entries.insert(key.clone(), value).is_none() {
+            self.insertion_order.push_back(key);
+            while self.entries.len() > self.capacity {
+                if let Some(evicted) = self.insertion_order.pop_front() {
+                    self.entries.remove(&evicted);
+                }
+            }
+        }
+    }
+
+    fn remove(&mut self, key: &K) {
+        if self.entries.remove(key).is_some() {
+            self.insertion_order.retain(|entry| entry != key);
+        }
+    }
+}
+
+/// Number of lookups answered by the [`OntologyCache`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct OntologyCacheStatistics {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// An in-process cache of ontology types shared by all stores of a pool.
+///
+/// As ontology types are immutable for a given [`VersionedUri`], entries keyed by a
+/// [`VersionedUri`] are never invalidated but only evicted when the cache is full. Only the lookup
+/// of the latest version of a [`BaseUri`] is invalidated when a type is updated.
+///
+/// Every type is stored together with the point in [`TransactionTime`] it was cached at. It's only
+/// returned for [`TimeProjection`]s, which contain that point in time, as the type may not have
+/// existed before.
+///
+/// Note, that the cache is local to the process, so updates of the latest version done by other
+/// processes connected to the same database are not observed.
+pub struct OntologyCache {
+    data_types: Mutex<BoundedMap<VersionedUri, (DataTypeWithMetadata, Timestamp<TransactionTime>)>>,
+    property_types:
+        Mutex<BoundedMap<VersionedUri, (PropertyTypeWithMetadata, Timestamp<TransactionTime>)>>,
+    entity_types:
+        Mutex<BoundedMap<VersionedUri, (EntityTypeWithMetadata, Timestamp<TransactionTime>)>>,
+    version_ids: Mutex<BoundedMap<VersionedUri, VersionId>>,
+    latest: Mutex<BoundedMap<BaseUri, VersionedUri>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // The cached maps are always in a consistent state, so it's fine to ignore poisoning.
+    mutex
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+impl OntologyCache {
+    /// Creates a cache holding at most `capacity` entries per kind of ontology type.
+    ///
+    /// A `capacity` of `0` disables the cache.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            data_types: Mutex::new(BoundedMap::new(capacity)),
+            property_types: Mutex::new(BoundedMap::new(capacity)),
+            entity_types: Mutex::new(BoundedMap::new(capacity)),
+            version_ids: Mutex::new(BoundedMap::new(capacity)),
+            latest: Mutex::new(BoundedMap::new(capacity)),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    #[must_use]
+    pub fn statistics(&self) -> OntologyCacheStatistics {
+        OntologyCacheStatistics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    fn record_lookup<V>(&self, record: &'static str, value: Option<V>) -> Option<V> {
+        if value.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        metrics::record_cache_lookup(record, value.is_some());
+        value
+    }
+
+    fn apply(&self, entry: CacheEntry) {
+        match entry {
+            CacheEntry::DataType(uri, data_type, cached_at) => {
+                lock(&self.data_types).insert(uri, (data_type, cached_at));
+            }
+            CacheEntry::PropertyType(uri, property_type, cached_at) => {
+                lock(&self.property_types).insert(uri, (property_type, cached_at));
+            }
+            CacheEntry::EntityType(uri, entity_type, cached_at) => {
+                lock(&self.entity_types).insert(uri, (entity_type, cached_at));
+            }
+            CacheEntry::VersionId(uri, version_id) => {
+                lock(&self.version_ids).insert(uri, version_id);
+            }
+            CacheEntry::Latest(uri) => {
+                lock(&self.latest).insert(uri.base_uri().clone(), uri);
+            }
+        }
+    }
+}
+
+impl Default for OntologyCache {
+    /// Creates a disabled cache.
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+/// An entry, which is added to the [`OntologyCache`].
+pub enum CacheEntry {
+    DataType(
+        VersionedUri,
+        DataTypeWithMetadata,
+        Timestamp<TransactionTime>,
+    ),
+    PropertyType(
+        VersionedUri,
+        PropertyTypeWithMetadata,
+        Timestamp<TransactionTime>,
+    ),
+    EntityType(
+        VersionedUri,
+        EntityTypeWithMetadata,
+        Timestamp<TransactionTime>,
+    ),
+    VersionId(VersionedUri, VersionId),
+    Latest(VersionedUri),
+}
+
+/// An ontology type, which can be stored in the [`OntologyCache`].
+pub trait CachedOntologyType: OntologyTypeWithMetadata + Clone {
+    /// The name of the record used in metrics.
+    const RECORD: &'static str;
+
+    /// Returns the cached type and the point in [`TransactionTime`] it was cached at.
+    fn get_cached(
+        cache: &OntologyCache,
+        uri: &VersionedUri,
+    ) -> Option<(Self, Timestamp<TransactionTime>)>;
+
+    fn into_cache_entry(
+        self,
+        uri: VersionedUri,
+        cached_at: Timestamp<TransactionTime>,
+    ) -> CacheEntry;
+}
+
+impl CachedOntologyType for DataTypeWithMetadata {
+    const RECORD: &'static str = "data_types";
+
+    fn get_cached(
+        cache: &OntologyCache,
+        uri: &VersionedUri,
+    ) -> Option<(Self, Timestamp<TransactionTime>)> {
+        lock(&cache.data_types).get(uri)
+    }
+
+    fn into_cache_entry(
+        self,
+        uri: VersionedUri,
+        cached_at: Timestamp<TransactionTime>,
+    ) -> CacheEntry {
+        CacheEntry::DataType(uri, self, cached_at)
+    }
+}
+
+impl CachedOntologyType for PropertyTypeWithMetadata {
+    const RECORD: &'static str = "property_types";
+
+    fn get_cached(
+        cache: &OntologyCache,
+        uri: &VersionedUri,
+    ) -> Option<(Self, Timestamp<TransactionTime>)> {
+        lock(&cache.property_types).get(uri)
+    }
+
+    fn into_cache_entry(
+        self,
+        uri: VersionedUri,
+        cached_at: Timestamp<TransactionTime>,
+    ) -> CacheEntry {
+        CacheEntry::PropertyType(uri, self, cached_at)
+    }
+}
+
+impl CachedOntologyType for EntityTypeWithMetadata {
+    const RECORD: &'static str = "entity_types";
+
+    fn get_cached(
+        cache: &OntologyCache,
+        uri: &VersionedUri,
+    ) -> Option<(Self, Timestamp<TransactionTime>)> {
+        lock(&cache.entity_types).get(uri)
+    }
+
+    fn into_cache_entry(
+        self,
+        uri: VersionedUri,
+        cached_at: Timestamp<TransactionTime>,
+    ) -> CacheEntry {
+        CacheEntry::EntityType(uri, self, cached_at)
+    }
+}
+
+/// Returns if an ontology type, which was known at `cached_at`, is contained in the
+/// `time_projection`.
+///
+/// Ontology types are never removed, so a type known at `cached_at` is known at any later point in
+/// [`TransactionTime`] as well.
+fn is_known_in(time_projection: &TimeProjection, cached_at: Timestamp<TransactionTime>) -> bool {
+    match time_projection {
+        TimeProjection::DecisionTime(projection) => projection.kernel.timestamp >= cached_at,
+        TimeProjection::TransactionTime(projection) => match projection.image.span.end {
+            TimespanBound::Unbounded => true,
+            TimespanBound::Included(end) => end >= cached_at,
+            TimespanBound::Excluded(end) => end > cached_at,
+        },
+    }
+}
+
+type PendingEntries = Arc<Mutex<Vec<CacheEntry>>>;
+
+/// Access to the [`OntologyCache`] from a single store.
+///
+/// Stores running inside of a transaction read from the shared cache, but entries they add are
+/// deferred until the transaction is committed, as the transaction might be rolled back.
+///
+/// A handle without a cache behaves like a handle to a cache with a capacity of `0`, but does not
+/// record any lookups.
+pub(crate) struct OntologyCacheHandle {
+    cache: Option<Arc<OntologyCache>>,
+    pending: Option<PendingEntries>,
+    parent: Option<PendingEntries>,
+}
+
+impl OntologyCacheHandle {
+    pub(crate) const fn new(cache: Arc<OntologyCache>) -> Self {
+        Self {
+            cache: Some(cache),
+            pending: None,
+            parent: None,
+        }
+    }
+
+    /// Creates a handle, which does not cache anything.
+    pub(crate) const fn disabled() -> Self {
+        Self {
+            cache: None,
+            pending: None,
+            parent: None,
+        }
+    }
+
+    /// Creates a handle for a transaction started by the store owning this handle.
+    pub(crate) fn transaction(&self) -> Self {
+        Self {
+            cache: self.cache.clone(),
+            pending: self.cache.as_ref().map(|_| PendingEntries::default()),
+            parent: self.pending.clone(),
+        }
+    }
+
+    /// Adds the entries of a committed transaction to the enclosing transaction or, if there is
+    /// none, to the shared cache.
+    pub(crate) fn commit(&self) {
+        let Some(pending) = &self.pending else {
+            return;
+        };
+
+        let entries = mem::take(&mut *lock(pending));
+        match (&self.parent, &self.cache) {
+            (Some(parent), _) => lock(parent).extend(entries),
+            (None, Some(cache)) => {
+                for entry in entries {
+                    cache.apply(entry);
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    fn add(&self, entry: CacheEntry) {
+        match (&self.pending, &self.cache) {
+            (Some(pending), _) => lock(pending).push(entry),
+            (None, Some(cache)) => cache.apply(entry),
+            (None, None) => {}
+        }
+    }
+
+    /// Returns the ontology type identified by `uri`, if it's cached and contained in the
+    /// `time_projection`.
+    pub(crate) fn get<T: CachedOntologyType>(
+        &self,
+        uri: &VersionedUri,
+        time_projection: &TimeProjection,
+    ) -> Option<T> {
+        let cache = self.cache.as_ref()?;
+        let ontology_type = T::get_cached(cache, uri)
+            .filter(|(_, cached_at)| is_known_in(time_projection, *cached_at))
+            .map(|(ontology_type, _)| ontology_type);
+        cache.record_lookup(T::RECORD, ontology_type)
+    }
+
+    pub(crate) fn insert<T: CachedOntologyType>(&self, uri: VersionedUri, ontology_type: T) {
+        self.add(ontology_type.into_cache_entry(uri, Timestamp::now()));
+    }
+
+    /// Returns the latest version of the ontology type identified by `base_uri`.
+    pub(crate) fn get_latest<T: CachedOntologyType>(&self, base_uri: &BaseUri) -> Option<T> {
+        let cache = self.cache.as_ref()?;
+        let uri = lock(&cache.latest).get(base_uri);
+        cache.record_lookup(
+            T::RECORD,
+            uri.and_then(|uri| T::get_cached(cache, &uri))
+                .map(|(ontology_type, _)| ontology_type),
+        )
+    }
+
+    /// Sets `uri` as the latest version of its [`BaseUri`].
+    ///
+    /// The previous latest version is invalidated immediately, even if the store is running inside
+    /// of a transaction.
+    pub(crate) fn set_latest(&self, uri: VersionedUri) {
+        if let Some(cache) = &self.cache {
+            lock(&cache.latest).remove(uri.base_uri());
+        }
+        self.add(CacheEntry::Latest(uri));
+    }
+
+    pub(crate) fn get_version_id(&self, uri: &VersionedUri) -> Option<VersionId> {
+        let cache = self.cache.as_ref()?;
+        cache.record_lookup("version_ids", lock(&cache.version_ids).get(uri))
+    }
+
+    pub(crate) fn insert_version_id(&self, uri: VersionedUri, version_id: VersionId) {
+        self.add(CacheEntry::VersionId(uri, version_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use type_system::{repr, DataType};
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::{
+        identifier::{
+            account::AccountId,
+            ontology::OntologyTypeEditionId,
+            time::{DecisionTime, Image, Kernel, Projection, Timespan},
+        },
+        ontology::OntologyElementMetadata,
+        provenance::{OwnedById, ProvenanceMetadata, UpdatedById},
+    };
+
+    #[test]
+    fn bounded_map_evicts_oldest_entry() {
+        let mut map = BoundedMap::new(2);
+        map.insert(1, "a");
+        map.insert(2, "b");
+        map.insert(1, "c");
+        map.insert(3, "d");
+
+        assert_eq!(map.get(&1), None);
+        assert_eq!(map.get(&2), Some("b"));
+        assert_eq!(map.get(&3), Some("d"));
+
+        map.remove(&2);
+        map.insert(4, "e");
+        assert_eq!(map.get(&3), Some("d"));
+        assert_eq!(map.get(&4), Some("e"));
+    }
+
+    #[test]
+    fn disabled_bounded_map() {
+        let mut map = BoundedMap::new(0);
+        map.insert(1, "a");
+        assert_eq!(map.get(&1), None);
+    }
+
+    fn versioned_uri(version: u32) -> VersionedUri {
+        VersionedUri::new(
+            BaseUri::new("https://example.com/data-type/".to_owned()).expect("invalid base URI"),
+            version,
+        )
+    }
+
+    #[test]
+    fn transaction_entries_are_added_on_commit() {
+        let cache = Arc::new(OntologyCache::new(10));
+        let handle = OntologyCacheHandle::new(Arc::clone(&cache));
+        let version_id = VersionId::new(Uuid::new_v4());
+
+        let transaction = handle.transaction();
+        transaction.insert_version_id(versioned_uri(1), version_id);
+        assert_eq!(handle.get_version_id(&versioned_uri(1)), None);
+        assert_eq!(transaction.get_version_id(&versioned_uri(1)), None);
+
+        transaction.commit();
+        assert_eq!(handle.get_version_id(&versioned_uri(1)), Some(version_id));
+
+        assert_eq!(cache.statistics(), OntologyCacheStatistics {
+            hits: 1,
+            misses: 2,
+        });
+    }
+
+    #[test]
+    fn rolled_back_entries_are_discarded() {
+        let handle = OntologyCacheHandle::new(Arc::new(OntologyCache::new(10)));
+
+        let transaction = handle.transaction();
+        let nested_transaction = transaction.transaction();
+        nested_transaction.insert_version_id(versioned_uri(1), VersionId::new(Uuid::new_v4()));
+        nested_transaction.commit();
+        assert_eq!(handle.get_version_id(&versioned_uri(1)), None);
+
+        // The outer transaction is dropped without being committed
+        drop(transaction);
+        assert_eq!(handle.get_version_id(&versioned_uri(1)), None);
+    }
+
+    #[test]
+    fn latest_is_invalidated_immediately() {
+        let cache = Arc::new(OntologyCache::new(10));
+        let handle = OntologyCacheHandle::new(Arc::clone(&cache));
+        handle.set_latest(versioned_uri(1));
+        assert_eq!(
+            lock(&cache.latest).get(versioned_uri(1).base_uri()),
+            Some(versioned_uri(1))
+        );
+
+        let transaction = handle.transaction();
+        transaction.set_latest(versioned_uri(2));
+        assert_eq!(lock(&cache.latest).get(versioned_uri(1).base_uri()), None);
+
+        transaction.commit();
+        assert_eq!(
+            lock(&cache.latest).get(versioned_uri(1).base_uri()),
+            Some(versioned_uri(2))
+        );
+    }
+
+    #[test]
+    fn disabled_handle() {
+        let handle = OntologyCacheHandle::disabled();
+        handle.insert_version_id(versioned_uri(1), VersionId::new(Uuid::new_v4()));
+        assert_eq!(handle.get_version_id(&versioned_uri(1)), None);
+
+        let transaction = handle.transaction();
+        transaction.set_latest(versioned_uri(1));
+        transaction.commit();
+        assert_eq!(
+            handle.get_latest::<DataTypeWithMetadata>(versioned_uri(1).base_uri()),
+            None
+        );
+    }
+
+    fn text_data_type() -> DataTypeWithMetadata {
+        let data_type_repr: repr::DataType =
+            serde_json::from_str(graph_test_data::data_type::TEXT_V1)
+                .expect("could not parse data type representation");
+        let data_type = DataType::try_from(data_type_repr).expect("could not parse data type");
+        let account_id = AccountId::new(Uuid::new_v4());
+        let metadata = OntologyElementMetadata::new(
+            OntologyTypeEditionId::from(data_type.id()),
+            ProvenanceMetadata::new(UpdatedById::new(account_id)),
+            OwnedById::new(account_id),
+        );
+        DataTypeWithMetadata::new(data_type, metadata)
+    }
+
+    fn projection_at(timestamp: Timestamp<TransactionTime>) -> TimeProjection {
+        TimeProjection::DecisionTime(Projection {
+            kernel: Kernel {
+                axis: TransactionTime::default(),
+                timestamp,
+            },
+            image: Image {
+                axis: DecisionTime::default(),
+                span: Timespan {
+                    start: TimespanBound::Unbounded,
+                    end: TimespanBound::Unbounded,
+                },
+            },
+        })
+    }
+
+    #[test]
+    fn ontology_types_are_not_returned_before_they_were_cached() {
+        let handle = OntologyCacheHandle::new(Arc::new(OntologyCache::new(10)));
+        let data_type = text_data_type();
+        let uri = data_type.inner().id().clone();
+        let before_caching = "2000-01-01T00:00:00Z"
+            .parse()
+            .expect("could not parse timestamp");
+
+        handle.insert(uri.clone(), data_type.clone());
+
+        assert_eq!(
+            handle.get::<DataTypeWithMetadata>(&uri, &projection_at(before_caching)),
+            None
+        );
+        assert_eq!(
+            handle.get::<DataTypeWithMetadata>(&uri, &projection_at(Timestamp::now())),
+            Some(data_type)
+        );
+    }
+}
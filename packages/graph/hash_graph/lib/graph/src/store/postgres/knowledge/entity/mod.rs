@@ -13,14 +13,19 @@ use crate::{
     identifier::{
         knowledge::{EntityEditionId, EntityId, EntityRecordId, EntityVersion},
         ontology::OntologyTypeEditionId,
-        time::{DecisionTime, Timestamp, VersionTimespan},
+        time::{DecisionTime, Timestamp, TransactionTime, VersionTimespan},
         EntityVertexId,
     },
-    knowledge::{Entity, EntityLinkOrder, EntityMetadata, EntityProperties, EntityUuid, LinkData},
+    knowledge::{
+        DraftId, Entity, EntityLinkOrder, EntityMetadata, EntityProperties, EntityUuid, LinkData,
+    },
+    metrics,
+    ontology::EntityTypeWithMetadata,
     provenance::{OwnedById, ProvenanceMetadata, UpdatedById},
     store::{
-        crud::Read,
-        error::{EntityDoesNotExist, RaceConditionOnUpdate},
+        error::{
+            DraftDoesNotExist, DraftPublishConflict, EntityDoesNotExist, RaceConditionOnUpdate,
+        },
         postgres::{DependencyContext, DependencyStatus},
         query::Filter,
         AsClient, EntityStore, InsertionError, PostgresStore, QueryError, Record, Store,
@@ -70,9 +75,10 @@ impl<C: AsClient> PostgresStore<C> {
                 DependencyStatus::Resolved => return Ok(()),
             };
 
-            if current_resolve_depth.is_of_type.outgoing > 0 {
-                let entity_type_id =
-                    OntologyTypeEditionId::from(entity.metadata().entity_type_id());
+            let entity_type_id = OntologyTypeEditionId::from(entity.metadata().entity_type_id());
+            if current_resolve_depth.is_of_type.outgoing > 0
+                && self.reserve_vertex::<EntityTypeWithMetadata>(subgraph, &entity_type_id)?
+            {
                 subgraph.edges.insert(Edge::KnowledgeGraph {
                     vertex_id: entity_vertex_id,
                     outward_edge: KnowledgeGraphOutwardEdges::ToOntology(OutwardEdge {
@@ -98,13 +104,19 @@ impl<C: AsClient> PostgresStore<C> {
             }
 
             if current_resolve_depth.has_left_entity.incoming > 0 {
-                for outgoing_link_entity in <Self as Read<Entity>>::read(
-                    self,
-                    &Filter::for_outgoing_link_by_source_entity_vertex_id(entity_vertex_id),
-                    &subgraph.resolved_time_projection,
-                )
-                .await?
+                for outgoing_link_entity in self
+                    .read_entities(
+                        &Filter::for_outgoing_link_by_source_entity_vertex_id(entity_vertex_id),
+                        &subgraph.resolved_time_projection,
+                        subgraph.draft_id,
+                    )
+                    .await?
                 {
+                    let outgoing_link_entity_vertex_id = outgoing_link_entity.vertex_id(time_axis);
+                    if !self.reserve_vertex::<Entity>(subgraph, &outgoing_link_entity_vertex_id)? {
+                        break;
+                    }
+
                     subgraph.edges.insert(Edge::KnowledgeGraph {
                         vertex_id: entity_vertex_id,
                         outward_edge: KnowledgeGraphOutwardEdges::ToKnowledgeGraph(OutwardEdge {
@@ -116,7 +128,6 @@ impl<C: AsClient> PostgresStore<C> {
                         }),
                     });
 
-                    let outgoing_link_entity_vertex_id = outgoing_link_entity.vertex_id(time_axis);
                     subgraph.insert(&outgoing_link_entity_vertex_id, outgoing_link_entity);
 
                     self.traverse_entity(
@@ -136,13 +147,19 @@ impl<C: AsClient> PostgresStore<C> {
             }
 
             if current_resolve_depth.has_right_entity.incoming > 0 {
-                for incoming_link_entity in <Self as Read<Entity>>::read(
-                    self,
-                    &Filter::for_incoming_link_by_source_entity_vertex_id(entity_vertex_id),
-                    &subgraph.resolved_time_projection,
-                )
-                .await?
+                for incoming_link_entity in self
+                    .read_entities(
+                        &Filter::for_incoming_link_by_source_entity_vertex_id(entity_vertex_id),
+                        &subgraph.resolved_time_projection,
+                        subgraph.draft_id,
+                    )
+                    .await?
                 {
+                    let incoming_link_entity_vertex_id = incoming_link_entity.vertex_id(time_axis);
+                    if !self.reserve_vertex::<Entity>(subgraph, &incoming_link_entity_vertex_id)? {
+                        break;
+                    }
+
                     subgraph.edges.insert(Edge::KnowledgeGraph {
                         vertex_id: entity_vertex_id,
                         outward_edge: KnowledgeGraphOutwardEdges::ToKnowledgeGraph(OutwardEdge {
@@ -154,7 +171,6 @@ impl<C: AsClient> PostgresStore<C> {
                         }),
                     });
 
-                    let incoming_link_entity_vertex_id = incoming_link_entity.vertex_id(time_axis);
                     subgraph.insert(&incoming_link_entity_vertex_id, incoming_link_entity);
 
                     self.traverse_entity(
@@ -174,13 +190,19 @@ impl<C: AsClient> PostgresStore<C> {
             }
 
             if current_resolve_depth.has_left_entity.outgoing > 0 {
-                for left_entity in <Self as Read<Entity>>::read(
-                    self,
-                    &Filter::for_left_entity_by_entity_vertex_id(entity_vertex_id),
-                    &subgraph.resolved_time_projection,
-                )
-                .await?
+                for left_entity in self
+                    .read_entities(
+                        &Filter::for_left_entity_by_entity_vertex_id(entity_vertex_id),
+                        &subgraph.resolved_time_projection,
+                        subgraph.draft_id,
+                    )
+                    .await?
                 {
+                    let left_entity_vertex_id = left_entity.vertex_id(time_axis);
+                    if !self.reserve_vertex::<Entity>(subgraph, &left_entity_vertex_id)? {
+                        break;
+                    }
+
                     subgraph.edges.insert(Edge::KnowledgeGraph {
                         vertex_id: entity_vertex_id,
                         outward_edge: KnowledgeGraphOutwardEdges::ToKnowledgeGraph(OutwardEdge {
@@ -192,7 +214,6 @@ impl<C: AsClient> PostgresStore<C> {
                         }),
                     });
 
-                    let left_entity_vertex_id = left_entity.vertex_id(time_axis);
                     subgraph.insert(&left_entity_vertex_id, left_entity);
 
                     self.traverse_entity(
@@ -212,13 +233,19 @@ impl<C: AsClient> PostgresStore<C> {
             }
 
             if current_resolve_depth.has_right_entity.outgoing > 0 {
-                for right_entity in <Self as Read<Entity>>::read(
-                    self,
-                    &Filter::for_right_entity_by_entity_vertex_id(entity_vertex_id),
-                    &subgraph.resolved_time_projection,
-                )
-                .await?
+                for right_entity in self
+                    .read_entities(
+                        &Filter::for_right_entity_by_entity_vertex_id(entity_vertex_id),
+                        &subgraph.resolved_time_projection,
+                        subgraph.draft_id,
+                    )
+                    .await?
                 {
+                    let right_entity_vertex_id = right_entity.vertex_id(time_axis);
+                    if !self.reserve_vertex::<Entity>(subgraph, &right_entity_vertex_id)? {
+                        break;
+                    }
+
                     subgraph.edges.insert(Edge::KnowledgeGraph {
                         vertex_id: entity_vertex_id,
                         outward_edge: KnowledgeGraphOutwardEdges::ToKnowledgeGraph(OutwardEdge {
@@ -230,7 +257,6 @@ impl<C: AsClient> PostgresStore<C> {
                         }),
                     });
 
-                    let right_entity_vertex_id = right_entity.vertex_id(time_axis);
                     subgraph.insert(&right_entity_vertex_id, right_entity);
 
                     self.traverse_entity(
@@ -253,6 +279,99 @@ impl<C: AsClient> PostgresStore<C> {
         }
         .boxed()
     }
+
+    /// Locks the draft for the rest of the transaction, so it cannot be changed concurrently.
+    ///
+    /// # Errors
+    ///
+    /// - [`DraftDoesNotExist`] if the draft does not exist
+    async fn lock_draft(&self, draft_id: DraftId) -> Result<(), UpdateError> {
+        self.as_client()
+            .query_opt("SELECT 1 FROM drafts WHERE draft_id = $1 FOR UPDATE;", &[
+                &draft_id,
+            ])
+            .await
+            .into_report()
+            .change_context(UpdateError)?
+            .ok_or_else(|| {
+                Report::new(DraftDoesNotExist)
+                    .attach(draft_id)
+                    .change_context(UpdateError)
+            })
+            .map(drop)
+    }
+
+    /// Locks the live entities changed in the draft for the rest of the transaction.
+    ///
+    /// Updates of live entities lock the same rows in `update_entity`, so an update, which is not
+    /// committed yet, is waited for. Afterwards, no update can be committed until the transaction
+    /// ends, so the result of [`check_draft_conflicts`] stays valid until the draft is published.
+    ///
+    /// [`check_draft_conflicts`]: Self::check_draft_conflicts
+    async fn lock_draft_entities(&self, draft_id: DraftId) -> Result<(), UpdateError> {
+        self.as_client()
+            .execute(
+                r#"
+                SELECT 1
+                FROM entity_ids
+                WHERE (entity_ids.owned_by_id, entity_ids.entity_uuid) IN (
+                    SELECT entity_drafts.owned_by_id, entity_drafts.entity_uuid
+                    FROM entity_drafts
+                    WHERE entity_drafts.draft_id = $1
+                )
+                ORDER BY entity_ids.owned_by_id, entity_ids.entity_uuid
+                FOR UPDATE;
+                "#,
+                &[&draft_id],
+            )
+            .await
+            .into_report()
+            .change_context(UpdateError)
+            .map(drop)
+    }
+
+    /// Fails if the live edition of an entity in the draft changed since the entity was first
+    /// changed in the draft.
+    ///
+    /// The entities have to be locked by [`lock_draft_entities`] before, otherwise an update may
+    /// be committed after the check.
+    ///
+    /// [`lock_draft_entities`]: Self::lock_draft_entities
+    async fn check_draft_conflicts(&self, draft_id: DraftId) -> Result<(), UpdateError> {
+        let conflicts = self
+            .as_client()
+            .query(
+                r#"
+                SELECT entity_drafts.owned_by_id, entity_drafts.entity_uuid
+                FROM entity_drafts
+                LEFT JOIN entity_versions
+                  ON entity_versions.owned_by_id = entity_drafts.owned_by_id
+                 AND entity_versions.entity_uuid = entity_drafts.entity_uuid
+                 AND entity_versions.decision_time @> COALESCE(entity_drafts.decision_time, now())
+                 AND upper_inf(entity_versions.transaction_time)
+                WHERE entity_drafts.draft_id = $1
+                  AND entity_versions.entity_record_id
+                      IS DISTINCT FROM entity_drafts.base_entity_record_id;
+                "#,
+                &[&draft_id],
+            )
+            .await
+            .into_report()
+            .change_context(UpdateError)?;
+
+        let mut result: Result<(), DraftPublishConflict> = Ok(());
+        for row in conflicts {
+            let error = Report::new(DraftPublishConflict).attach(EntityId::new(
+                OwnedById::new(row.get(0)),
+                EntityUuid::new(row.get(1)),
+            ));
+            match &mut result {
+                Err(report) => report.extend_one(error),
+                Ok(()) => result = Err(error),
+            }
+        }
+        result.change_context(UpdateError)
+    }
 }
 
 #[async_trait]
@@ -267,8 +386,12 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
         archived: bool,
         entity_type_id: VersionedUri,
         properties: EntityProperties,
+        property_source: Option<String>,
         link_data: Option<LinkData>,
+        draft_id: Option<DraftId>,
     ) -> Result<EntityMetadata, InsertionError> {
+        let _timer = metrics::store_operation("create", "entities");
+
         let entity_id = EntityId::new(
             owned_by_id,
             entity_uuid.unwrap_or_else(|| EntityUuid::new(Uuid::new_v4())),
@@ -279,6 +402,10 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
             .await
             .change_context(InsertionError)?;
 
+        self.validate_entity_properties(&entity_type_id, &properties)
+            .await
+            .change_context(InsertionError)?;
+
         let properties = serde_json::to_value(properties)
             .into_report()
             .change_context(InsertionError)?;
@@ -300,12 +427,14 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
                         _archived := $5,
                         _entity_type_version_id := $6,
                         _properties := $7,
-                        _left_owned_by_id := $8,
-                        _left_entity_uuid := $9,
-                        _right_owned_by_id := $10,
-                        _right_entity_uuid := $11,
-                        _left_to_right_order := $12,
-                        _right_to_left_order := $13
+                        _property_source := $8,
+                        _left_owned_by_id := $9,
+                        _left_entity_uuid := $10,
+                        _right_owned_by_id := $11,
+                        _right_entity_uuid := $12,
+                        _left_to_right_order := $13,
+                        _right_to_left_order := $14,
+                        _draft_id := $15
                     );
                 "#,
                 &[
@@ -316,6 +445,7 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
                     &archived,
                     &entity_type_version_id,
                     &properties,
+                    &property_source,
                     &link_data
                         .as_ref()
                         .map(|metadata| metadata.left_entity_id().owned_by_id()),
@@ -330,11 +460,13 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
                         .map(|metadata| metadata.right_entity_id().entity_uuid()),
                     &link_data.as_ref().map(LinkData::left_to_right_order),
                     &link_data.as_ref().map(LinkData::right_to_left_order),
+                    &draft_id,
                 ],
             )
             .await
             .into_report()
             .change_context(InsertionError)?;
+        self.record_write();
 
         Ok(EntityMetadata::new(
             EntityEditionId::new(entity_id, EntityRecordId::new(row.get(0))),
@@ -345,6 +477,7 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
             entity_type_id,
             ProvenanceMetadata::new(updated_by_id),
             archived,
+            draft_id,
         ))
     }
 
@@ -432,6 +565,7 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
                     entity_type_id.clone(),
                     ProvenanceMetadata::new(actor_id),
                     false,
+                    None,
                 )
             })
             .collect())
@@ -439,10 +573,13 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
 
     #[tracing::instrument(level = "info", skip(self))]
     async fn get_entity(&self, query: &StructuralQuery<Entity>) -> Result<Subgraph, QueryError> {
+        let _timer = metrics::store_operation("get", "entities");
+
         let StructuralQuery {
             ref filter,
             graph_resolve_depths,
             ref time_projection,
+            draft_id,
         } = *query;
 
         let mut subgraph = Subgraph::new(
@@ -450,12 +587,21 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
             time_projection.clone(),
             time_projection.clone().resolve(),
         );
+        subgraph.draft_id = draft_id;
+        let graph_resolve_depths =
+            self.limit_resolve_depths(&mut subgraph, graph_resolve_depths)?;
         let mut dependency_context = DependencyContext::default();
         let time_axis = subgraph.resolved_time_projection.time_axis();
 
-        for entity in Read::<Entity>::read(self, filter, &subgraph.resolved_time_projection).await?
+        for entity in self
+            .read_entities(filter, &subgraph.resolved_time_projection, draft_id)
+            .await?
         {
             let vertex_id = entity.vertex_id(time_axis);
+            if !self.reserve_vertex::<Entity>(&mut subgraph, &vertex_id)? {
+                break;
+            }
+
             // Insert the vertex into the subgraph to avoid another lookup when traversing it
             subgraph.insert(&vertex_id, entity);
 
@@ -473,6 +619,32 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
         Ok(subgraph)
     }
 
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn changed_entity_ids(
+        &self,
+        from: Timestamp<TransactionTime>,
+        to: Timestamp<TransactionTime>,
+    ) -> Result<Vec<EntityId>, QueryError> {
+        let _timer = metrics::store_operation("read", "entity_versions");
+
+        Ok(self
+            .as_client()
+            .query(
+                r#"
+                SELECT DISTINCT owned_by_id, entity_uuid
+                FROM entity_versions
+                WHERE lower(transaction_time) > $1 AND lower(transaction_time) <= $2;
+                "#,
+                &[&from, &to],
+            )
+            .await
+            .into_report()
+            .change_context(QueryError)?
+            .into_iter()
+            .map(|row| EntityId::new(OwnedById::new(row.get(0)), EntityUuid::new(row.get(1))))
+            .collect())
+    }
+
     #[tracing::instrument(level = "info", skip(self, properties))]
     async fn update_entity(
         &mut self,
@@ -482,13 +654,21 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
         archived: bool,
         entity_type_id: VersionedUri,
         properties: EntityProperties,
+        property_source: Option<String>,
         link_order: EntityLinkOrder,
+        draft_id: Option<DraftId>,
     ) -> Result<EntityMetadata, UpdateError> {
+        let _timer = metrics::store_operation("update", "entities");
+
         let entity_type_version_id = self
             .version_id_by_uri(&entity_type_id)
             .await
             .change_context(UpdateError)?;
 
+        self.validate_entity_properties(&entity_type_id, &properties)
+            .await
+            .change_context(UpdateError)?;
+
         let properties = serde_json::to_value(properties)
             .into_report()
             .change_context(UpdateError)?;
@@ -534,8 +714,10 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
                         _archived := $5,
                         _entity_type_version_id := $6,
                         _properties := $7,
-                        _left_to_right_order := $8,
-                        _right_to_left_order := $9
+                        _property_source := $8,
+                        _left_to_right_order := $9,
+                        _right_to_left_order := $10,
+                        _draft_id := $11
                     );
                 "#,
                 &[
@@ -546,8 +728,10 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
                     &archived,
                     &entity_type_version_id,
                     &properties,
+                    &property_source,
                     &link_order.left_to_right(),
                     &link_order.right_to_left(),
+                    &draft_id,
                 ],
             )
             .await
@@ -571,6 +755,81 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
             entity_type_id,
             ProvenanceMetadata::new(updated_by_id),
             archived,
+            draft_id,
         ))
     }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn create_draft(&mut self, owned_by_id: OwnedById) -> Result<DraftId, InsertionError> {
+        let _timer = metrics::store_operation("create", "drafts");
+
+        let draft_id = DraftId::new(Uuid::new_v4());
+
+        self.as_client()
+            .execute(
+                "INSERT INTO drafts (draft_id, owned_by_id) VALUES ($1, $2);",
+                &[&draft_id, &owned_by_id],
+            )
+            .await
+            .into_report()
+            .change_context(InsertionError)?;
+        self.record_write();
+
+        Ok(draft_id)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn publish_draft(
+        &mut self,
+        draft_id: DraftId,
+    ) -> Result<Vec<EntityEditionId>, UpdateError> {
+        let _timer = metrics::store_operation("publish", "drafts");
+
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+        transaction.lock_draft(draft_id).await?;
+        transaction.lock_draft_entities(draft_id).await?;
+        transaction.check_draft_conflicts(draft_id).await?;
+
+        let edition_ids = transaction
+            .as_client()
+            .query(
+                r#"
+                SELECT owned_by_id, entity_uuid, entity_record_id
+                FROM publish_draft(_draft_id := $1);
+                "#,
+                &[&draft_id],
+            )
+            .await
+            .into_report()
+            .change_context(UpdateError)?
+            .into_iter()
+            .map(|row| {
+                EntityEditionId::new(
+                    EntityId::new(OwnedById::new(row.get(0)), EntityUuid::new(row.get(1))),
+                    EntityRecordId::new(row.get(2)),
+                )
+            })
+            .collect();
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        Ok(edition_ids)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn discard_draft(&mut self, draft_id: DraftId) -> Result<(), UpdateError> {
+        let _timer = metrics::store_operation("discard", "drafts");
+
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+        transaction.lock_draft(draft_id).await?;
+
+        transaction
+            .as_client()
+            .execute("SELECT discard_draft(_draft_id := $1);", &[&draft_id])
+            .await
+            .into_report()
+            .change_context(UpdateError)?;
+
+        transaction.commit().await.change_context(UpdateError)
+    }
 }
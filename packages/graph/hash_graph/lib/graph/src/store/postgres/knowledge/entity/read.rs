@@ -13,7 +13,11 @@ use crate::{
         knowledge::{EntityEditionId, EntityId, EntityRecordId, EntityVersion},
         time::{TimeProjection, VersionTimespan},
     },
-    knowledge::{Entity, EntityProperties, EntityQueryPath, EntityUuid, LinkData},
+    knowledge::{
+        DraftId, Entity, EntityProperties, EntityPropertyProvenance, EntityQueryPath, EntityUuid,
+        LinkData,
+    },
+    metrics,
     ontology::EntityTypeQueryPath,
     provenance::{OwnedById, ProvenanceMetadata, UpdatedById},
     store::{
@@ -26,12 +30,29 @@ use crate::{
 
 #[async_trait]
 impl<C: AsClient> crud::Read<Entity> for PostgresStore<C> {
-    #[tracing::instrument(level = "info", skip(self))]
     async fn read(
         &self,
         filter: &Filter<Entity>,
         time_projection: &TimeProjection,
     ) -> Result<Vec<Entity>, QueryError> {
+        self.read_entities(filter, time_projection, None).await
+    }
+}
+
+impl<C: AsClient> PostgresStore<C> {
+    /// Reads the [`Entity`]s matching `filter`.
+    ///
+    /// If a `draft_id` is passed, the editions of that draft are read in place of the live
+    /// editions of the same entities, otherwise only live editions are read.
+    #[tracing::instrument(level = "info", skip(self))]
+    pub(crate) async fn read_entities(
+        &self,
+        filter: &Filter<'_, Entity>,
+        time_projection: &TimeProjection,
+        draft_id: Option<DraftId>,
+    ) -> Result<Vec<Entity>, QueryError> {
+        let _timer = metrics::store_operation("read", "entities");
+
         // We can't define these inline otherwise we'll drop while borrowed
         let left_entity_uuid_path = EntityQueryPath::LeftEntity(Box::new(EntityQueryPath::Uuid));
         let left_owned_by_id_query_path =
@@ -41,6 +62,9 @@ impl<C: AsClient> crud::Read<Entity> for PostgresStore<C> {
             EntityQueryPath::RightEntity(Box::new(EntityQueryPath::OwnedById));
 
         let mut compiler = SelectCompiler::new(time_projection);
+        if let Some(draft_id) = &draft_id {
+            compiler.include_draft(draft_id);
+        }
 
         let owned_by_id_index = compiler.add_selection_path(&EntityQueryPath::OwnedById);
         let entity_uuid_index = compiler.add_selection_path(&EntityQueryPath::Uuid);
@@ -56,6 +80,8 @@ impl<C: AsClient> crud::Read<Entity> for PostgresStore<C> {
             compiler.add_selection_path(&EntityQueryPath::Type(EntityTypeQueryPath::VersionedUri));
 
         let properties_index = compiler.add_selection_path(&EntityQueryPath::Properties(None));
+        let property_provenance_index =
+            compiler.add_selection_path(&EntityQueryPath::PropertyProvenance(None));
 
         let left_entity_uuid_index = compiler.add_selection_path(&left_entity_uuid_path);
         let left_entity_owned_by_id_index =
@@ -71,6 +97,7 @@ impl<C: AsClient> crud::Read<Entity> for PostgresStore<C> {
         let updated_by_id_index = compiler.add_selection_path(&EntityQueryPath::UpdatedById);
 
         let archived_index = compiler.add_selection_path(&EntityQueryPath::Archived);
+        let draft_id_index = compiler.add_selection_path(&EntityQueryPath::DraftId);
 
         compiler.add_filter(filter);
         let (statement, parameters) = compiler.compile();
@@ -86,6 +113,10 @@ impl<C: AsClient> crud::Read<Entity> for PostgresStore<C> {
                     serde_json::from_value(row.get(properties_index))
                         .into_report()
                         .change_context(QueryError)?;
+                let property_provenance: EntityPropertyProvenance =
+                    serde_json::from_value(row.get(property_provenance_index))
+                        .into_report()
+                        .change_context(QueryError)?;
                 let entity_type_uri = VersionedUri::from_str(row.get(type_id_index))
                     .into_report()
                     .change_context(QueryError)?;
@@ -134,6 +165,7 @@ impl<C: AsClient> crud::Read<Entity> for PostgresStore<C> {
 
                 Ok(Entity::new(
                     properties,
+                    property_provenance,
                     link_data,
                     EntityEditionId::new(
                         EntityId::new(owned_by_id, entity_uuid),
@@ -148,6 +180,7 @@ impl<C: AsClient> crud::Read<Entity> for PostgresStore<C> {
                     // TODO: only the historic table would have an `archived` field.
                     //   Consider what we should do about that.
                     row.get(archived_index),
+                    row.get(draft_id_index),
                 ))
             })
             .try_collect()
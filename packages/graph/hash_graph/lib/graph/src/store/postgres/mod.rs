@@ -1,14 +1,17 @@
+mod cache;
 mod knowledge;
 mod ontology;
 
 mod pool;
 mod query;
 mod version_id;
+mod webhook;
 
 use std::{
     collections::{hash_map::RawEntryMut, HashMap},
     fmt::Debug,
     hash::Hash,
+    sync::Arc,
 };
 
 use async_trait::async_trait;
@@ -22,13 +25,28 @@ use type_system::{
 };
 use uuid::Uuid;
 
-pub use self::pool::{AsClient, PostgresStorePool};
+use self::{
+    cache::{CachedOntologyType, OntologyCacheHandle},
+    pool::WriteTracker,
+};
+pub use self::{
+    cache::{OntologyCache, OntologyCacheStatistics},
+    pool::{AsClient, PostgresStorePool},
+};
 use crate::{
     identifier::{
-        account::AccountId, ontology::OntologyTypeEditionId, time::UnresolvedTimeProjection,
+        account::AccountId,
+        ontology::OntologyTypeEditionId,
+        time::{TimeProjection, UnresolvedTimeProjection},
         EntityVertexId,
     },
-    ontology::{OntologyElementMetadata, OntologyTypeWithMetadata},
+    knowledge::EntityProperties,
+    metrics,
+    ontology::{
+        validation::PropertyValidator, ConstraintViolation, DataTypeWithMetadata,
+        EntityTypeWithMetadata, OntologyElementMetadata, OntologyTypeWithMetadata,
+        PropertyTypeWithMetadata,
+    },
     provenance::{OwnedById, ProvenanceMetadata, UpdatedById},
     store::{
         crud::Read,
@@ -36,9 +54,9 @@ use crate::{
         postgres::{ontology::OntologyDatabaseType, query::PostgresRecord, version_id::VersionId},
         query::{Filter, OntologyQueryPath},
         AccountStore, BaseUriAlreadyExists, BaseUriDoesNotExist, InsertionError, QueryError,
-        Record, Store, StoreError, Transaction, UpdateError,
+        QueryLimitExceeded, QueryLimits, Record, Store, StoreError, Transaction, UpdateError,
     },
-    subgraph::edges::GraphResolveDepths,
+    subgraph::{edges::GraphResolveDepths, Subgraph, SubgraphIndex},
 };
 #[cfg(feature = "__internal_bench")]
 use crate::{
@@ -46,7 +64,7 @@ use crate::{
         knowledge::{EntityId, EntityRecordId, EntityVersion},
         time::{DecisionTime, Timestamp, VersionTimespan},
     },
-    knowledge::{EntityProperties, LinkOrder},
+    knowledge::LinkOrder,
 };
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -109,9 +127,29 @@ pub struct DependencyContext {
     pub knowledge_dependency_map: DependencyMap<EntityVertexId>,
 }
 
+/// The columns the store relies on, given as `(table, column)`.
+///
+/// The migrations are edited in place, so the name of the latest applied migration does not tell
+/// if the database was migrated by an older version. Instead, the readiness check requires these
+/// schema objects, which the most recent changes to the schema introduced, to exist.
+const REQUIRED_COLUMNS: &[(&str, &str)] = &[
+    ("type_ids", "transaction_time"),
+    ("data_types", "external"),
+    ("property_types", "external"),
+    ("entity_types", "external"),
+    ("entity_editions", "property_provenance"),
+    ("drafts", "draft_id"),
+    ("entity_drafts", "base_entity_record_id"),
+    ("webhooks", "filter"),
+    ("webhook_dead_letters", "failed_at"),
+];
+
 /// A Postgres-backed store
 pub struct PostgresStore<C> {
     client: C,
+    limits: QueryLimits,
+    cache: OntologyCacheHandle,
+    write_tracker: Option<Arc<WriteTracker>>,
 }
 
 #[async_trait]
@@ -122,14 +160,60 @@ impl<C: AsClient> Store for PostgresStore<C> {
     = PostgresStore<tokio_postgres::Transaction<'t>>;
 
     async fn transaction(&mut self) -> Result<Self::Transaction<'_>, StoreError> {
-        Ok(PostgresStore::new(
+        let limits = self.limits;
+        let cache = self.cache.transaction();
+        let write_tracker = self.write_tracker.clone();
+        Ok(PostgresStore::from_parts(
             self.as_mut_client()
                 .transaction()
                 .await
                 .into_report()
                 .change_context(StoreError)?,
+            limits,
+            cache,
+            write_tracker,
         ))
     }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn check_readiness(&self) -> Result<(), StoreError> {
+        let (tables, columns): (Vec<_>, Vec<_>) = REQUIRED_COLUMNS.iter().copied().unzip();
+        let missing_columns = self
+            .as_client()
+            .query(
+                r#"
+                    SELECT required.table_name, required.column_name
+                    FROM unnest($1::text[], $2::text[]) AS required(table_name, column_name)
+                    WHERE NOT EXISTS (
+                        SELECT 1
+                        FROM information_schema.columns
+                        WHERE columns.table_schema = current_schema()
+                          AND columns.table_name = required.table_name
+                          AND columns.column_name = required.column_name
+                    );
+                "#,
+                &[&tables, &columns],
+            )
+            .await
+            .into_report()
+            .change_context(StoreError)
+            .attach_printable("could not read the database schema")?;
+
+        let mut result: Result<(), StoreError> = Ok(());
+        for row in missing_columns {
+            let table: String = row.get(0);
+            let column: String = row.get(1);
+            let report = Report::new(StoreError).attach_printable(format!(
+                "the column `{table}.{column}` is missing, the latest migrations were not applied"
+            ));
+            match &mut result {
+                Err(error) => error.extend_one(report),
+                Ok(()) => result = Err(report),
+            }
+        }
+
+        result
+    }
 }
 
 #[async_trait]
@@ -139,7 +223,12 @@ impl Transaction for PostgresStore<tokio_postgres::Transaction<'_>> {
             .commit()
             .await
             .into_report()
-            .change_context(StoreError)
+            .change_context(StoreError)?;
+        self.cache.commit();
+        if let Some(write_tracker) = &self.write_tracker {
+            write_tracker.record_write();
+        }
+        Ok(())
     }
 
     async fn rollback(self) -> Result<(), StoreError> {
@@ -158,7 +247,231 @@ where
     /// Creates a new `PostgresDatabase` object.
     #[must_use]
     pub const fn new(client: C) -> Self {
-        Self { client }
+        Self::with_query_limits(client, QueryLimits::unlimited())
+    }
+
+    /// Creates a new `PostgresDatabase` object, which applies the specified [`QueryLimits`] to
+    /// structural queries.
+    ///
+    /// The store does not use an [`OntologyCache`], stores sharing a cache are acquired from a
+    /// [`PostgresStorePool`].
+    #[must_use]
+    pub const fn with_query_limits(client: C, limits: QueryLimits) -> Self {
+        Self::from_parts(client, limits, OntologyCacheHandle::disabled(), None)
+    }
+
+    pub(crate) const fn from_parts(
+        client: C,
+        limits: QueryLimits,
+        cache: OntologyCacheHandle,
+        write_tracker: Option<Arc<WriteTracker>>,
+    ) -> Self {
+        Self {
+            client,
+            limits,
+            cache,
+            write_tracker,
+        }
+    }
+
+    /// Notifies the pool the store was acquired from, that a mutation has been committed to the
+    /// primary database, so subsequent reads within the read-your-writes window are not sent to a
+    /// read replica.
+    fn record_write(&self) {
+        if let Some(write_tracker) = &self.write_tracker {
+            write_tracker.record_write();
+        }
+    }
+
+    /// Reads the ontology type identified by `vertex_id` into the `subgraph` if it's not already
+    /// contained.
+    ///
+    /// The [`OntologyCache`] is consulted before reading the type from the database, if the type
+    /// was cached at a point in time contained in the `time_projection`.
+    ///
+    /// # Errors
+    ///
+    /// - if reading the ontology type from the database failed
+    async fn get_or_read_ontology_type<'r, T>(
+        &self,
+        subgraph: &'r mut Subgraph,
+        vertex_id: &OntologyTypeEditionId,
+        time_projection: &TimeProjection,
+    ) -> Result<&'r T, QueryError>
+    where
+        T: CachedOntologyType<VertexId = OntologyTypeEditionId> + Sync + 'r,
+        Self: Read<T>,
+    {
+        let is_contained = matches!(
+            <OntologyTypeEditionId as SubgraphIndex<T>>::subgraph_vertex_entry(vertex_id, subgraph),
+            RawEntryMut::Occupied(_)
+        );
+
+        if !is_contained {
+            let uri = VersionedUri::from(vertex_id);
+            let ontology_type = match self.cache.get::<T>(&uri, time_projection) {
+                Some(ontology_type) => ontology_type,
+                None => {
+                    let ontology_type = <Self as Read<T>>::read_one(
+                        self,
+                        &T::create_filter_for_vertex_id(vertex_id),
+                        time_projection,
+                    )
+                    .await?;
+                    self.cache.insert(uri, ontology_type.clone());
+                    ontology_type
+                }
+            };
+            subgraph.insert(vertex_id, ontology_type);
+        }
+
+        subgraph.get_or_read(self, vertex_id, time_projection).await
+    }
+
+    /// Reads the latest edition of the ontology type identified by `uri`.
+    ///
+    /// The [`OntologyCache`] is consulted before reading the type from the database.
+    ///
+    /// # Errors
+    ///
+    /// - if reading the ontology type from the database failed
+    async fn read_ontology_type<T>(&self, uri: &VersionedUri) -> Result<T, QueryError>
+    where
+        T: CachedOntologyType<VertexId = OntologyTypeEditionId> + Send,
+        Self: Read<T>,
+    {
+        let time_projection = UnresolvedTimeProjection::default().resolve();
+        if let Some(ontology_type) = self.cache.get::<T>(uri, &time_projection) {
+            return Ok(ontology_type);
+        }
+
+        let ontology_type = <Self as Read<T>>::read_one(
+            self,
+            &T::create_filter_for_vertex_id(&OntologyTypeEditionId::from(uri)),
+            &time_projection,
+        )
+        .await?;
+        self.cache.insert(uri.clone(), ontology_type.clone());
+        Ok(ontology_type)
+    }
+
+    /// Validates `properties` against the constraints of the data types, which the property
+    /// types of the entity type refer to.
+    ///
+    /// # Errors
+    ///
+    /// - [`ConstraintViolation`], if a property value does not satisfy its property type
+    /// - if reading the required ontology types failed
+    ///
+    /// [`ConstraintViolation`]: crate::ontology::ConstraintViolation
+    #[tracing::instrument(level = "debug", skip(self, properties))]
+    async fn validate_entity_properties(
+        &self,
+        entity_type_id: &VersionedUri,
+        properties: &EntityProperties,
+    ) -> Result<(), ConstraintViolation> {
+        let mut validator = PropertyValidator::new(entity_type_id.clone());
+        loop {
+            let entity_type_ids = validator.missing_entity_types();
+            let property_type_ids = validator.missing_property_types();
+            let data_type_ids = validator.missing_data_types();
+            if entity_type_ids.is_empty()
+                && property_type_ids.is_empty()
+                && data_type_ids.is_empty()
+            {
+                break;
+            }
+
+            for uri in entity_type_ids {
+                let entity_type: EntityTypeWithMetadata = self
+                    .read_ontology_type(&uri)
+                    .await
+                    .change_context(ConstraintViolation)?;
+                validator.insert_entity_type(entity_type.inner().clone())?;
+            }
+            for uri in property_type_ids {
+                let property_type: PropertyTypeWithMetadata =
+                    self.read_ontology_type(&uri)
+                        .await
+                        .change_context(ConstraintViolation)?;
+                validator.insert_property_type(property_type.inner().clone())?;
+            }
+            for uri in data_type_ids {
+                let data_type: DataTypeWithMetadata = self
+                    .read_ontology_type(&uri)
+                    .await
+                    .change_context(ConstraintViolation)?;
+                validator.insert_data_type(data_type.inner().clone())?;
+            }
+        }
+
+        validator.validate(properties)
+    }
+
+    /// Caps the `requested` depths at the maximum depths of the [`QueryLimits`].
+    ///
+    /// If a depth was capped, the `subgraph` is flagged as incomplete.
+    ///
+    /// # Errors
+    ///
+    /// - [`QueryLimitExceeded`], if a requested depth exceeds the limit and truncating subgraphs is
+    ///   disabled
+    fn limit_resolve_depths(
+        &self,
+        subgraph: &mut Subgraph,
+        requested: GraphResolveDepths,
+    ) -> Result<GraphResolveDepths, QueryError> {
+        let (depths, exceeded) = self.limits.clamp_resolve_depths(requested);
+        if exceeded {
+            if !self.limits.truncate_subgraphs() {
+                return Err(Report::new(QueryLimitExceeded)
+                    .attach_printable(format!(
+                        "maximum resolve depths: {:?}",
+                        self.limits.max_resolve_depths()
+                    ))
+                    .change_context(QueryError));
+            }
+            subgraph.incomplete = true;
+        }
+        subgraph.depths = depths;
+        Ok(depths)
+    }
+
+    /// Checks if the vertex may be added to the `subgraph` without exceeding the maximum number
+    /// of vertices of the [`QueryLimits`].
+    ///
+    /// Returns `true` if the vertex is already contained in the subgraph or if the limit is not
+    /// reached yet. Otherwise, `false` is returned and the subgraph is flagged as incomplete.
+    ///
+    /// # Errors
+    ///
+    /// - [`QueryLimitExceeded`], if the limit is reached and truncating subgraphs is disabled
+    fn reserve_vertex<R: Record>(
+        &self,
+        subgraph: &mut Subgraph,
+        vertex_id: &R::VertexId,
+    ) -> Result<bool, QueryError> {
+        let Some(max_vertices) = self.limits.max_vertices() else {
+            return Ok(true);
+        };
+
+        if subgraph.vertices.len() < max_vertices
+            || matches!(
+                vertex_id.subgraph_vertex_entry(subgraph),
+                RawEntryMut::Occupied(_)
+            )
+        {
+            return Ok(true);
+        }
+
+        if !self.limits.truncate_subgraphs() {
+            return Err(Report::new(QueryLimitExceeded)
+                .attach_printable(format!("maximum number of vertices: {max_vertices}"))
+                .change_context(QueryError));
+        }
+
+        subgraph.incomplete = true;
+        Ok(false)
     }
 
     /// Checks if the specified [`BaseUri`] exists in the database.
@@ -313,7 +626,7 @@ where
         &self,
         database_type: T,
         owned_by_id: OwnedById,
-        updated_by_id: UpdatedById,
+        provenance_metadata: ProvenanceMetadata,
     ) -> Result<(VersionId, OntologyElementMetadata), InsertionError>
     where
         T: OntologyDatabaseType<Representation: Send> + Send + Sync,
@@ -346,14 +659,17 @@ where
         self.insert_version_id(version_id).await?;
         self.insert_uri(&uri, version_id).await?;
 
-        self.insert_with_id(version_id, database_type, owned_by_id, updated_by_id)
+        self.insert_with_id(version_id, database_type, owned_by_id, provenance_metadata)
             .await?;
 
+        self.cache.insert_version_id(uri.clone(), version_id);
+        self.cache.set_latest(uri.clone());
+
         Ok((
             version_id,
             OntologyElementMetadata::new(
                 OntologyTypeEditionId::from(&uri),
-                ProvenanceMetadata::new(updated_by_id),
+                provenance_metadata,
                 owned_by_id,
             ),
         ))
@@ -377,7 +693,7 @@ where
     ) -> Result<(VersionId, OntologyElementMetadata), UpdateError>
     where
         T: OntologyDatabaseType<Representation: Send> + Send + Sync,
-        T::WithMetadata: PostgresRecord + Send,
+        T::WithMetadata: PostgresRecord + CachedOntologyType + Send,
         for<'p> <T::WithMetadata as Record>::QueryPath<'p>: OntologyQueryPath + Send + Sync,
     {
         let uri = database_type.id().clone();
@@ -394,13 +710,24 @@ where
 
         // TODO - address potential race condition
         //  https://app.asana.com/0/1202805690238892/1203201674100967/f
-        let previous_ontology_type = <Self as Read<T::WithMetadata>>::read_one(
-            self,
-            &Filter::for_latest_base_uri(uri.base_uri()),
-            &UnresolvedTimeProjection::default().resolve(),
-        )
-        .await
-        .change_context(UpdateError)?;
+        let previous_ontology_type = match self.cache.get_latest::<T::WithMetadata>(uri.base_uri())
+        {
+            Some(previous_ontology_type) => previous_ontology_type,
+            None => {
+                let previous_ontology_type = <Self as Read<T::WithMetadata>>::read_one(
+                    self,
+                    &Filter::for_latest_base_uri(uri.base_uri()),
+                    &UnresolvedTimeProjection::default().resolve(),
+                )
+                .await
+                .change_context(UpdateError)?;
+                self.cache.insert(
+                    VersionedUri::from(previous_ontology_type.metadata().edition_id()),
+                    previous_ontology_type.clone(),
+                );
+                previous_ontology_type
+            }
+        };
 
         let owned_by_id = previous_ontology_type.metadata().owned_by_id();
 
@@ -411,22 +738,26 @@ where
         self.insert_uri(&uri, version_id)
             .await
             .change_context(UpdateError)?;
-        self.insert_with_id(version_id, database_type, owned_by_id, updated_by_id)
+        let provenance_metadata = ProvenanceMetadata::new(updated_by_id);
+        self.insert_with_id(version_id, database_type, owned_by_id, provenance_metadata)
             .await
             .change_context(UpdateError)?;
 
+        self.cache.insert_version_id(uri.clone(), version_id);
+        self.cache.set_latest(uri.clone());
+
         Ok((
             version_id,
             OntologyElementMetadata::new(
                 OntologyTypeEditionId::from(&uri),
-                ProvenanceMetadata::new(updated_by_id),
+                provenance_metadata,
                 owned_by_id,
             ),
         ))
     }
 
     /// Inserts an [`OntologyDatabaseType`] identified by [`VersionId`], and associated with an
-    /// [`OwnedById`] and [`UpdatedById`], into the database.
+    /// [`OwnedById`] and [`ProvenanceMetadata`], into the database.
     ///
     /// # Errors
     ///
@@ -437,7 +768,7 @@ where
         version_id: VersionId,
         database_type: T,
         owned_by_id: OwnedById,
-        updated_by_id: UpdatedById,
+        provenance_metadata: ProvenanceMetadata,
     ) -> Result<(), InsertionError>
     where
         T: OntologyDatabaseType<Representation: Send> + Send + Sync,
@@ -453,8 +784,8 @@ where
             .query_one(
                 &format!(
                     r#"
-                        INSERT INTO {} (version_id, schema, owned_by_id, updated_by_id)
-                        VALUES ($1, $2, $3, $4)
+                        INSERT INTO {} (version_id, schema, owned_by_id, updated_by_id, external)
+                        VALUES ($1, $2, $3, $4, $5)
                         RETURNING version_id;
                     "#,
                     T::table()
@@ -463,7 +794,8 @@ where
                     &version_id,
                     &value,
                     &owned_by_id.as_account_id(),
-                    &updated_by_id.as_account_id(),
+                    &provenance_metadata.updated_by_id().as_account_id(),
+                    &provenance_metadata.is_external(),
                 ],
             )
             .await
@@ -651,8 +983,12 @@ where
     /// - if the entry referred to by `uri` does not exist.
     #[tracing::instrument(level = "debug", skip(self))]
     async fn version_id_by_uri(&self, uri: &VersionedUri) -> Result<VersionId, QueryError> {
+        if let Some(version_id) = self.cache.get_version_id(uri) {
+            return Ok(version_id);
+        }
+
         let version = i64::from(uri.version());
-        Ok(self
+        let version_id = self
             .client
             .as_client()
             .query_one(
@@ -667,7 +1003,10 @@ where
             .into_report()
             .change_context(QueryError)
             .attach_printable_lazy(|| uri.clone())?
-            .get(0))
+            .get(0);
+
+        self.cache.insert_version_id(uri.clone(), version_id);
+        Ok(version_id)
     }
 }
 
@@ -814,6 +1153,7 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
                     archived,
                     entity_type_version_id,
                     properties,
+                    property_provenance,
                     left_to_right_order,
                     right_to_left_order
                 )
@@ -822,6 +1162,7 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
                     archived,
                     entity_type_version_id,
                     properties,
+                    merge_property_provenance(properties, NULL, NULL, updated_by_id, NULL),
                     left_to_right_order,
                     right_to_left_order
                 FROM entity_editions_temp
@@ -955,6 +1296,8 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
 impl<C: AsClient> AccountStore for PostgresStore<C> {
     #[tracing::instrument(level = "info", skip(self))]
     async fn insert_account_id(&mut self, account_id: AccountId) -> Result<(), InsertionError> {
+        let _timer = metrics::store_operation("create", "accounts");
+
         self.as_client()
             .query_one(
                 r#"
@@ -968,6 +1311,7 @@ impl<C: AsClient> AccountStore for PostgresStore<C> {
             .into_report()
             .change_context(InsertionError)
             .attach_printable(account_id)?;
+        self.record_write();
 
         Ok(())
     }
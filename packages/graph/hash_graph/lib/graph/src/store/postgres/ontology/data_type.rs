@@ -4,8 +4,9 @@ use type_system::DataType;
 
 use crate::{
     identifier::ontology::OntologyTypeEditionId,
-    ontology::{DataTypeWithMetadata, OntologyElementMetadata},
-    provenance::{OwnedById, UpdatedById},
+    metrics,
+    ontology::{DataTypeConstraints, DataTypeWithMetadata, OntologyElementMetadata},
+    provenance::{OwnedById, ProvenanceMetadata, UpdatedById},
     store::{
         crud::Read,
         postgres::{DependencyContext, DependencyStatus},
@@ -16,6 +17,27 @@ use crate::{
 };
 
 impl<C: AsClient> PostgresStore<C> {
+    /// Inserts the [`DataType`] in a new transaction.
+    #[tracing::instrument(level = "debug", skip(self, data_type))]
+    async fn insert_data_type(
+        &mut self,
+        data_type: DataType,
+        owned_by_id: OwnedById,
+        provenance_metadata: ProvenanceMetadata,
+    ) -> Result<OntologyElementMetadata, InsertionError> {
+        DataTypeConstraints::from_data_type(&data_type).change_context(InsertionError)?;
+
+        let transaction = self.transaction().await.change_context(InsertionError)?;
+
+        let (_, metadata) = transaction
+            .create(data_type, owned_by_id, provenance_metadata)
+            .await?;
+
+        transaction.commit().await.change_context(InsertionError)?;
+
+        Ok(metadata)
+    }
+
     /// Internal method to read a [`DataTypeWithMetadata`] into a [`DependencyContext`].
     ///
     /// This is used to recursively resolve a type, so the result can be reused.
@@ -33,10 +55,17 @@ impl<C: AsClient> PostgresStore<C> {
 
         let _data_type = match dependency_status {
             DependencyStatus::Unresolved => {
+                if !self.reserve_vertex::<DataTypeWithMetadata>(subgraph, data_type_id)? {
+                    return Ok(());
+                }
+
                 let time_projection = subgraph.resolved_time_projection.clone();
-                subgraph
-                    .get_or_read::<DataTypeWithMetadata>(self, data_type_id, &time_projection)
-                    .await?
+                self.get_or_read_ontology_type::<DataTypeWithMetadata>(
+                    subgraph,
+                    data_type_id,
+                    &time_projection,
+                )
+                .await?
             }
             DependencyStatus::Resolved => return Ok(()),
         };
@@ -58,15 +87,31 @@ impl<C: AsClient> DataTypeStore for PostgresStore<C> {
         owned_by_id: OwnedById,
         updated_by_id: UpdatedById,
     ) -> Result<OntologyElementMetadata, InsertionError> {
-        let transaction = self.transaction().await.change_context(InsertionError)?;
-
-        let (_, metadata) = transaction
-            .create(data_type, owned_by_id, updated_by_id)
-            .await?;
-
-        transaction.commit().await.change_context(InsertionError)?;
+        let _timer = metrics::store_operation("create", "data_types");
+
+        self.insert_data_type(
+            data_type,
+            owned_by_id,
+            ProvenanceMetadata::new(updated_by_id),
+        )
+        .await
+    }
 
-        Ok(metadata)
+    #[tracing::instrument(level = "info", skip(self, data_type))]
+    async fn import_data_type(
+        &mut self,
+        data_type: DataType,
+        owned_by_id: OwnedById,
+        updated_by_id: UpdatedById,
+    ) -> Result<OntologyElementMetadata, InsertionError> {
+        let _timer = metrics::store_operation("import", "data_types");
+
+        self.insert_data_type(
+            data_type,
+            owned_by_id,
+            ProvenanceMetadata::new_external(updated_by_id),
+        )
+        .await
     }
 
     #[tracing::instrument(level = "info", skip(self))]
@@ -74,10 +119,13 @@ impl<C: AsClient> DataTypeStore for PostgresStore<C> {
         &self,
         query: &StructuralQuery<DataTypeWithMetadata>,
     ) -> Result<Subgraph, QueryError> {
+        let _timer = metrics::store_operation("get", "data_types");
+
         let StructuralQuery {
             ref filter,
             graph_resolve_depths,
             ref time_projection,
+            draft_id: _,
         } = *query;
 
         let mut subgraph = Subgraph::new(
@@ -85,6 +133,8 @@ impl<C: AsClient> DataTypeStore for PostgresStore<C> {
             time_projection.clone(),
             time_projection.clone().resolve(),
         );
+        let graph_resolve_depths =
+            self.limit_resolve_depths(&mut subgraph, graph_resolve_depths)?;
         let mut dependency_context = DependencyContext::default();
         let time_axis = subgraph.resolved_time_projection.time_axis();
 
@@ -93,6 +143,10 @@ impl<C: AsClient> DataTypeStore for PostgresStore<C> {
                 .await?
         {
             let vertex_id = data_type.vertex_id(time_axis);
+            if !self.reserve_vertex::<DataTypeWithMetadata>(&mut subgraph, &vertex_id)? {
+                break;
+            }
+
             // Insert the vertex into the subgraph to avoid another lookup when traversing it
             subgraph.insert(&vertex_id, data_type);
 
@@ -116,6 +170,10 @@ impl<C: AsClient> DataTypeStore for PostgresStore<C> {
         data_type: DataType,
         updated_by_id: UpdatedById,
     ) -> Result<OntologyElementMetadata, UpdateError> {
+        let _timer = metrics::store_operation("update", "data_types");
+
+        DataTypeConstraints::from_data_type(&data_type).change_context(UpdateError)?;
+
         let transaction = self.transaction().await.change_context(UpdateError)?;
 
         let (_, metadata) = transaction
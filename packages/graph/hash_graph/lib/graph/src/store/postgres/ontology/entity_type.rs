@@ -7,8 +7,12 @@ use type_system::{EntityType, EntityTypeReference, PropertyTypeReference};
 
 use crate::{
     identifier::ontology::OntologyTypeEditionId,
-    ontology::{EntityTypeWithMetadata, OntologyElementMetadata, OntologyTypeWithMetadata},
-    provenance::{OwnedById, UpdatedById},
+    metrics,
+    ontology::{
+        EntityTypeWithMetadata, OntologyElementMetadata, OntologyTypeWithMetadata,
+        PropertyTypeWithMetadata,
+    },
+    provenance::{OwnedById, ProvenanceMetadata, UpdatedById},
     store::{
         crud::Read,
         postgres::{DependencyContext, DependencyStatus},
@@ -26,6 +30,40 @@ use crate::{
 };
 
 impl<C: AsClient> PostgresStore<C> {
+    /// Inserts the [`EntityType`] and its references in a new transaction.
+    #[tracing::instrument(level = "debug", skip(self, entity_type))]
+    async fn insert_entity_type(
+        &mut self,
+        entity_type: EntityType,
+        owned_by_id: OwnedById,
+        provenance_metadata: ProvenanceMetadata,
+    ) -> Result<OntologyElementMetadata, InsertionError> {
+        let transaction = self.transaction().await.change_context(InsertionError)?;
+
+        // This clone is currently necessary because we extract the references as we insert them.
+        // We can only insert them after the type has been created, and so we currently extract them
+        // after as well. See `insert_entity_type_references` taking `&entity_type`
+        let (version_id, metadata) = transaction
+            .create(entity_type.clone(), owned_by_id, provenance_metadata)
+            .await?;
+
+        transaction
+            .insert_entity_type_references(&entity_type, version_id)
+            .await
+            .change_context(InsertionError)
+            .attach_printable_lazy(|| {
+                format!(
+                    "could not insert references for entity type: {}",
+                    entity_type.id()
+                )
+            })
+            .attach_lazy(|| entity_type.clone())?;
+
+        transaction.commit().await.change_context(InsertionError)?;
+
+        Ok(metadata)
+    }
+
     /// Internal method to read a [`EntityTypeWithMetadata`] into four [`DependencyContext`]s.
     ///
     /// This is used to recursively resolve a type, so the result can be reused.
@@ -44,14 +82,17 @@ impl<C: AsClient> PostgresStore<C> {
 
             let entity_type = match dependency_status {
                 DependencyStatus::Unresolved => {
+                    if !self.reserve_vertex::<EntityTypeWithMetadata>(subgraph, entity_type_id)? {
+                        return Ok(());
+                    }
+
                     let time_projection = subgraph.resolved_time_projection.clone();
-                    subgraph
-                        .get_or_read::<EntityTypeWithMetadata>(
-                            self,
-                            entity_type_id,
-                            &time_projection,
-                        )
-                        .await?
+                    self.get_or_read_ontology_type::<EntityTypeWithMetadata>(
+                        subgraph,
+                        entity_type_id,
+                        &time_projection,
+                    )
+                    .await?
                 }
                 DependencyStatus::Resolved => return Ok(()),
             };
@@ -107,6 +148,13 @@ impl<C: AsClient> PostgresStore<C> {
 
             if let Some(property_type_ref_uris) = property_type_ref_uris {
                 for property_type_ref_uri in property_type_ref_uris {
+                    if !self.reserve_vertex::<PropertyTypeWithMetadata>(
+                        subgraph,
+                        &OntologyTypeEditionId::from(&property_type_ref_uri),
+                    )? {
+                        continue;
+                    }
+
                     subgraph.edges.insert(Edge::Ontology {
                         vertex_id: entity_type_id.clone(),
                         outward_edge: OntologyOutwardEdges::ToOntology(OutwardEdge {
@@ -135,6 +183,13 @@ impl<C: AsClient> PostgresStore<C> {
 
             if let Some(inherits_from_type_ref_uris) = inherits_from_type_ref_uris {
                 for inherits_from_type_ref_uri in inherits_from_type_ref_uris {
+                    if !self.reserve_vertex::<EntityTypeWithMetadata>(
+                        subgraph,
+                        &OntologyTypeEditionId::from(&inherits_from_type_ref_uri),
+                    )? {
+                        continue;
+                    }
+
                     subgraph.edges.insert(Edge::Ontology {
                         vertex_id: entity_type_id.clone(),
                         outward_edge: OntologyOutwardEdges::ToOntology(OutwardEdge {
@@ -165,6 +220,13 @@ impl<C: AsClient> PostgresStore<C> {
             if let Some(link_mappings) = link_mappings {
                 for (link_type_uri, destination_type_uris) in link_mappings {
                     if current_resolve_depth.constrains_links_on.outgoing > 0 {
+                        if !self.reserve_vertex::<EntityTypeWithMetadata>(
+                            subgraph,
+                            &OntologyTypeEditionId::from(&link_type_uri),
+                        )? {
+                            continue;
+                        }
+
                         subgraph.edges.insert(Edge::Ontology {
                             vertex_id: entity_type_id.clone(),
                             outward_edge: OntologyOutwardEdges::ToOntology(OutwardEdge {
@@ -195,6 +257,13 @@ impl<C: AsClient> PostgresStore<C> {
                             > 0
                         {
                             for destination_type_uri in destination_type_uris {
+                                if !self.reserve_vertex::<EntityTypeWithMetadata>(
+                                    subgraph,
+                                    &OntologyTypeEditionId::from(&destination_type_uri),
+                                )? {
+                                    continue;
+                                }
+
                                 subgraph.edges.insert(Edge::Ontology {
                                     vertex_id: entity_type_id.clone(),
                                     outward_edge: OntologyOutwardEdges::ToOntology(OutwardEdge {
@@ -242,30 +311,31 @@ impl<C: AsClient> EntityTypeStore for PostgresStore<C> {
         owned_by_id: OwnedById,
         updated_by_id: UpdatedById,
     ) -> Result<OntologyElementMetadata, InsertionError> {
-        let transaction = self.transaction().await.change_context(InsertionError)?;
+        let _timer = metrics::store_operation("create", "entity_types");
 
-        // This clone is currently necessary because we extract the references as we insert them.
-        // We can only insert them after the type has been created, and so we currently extract them
-        // after as well. See `insert_entity_type_references` taking `&entity_type`
-        let (version_id, metadata) = transaction
-            .create(entity_type.clone(), owned_by_id, updated_by_id)
-            .await?;
-
-        transaction
-            .insert_entity_type_references(&entity_type, version_id)
-            .await
-            .change_context(InsertionError)
-            .attach_printable_lazy(|| {
-                format!(
-                    "could not insert references for entity type: {}",
-                    entity_type.id()
-                )
-            })
-            .attach_lazy(|| entity_type.clone())?;
+        self.insert_entity_type(
+            entity_type,
+            owned_by_id,
+            ProvenanceMetadata::new(updated_by_id),
+        )
+        .await
+    }
 
-        transaction.commit().await.change_context(InsertionError)?;
+    #[tracing::instrument(level = "info", skip(self, entity_type))]
+    async fn import_entity_type(
+        &mut self,
+        entity_type: EntityType,
+        owned_by_id: OwnedById,
+        updated_by_id: UpdatedById,
+    ) -> Result<OntologyElementMetadata, InsertionError> {
+        let _timer = metrics::store_operation("import", "entity_types");
 
-        Ok(metadata)
+        self.insert_entity_type(
+            entity_type,
+            owned_by_id,
+            ProvenanceMetadata::new_external(updated_by_id),
+        )
+        .await
     }
 
     #[tracing::instrument(level = "info", skip(self))]
@@ -273,10 +343,13 @@ impl<C: AsClient> EntityTypeStore for PostgresStore<C> {
         &self,
         query: &StructuralQuery<EntityTypeWithMetadata>,
     ) -> Result<Subgraph, QueryError> {
+        let _timer = metrics::store_operation("get", "entity_types");
+
         let StructuralQuery {
             ref filter,
             graph_resolve_depths,
             ref time_projection,
+            draft_id: _,
         } = *query;
 
         let mut subgraph = Subgraph::new(
@@ -284,6 +357,8 @@ impl<C: AsClient> EntityTypeStore for PostgresStore<C> {
             time_projection.clone(),
             time_projection.clone().resolve(),
         );
+        let graph_resolve_depths =
+            self.limit_resolve_depths(&mut subgraph, graph_resolve_depths)?;
         let mut dependency_context = DependencyContext::default();
         let time_axis = subgraph.resolved_time_projection.time_axis();
 
@@ -292,6 +367,10 @@ impl<C: AsClient> EntityTypeStore for PostgresStore<C> {
                 .await?
         {
             let vertex_id = entity_type.vertex_id(time_axis);
+            if !self.reserve_vertex::<EntityTypeWithMetadata>(&mut subgraph, &vertex_id)? {
+                break;
+            }
+
             // Insert the vertex into the subgraph to avoid another lookup when traversing it
             subgraph.insert(&vertex_id, entity_type);
 
@@ -315,6 +394,8 @@ impl<C: AsClient> EntityTypeStore for PostgresStore<C> {
         entity_type: EntityType,
         updated_by: UpdatedById,
     ) -> Result<OntologyElementMetadata, UpdateError> {
+        let _timer = metrics::store_operation("update", "entity_types");
+
         let transaction = self.transaction().await.change_context(UpdateError)?;
 
         // This clone is currently necessary because we extract the references as we insert them.
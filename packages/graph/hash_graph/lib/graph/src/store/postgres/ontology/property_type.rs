@@ -7,8 +7,12 @@ use type_system::{DataTypeReference, PropertyType, PropertyTypeReference};
 
 use crate::{
     identifier::ontology::OntologyTypeEditionId,
-    ontology::{OntologyElementMetadata, OntologyTypeWithMetadata, PropertyTypeWithMetadata},
-    provenance::{OwnedById, UpdatedById},
+    metrics,
+    ontology::{
+        DataTypeWithMetadata, OntologyElementMetadata, OntologyTypeWithMetadata,
+        PropertyTypeWithMetadata,
+    },
+    provenance::{OwnedById, ProvenanceMetadata, UpdatedById},
     store::{
         crud::Read,
         postgres::{DependencyContext, DependencyStatus},
@@ -26,6 +30,40 @@ use crate::{
 };
 
 impl<C: AsClient> PostgresStore<C> {
+    /// Inserts the [`PropertyType`] and its references in a new transaction.
+    #[tracing::instrument(level = "debug", skip(self, property_type))]
+    async fn insert_property_type(
+        &mut self,
+        property_type: PropertyType,
+        owned_by_id: OwnedById,
+        provenance_metadata: ProvenanceMetadata,
+    ) -> Result<OntologyElementMetadata, InsertionError> {
+        let transaction = self.transaction().await.change_context(InsertionError)?;
+
+        // This clone is currently necessary because we extract the references as we insert them.
+        // We can only insert them after the type has been created, and so we currently extract them
+        // after as well. See `insert_property_type_references` taking `&property_type`
+        let (version_id, metadata) = transaction
+            .create(property_type.clone(), owned_by_id, provenance_metadata)
+            .await?;
+
+        transaction
+            .insert_property_type_references(&property_type, version_id)
+            .await
+            .change_context(InsertionError)
+            .attach_printable_lazy(|| {
+                format!(
+                    "could not insert references for property type: {}",
+                    property_type.id()
+                )
+            })
+            .attach_lazy(|| property_type.clone())?;
+
+        transaction.commit().await.change_context(InsertionError)?;
+
+        Ok(metadata)
+    }
+
     /// Internal method to read a [`PropertyTypeWithMetadata`] into two [`DependencyContext`]s.
     ///
     /// This is used to recursively resolve a type, so the result can be reused.
@@ -44,14 +82,19 @@ impl<C: AsClient> PostgresStore<C> {
 
             let property_type = match dependency_status {
                 DependencyStatus::Unresolved => {
+                    if !self
+                        .reserve_vertex::<PropertyTypeWithMetadata>(subgraph, property_type_id)?
+                    {
+                        return Ok(());
+                    }
+
                     let time_projection = subgraph.resolved_time_projection.clone();
-                    subgraph
-                        .get_or_read::<PropertyTypeWithMetadata>(
-                            self,
-                            property_type_id,
-                            &time_projection,
-                        )
-                        .await?
+                    self.get_or_read_ontology_type::<PropertyTypeWithMetadata>(
+                        subgraph,
+                        property_type_id,
+                        &time_projection,
+                    )
+                    .await?
                 }
                 DependencyStatus::Resolved => return Ok(()),
             };
@@ -82,6 +125,13 @@ impl<C: AsClient> PostgresStore<C> {
 
             if let Some(data_type_ref_uris) = data_type_ref_uris {
                 for data_type_ref in data_type_ref_uris {
+                    if !self.reserve_vertex::<DataTypeWithMetadata>(
+                        subgraph,
+                        &OntologyTypeEditionId::from(&data_type_ref),
+                    )? {
+                        continue;
+                    }
+
                     subgraph.edges.insert(Edge::Ontology {
                         vertex_id: property_type_id.clone(),
                         outward_edge: OntologyOutwardEdges::ToOntology(OutwardEdge {
@@ -109,6 +159,13 @@ impl<C: AsClient> PostgresStore<C> {
 
             if let Some(property_type_ref_uris) = property_type_ref_uris {
                 for property_type_ref_uri in property_type_ref_uris {
+                    if !self.reserve_vertex::<PropertyTypeWithMetadata>(
+                        subgraph,
+                        &OntologyTypeEditionId::from(&property_type_ref_uri),
+                    )? {
+                        continue;
+                    }
+
                     subgraph.edges.insert(Edge::Ontology {
                         vertex_id: property_type_id.clone(),
                         outward_edge: OntologyOutwardEdges::ToOntology(OutwardEdge {
@@ -150,30 +207,31 @@ impl<C: AsClient> PropertyTypeStore for PostgresStore<C> {
         owned_by_id: OwnedById,
         updated_by_id: UpdatedById,
     ) -> Result<OntologyElementMetadata, InsertionError> {
-        let transaction = self.transaction().await.change_context(InsertionError)?;
-
-        // This clone is currently necessary because we extract the references as we insert them.
-        // We can only insert them after the type has been created, and so we currently extract them
-        // after as well. See `insert_property_type_references` taking `&property_type`
-        let (version_id, metadata) = transaction
-            .create(property_type.clone(), owned_by_id, updated_by_id)
-            .await?;
+        let _timer = metrics::store_operation("create", "property_types");
 
-        transaction
-            .insert_property_type_references(&property_type, version_id)
-            .await
-            .change_context(InsertionError)
-            .attach_printable_lazy(|| {
-                format!(
-                    "could not insert references for property type: {}",
-                    property_type.id()
-                )
-            })
-            .attach_lazy(|| property_type.clone())?;
+        self.insert_property_type(
+            property_type,
+            owned_by_id,
+            ProvenanceMetadata::new(updated_by_id),
+        )
+        .await
+    }
 
-        transaction.commit().await.change_context(InsertionError)?;
+    #[tracing::instrument(level = "info", skip(self, property_type))]
+    async fn import_property_type(
+        &mut self,
+        property_type: PropertyType,
+        owned_by_id: OwnedById,
+        updated_by_id: UpdatedById,
+    ) -> Result<OntologyElementMetadata, InsertionError> {
+        let _timer = metrics::store_operation("import", "property_types");
 
-        Ok(metadata)
+        self.insert_property_type(
+            property_type,
+            owned_by_id,
+            ProvenanceMetadata::new_external(updated_by_id),
+        )
+        .await
     }
 
     #[tracing::instrument(level = "info", skip(self))]
@@ -181,10 +239,13 @@ impl<C: AsClient> PropertyTypeStore for PostgresStore<C> {
         &self,
         query: &StructuralQuery<PropertyTypeWithMetadata>,
     ) -> Result<Subgraph, QueryError> {
+        let _timer = metrics::store_operation("get", "property_types");
+
         let StructuralQuery {
             ref filter,
             graph_resolve_depths,
             ref time_projection,
+            draft_id: _,
         } = *query;
 
         let mut subgraph = Subgraph::new(
@@ -192,6 +253,8 @@ impl<C: AsClient> PropertyTypeStore for PostgresStore<C> {
             time_projection.clone(),
             time_projection.clone().resolve(),
         );
+        let graph_resolve_depths =
+            self.limit_resolve_depths(&mut subgraph, graph_resolve_depths)?;
         let mut dependency_context = DependencyContext::default();
         let time_axis = subgraph.resolved_time_projection.time_axis();
 
@@ -200,6 +263,10 @@ impl<C: AsClient> PropertyTypeStore for PostgresStore<C> {
                 .await?
         {
             let vertex_id = property_type.vertex_id(time_axis);
+            if !self.reserve_vertex::<PropertyTypeWithMetadata>(&mut subgraph, &vertex_id)? {
+                break;
+            }
+
             // Insert the vertex into the subgraph to avoid another lookup when traversing it
             subgraph.insert(&vertex_id, property_type);
 
@@ -223,6 +290,8 @@ impl<C: AsClient> PropertyTypeStore for PostgresStore<C> {
         property_type: PropertyType,
         updated_by: UpdatedById,
     ) -> Result<OntologyElementMetadata, UpdateError> {
+        let _timer = metrics::store_operation("update", "property_types");
+
         let transaction = self.transaction().await.change_context(UpdateError)?;
 
         // This clone is currently necessary because we extract the references as we insert them.
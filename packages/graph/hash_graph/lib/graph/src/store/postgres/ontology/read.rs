@@ -8,6 +8,7 @@ use type_system::uri::VersionedUri;
 
 use crate::{
     identifier::{ontology::OntologyTypeEditionId, time::TimeProjection},
+    metrics,
     ontology::{OntologyElementMetadata, OntologyType, OntologyTypeWithMetadata},
     provenance::{OwnedById, ProvenanceMetadata, UpdatedById},
     store::{
@@ -30,10 +31,14 @@ where
         filter: &Filter<T>,
         time_projection: &TimeProjection,
     ) -> Result<Vec<T>, QueryError> {
+        let _timer = metrics::store_operation("read", T::base_table().as_str());
+
         let versioned_uri_path = <T::QueryPath<'static> as OntologyQueryPath>::versioned_uri();
         let schema_path = <T::QueryPath<'static> as OntologyQueryPath>::schema();
         let owned_by_id_path = <T::QueryPath<'static> as OntologyQueryPath>::owned_by_id();
         let updated_by_id_path = <T::QueryPath<'static> as OntologyQueryPath>::updated_by_id();
+        let external_path = <T::QueryPath<'static> as OntologyQueryPath>::external();
+        let version_path = <T::QueryPath<'static> as OntologyQueryPath>::version();
 
         let mut compiler = SelectCompiler::new(time_projection);
 
@@ -45,6 +50,8 @@ where
         let schema_index = compiler.add_selection_path(&schema_path);
         let owned_by_id_index = compiler.add_selection_path(&owned_by_id_path);
         let updated_by_id_path_index = compiler.add_selection_path(&updated_by_id_path);
+        let external_index = compiler.add_selection_path(&external_path);
+        compiler.pin_ontology_type_table(&version_path);
 
         compiler.add_filter(filter);
         let (statement, parameters) = compiler.compile();
@@ -68,12 +75,17 @@ where
                     .change_context(QueryError)?;
                 let owned_by_id = OwnedById::new(row.get(owned_by_id_index));
                 let updated_by_id = UpdatedById::new(row.get(updated_by_id_path_index));
+                let provenance_metadata = if row.get(external_index) {
+                    ProvenanceMetadata::new_external(updated_by_id)
+                } else {
+                    ProvenanceMetadata::new(updated_by_id)
+                };
 
                 Ok(T::new(
                     record,
                     OntologyElementMetadata::new(
                         OntologyTypeEditionId::from(&versioned_uri),
-                        ProvenanceMetadata::new(updated_by_id),
+                        provenance_metadata,
                         owned_by_id,
                     ),
                 ))
@@ -1,3 +1,11 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
 use async_trait::async_trait;
 use bb8_postgres::{
     bb8::{ErrorSink, ManageConnection, Pool, PooledConnection, RunError},
@@ -9,14 +17,68 @@ use tokio_postgres::{
     Client, Config, Error, GenericClient, Socket, Transaction,
 };
 
-use crate::store::{DatabaseConnectionInfo, PostgresStore, StoreError, StorePool};
+use crate::{
+    metrics,
+    store::{
+        postgres::{cache::OntologyCacheHandle, OntologyCache, OntologyCacheStatistics},
+        DatabaseConnectionInfo, PostgresStore, QueryLimits, ReadReplicaConfig, StoreError,
+        StorePool,
+    },
+};
 
+/// A pool of connections to a primary Postgres database and, optionally, to read replicas.
+///
+/// Stores acquired by [`StorePool::acquire`] always connect to the primary database, stores
+/// acquired by [`StorePool::acquire_read`] are distributed across the read replicas in a
+/// round-robin fashion.
 pub struct PostgresStorePool<Tls>
 where
     Tls: MakeTlsConnect<Socket>,
     PostgresConnectionManager<Tls>: ManageConnection,
 {
     pool: Pool<PostgresConnectionManager<Tls>>,
+    read_replicas: Vec<Pool<PostgresConnectionManager<Tls>>>,
+    next_read_replica: AtomicUsize,
+    write_tracker: Option<Arc<WriteTracker>>,
+    query_limits: QueryLimits,
+    ontology_cache: Arc<OntologyCache>,
+}
+
+/// Keeps track of the last write to the primary database for the read-your-writes window.
+///
+/// The tracker is shared between the pool and the stores acquired from it. Stores report a write
+/// once a mutation has been committed, so acquiring a store from the primary database alone does
+/// not prevent reads from being sent to a read replica.
+pub(crate) struct WriteTracker {
+    window: Duration,
+    created_at: Instant,
+    /// Milliseconds since `created_at` at which a write was committed the last time.
+    last_write: AtomicU64,
+}
+
+impl WriteTracker {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            created_at: Instant::now(),
+            last_write: AtomicU64::new(u64::MAX),
+        }
+    }
+
+    pub(crate) fn record_write(&self) {
+        self.last_write.store(
+            self.created_at.elapsed().as_millis() as u64,
+            Ordering::Relaxed,
+        );
+    }
+
+    /// Returns if a write has been committed within the read-your-writes window.
+    fn is_within_window(&self) -> bool {
+        let last_write = self.last_write.load(Ordering::Relaxed);
+        last_write != u64::MAX
+            && self.created_at.elapsed().as_millis() as u64
+                <= last_write.saturating_add(self.window.as_millis() as u64)
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -46,6 +108,71 @@ where
     ///
     /// - if creating a connection returns an error.
     pub async fn new(db_info: &DatabaseConnectionInfo, tls: Tls) -> Result<Self, StoreError> {
+        Self::with_read_replicas(db_info, &ReadReplicaConfig::default(), tls).await
+    }
+
+    /// Creates a new `PostgresDatabasePool` which routes reads to the specified read replicas.
+    ///
+    /// # Errors
+    ///
+    /// - if creating a connection to the primary database or to one of the replicas returns an
+    ///   error.
+    pub async fn with_read_replicas(
+        db_info: &DatabaseConnectionInfo,
+        read_replica_config: &ReadReplicaConfig,
+        tls: Tls,
+    ) -> Result<Self, StoreError> {
+        Self::with_config(
+            db_info,
+            read_replica_config,
+            QueryLimits::default(),
+            OntologyCache::default(),
+            tls,
+        )
+        .await
+    }
+
+    /// Creates a new `PostgresDatabasePool` which routes reads to the specified read replicas,
+    /// applies the [`QueryLimits`] to the acquired stores, and shares the [`OntologyCache`]
+    /// between them.
+    ///
+    /// # Errors
+    ///
+    /// - if creating a connection to the primary database or to one of the replicas returns an
+    ///   error.
+    pub async fn with_config(
+        db_info: &DatabaseConnectionInfo,
+        read_replica_config: &ReadReplicaConfig,
+        query_limits: QueryLimits,
+        ontology_cache: OntologyCache,
+        tls: Tls,
+    ) -> Result<Self, StoreError> {
+        let statement_timeout = query_limits.statement_timeout();
+        let pool = Self::create_pool(db_info, statement_timeout, tls.clone()).await?;
+
+        let mut read_replicas = Vec::with_capacity(read_replica_config.replicas().len());
+        for replica_info in read_replica_config.replicas() {
+            read_replicas
+                .push(Self::create_pool(replica_info, statement_timeout, tls.clone()).await?);
+        }
+
+        Ok(Self {
+            pool,
+            read_replicas,
+            next_read_replica: AtomicUsize::new(0),
+            write_tracker: read_replica_config
+                .read_your_writes_window()
+                .map(|window| Arc::new(WriteTracker::new(window))),
+            query_limits,
+            ontology_cache: Arc::new(ontology_cache),
+        })
+    }
+
+    async fn create_pool(
+        db_info: &DatabaseConnectionInfo,
+        statement_timeout: Option<Duration>,
+        tls: Tls,
+    ) -> Result<Pool<PostgresConnectionManager<Tls>>, StoreError> {
         tracing::debug!(url=%db_info, "Creating connection pool to Postgres");
         let mut config = Config::new();
         config
@@ -54,17 +181,68 @@ where
             .host(db_info.host())
             .port(db_info.port())
             .dbname(db_info.database());
+        if let Some(statement_timeout) = statement_timeout {
+            config.options(&format!(
+                "-c statement_timeout={}",
+                statement_timeout.as_millis()
+            ));
+        }
 
-        Ok(Self {
-            pool: Pool::builder()
-                .error_sink(Box::new(ErrorLogger))
-                .build(PostgresConnectionManager::new(config, tls))
-                .await
-                .into_report()
-                .change_context(StoreError)
-                .attach_printable_lazy(|| db_info.clone())?,
-        })
+        Pool::builder()
+            .error_sink(Box::new(ErrorLogger))
+            .build(PostgresConnectionManager::new(config, tls))
+            .await
+            .into_report()
+            .change_context(StoreError)
+            .attach_printable_lazy(|| db_info.clone())
+    }
+}
+
+impl<Tls> PostgresStorePool<Tls>
+where
+    Tls: MakeTlsConnect<Socket>,
+    PostgresConnectionManager<Tls>: ManageConnection,
+{
+    /// Returns the number of hits and misses of the shared [`OntologyCache`].
+    #[must_use]
+    pub fn ontology_cache_statistics(&self) -> OntologyCacheStatistics {
+        self.ontology_cache.statistics()
     }
+
+    fn store<C: AsClient>(&self, client: C) -> PostgresStore<C> {
+        PostgresStore::from_parts(
+            client,
+            self.query_limits,
+            OntologyCacheHandle::new(Arc::clone(&self.ontology_cache)),
+            self.write_tracker.clone(),
+        )
+    }
+
+    /// Returns the read replica the next read should be sent to.
+    ///
+    /// Returns `None` if no replica is configured or if a write has been committed within the
+    /// read-your-writes window, so the read has to be sent to the primary database.
+    fn read_replica(&self) -> Option<&Pool<PostgresConnectionManager<Tls>>> {
+        if self.read_replicas.is_empty() {
+            return None;
+        }
+
+        if self
+            .write_tracker
+            .as_ref()
+            .map_or(false, |tracker| tracker.is_within_window())
+        {
+            return None;
+        }
+
+        let index = self.next_read_replica.fetch_add(1, Ordering::Relaxed);
+        self.read_replicas.get(index % self.read_replicas.len())
+    }
+}
+
+fn record_pool_state<M: ManageConnection>(pool_name: &str, pool: &Pool<M>) {
+    let state = pool.state();
+    metrics::record_pool_state(pool_name, state.connections, state.idle_connections);
 }
 
 #[async_trait]
@@ -80,11 +258,37 @@ where
     type Store<'pool> = PostgresStore<PooledConnection<'pool, PostgresConnectionManager<Tls>>>;
 
     async fn acquire(&self) -> Result<Self::Store<'_>, Self::Error> {
-        Ok(PostgresStore::new(self.pool.get().await?))
+        let connection = self.pool.get().await?;
+        record_pool_state("primary", &self.pool);
+        Ok(self.store(connection))
     }
 
     async fn acquire_owned(&self) -> Result<Self::Store<'static>, Self::Error> {
-        Ok(PostgresStore::new(self.pool.get_owned().await?))
+        let connection = self.pool.get_owned().await?;
+        record_pool_state("primary", &self.pool);
+        Ok(self.store(connection))
+    }
+
+    async fn acquire_read(&self) -> Result<Self::Store<'_>, Self::Error> {
+        if let Some(replica) = self.read_replica() {
+            match replica.get().await {
+                Ok(connection) => {
+                    record_pool_state("replica", replica);
+                    return Ok(self.store(connection));
+                }
+                Err(error) => {
+                    tracing::warn!(
+                        ?error,
+                        "Could not acquire a connection to a read replica, falling back to the \
+                         primary database"
+                    );
+                }
+            }
+        }
+
+        let connection = self.pool.get().await?;
+        record_pool_state("primary", &self.pool);
+        Ok(self.store(connection))
     }
 }
 
@@ -5,10 +5,11 @@ use tokio_postgres::row::RowIndex;
 
 use crate::{
     identifier::time::{TimeAxis, TimeProjection, Timespan, TimespanBound},
+    knowledge::DraftId,
     store::{
         postgres::query::{
             expression::Constant,
-            table::{Entities, EntityTypes, JsonField, Relation, TypeIds},
+            table::{DraftEntities, Entities, EntityTypes, JsonField, Relation, TypeIds},
             Alias, AliasedColumn, AliasedTable, Column, Condition, Distinctness, EqualityOperator,
             Expression, Function, JoinExpression, OrderByExpression, Ordering, PostgresQueryPath,
             PostgresRecord, SelectExpression, SelectStatement, Table, Transpile, WhereExpression,
@@ -35,6 +36,26 @@ pub struct CompilerArtifacts<'p> {
     temporal_tables: Option<TemporalTableInfo>,
 }
 
+impl<'p> CompilerArtifacts<'p> {
+    /// Returns the information about the pinned temporal tables.
+    ///
+    /// On first access, the kernel timestamp of the `time_projection` is added as parameter.
+    fn temporal_tables(&mut self, time_projection: &'p TimeProjection) -> &mut TemporalTableInfo {
+        let parameters = &mut self.parameters;
+        self.temporal_tables.get_or_insert_with(|| {
+            parameters.push(match time_projection {
+                TimeProjection::DecisionTime(projection) => &projection.kernel.timestamp,
+                TimeProjection::TransactionTime(projection) => &projection.kernel.timestamp,
+            });
+
+            TemporalTableInfo {
+                tables: HashSet::new(),
+                parameter_index: parameters.len(),
+            }
+        })
+    }
+}
+
 pub struct SelectCompiler<'c, 'p, T> {
     statement: SelectStatement<'c>,
     artifacts: CompilerArtifacts<'p>,
@@ -89,39 +110,87 @@ impl<'c, 'p: 'c, R: PostgresRecord> SelectCompiler<'c, 'p, R> {
         default
     }
 
-    fn pin_entity_table(&mut self, alias: Alias) {
-        let table = Table::Entities.aliased(alias);
-        let temporal_table_info = self.artifacts.temporal_tables.get_or_insert_with(|| {
-            self.artifacts.parameters.push(match self.time_projection {
-                TimeProjection::DecisionTime(projection) => &projection.kernel.timestamp,
-                TimeProjection::TransactionTime(projection) => &projection.kernel.timestamp,
-            });
+    /// Includes the editions of the draft specified by `draft_id` in the selection.
+    ///
+    /// Entities, which were created or updated in the draft, are read with their draft edition
+    /// instead of their live edition.
+    // Warning: This adds a CTE to the statement, which is overwriting the `entities` table, see
+    //          `compile_latest_ontology_version_filter`
+    pub fn include_draft(&mut self, draft_id: &'p DraftId) {
+        self.artifacts.parameters.push(draft_id);
+        let alias = Alias {
+            condition_index: 0,
+            chain_depth: 0,
+            number: 0,
+        };
+
+        let mut where_expression = WhereExpression::default();
+        where_expression.add_condition(Condition::Equal(
+            Some(Expression::Column(
+                Column::DraftEntities(DraftEntities::VisibleInDraftId).aliased(alias),
+            )),
+            Some(Expression::Parameter(self.artifacts.parameters.len())),
+        ));
 
-            TemporalTableInfo {
-                tables: HashSet::new(),
-                parameter_index: self.artifacts.parameters.len(),
-            }
-        });
+        self.statement
+            .with
+            .add_statement(Table::Entities, SelectStatement {
+                with: WithExpression::default(),
+                distinct: Vec::new(),
+                selects: vec![SelectExpression::new(Expression::Asterisk, None)],
+                from: Table::DraftEntities.aliased(alias),
+                joins: vec![],
+                where_expression,
+                order_by_expression: OrderByExpression::default(),
+            });
+    }
 
+    /// Restricts the rows of `table` to those whose `column` contains the kernel timestamp.
+    fn pin_table(&mut self, table: AliasedTable, column: Column<'c>) {
+        let temporal_table_info = self.artifacts.temporal_tables(self.time_projection);
         if !temporal_table_info.tables.contains(&table) {
-            // Adds the kernel timestamp condition, so for the projected decision time, we use the
-            // transaction time and vice versa.
             self.statement
                 .where_expression
                 .add_condition(Condition::TimerangeContainsTimestamp(
-                    Expression::Column(
-                        Column::Entities(match self.time_projection.time_axis() {
-                            TimeAxis::DecisionTime => Entities::TransactionTime,
-                            TimeAxis::TransactionTime => Entities::DecisionTime,
-                        })
-                        .aliased(alias),
-                    ),
+                    Expression::Column(column.aliased(table.alias)),
                     Expression::Parameter(temporal_table_info.parameter_index),
                 ));
             temporal_table_info.tables.insert(table);
         }
     }
 
+    fn pin_entity_table(&mut self, alias: Alias) {
+        // Adds the kernel timestamp condition, so for the projected decision time, we use the
+        // transaction time and vice versa.
+        self.pin_table(
+            Table::Entities.aliased(alias),
+            Column::Entities(match self.time_projection.time_axis() {
+                TimeAxis::DecisionTime => Entities::TransactionTime,
+                TimeAxis::TransactionTime => Entities::DecisionTime,
+            }),
+        );
+    }
+
+    fn pin_ontology_table(&mut self, alias: Alias) {
+        // Ontology types only have a transaction time. When projecting the transaction time, the
+        // image is unbounded (see `new`), so every edition of an ontology type is contained.
+        if self.time_projection.time_axis() == TimeAxis::DecisionTime {
+            self.pin_table(
+                Table::TypeIds.aliased(alias),
+                Column::TypeIds(TypeIds::TransactionTime),
+            );
+        }
+    }
+
+    /// Restricts the selection to ontology types, which were known at the kernel timestamp of the
+    /// time projection.
+    ///
+    /// The `path` has to point to a column of [`Table::TypeIds`].
+    pub fn pin_ontology_type_table(&mut self, path: &R::QueryPath<'_>) {
+        let alias = self.add_join_statements(path);
+        self.pin_ontology_table(alias);
+    }
+
     /// Adds a new path to the selection.
     ///
     /// Optionally, the added selection can be distinct or ordered by providing [`Distinctness`]
@@ -228,6 +297,22 @@ impl<'c, 'p: 'c, R: PostgresRecord> SelectCompiler<'c, 'p, R> {
             number: 0,
         });
 
+        // Only versions, which were known at the kernel timestamp, are considered for the latest
+        // version.
+        let mut where_expression = WhereExpression::default();
+        if self.time_projection.time_axis() == TimeAxis::DecisionTime {
+            where_expression.add_condition(Condition::TimerangeContainsTimestamp(
+                Expression::Column(
+                    Column::TypeIds(TypeIds::TransactionTime).aliased(version_column.alias),
+                ),
+                Expression::Parameter(
+                    self.artifacts
+                        .temporal_tables(self.time_projection)
+                        .parameter_index,
+                ),
+            ));
+        }
+
         // Add a WITH expression selecting the partitioned version
         self.statement
             .with
@@ -250,7 +335,7 @@ impl<'c, 'p: 'c, R: PostgresRecord> SelectCompiler<'c, 'p, R> {
                 ],
                 from: version_column.table(),
                 joins: vec![],
-                where_expression: WhereExpression::default(),
+                where_expression,
                 order_by_expression: OrderByExpression::default(),
             });
 
@@ -348,15 +433,25 @@ impl<'c, 'p: 'c, R: PostgresRecord> SelectCompiler<'c, 'p, R> {
 
     pub fn compile_path_column(&mut self, path: &'p R::QueryPath<'_>) -> AliasedColumn<'c> {
         let column = path.terminating_column();
-        let column =
-            if let Column::Entities(Entities::Properties(Some(JsonField::Text(field)))) = column {
+        let column = match column {
+            Column::Entities(Entities::Properties(Some(JsonField::Text(field)))) => {
                 self.artifacts.parameters.push(field);
                 Column::Entities(Entities::Properties(Some(JsonField::Parameter(
                     self.artifacts.parameters.len(),
                 ))))
-            } else {
-                column
-            };
+            }
+            Column::Entities(Entities::PropertyProvenance(Some((
+                JsonField::Text(property),
+                field,
+            )))) => {
+                self.artifacts.parameters.push(property);
+                Column::Entities(Entities::PropertyProvenance(Some((
+                    JsonField::Parameter(self.artifacts.parameters.len()),
+                    field,
+                ))))
+            }
+            column => column,
+        };
 
         let alias = self.add_join_statements(path);
 
@@ -506,6 +601,10 @@ impl<'c, 'p: 'c, R: PostgresRecord> SelectCompiler<'c, 'p, R> {
                             }
                         }
 
+                        if current_table.table == Table::TypeIds {
+                            self.pin_ontology_table(current_table.alias);
+                        }
+
                         continue;
                     }
                 }
@@ -535,6 +634,9 @@ impl<'c, 'p: 'c, R: PostgresRecord> SelectCompiler<'c, 'p, R> {
                     if matches!(current_column.column, Column::Entities(_)) {
                         self.pin_entity_table(current_alias);
                     }
+                    if current_table.table == Table::TypeIds {
+                        self.pin_ontology_table(current_table.alias);
+                    }
                 }
             }
             self.add_special_relation_conditions(relation, current_alias, current_table);
@@ -94,7 +94,10 @@ mod tests {
     use postgres_types::ToSql;
 
     use crate::{
-        identifier::time::UnresolvedTimeProjection,
+        identifier::time::{
+            TimespanBound, Timestamp, TransactionTime, UnresolvedImage, UnresolvedKernel,
+            UnresolvedProjection, UnresolvedTimeProjection,
+        },
         ontology::{DataTypeQueryPath, DataTypeWithMetadata},
         store::{
             postgres::query::{SelectCompiler, Transpile},
@@ -102,12 +105,23 @@ mod tests {
         },
     };
 
+    fn kernel() -> Timestamp<TransactionTime> {
+        "2023-01-01T00:00:00Z".parse().expect("invalid timestamp")
+    }
+
     fn test_condition<'p, 'f: 'p>(
         filter: &'f Filter<'p, DataTypeWithMetadata>,
         rendered: &'static str,
         parameters: &[&'p dyn ToSql],
     ) {
-        let time_projection = UnresolvedTimeProjection::default().resolve();
+        let time_projection = UnresolvedTimeProjection::DecisionTime(UnresolvedProjection {
+            kernel: UnresolvedKernel::new(Some(kernel())),
+            image: UnresolvedImage::new(
+                Some(TimespanBound::Unbounded),
+                Some(TimespanBound::Unbounded),
+            ),
+        })
+        .resolve();
         let mut compiler = SelectCompiler::new(&time_projection);
         let condition = compiler.compile_filter(filter);
 
@@ -202,8 +216,9 @@ mod tests {
                     Some(FilterExpression::Parameter(Parameter::Number(1.0))),
                 ),
             ]),
-            r#"("type_ids_0_1_0"."base_uri" = $1) AND ("type_ids_0_1_0"."version" = $2)"#,
+            r#"("type_ids_0_1_0"."base_uri" = $2) AND ("type_ids_0_1_0"."version" = $3)"#,
             &[
+                &kernel(),
                 &"https://blockprotocol.org/@blockprotocol/types/data-type/text/",
                 &1.0,
             ],
@@ -236,8 +251,9 @@ mod tests {
                     Some(FilterExpression::Parameter(Parameter::Number(1.0))),
                 ),
             ]),
-            r#"(("type_ids_0_1_0"."base_uri" = $1) OR ("type_ids_0_1_0"."version" = $2))"#,
+            r#"(("type_ids_0_1_0"."base_uri" = $2) OR ("type_ids_0_1_0"."version" = $3))"#,
             &[
+                &kernel(),
                 &"https://blockprotocol.org/@blockprotocol/types/data-type/text/",
                 &1.0,
             ],
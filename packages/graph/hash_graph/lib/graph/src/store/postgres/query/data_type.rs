@@ -31,6 +31,7 @@ impl PostgresQueryPath for DataTypeQueryPath {
             Self::VersionId => Column::DataTypes(DataTypes::VersionId),
             Self::OwnedById => Column::DataTypes(DataTypes::OwnedById),
             Self::UpdatedById => Column::DataTypes(DataTypes::UpdatedById),
+            Self::External => Column::DataTypes(DataTypes::External),
             Self::Schema => Column::DataTypes(DataTypes::Schema(None)),
             Self::VersionedUri => Column::DataTypes(DataTypes::Schema(Some(JsonField::Text(
                 &Cow::Borrowed("$id"),
@@ -47,6 +47,7 @@ impl PostgresQueryPath for EntityQueryPath<'_> {
             Self::TransactionTime => Column::Entities(Entities::TransactionTime),
             Self::ProjectedTime => Column::Entities(Entities::ProjectedTime),
             Self::Archived => Column::Entities(Entities::Archived),
+            Self::DraftId => Column::Entities(Entities::DraftId),
             Self::Type(path) => path.terminating_column(),
             Self::OwnedById => Column::Entities(Entities::OwnedById),
             Self::UpdatedById => Column::Entities(Entities::UpdatedById),
@@ -73,6 +74,10 @@ impl PostgresQueryPath for EntityQueryPath<'_> {
                 .map_or(Column::Entities(Entities::Properties(None)), |path| {
                     Column::Entities(Entities::Properties(Some(JsonField::Text(path))))
                 }),
+            Self::PropertyProvenance(path) => Column::Entities(Entities::PropertyProvenance(
+                path.as_ref()
+                    .map(|(property, field)| (JsonField::Text(property), field.as_str())),
+            )),
         }
     }
 }
@@ -39,6 +39,7 @@ impl PostgresQueryPath for EntityTypeQueryPath {
             Self::VersionId => Column::EntityTypes(EntityTypes::VersionId),
             Self::OwnedById => Column::EntityTypes(EntityTypes::OwnedById),
             Self::UpdatedById => Column::EntityTypes(EntityTypes::UpdatedById),
+            Self::External => Column::EntityTypes(EntityTypes::External),
             Self::Schema => Column::EntityTypes(EntityTypes::Schema(None)),
             Self::VersionedUri => Column::EntityTypes(EntityTypes::Schema(Some(JsonField::Text(
                 &Cow::Borrowed("$id"),
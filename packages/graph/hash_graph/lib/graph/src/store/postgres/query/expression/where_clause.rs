@@ -49,7 +49,7 @@ mod tests {
 
     use super::*;
     use crate::{
-        identifier::time::UnresolvedTimeProjection,
+        identifier::time::{TransactionTime, UnresolvedTimeProjection},
         ontology::{DataTypeQueryPath, DataTypeWithMetadata},
         store::{
             postgres::query::{test_helper::trim_whitespace, SelectCompiler},
@@ -60,6 +60,8 @@ mod tests {
     #[test]
     fn transpile_where_expression() {
         let time_projection = UnresolvedTimeProjection::default().resolve();
+        let kernel = time_projection.kernel().cast::<TransactionTime>();
+        let kernel = format!("{kernel:?}");
         let mut compiler = SelectCompiler::<DataTypeWithMetadata>::new(&time_projection);
         let mut where_clause = WhereExpression::default();
         assert_eq!(where_clause.transpile_to_string(), "");
@@ -96,7 +98,7 @@ mod tests {
             trim_whitespace(
                 r#"
                 WHERE "type_ids_0_1_0"."version" = "type_ids_0_1_0"."latest_version"
-                  AND ("type_ids_0_1_0"."base_uri" = $1) AND ("type_ids_0_1_0"."version" = $2)"#
+                  AND ("type_ids_0_1_0"."base_uri" = $2) AND ("type_ids_0_1_0"."version" = $3)"#
             )
         );
 
@@ -111,7 +113,7 @@ mod tests {
             trim_whitespace(
                 r#"
                 WHERE "type_ids_0_1_0"."version" = "type_ids_0_1_0"."latest_version"
-                  AND ("type_ids_0_1_0"."base_uri" = $1) AND ("type_ids_0_1_0"."version" = $2)
+                  AND ("type_ids_0_1_0"."base_uri" = $2) AND ("type_ids_0_1_0"."version" = $3)
                   AND "data_types_0_0_0"."schema"->>'description' IS NOT NULL"#
             )
         );
@@ -137,9 +139,9 @@ mod tests {
             trim_whitespace(
                 r#"
                 WHERE "type_ids_0_1_0"."version" = "type_ids_0_1_0"."latest_version"
-                  AND ("type_ids_0_1_0"."base_uri" = $1) AND ("type_ids_0_1_0"."version" = $2)
+                  AND ("type_ids_0_1_0"."base_uri" = $2) AND ("type_ids_0_1_0"."version" = $3)
                   AND "data_types_0_0_0"."schema"->>'description' IS NOT NULL
-                  AND (("data_types_0_0_0"."schema"->>'title' = $3) OR ("data_types_0_0_0"."schema"->>'description' = $4))"#
+                  AND (("data_types_0_0_0"."schema"->>'title' = $4) OR ("data_types_0_0_0"."schema"->>'description' = $5))"#
             )
         );
 
@@ -150,6 +152,7 @@ mod tests {
             .map(|parameter| format!("{parameter:?}"))
             .collect::<Vec<_>>();
         assert_eq!(parameters, &[
+            kernel.as_str(),
             "\"https://blockprotocol.org/@blockprotocol/types/data-type/text/\"",
             "1.0",
             "\"some title\"",
@@ -35,6 +35,7 @@ impl PostgresQueryPath for PropertyTypeQueryPath {
             Self::VersionId => Column::PropertyTypes(PropertyTypes::VersionId),
             Self::OwnedById => Column::PropertyTypes(PropertyTypes::OwnedById),
             Self::UpdatedById => Column::PropertyTypes(PropertyTypes::UpdatedById),
+            Self::External => Column::PropertyTypes(PropertyTypes::External),
             Self::Schema => Column::PropertyTypes(PropertyTypes::Schema(None)),
             Self::VersionedUri => Column::PropertyTypes(PropertyTypes::Schema(Some(
                 JsonField::Text(&Cow::Borrowed("$id")),
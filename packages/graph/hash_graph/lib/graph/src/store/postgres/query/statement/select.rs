@@ -82,7 +82,7 @@ mod tests {
 
     use crate::{
         identifier::time::{TransactionTime, UnresolvedTimeProjection},
-        knowledge::{Entity, EntityQueryPath},
+        knowledge::{DraftId, Entity, EntityQueryPath, PropertyProvenanceField},
         ontology::{
             DataTypeQueryPath, DataTypeWithMetadata, EntityTypeQueryPath, EntityTypeWithMetadata,
             PropertyTypeQueryPath, PropertyTypeWithMetadata,
@@ -154,6 +154,7 @@ mod tests {
     #[test]
     fn specific_version() {
         let time_projection = UnresolvedTimeProjection::default().resolve();
+        let kernel = time_projection.kernel().cast::<TransactionTime>();
         let mut compiler = SelectCompiler::<DataTypeWithMetadata>::with_asterisk(&time_projection);
 
         let filter = Filter::All(vec![
@@ -177,9 +178,11 @@ mod tests {
             FROM "data_types" AS "data_types_0_0_0"
             INNER JOIN "type_ids" AS "type_ids_0_1_0"
               ON "type_ids_0_1_0"."version_id" = "data_types_0_0_0"."version_id"
-            WHERE ("type_ids_0_1_0"."base_uri" = $1) AND ("type_ids_0_1_0"."version" = $2)
+            WHERE "type_ids_0_1_0"."transaction_time" @> $1::TIMESTAMPTZ
+              AND ("type_ids_0_1_0"."base_uri" = $2) AND ("type_ids_0_1_0"."version" = $3)
             "#,
             &[
+                &kernel,
                 &"https://blockprotocol.org/@blockprotocol/types/data-type/text/",
                 &1.0,
             ],
@@ -189,6 +192,7 @@ mod tests {
     #[test]
     fn latest_version() {
         let time_projection = UnresolvedTimeProjection::default().resolve();
+        let kernel = time_projection.kernel().cast::<TransactionTime>();
         let mut compiler = SelectCompiler::<DataTypeWithMetadata>::with_asterisk(&time_projection);
 
         compiler.add_filter(&Filter::Equal(
@@ -201,20 +205,22 @@ mod tests {
         test_compilation(
             &compiler,
             r#"
-            WITH "type_ids" AS (SELECT *, MAX("type_ids_0_0_0"."version") OVER (PARTITION BY "type_ids_0_0_0"."base_uri") AS "latest_version" FROM "type_ids" AS "type_ids_0_0_0")
+            WITH "type_ids" AS (SELECT *, MAX("type_ids_0_0_0"."version") OVER (PARTITION BY "type_ids_0_0_0"."base_uri") AS "latest_version" FROM "type_ids" AS "type_ids_0_0_0" WHERE "type_ids_0_0_0"."transaction_time" @> $1::TIMESTAMPTZ)
             SELECT *
             FROM "data_types" AS "data_types_0_0_0"
             INNER JOIN "type_ids" AS "type_ids_0_1_0"
               ON "type_ids_0_1_0"."version_id" = "data_types_0_0_0"."version_id"
-            WHERE "type_ids_0_1_0"."version" = "type_ids_0_1_0"."latest_version"
+            WHERE "type_ids_0_1_0"."transaction_time" @> $1::TIMESTAMPTZ
+              AND "type_ids_0_1_0"."version" = "type_ids_0_1_0"."latest_version"
             "#,
-            &[],
+            &[&kernel],
         );
     }
 
     #[test]
     fn not_latest_version() {
         let time_projection = UnresolvedTimeProjection::default().resolve();
+        let kernel = time_projection.kernel().cast::<TransactionTime>();
         let mut compiler = SelectCompiler::<DataTypeWithMetadata>::with_asterisk(&time_projection);
 
         compiler.add_filter(&Filter::NotEqual(
@@ -227,20 +233,22 @@ mod tests {
         test_compilation(
             &compiler,
             r#"
-            WITH "type_ids" AS (SELECT *, MAX("type_ids_0_0_0"."version") OVER (PARTITION BY "type_ids_0_0_0"."base_uri") AS "latest_version" FROM "type_ids" AS "type_ids_0_0_0")
+            WITH "type_ids" AS (SELECT *, MAX("type_ids_0_0_0"."version") OVER (PARTITION BY "type_ids_0_0_0"."base_uri") AS "latest_version" FROM "type_ids" AS "type_ids_0_0_0" WHERE "type_ids_0_0_0"."transaction_time" @> $1::TIMESTAMPTZ)
             SELECT *
             FROM "data_types" AS "data_types_0_0_0"
             INNER JOIN "type_ids" AS "type_ids_0_1_0"
               ON "type_ids_0_1_0"."version_id" = "data_types_0_0_0"."version_id"
-            WHERE "type_ids_0_1_0"."version" != "type_ids_0_1_0"."latest_version"
+            WHERE "type_ids_0_1_0"."transaction_time" @> $1::TIMESTAMPTZ
+              AND "type_ids_0_1_0"."version" != "type_ids_0_1_0"."latest_version"
             "#,
-            &[],
+            &[&kernel],
         );
     }
 
     #[test]
     fn property_type_by_referenced_data_types() {
         let time_projection = UnresolvedTimeProjection::default().resolve();
+        let kernel = time_projection.kernel().cast::<TransactionTime>();
         let mut compiler =
             SelectCompiler::<PropertyTypeWithMetadata>::with_asterisk(&time_projection);
 
@@ -299,10 +307,12 @@ mod tests {
             INNER JOIN "type_ids" AS "type_ids_1_2_0"
               ON "type_ids_1_2_0"."version_id" = "property_type_data_type_references_1_1_0"."target_data_type_version_id"
             WHERE "data_types_0_2_0"."schema"->>'title' = $1
-              AND ("type_ids_1_2_0"."base_uri" = $2) AND ("type_ids_1_2_0"."version" = $3)
+              AND "type_ids_1_2_0"."transaction_time" @> $2::TIMESTAMPTZ
+              AND ("type_ids_1_2_0"."base_uri" = $3) AND ("type_ids_1_2_0"."version" = $4)
             "#,
             &[
                 &"Text",
+                &kernel,
                 &"https://blockprotocol.org/@blockprotocol/types/data-type/text/",
                 &1.0,
             ],
@@ -413,6 +423,7 @@ mod tests {
     #[test]
     fn entity_type_by_inheritance() {
         let time_projection = UnresolvedTimeProjection::default().resolve();
+        let kernel = time_projection.kernel().cast::<TransactionTime>();
         let mut compiler =
             SelectCompiler::<EntityTypeWithMetadata>::with_asterisk(&time_projection);
 
@@ -438,9 +449,13 @@ mod tests {
             INNER JOIN "type_ids" AS "type_ids_0_3_0"
               ON "type_ids_0_3_0"."version_id" = "entity_types_0_2_0"."version_id"
             WHERE jsonb_contains("entity_types_0_0_0"."schema"->'allOf', jsonb_build_array(jsonb_build_object('$ref', "entity_types_0_2_0"."schema"->>'$id'))) IS NOT NULL
-              AND "type_ids_0_3_0"."base_uri" = $1
+              AND "type_ids_0_3_0"."transaction_time" @> $1::TIMESTAMPTZ
+              AND "type_ids_0_3_0"."base_uri" = $2
             "#,
-            &[&"https://blockprotocol.org/@blockprotocol/types/entity-type/link/"],
+            &[
+                &kernel,
+                &"https://blockprotocol.org/@blockprotocol/types/entity-type/link/",
+            ],
         );
     }
 
@@ -598,6 +613,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn entity_property_provenance_query() {
+        let time_projection = UnresolvedTimeProjection::default().resolve();
+        let kernel = time_projection.kernel().cast::<TransactionTime>();
+        let mut compiler = SelectCompiler::<Entity>::with_asterisk(&time_projection);
+
+        let filter = Filter::Equal(
+            Some(FilterExpression::Path(EntityQueryPath::PropertyProvenance(
+                Some((
+                    Cow::Borrowed("https://blockprotocol.org/@alice/types/property-type/name/"),
+                    PropertyProvenanceField::Source,
+                )),
+            ))),
+            Some(FilterExpression::Parameter(Parameter::Text(Cow::Borrowed(
+                "https://example.com/",
+            )))),
+        );
+        compiler.add_filter(&filter);
+
+        test_compilation(
+            &compiler,
+            r#"
+            SELECT *
+            FROM "entities" AS "entities_0_0_0"
+            WHERE "entities_0_0_0"."transaction_time" @> $2::TIMESTAMPTZ
+              AND "entities_0_0_0"."property_provenance"->$1->>'source' = $3
+            "#,
+            &[
+                &"https://blockprotocol.org/@alice/types/property-type/name/",
+                &kernel,
+                &"https://example.com/",
+            ],
+        );
+    }
+
+    #[test]
+    fn entity_draft_query() {
+        let time_projection = UnresolvedTimeProjection::default().resolve();
+        let kernel = time_projection.kernel().cast::<TransactionTime>();
+        let draft_id = DraftId::new(Uuid::nil());
+        let mut compiler = SelectCompiler::<Entity>::with_asterisk(&time_projection);
+        compiler.include_draft(&draft_id);
+
+        let filter = Filter::Equal(Some(FilterExpression::Path(EntityQueryPath::DraftId)), None);
+        compiler.add_filter(&filter);
+
+        test_compilation(
+            &compiler,
+            r#"
+            WITH "entities" AS (SELECT * FROM "draft_entities" AS "draft_entities_0_0_0" WHERE "draft_entities_0_0_0"."visible_in_draft_id" = $1)
+            SELECT *
+            FROM "entities" AS "entities_0_0_0"
+            WHERE "entities_0_0_0"."transaction_time" @> $2::TIMESTAMPTZ
+              AND "entities_0_0_0"."draft_id" IS NULL
+            "#,
+            &[&draft_id, &kernel],
+        );
+    }
+
     #[test]
     fn entity_outgoing_link_query() {
         let time_projection = UnresolvedTimeProjection::default().resolve();
@@ -752,6 +826,7 @@ mod tests {
             );
 
             let time_projection = UnresolvedTimeProjection::default().resolve();
+            let kernel = time_projection.kernel().cast::<TransactionTime>();
             let mut compiler =
                 SelectCompiler::<DataTypeWithMetadata>::with_asterisk(&time_projection);
 
@@ -765,9 +840,10 @@ mod tests {
                 FROM "data_types" AS "data_types_0_0_0"
                 INNER JOIN "type_ids" AS "type_ids_0_1_0"
                   ON "type_ids_0_1_0"."version_id" = "data_types_0_0_0"."version_id"
-                WHERE ("type_ids_0_1_0"."base_uri" = $1) AND ("type_ids_0_1_0"."version" = $2)
+                WHERE "type_ids_0_1_0"."transaction_time" @> $1::TIMESTAMPTZ
+                  AND ("type_ids_0_1_0"."base_uri" = $2) AND ("type_ids_0_1_0"."version" = $3)
                 "#,
-                &[&uri.base_uri().as_str(), &i64::from(uri.version())],
+                &[&kernel, &uri.base_uri().as_str(), &i64::from(uri.version())],
             );
         }
 
@@ -782,6 +858,7 @@ mod tests {
             );
 
             let time_projection = UnresolvedTimeProjection::default().resolve();
+            let kernel = time_projection.kernel().cast::<TransactionTime>();
             let mut compiler =
                 SelectCompiler::<DataTypeWithMetadata>::with_asterisk(&time_projection);
 
@@ -795,9 +872,14 @@ mod tests {
                 FROM "data_types" AS "data_types_0_0_0"
                 INNER JOIN "type_ids" AS "type_ids_0_1_0"
                   ON "type_ids_0_1_0"."version_id" = "data_types_0_0_0"."version_id"
-                WHERE ("type_ids_0_1_0"."base_uri" = $1) AND ("type_ids_0_1_0"."version" = $2)
+                WHERE "type_ids_0_1_0"."transaction_time" @> $1::TIMESTAMPTZ
+                  AND ("type_ids_0_1_0"."base_uri" = $2) AND ("type_ids_0_1_0"."version" = $3)
                 "#,
-                &[&uri.base_id().as_str(), &i64::from(uri.version().inner())],
+                &[
+                    &kernel,
+                    &uri.base_id().as_str(),
+                    &i64::from(uri.version().inner()),
+                ],
             );
         }
 
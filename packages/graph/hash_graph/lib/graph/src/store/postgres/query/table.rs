@@ -14,6 +14,7 @@ pub enum Table {
     PropertyTypes,
     EntityTypes,
     Entities,
+    DraftEntities,
     PropertyTypeDataTypeReferences,
     PropertyTypePropertyTypeReferences,
     EntityTypePropertyTypeReferences,
@@ -25,13 +26,14 @@ impl Table {
         AliasedTable { table: self, alias }
     }
 
-    const fn as_str(self) -> &'static str {
+    pub const fn as_str(self) -> &'static str {
         match self {
             Self::TypeIds => "type_ids",
             Self::DataTypes => "data_types",
             Self::PropertyTypes => "property_types",
             Self::EntityTypes => "entity_types",
             Self::Entities => "entities",
+            Self::DraftEntities => "draft_entities",
             Self::PropertyTypeDataTypeReferences => "property_type_data_type_references",
             Self::PropertyTypePropertyTypeReferences => "property_type_property_type_references",
             Self::EntityTypePropertyTypeReferences => "entity_type_property_type_references",
@@ -59,6 +61,7 @@ pub enum TypeIds {
     BaseUri,
     Version,
     LatestVersion,
+    TransactionTime,
 }
 
 impl Transpile for TypeIds {
@@ -68,6 +71,7 @@ impl Transpile for TypeIds {
             Self::BaseUri => "base_uri",
             Self::Version => "version",
             Self::LatestVersion => "latest_version",
+            Self::TransactionTime => "transaction_time",
         };
         write!(fmt, r#"."{column}""#)
     }
@@ -81,6 +85,7 @@ macro_rules! impl_ontology_column {
                 VersionId,
                 OwnedById,
                 UpdatedById,
+                External,
                 Schema(Option<JsonField<'static>>),
             }
 
@@ -89,7 +94,8 @@ macro_rules! impl_ontology_column {
                     match self {
                         Self::VersionId
                         | Self::OwnedById
-                        | Self::UpdatedById => false,
+                        | Self::UpdatedById
+                        | Self::External => false,
                         Self::Schema(_) => true,
                     }
                 }
@@ -101,6 +107,7 @@ macro_rules! impl_ontology_column {
                         Self::VersionId => "version_id",
                         Self::OwnedById => "owned_by_id",
                         Self::UpdatedById => "updated_by_id",
+                        Self::External => "external",
                         Self::Schema(None) => "schema",
                         Self::Schema(Some(path)) => match path {
                             JsonField::Json(field) => {
@@ -135,10 +142,15 @@ pub enum Entities<'p> {
     //   see https://app.asana.com/0/0/1203701389454316/f
     ProjectedTime,
     Archived,
+    /// The draft the edition belongs to, `NULL` for live editions.
+    DraftId,
     OwnedById,
     UpdatedById,
     EntityTypeVersionId,
     Properties(Option<JsonField<'p>>),
+    /// The property provenance of the entity, optionally narrowed down to a single field of the
+    /// provenance of a single property.
+    PropertyProvenance(Option<(JsonField<'p>, &'static str)>),
     LeftToRightOrder,
     RightToLeftOrder,
     LeftEntityUuid,
@@ -158,8 +170,11 @@ impl Entities<'_> {
             | Self::Archived
             | Self::OwnedById
             | Self::UpdatedById
-            | Self::EntityTypeVersionId => false,
+            | Self::EntityTypeVersionId
+            | Self::PropertyProvenance(None) => false,
             Self::Properties(_)
+            | Self::PropertyProvenance(Some(_))
+            | Self::DraftId
             | Self::LeftEntityUuid
             | Self::RightEntityUuid
             | Self::LeftEntityOwnedById
@@ -179,6 +194,7 @@ impl Transpile for Entities<'_> {
             Self::TransactionTime => "transaction_time",
             Self::ProjectedTime => unreachable!("projected time is not a column"),
             Self::Archived => "archived",
+            Self::DraftId => "draft_id",
             Self::OwnedById => "owned_by_id",
             Self::UpdatedById => "updated_by_id",
             Self::EntityTypeVersionId => "entity_type_version_id",
@@ -196,6 +212,20 @@ impl Transpile for Entities<'_> {
                     }
                 };
             }
+            Self::PropertyProvenance(None) => "property_provenance",
+            Self::PropertyProvenance(Some((property, field))) => {
+                return match property {
+                    JsonField::Json(property) => {
+                        write!(fmt, r#"."property_provenance"->'{property}'->'{field}'"#)
+                    }
+                    JsonField::Text(property) => {
+                        write!(fmt, r#"."property_provenance"->'{property}'->>'{field}'"#)
+                    }
+                    JsonField::Parameter(index) => {
+                        write!(fmt, r#"."property_provenance"->${index}->>'{field}'"#)
+                    }
+                };
+            }
             Self::LeftToRightOrder => "left_to_right_order",
             Self::RightToLeftOrder => "right_to_left_order",
             Self::LeftEntityUuid => "left_entity_uuid",
@@ -207,6 +237,23 @@ impl Transpile for Entities<'_> {
     }
 }
 
+/// The columns of [`Table::DraftEntities`], which are not available in [`Table::Entities`].
+///
+/// The view contains every column of [`Table::Entities`] as well, so it can replace the table in a
+/// query, which includes a draft.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DraftEntities {
+    VisibleInDraftId,
+}
+
+impl Transpile for DraftEntities {
+    fn transpile(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, r#"."{}""#, match self {
+            Self::VisibleInDraftId => "visible_in_draft_id",
+        })
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum PropertyTypeDataTypeReferences {
     SourcePropertyTypeVersionId,
@@ -274,6 +321,7 @@ pub enum Column<'p> {
     PropertyTypes(PropertyTypes),
     EntityTypes(EntityTypes),
     Entities(Entities<'p>),
+    DraftEntities(DraftEntities),
     PropertyTypeDataTypeReferences(PropertyTypeDataTypeReferences),
     PropertyTypePropertyTypeReferences(PropertyTypePropertyTypeReferences),
     EntityTypePropertyTypeReferences(EntityTypePropertyTypeReferences),
@@ -288,6 +336,7 @@ impl<'p> Column<'p> {
             Self::PropertyTypes(_) => Table::PropertyTypes,
             Self::EntityTypes(_) => Table::EntityTypes,
             Self::Entities(_) => Table::Entities,
+            Self::DraftEntities(_) => Table::DraftEntities,
             Self::PropertyTypeDataTypeReferences(_) => Table::PropertyTypeDataTypeReferences,
             Self::PropertyTypePropertyTypeReferences(_) => {
                 Table::PropertyTypePropertyTypeReferences
@@ -324,6 +373,7 @@ impl Transpile for Column<'_> {
             Self::PropertyTypes(column) => column.transpile(fmt),
             Self::EntityTypes(column) => column.transpile(fmt),
             Self::Entities(column) => column.transpile(fmt),
+            Self::DraftEntities(column) => column.transpile(fmt),
             Self::PropertyTypeDataTypeReferences(column) => column.transpile(fmt),
             Self::PropertyTypePropertyTypeReferences(column) => column.transpile(fmt),
             Self::EntityTypePropertyTypeReferences(column) => column.transpile(fmt),
@@ -0,0 +1,145 @@
+use async_trait::async_trait;
+use error_stack::{IntoReport, Result, ResultExt};
+
+use crate::{
+    identifier::knowledge::{EntityEditionId, EntityId, EntityRecordId},
+    metrics,
+    store::{
+        webhook::{DeadLetter, Webhook, WebhookId, WebhookStore},
+        AsClient, InsertionError, PostgresStore, QueryError,
+    },
+};
+
+#[async_trait]
+impl<C: AsClient> WebhookStore for PostgresStore<C> {
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn create_webhook(&mut self, webhook: &Webhook) -> Result<(), InsertionError> {
+        let _timer = metrics::store_operation("create", "webhooks");
+
+        webhook
+            .validate_target_url()
+            .change_context(InsertionError)
+            .attach_printable(webhook.webhook_id())?;
+
+        self.as_client()
+            .query_one(
+                r#"
+                INSERT INTO webhooks (webhook_id, owned_by_id, target_url, secret, filter)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING webhook_id;
+                "#,
+                &[
+                    &webhook.webhook_id(),
+                    &webhook.owned_by_id(),
+                    &webhook.target_url(),
+                    &webhook.secret(),
+                    webhook.filter(),
+                ],
+            )
+            .await
+            .into_report()
+            .change_context(InsertionError)
+            .attach_printable(webhook.webhook_id())?;
+        self.record_write();
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn webhooks(&self) -> Result<Vec<Webhook>, QueryError> {
+        let _timer = metrics::store_operation("read", "webhooks");
+
+        self.as_client()
+            .query(
+                r#"
+                SELECT webhook_id, owned_by_id, target_url, secret, filter
+                FROM webhooks;
+                "#,
+                &[],
+            )
+            .await
+            .into_report()
+            .change_context(QueryError)?
+            .into_iter()
+            .map(|row| {
+                Webhook::new(row.get(0), row.get(1), row.get(2), row.get(3), row.get(4))
+                    .change_context(QueryError)
+            })
+            .collect()
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn insert_dead_letter(&mut self, dead_letter: &DeadLetter) -> Result<(), InsertionError> {
+        let _timer = metrics::store_operation("create", "webhook_dead_letters");
+
+        let entity_edition_id = dead_letter.entity_edition_id();
+        self.as_client()
+            .query_one(
+                r#"
+                INSERT INTO webhook_dead_letters (
+                    webhook_id,
+                    owned_by_id,
+                    entity_uuid,
+                    entity_record_id,
+                    payload,
+                    attempts,
+                    error
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                RETURNING webhook_id;
+                "#,
+                &[
+                    &dead_letter.webhook_id(),
+                    &entity_edition_id.base_id().owned_by_id(),
+                    &entity_edition_id.base_id().entity_uuid(),
+                    &entity_edition_id.record_id(),
+                    dead_letter.payload(),
+                    &i64::from(dead_letter.attempts()),
+                    &dead_letter.error(),
+                ],
+            )
+            .await
+            .into_report()
+            .change_context(InsertionError)
+            .attach_printable(dead_letter.webhook_id())?;
+        self.record_write();
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn dead_letters(&self, webhook_id: WebhookId) -> Result<Vec<DeadLetter>, QueryError> {
+        let _timer = metrics::store_operation("read", "webhook_dead_letters");
+
+        self.as_client()
+            .query(
+                r#"
+                SELECT owned_by_id, entity_uuid, entity_record_id, payload, attempts, error
+                FROM webhook_dead_letters
+                WHERE webhook_id = $1
+                ORDER BY failed_at;
+                "#,
+                &[&webhook_id],
+            )
+            .await
+            .into_report()
+            .change_context(QueryError)?
+            .into_iter()
+            .map(|row| {
+                let attempts: i64 = row.get(4);
+                Ok(DeadLetter::new(
+                    webhook_id,
+                    EntityEditionId::new(
+                        EntityId::new(row.get(0), row.get(1)),
+                        EntityRecordId::new(row.get(2)),
+                    ),
+                    row.get(3),
+                    u32::try_from(attempts)
+                        .into_report()
+                        .change_context(QueryError)?,
+                    row.get(5),
+                ))
+            })
+            .collect()
+    }
+}
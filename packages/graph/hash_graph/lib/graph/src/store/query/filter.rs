@@ -8,7 +8,7 @@ use uuid::Uuid;
 
 use crate::{
     identifier::{
-        knowledge::EntityId,
+        knowledge::{EntityEditionId, EntityId},
         ontology::OntologyTypeEditionId,
         time::{ProjectedTime, Timestamp},
         EntityVertexId,
@@ -24,7 +24,8 @@ use crate::{
 #[derive(Derivative, Deserialize)]
 #[derivative(
     Debug(bound = "R::QueryPath<'p>: fmt::Debug"),
-    PartialEq(bound = "R::QueryPath<'p>: PartialEq")
+    PartialEq(bound = "R::QueryPath<'p>: PartialEq"),
+    Clone(bound = "R::QueryPath<'p>: Clone")
 )]
 #[serde(
     rename_all = "camelCase",
@@ -129,6 +130,21 @@ impl<'p> Filter<'p, Entity> {
         ])
     }
 
+    /// Creates a `Filter` to search for a specific entity edition, identified by its
+    /// [`EntityEditionId`].
+    #[must_use]
+    pub fn for_entity_by_edition_id(edition_id: EntityEditionId) -> Self {
+        Self::All(vec![
+            Self::for_entity_by_entity_id(edition_id.base_id()),
+            Self::Equal(
+                Some(FilterExpression::Path(EntityQueryPath::RecordId)),
+                Some(FilterExpression::Parameter(Parameter::SignedInteger(
+                    edition_id.record_id().as_i64(),
+                ))),
+            ),
+        ])
+    }
+
     /// Creates a `Filter` to search for a specific entity edition, identified by its
     /// [`EntityVertexId`].
     #[must_use]
@@ -326,7 +342,8 @@ where
 #[derive(Derivative, Deserialize)]
 #[derivative(
     Debug(bound = "R::QueryPath<'p>: fmt::Debug"),
-    PartialEq(bound = "R::QueryPath<'p>: PartialEq")
+    PartialEq(bound = "R::QueryPath<'p>: PartialEq"),
+    Clone(bound = "R::QueryPath<'p>: Clone")
 )]
 #[serde(
     rename_all = "camelCase",
@@ -337,7 +354,7 @@ pub enum FilterExpression<'p, R: Record + ?Sized> {
     Parameter(Parameter<'p>),
 }
 
-#[derive(Debug, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 #[serde(untagged)]
 pub enum Parameter<'p> {
     Boolean(bool),
@@ -1,8 +1,12 @@
 mod filter;
+mod syntax;
 
 use std::fmt;
 
-pub use self::filter::{Filter, FilterExpression, Parameter, ParameterConversionError};
+pub use self::{
+    filter::{Filter, FilterExpression, Parameter, ParameterConversionError},
+    syntax::{parse_filter, FilterSyntaxError},
+};
 
 pub trait QueryPath {
     /// Returns what type this resolved `Path` has.
@@ -68,6 +72,9 @@ pub trait OntologyQueryPath {
     /// [`UpdatedById`]: crate::provenance::UpdatedById
     fn updated_by_id() -> Self;
 
+    /// Returns the path identifying whether the type was fetched from a remote location.
+    fn external() -> Self;
+
     /// Returns the path identifying the schema.
     fn schema() -> Self;
 }
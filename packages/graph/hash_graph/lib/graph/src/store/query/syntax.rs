@@ -0,0 +1,409 @@
+//! A compact, textual syntax for [`Filter`]s.
+//!
+//! The syntax is meant for humans, e.g. on the command line, and is translated into the JSON
+//! representation of a [`Filter`], which can then be deserialized for any [`Record`].
+//!
+//! ```text
+//! type.baseUri = "https://example.com/@alice/types/entity-type/person/" and archived = false
+//! ```
+//!
+//! - A comparison is written as `lhs = rhs` or `lhs != rhs`. Each side is either a path or a value.
+//! - A path is a sequence of segments separated by dots, e.g. `type.version`. The first segment is
+//!   an identifier, following segments may also be quoted, e.g. `properties."https://..."`.
+//! - A value is a quoted string, a number, `true`, `false`, or `null`.
+//! - Comparisons are combined with `and`, `or`, and `not`, where `not` binds strongest and `or`
+//!   weakest. Parentheses can be used for grouping.
+//!
+//! [`Filter`]: crate::store::query::Filter
+//! [`Record`]: crate::store::Record
+
+use std::fmt;
+
+use error_stack::{Report, Result};
+use serde_json::{json, Number, Value};
+
+#[derive(Debug)]
+#[must_use]
+pub struct FilterSyntaxError;
+
+impl fmt::Display for FilterSyntaxError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("The filter expression is invalid")
+    }
+}
+
+impl error_stack::Context for FilterSyntaxError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Identifier(String),
+    String(String),
+    Number(Number),
+    Dot,
+    Equal,
+    NotEqual,
+    OpenParen,
+    CloseParen,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Identifier(identifier) => fmt::Display::fmt(identifier, fmt),
+            Self::String(string) => fmt::Debug::fmt(string, fmt),
+            Self::Number(number) => fmt::Display::fmt(number, fmt),
+            Self::Dot => fmt.write_str("."),
+            Self::Equal => fmt.write_str("="),
+            Self::NotEqual => fmt.write_str("!="),
+            Self::OpenParen => fmt.write_str("("),
+            Self::CloseParen => fmt.write_str(")"),
+        }
+    }
+}
+
+fn syntax_error(position: usize, message: impl fmt::Display) -> Report<FilterSyntaxError> {
+    Report::new(FilterSyntaxError).attach_printable(format!("at position {position}: {message}"))
+}
+
+/// Splits `input` into tokens and their byte position.
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>, FilterSyntaxError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(position, char)) = chars.peek() {
+        let token = match char {
+            _ if char.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '.' => {
+                chars.next();
+                Token::Dot
+            }
+            '(' => {
+                chars.next();
+                Token::OpenParen
+            }
+            ')' => {
+                chars.next();
+                Token::CloseParen
+            }
+            '=' => {
+                chars.next();
+                Token::Equal
+            }
+            '!' => {
+                chars.next();
+                match chars.next() {
+                    Some((_, '=')) => Token::NotEqual,
+                    _ => return Err(syntax_error(position, "expected `!=`")),
+                }
+            }
+            '"' => {
+                chars.next();
+                let mut string = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, escaped @ ('"' | '\\'))) => string.push(escaped),
+                            Some((_, 'n')) => string.push('\n'),
+                            Some((_, 't')) => string.push('\t'),
+                            _ => return Err(syntax_error(position, "invalid escape sequence")),
+                        },
+                        Some((_, char)) => string.push(char),
+                        None => return Err(syntax_error(position, "unterminated string")),
+                    }
+                }
+                Token::String(string)
+            }
+            '-' | '0'..='9' => {
+                let mut end = position;
+                while let Some(&(index, char)) = chars.peek() {
+                    if !(char.is_ascii_digit() || char == '-' || char == '.' || char == 'e') {
+                        break;
+                    }
+                    end = index + char.len_utf8();
+                    chars.next();
+                }
+                let number = input[position..end]
+                    .parse::<Number>()
+                    .map_err(|error| syntax_error(position, error))?;
+                Token::Number(number)
+            }
+            _ if char.is_alphabetic() || char == '_' => {
+                let mut end = position;
+                while let Some(&(index, char)) = chars.peek() {
+                    if !(char.is_alphanumeric() || char == '_' || char == '-') {
+                        break;
+                    }
+                    end = index + char.len_utf8();
+                    chars.next();
+                }
+                Token::Identifier(input[position..end].to_owned())
+            }
+            _ => {
+                return Err(syntax_error(
+                    position,
+                    format!("unexpected character `{char}`"),
+                ));
+            }
+        };
+        tokens.push((position, token));
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(_, token)| token)
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map_or(self.end, |(offset, _)| *offset)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .map(|(_, token)| token.clone());
+        self.position += 1;
+        token
+    }
+
+    fn next_is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Identifier(identifier)) if identifier == keyword)
+    }
+
+    fn parse_or(&mut self) -> Result<Value, FilterSyntaxError> {
+        let mut operands = vec![self.parse_and()?];
+        while self.next_is_keyword("or") {
+            self.next();
+            operands.push(self.parse_and()?);
+        }
+        Ok(combine("any", operands))
+    }
+
+    fn parse_and(&mut self) -> Result<Value, FilterSyntaxError> {
+        let mut operands = vec![self.parse_not()?];
+        while self.next_is_keyword("and") {
+            self.next();
+            operands.push(self.parse_not()?);
+        }
+        Ok(combine("all", operands))
+    }
+
+    fn parse_not(&mut self) -> Result<Value, FilterSyntaxError> {
+        if self.next_is_keyword("not") {
+            self.next();
+            return Ok(json!({ "not": self.parse_not()? }));
+        }
+
+        if self.peek() == Some(&Token::OpenParen) {
+            self.next();
+            let filter = self.parse_or()?;
+            let offset = self.offset();
+            return match self.next() {
+                Some(Token::CloseParen) => Ok(filter),
+                _ => Err(syntax_error(offset, "expected `)`")),
+            };
+        }
+
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Value, FilterSyntaxError> {
+        let lhs = self.parse_operand()?;
+        let offset = self.offset();
+        let operator = match self.next() {
+            Some(Token::Equal) => "equal",
+            Some(Token::NotEqual) => "notEqual",
+            Some(token) => {
+                return Err(syntax_error(
+                    offset,
+                    format!("expected `=` or `!=`, found `{token}`"),
+                ));
+            }
+            None => return Err(syntax_error(offset, "expected `=` or `!=`")),
+        };
+        let rhs = self.parse_operand()?;
+
+        Ok(json!({ operator: [lhs, rhs] }))
+    }
+
+    fn parse_operand(&mut self) -> Result<Value, FilterSyntaxError> {
+        let offset = self.offset();
+        match self.next() {
+            Some(Token::String(string)) => Ok(json!({ "parameter": string })),
+            Some(Token::Number(number)) => Ok(json!({ "parameter": number })),
+            Some(Token::Identifier(identifier)) => match identifier.as_str() {
+                "true" => Ok(json!({ "parameter": true })),
+                "false" => Ok(json!({ "parameter": false })),
+                "null" => Ok(Value::Null),
+                _ => self.parse_path(identifier),
+            },
+            Some(token) => Err(syntax_error(
+                offset,
+                format!("expected a path or a value, found `{token}`"),
+            )),
+            None => Err(syntax_error(offset, "expected a path or a value")),
+        }
+    }
+
+    fn parse_path(&mut self, first: String) -> Result<Value, FilterSyntaxError> {
+        let mut segments = vec![Value::String(first)];
+        while self.peek() == Some(&Token::Dot) {
+            self.next();
+            let offset = self.offset();
+            match self.next() {
+                Some(Token::Identifier(segment) | Token::String(segment)) => {
+                    segments.push(Value::String(segment));
+                }
+                Some(Token::Number(segment)) => segments.push(Value::String(segment.to_string())),
+                _ => return Err(syntax_error(offset, "expected a path segment")),
+            }
+        }
+        Ok(json!({ "path": segments }))
+    }
+}
+
+fn combine(operator: &str, mut operands: Vec<Value>) -> Value {
+    if operands.len() == 1 {
+        operands.remove(0)
+    } else {
+        json!({ operator: operands })
+    }
+}
+
+/// Parses a filter expression into the JSON representation of a [`Filter`].
+///
+/// See the [module documentation] for the syntax.
+///
+/// # Errors
+///
+/// - [`FilterSyntaxError`], if `input` is not a valid filter expression
+///
+/// [`Filter`]: crate::store::query::Filter
+/// [module documentation]: self
+pub fn parse_filter(input: &str) -> Result<Value, FilterSyntaxError> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        position: 0,
+        end: input.len(),
+    };
+
+    let filter = parser.parse_or()?;
+    if let Some(token) = parser.peek() {
+        return Err(syntax_error(
+            parser.offset(),
+            format!("unexpected `{token}`"),
+        ));
+    }
+
+    Ok(filter)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{knowledge::Entity, store::query::Filter};
+
+    #[test]
+    fn comparison() {
+        assert_eq!(
+            parse_filter(r#"type.baseUri = "https://example.com/""#).expect("invalid filter"),
+            json!({
+                "equal": [
+                    { "path": ["type", "baseUri"] },
+                    { "parameter": "https://example.com/" }
+                ]
+            })
+        );
+        assert_eq!(
+            parse_filter("archived != true").expect("invalid filter"),
+            json!({ "notEqual": [{ "path": ["archived"] }, { "parameter": true }] })
+        );
+        assert_eq!(
+            parse_filter("type.version = 1").expect("invalid filter"),
+            json!({ "equal": [{ "path": ["type", "version"] }, { "parameter": 1 }] })
+        );
+        assert_eq!(
+            parse_filter(r#"properties."https://example.com/name/" = null"#)
+                .expect("invalid filter"),
+            json!({ "equal": [{ "path": ["properties", "https://example.com/name/"] }, null] })
+        );
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(
+            parse_filter("a = 1 or not b = 2 and c = 3").expect("invalid filter"),
+            json!({
+                "any": [
+                    { "equal": [{ "path": ["a"] }, { "parameter": 1 }] },
+                    {
+                        "all": [
+                            { "not": { "equal": [{ "path": ["b"] }, { "parameter": 2 }] } },
+                            { "equal": [{ "path": ["c"] }, { "parameter": 3 }] }
+                        ]
+                    }
+                ]
+            })
+        );
+        assert_eq!(
+            parse_filter("(a = 1 or b = 2) and c = 3").expect("invalid filter"),
+            json!({
+                "all": [
+                    {
+                        "any": [
+                            { "equal": [{ "path": ["a"] }, { "parameter": 1 }] },
+                            { "equal": [{ "path": ["b"] }, { "parameter": 2 }] }
+                        ]
+                    },
+                    { "equal": [{ "path": ["c"] }, { "parameter": 3 }] }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for input in [
+            "",
+            "a",
+            "a = ",
+            "a == 1",
+            r#"a = "unterminated"#,
+            "(a = 1",
+            "a = 1 b = 2",
+            "a. = 1",
+        ] {
+            parse_filter(input).expect_err(input);
+        }
+    }
+
+    #[test]
+    fn entity_filter() {
+        let value = parse_filter(
+            r#"type.baseUri = "https://example.com/@alice/types/entity-type/person/" and archived = false"#,
+        )
+        .expect("invalid filter");
+
+        let mut filter = Filter::<Entity>::deserialize(&value).expect("invalid entity filter");
+        filter
+            .convert_parameters()
+            .expect("could not convert parameters");
+    }
+}
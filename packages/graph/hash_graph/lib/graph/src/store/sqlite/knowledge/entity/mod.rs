@@ -0,0 +1,1430 @@
+mod read;
+
+use std::collections::hash_map::RawEntryMut;
+
+use async_trait::async_trait;
+use error_stack::{IntoReport, Report, Result, ResultExt};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value as JsonValue;
+use type_system::uri::VersionedUri;
+use uuid::Uuid;
+
+use crate::{
+    identifier::{
+        account::AccountId,
+        knowledge::{EntityEditionId, EntityId, EntityRecordId, EntityVersion},
+        ontology::OntologyTypeEditionId,
+        time::{DecisionTime, Timestamp, TransactionTime, VersionTimespan},
+        EntityVertexId,
+    },
+    knowledge::{
+        DraftId, Entity, EntityLinkOrder, EntityMetadata, EntityProperties, EntityUuid, LinkData,
+        LinkOrder,
+    },
+    metrics,
+    ontology::EntityTypeWithMetadata,
+    provenance::{OwnedById, PropertyProvenance, ProvenanceMetadata, UpdatedById},
+    store::{
+        error::{
+            DraftDoesNotExist, DraftPublishConflict, EntityDoesNotExist, RaceConditionOnUpdate,
+        },
+        postgres::{DependencyContext, DependencyStatus},
+        query::Filter,
+        sqlite::{ontology::OntologyKind, query::SelectStatement, AsConnection, Session},
+        EntityStore, InsertionError, QueryError, Record, SqliteStore, Store, Transaction,
+        UpdateError,
+    },
+    subgraph::{
+        edges::{
+            Edge, EdgeResolveDepths, GraphResolveDepths, KnowledgeGraphEdgeKind,
+            KnowledgeGraphOutwardEdges, OutgoingEdgeResolveDepth, OutwardEdge, SharedEdgeKind,
+        },
+        query::StructuralQuery,
+        Subgraph, SubgraphIndex,
+    },
+};
+
+/// The properties and the property provenance of an entity edition.
+type PropertiesWithProvenance = (JsonValue, JsonValue);
+
+/// Attributes the `properties` of a new edition.
+///
+/// Properties, which kept their value, keep their provenance, all other properties are attributed
+/// to the current update. This mirrors `merge_property_provenance` of the Postgres store.
+fn merge_property_provenance(
+    properties: &JsonValue,
+    old: Option<&PropertiesWithProvenance>,
+    updated_by_id: UpdatedById,
+    source: Option<&str>,
+    now: Timestamp<TransactionTime>,
+) -> serde_json::Result<JsonValue> {
+    let Some(properties) = properties.as_object() else {
+        return Ok(JsonValue::Object(serde_json::Map::new()));
+    };
+
+    properties
+        .iter()
+        .map(|(key, value)| {
+            let kept = old.and_then(|(old_properties, old_property_provenance)| {
+                (old_properties.get(key) == Some(value))
+                    .then(|| old_property_provenance.get(key))
+                    .flatten()
+            });
+            let provenance = match kept {
+                Some(provenance) => provenance.clone(),
+                None => serde_json::to_value(PropertyProvenance::new(
+                    updated_by_id,
+                    now,
+                    source.map(ToOwned::to_owned),
+                ))?,
+            };
+            Ok((key.clone(), provenance))
+        })
+        .collect::<serde_json::Result<_>>()
+        .map(JsonValue::Object)
+}
+
+/// Inserts an entity edition and returns its record id.
+#[expect(clippy::too_many_arguments)]
+fn insert_edition(
+    connection: &Connection,
+    entity_type_version_id: Uuid,
+    properties: &JsonValue,
+    property_provenance: &JsonValue,
+    left_to_right_order: Option<LinkOrder>,
+    right_to_left_order: Option<LinkOrder>,
+    updated_by_id: UpdatedById,
+    archived: bool,
+) -> rusqlite::Result<i64> {
+    connection
+        .prepare_cached(
+            r#"
+                INSERT INTO entity_editions (
+                    updated_by_id,
+                    archived,
+                    entity_type_version_id,
+                    properties,
+                    property_provenance,
+                    left_to_right_order,
+                    right_to_left_order
+                )
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);
+            "#,
+        )?
+        .execute(params![
+            updated_by_id.as_uuid(),
+            archived,
+            entity_type_version_id,
+            properties,
+            property_provenance,
+            left_to_right_order.map(LinkOrder::as_i32),
+            right_to_left_order.map(LinkOrder::as_i32),
+        ])?;
+    Ok(connection.last_insert_rowid())
+}
+
+/// Inserts a version of an entity, empty intervals are skipped.
+fn insert_version(
+    connection: &Connection,
+    entity_id: EntityId,
+    entity_record_id: i64,
+    decision_time: VersionTimespan<()>,
+    transaction_time: VersionTimespan<()>,
+) -> rusqlite::Result<()> {
+    if decision_time.end == Some(decision_time.start)
+        || transaction_time.end == Some(transaction_time.start)
+    {
+        return Ok(());
+    }
+
+    connection
+        .prepare_cached(
+            r#"
+                INSERT INTO entity_versions (
+                    owned_by_id,
+                    entity_uuid,
+                    entity_record_id,
+                    decision_time_start,
+                    decision_time_end,
+                    transaction_time_start,
+                    transaction_time_end
+                )
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);
+            "#,
+        )?
+        .execute(params![
+            entity_id.owned_by_id().as_uuid(),
+            entity_id.entity_uuid().as_uuid(),
+            entity_record_id,
+            decision_time.start,
+            decision_time.end,
+            transaction_time.start,
+            transaction_time.end,
+        ])?;
+    Ok(())
+}
+
+/// Reads the properties of the live edition of the entity, which is valid at `decision_time`.
+fn read_live_properties(
+    connection: &Connection,
+    entity_id: EntityId,
+    decision_time: Timestamp<()>,
+    now: Timestamp<()>,
+) -> rusqlite::Result<Option<PropertiesWithProvenance>> {
+    connection
+        .query_row(
+            r#"
+                SELECT entity_editions.properties, entity_editions.property_provenance
+                FROM entity_versions
+                JOIN entity_editions
+                  ON entity_versions.entity_record_id = entity_editions.entity_record_id
+                WHERE entity_versions.owned_by_id = ?1
+                  AND entity_versions.entity_uuid = ?2
+                  AND entity_versions.decision_time_start <= ?3
+                  AND (
+                    entity_versions.decision_time_end IS NULL
+                    OR entity_versions.decision_time_end > ?3
+                  )
+                  AND entity_versions.transaction_time_start <= ?4
+                  AND (
+                    entity_versions.transaction_time_end IS NULL
+                    OR entity_versions.transaction_time_end > ?4
+                  );
+            "#,
+            params![
+                entity_id.owned_by_id().as_uuid(),
+                entity_id.entity_uuid().as_uuid(),
+                decision_time,
+                now
+            ],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+}
+
+/// Replaces the live edition of the entity, which is valid at `decision_time`, by the edition
+/// identified by `entity_record_id`.
+///
+/// The replaced edition is kept in the history as the `update_entity_version_trigger` of the
+/// Postgres store does: It stays valid up until `now` in the transaction time and up until
+/// `decision_time` in the decision time. Returns `None` if there is no such live edition.
+fn replace_live_edition(
+    connection: &Connection,
+    entity_id: EntityId,
+    entity_record_id: i64,
+    decision_time: Timestamp<()>,
+    now: Timestamp<()>,
+) -> rusqlite::Result<Option<(VersionTimespan<()>, VersionTimespan<()>)>> {
+    let Some((rowid, old_entity_record_id, old_decision_time, old_transaction_time_start)) =
+        connection
+            .query_row(
+                r#"
+                    SELECT
+                        rowid,
+                        entity_record_id,
+                        decision_time_start,
+                        decision_time_end,
+                        transaction_time_start
+                    FROM entity_versions
+                    WHERE owned_by_id = ?1
+                      AND entity_uuid = ?2
+                      AND decision_time_start <= ?3
+                      AND (decision_time_end IS NULL OR decision_time_end > ?3)
+                      AND transaction_time_start <= ?4
+                      AND (transaction_time_end IS NULL OR transaction_time_end > ?4);
+                "#,
+                params![
+                    entity_id.owned_by_id().as_uuid(),
+                    entity_id.entity_uuid().as_uuid(),
+                    decision_time,
+                    now
+                ],
+                |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, i64>(1)?,
+                        VersionTimespan::<()> {
+                            start: row.get(2)?,
+                            end: row.get(3)?,
+                        },
+                        row.get::<_, Timestamp<()>>(4)?,
+                    ))
+                },
+            )
+            .optional()?
+    else {
+        return Ok(None);
+    };
+
+    let new_decision_time = VersionTimespan {
+        start: decision_time,
+        end: old_decision_time.end,
+    };
+    let new_transaction_time = VersionTimespan {
+        start: now,
+        end: None,
+    };
+
+    connection.execute(
+        r#"
+            UPDATE entity_versions
+            SET entity_record_id = ?2,
+                decision_time_start = ?3,
+                transaction_time_start = ?4,
+                transaction_time_end = NULL
+            WHERE rowid = ?1;
+        "#,
+        params![rowid, entity_record_id, decision_time, now],
+    )?;
+
+    // The old edition with the old decision time and the transaction time up until now
+    insert_version(
+        connection,
+        entity_id,
+        old_entity_record_id,
+        old_decision_time,
+        VersionTimespan {
+            start: old_transaction_time_start,
+            end: Some(now),
+        },
+    )?;
+
+    // The old edition with the previous decision time until the new decision time
+    insert_version(
+        connection,
+        entity_id,
+        old_entity_record_id,
+        VersionTimespan {
+            start: old_decision_time.start,
+            end: Some(decision_time),
+        },
+        new_transaction_time,
+    )?;
+
+    Ok(Some((new_decision_time, new_transaction_time)))
+}
+
+/// Inserts or replaces the edition of the entity in the draft.
+///
+/// When the entity is first changed in the draft, the live edition at that time is recorded as the
+/// base of the draft, so publishing the draft can detect updates made in the meantime. Returns the
+/// decision time and transaction time of the drafted edition.
+fn upsert_draft_edition(
+    connection: &Connection,
+    draft_id: DraftId,
+    entity_id: EntityId,
+    entity_record_id: i64,
+    decision_time: Option<Timestamp<()>>,
+    now: Timestamp<()>,
+) -> rusqlite::Result<(VersionTimespan<()>, VersionTimespan<()>)> {
+    connection
+        .prepare_cached(
+            r#"
+                INSERT INTO entity_drafts (
+                    draft_id,
+                    owned_by_id,
+                    entity_uuid,
+                    entity_record_id,
+                    base_entity_record_id,
+                    decision_time,
+                    transaction_time_start
+                )
+                VALUES (?1, ?2, ?3, ?4, (
+                    SELECT entity_record_id
+                    FROM entity_versions
+                    WHERE owned_by_id = ?2
+                      AND entity_uuid = ?3
+                      AND decision_time_start <= ?7
+                      AND (decision_time_end IS NULL OR decision_time_end > ?7)
+                      AND transaction_time_start <= ?6
+                      AND (transaction_time_end IS NULL OR transaction_time_end > ?6)
+                ), ?5, ?6)
+                ON CONFLICT (draft_id, owned_by_id, entity_uuid) DO UPDATE
+                SET entity_record_id = excluded.entity_record_id,
+                    decision_time = excluded.decision_time,
+                    transaction_time_start = excluded.transaction_time_start;
+            "#,
+        )?
+        .execute(params![
+            draft_id.as_uuid(),
+            entity_id.owned_by_id().as_uuid(),
+            entity_id.entity_uuid().as_uuid(),
+            entity_record_id,
+            decision_time,
+            now,
+            decision_time.unwrap_or(now),
+        ])?;
+
+    // Drafts keep an unspecified decision time, so it is set when the draft is published.
+    Ok((
+        VersionTimespan {
+            start: decision_time.unwrap_or(now),
+            end: None,
+        },
+        VersionTimespan {
+            start: now,
+            end: None,
+        },
+    ))
+}
+
+/// Inserts the id of a new entity.
+fn insert_entity_id(
+    connection: &Connection,
+    entity_id: EntityId,
+    link_data: Option<&LinkData>,
+) -> rusqlite::Result<()> {
+    let left_entity_id = link_data.map(LinkData::left_entity_id);
+    let right_entity_id = link_data.map(LinkData::right_entity_id);
+
+    connection
+        .prepare_cached(
+            r#"
+                INSERT INTO entity_ids (
+                    owned_by_id,
+                    entity_uuid,
+                    left_owned_by_id,
+                    left_entity_uuid,
+                    right_owned_by_id,
+                    right_entity_uuid
+                )
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6);
+            "#,
+        )?
+        .execute(params![
+            entity_id.owned_by_id().as_uuid(),
+            entity_id.entity_uuid().as_uuid(),
+            left_entity_id.map(|entity_id| entity_id.owned_by_id().as_uuid()),
+            left_entity_id.map(|entity_id| entity_id.entity_uuid().as_uuid()),
+            right_entity_id.map(|entity_id| entity_id.owned_by_id().as_uuid()),
+            right_entity_id.map(|entity_id| entity_id.entity_uuid().as_uuid()),
+        ])?;
+    Ok(())
+}
+
+/// Reads the properties of the edition of the entity in the draft.
+fn read_draft_properties(
+    connection: &Connection,
+    draft_id: DraftId,
+    entity_id: EntityId,
+) -> rusqlite::Result<Option<PropertiesWithProvenance>> {
+    connection
+        .query_row(
+            r#"
+                SELECT entity_editions.properties, entity_editions.property_provenance
+                FROM entity_drafts
+                JOIN entity_editions
+                  ON entity_drafts.entity_record_id = entity_editions.entity_record_id
+                WHERE entity_drafts.draft_id = ?1
+                  AND entity_drafts.owned_by_id = ?2
+                  AND entity_drafts.entity_uuid = ?3;
+            "#,
+            params![
+                draft_id.as_uuid(),
+                entity_id.owned_by_id().as_uuid(),
+                entity_id.entity_uuid().as_uuid()
+            ],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+}
+
+fn entity_id_from_row(owned_by_id: Uuid, entity_uuid: Uuid) -> EntityId {
+    EntityId::new(
+        OwnedById::new(AccountId::new(owned_by_id)),
+        EntityUuid::new(entity_uuid),
+    )
+}
+
+impl Session<'_> {
+    /// Reads the entities matching the `filter` in the time projection and the draft of the
+    /// `subgraph`.
+    fn read_entities(
+        &self,
+        filter: &Filter<Entity>,
+        subgraph: &Subgraph,
+    ) -> Result<Vec<Entity>, QueryError> {
+        SelectStatement::compile(
+            filter,
+            &subgraph.resolved_time_projection,
+            subgraph.draft_id,
+        )
+        .read(self.connection)
+    }
+
+    /// Internal method to read an [`Entity`] into a [`DependencyContext`].
+    ///
+    /// This is used to recursively resolve a type, so the result can be reused.
+    #[tracing::instrument(level = "trace", skip(self, dependency_context, subgraph))]
+    pub(crate) fn traverse_entity(
+        &self,
+        entity_vertex_id: EntityVertexId,
+        dependency_context: &mut DependencyContext,
+        subgraph: &mut Subgraph,
+        current_resolve_depth: GraphResolveDepths,
+    ) -> Result<(), QueryError> {
+        let dependency_status = dependency_context
+            .knowledge_dependency_map
+            .insert(&entity_vertex_id, current_resolve_depth);
+
+        let time_axis = subgraph.resolved_time_projection.time_axis();
+
+        let entity: &Entity = match dependency_status {
+            DependencyStatus::Unresolved => {
+                match entity_vertex_id.subgraph_vertex_entry(subgraph) {
+                    RawEntryMut::Occupied(entry) => entry.into_mut(),
+                    RawEntryMut::Vacant(_) => {
+                        // Entities are always inserted into the subgraph before they are
+                        // resolved, so this should never happen. If it does, it is a bug.
+                        unreachable!("entity should already be in the subgraph")
+                    }
+                }
+            }
+
+            DependencyStatus::Resolved => return Ok(()),
+        };
+
+        let entity_type_id = OntologyTypeEditionId::from(entity.metadata().entity_type_id());
+        if current_resolve_depth.is_of_type.outgoing > 0
+            && self.reserve_vertex::<EntityTypeWithMetadata>(subgraph, &entity_type_id)?
+        {
+            subgraph.edges.insert(Edge::KnowledgeGraph {
+                vertex_id: entity_vertex_id,
+                outward_edge: KnowledgeGraphOutwardEdges::ToOntology(OutwardEdge {
+                    kind: SharedEdgeKind::IsOfType,
+                    reversed: false,
+                    right_endpoint: entity_type_id.clone(),
+                }),
+            });
+
+            self.traverse_entity_type(
+                &entity_type_id,
+                dependency_context,
+                subgraph,
+                GraphResolveDepths {
+                    is_of_type: OutgoingEdgeResolveDepth {
+                        outgoing: current_resolve_depth.is_of_type.outgoing - 1,
+                        ..current_resolve_depth.is_of_type
+                    },
+                    ..current_resolve_depth
+                },
+            )?;
+        }
+
+        if current_resolve_depth.has_left_entity.incoming > 0 {
+            for outgoing_link_entity in self.read_entities(
+                &Filter::for_outgoing_link_by_source_entity_vertex_id(entity_vertex_id),
+                subgraph,
+            )? {
+                let outgoing_link_entity_vertex_id = outgoing_link_entity.vertex_id(time_axis);
+                if !self.reserve_vertex::<Entity>(subgraph, &outgoing_link_entity_vertex_id)? {
+                    break;
+                }
+
+                subgraph.edges.insert(Edge::KnowledgeGraph {
+                    vertex_id: entity_vertex_id,
+                    outward_edge: KnowledgeGraphOutwardEdges::ToKnowledgeGraph(OutwardEdge {
+                        // (HasLeftEntity, reversed=true) is equivalent to an
+                        // outgoing link `Entity`
+                        kind: KnowledgeGraphEdgeKind::HasLeftEntity,
+                        reversed: true,
+                        right_endpoint: outgoing_link_entity.metadata().edition_id().base_id(),
+                    }),
+                });
+
+                subgraph.insert(&outgoing_link_entity_vertex_id, outgoing_link_entity);
+
+                self.traverse_entity(
+                    outgoing_link_entity_vertex_id,
+                    dependency_context,
+                    subgraph,
+                    GraphResolveDepths {
+                        has_left_entity: EdgeResolveDepths {
+                            incoming: current_resolve_depth.has_left_entity.incoming - 1,
+                            ..current_resolve_depth.has_left_entity
+                        },
+                        ..current_resolve_depth
+                    },
+                )?;
+            }
+        }
+
+        if current_resolve_depth.has_right_entity.incoming > 0 {
+            for incoming_link_entity in self.read_entities(
+                &Filter::for_incoming_link_by_source_entity_vertex_id(entity_vertex_id),
+                subgraph,
+            )? {
+                let incoming_link_entity_vertex_id = incoming_link_entity.vertex_id(time_axis);
+                if !self.reserve_vertex::<Entity>(subgraph, &incoming_link_entity_vertex_id)? {
+                    break;
+                }
+
+                subgraph.edges.insert(Edge::KnowledgeGraph {
+                    vertex_id: entity_vertex_id,
+                    outward_edge: KnowledgeGraphOutwardEdges::ToKnowledgeGraph(OutwardEdge {
+                        // (HasRightEntity, reversed=true) is equivalent to an
+                        // incoming link `Entity`
+                        kind: KnowledgeGraphEdgeKind::HasRightEntity,
+                        reversed: true,
+                        right_endpoint: incoming_link_entity.metadata().edition_id().base_id(),
+                    }),
+                });
+
+                subgraph.insert(&incoming_link_entity_vertex_id, incoming_link_entity);
+
+                self.traverse_entity(
+                    incoming_link_entity_vertex_id,
+                    dependency_context,
+                    subgraph,
+                    GraphResolveDepths {
+                        has_right_entity: EdgeResolveDepths {
+                            incoming: current_resolve_depth.has_right_entity.incoming - 1,
+                            ..current_resolve_depth.has_right_entity
+                        },
+                        ..current_resolve_depth
+                    },
+                )?;
+            }
+        }
+
+        if current_resolve_depth.has_left_entity.outgoing > 0 {
+            for left_entity in self.read_entities(
+                &Filter::for_left_entity_by_entity_vertex_id(entity_vertex_id),
+                subgraph,
+            )? {
+                let left_entity_vertex_id = left_entity.vertex_id(time_axis);
+                if !self.reserve_vertex::<Entity>(subgraph, &left_entity_vertex_id)? {
+                    break;
+                }
+
+                subgraph.edges.insert(Edge::KnowledgeGraph {
+                    vertex_id: entity_vertex_id,
+                    outward_edge: KnowledgeGraphOutwardEdges::ToKnowledgeGraph(OutwardEdge {
+                        // (HasLeftEndpoint, reversed=true) is equivalent to an
+                        // outgoing `Link` `Entity`
+                        kind: KnowledgeGraphEdgeKind::HasLeftEntity,
+                        reversed: false,
+                        right_endpoint: left_entity.metadata().edition_id().base_id(),
+                    }),
+                });
+
+                subgraph.insert(&left_entity_vertex_id, left_entity);
+
+                self.traverse_entity(
+                    left_entity_vertex_id,
+                    dependency_context,
+                    subgraph,
+                    GraphResolveDepths {
+                        has_left_entity: EdgeResolveDepths {
+                            outgoing: current_resolve_depth.has_left_entity.outgoing - 1,
+                            ..current_resolve_depth.has_left_entity
+                        },
+                        ..current_resolve_depth
+                    },
+                )?;
+            }
+        }
+
+        if current_resolve_depth.has_right_entity.outgoing > 0 {
+            for right_entity in self.read_entities(
+                &Filter::for_right_entity_by_entity_vertex_id(entity_vertex_id),
+                subgraph,
+            )? {
+                let right_entity_vertex_id = right_entity.vertex_id(time_axis);
+                if !self.reserve_vertex::<Entity>(subgraph, &right_entity_vertex_id)? {
+                    break;
+                }
+
+                subgraph.edges.insert(Edge::KnowledgeGraph {
+                    vertex_id: entity_vertex_id,
+                    outward_edge: KnowledgeGraphOutwardEdges::ToKnowledgeGraph(OutwardEdge {
+                        // (HasLeftEndpoint, reversed=true) is equivalent to an
+                        // outgoing `Link` `Entity`
+                        kind: KnowledgeGraphEdgeKind::HasRightEntity,
+                        reversed: false,
+                        right_endpoint: right_entity.metadata().edition_id().base_id(),
+                    }),
+                });
+
+                subgraph.insert(&right_entity_vertex_id, right_entity);
+
+                self.traverse_entity(
+                    right_entity_vertex_id,
+                    dependency_context,
+                    subgraph,
+                    GraphResolveDepths {
+                        has_right_entity: EdgeResolveDepths {
+                            outgoing: current_resolve_depth.has_right_entity.outgoing - 1,
+                            ..current_resolve_depth.has_right_entity
+                        },
+                        ..current_resolve_depth
+                    },
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the draft exists.
+    ///
+    /// The draft cannot be changed concurrently, as the write lock of the database is acquired when
+    /// a transaction begins.
+    ///
+    /// # Errors
+    ///
+    /// - [`DraftDoesNotExist`] if the draft does not exist
+    fn lock_draft(&self, draft_id: DraftId) -> Result<(), UpdateError> {
+        let draft_exists: bool = self
+            .connection
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM drafts WHERE draft_id = ?1);",
+                [draft_id.as_uuid()],
+                |row| row.get(0),
+            )
+            .into_report()
+            .change_context(UpdateError)?;
+
+        if draft_exists {
+            Ok(())
+        } else {
+            Err(Report::new(DraftDoesNotExist)
+                .attach(draft_id)
+                .change_context(UpdateError))
+        }
+    }
+
+    /// Fails if the live edition of an entity in the draft changed since the entity was first
+    /// changed in the draft.
+    ///
+    /// # Errors
+    ///
+    /// - [`DraftPublishConflict`] for every entity, which was updated since
+    fn check_draft_conflicts(
+        &self,
+        draft_id: DraftId,
+        now: Timestamp<()>,
+    ) -> Result<(), UpdateError> {
+        let conflicts = self
+            .connection
+            .prepare_cached(
+                r#"
+                    SELECT entity_drafts.owned_by_id, entity_drafts.entity_uuid
+                    FROM entity_drafts
+                    LEFT JOIN entity_versions
+                      ON entity_versions.owned_by_id = entity_drafts.owned_by_id
+                     AND entity_versions.entity_uuid = entity_drafts.entity_uuid
+                     AND entity_versions.decision_time_start
+                         <= COALESCE(entity_drafts.decision_time, ?2)
+                     AND (entity_versions.decision_time_end IS NULL
+                          OR entity_versions.decision_time_end
+                             > COALESCE(entity_drafts.decision_time, ?2))
+                     AND entity_versions.transaction_time_start <= ?2
+                     AND (entity_versions.transaction_time_end IS NULL
+                          OR entity_versions.transaction_time_end > ?2)
+                    WHERE entity_drafts.draft_id = ?1
+                      AND entity_versions.entity_record_id
+                          IS NOT entity_drafts.base_entity_record_id;
+                "#,
+            )
+            .and_then(|mut statement| {
+                let conflicts = statement
+                    .query_map(params![draft_id.as_uuid(), now], |row| {
+                        Ok(entity_id_from_row(row.get(0)?, row.get(1)?))
+                    })?
+                    .collect::<rusqlite::Result<Vec<_>>>();
+                conflicts
+            })
+            .into_report()
+            .change_context(UpdateError)?;
+
+        let mut result: Result<(), DraftPublishConflict> = Ok(());
+        for entity_id in conflicts {
+            let error = Report::new(DraftPublishConflict).attach(entity_id);
+            match &mut result {
+                Err(report) => report.extend_one(error),
+                Ok(()) => result = Err(error),
+            }
+        }
+        result.change_context(UpdateError)
+    }
+}
+
+#[async_trait]
+impl<C: AsConnection> EntityStore for SqliteStore<C> {
+    #[tracing::instrument(level = "info", skip(self, properties))]
+    async fn create_entity(
+        &mut self,
+        owned_by_id: OwnedById,
+        entity_uuid: Option<EntityUuid>,
+        decision_time: Option<Timestamp<DecisionTime>>,
+        updated_by_id: UpdatedById,
+        archived: bool,
+        entity_type_id: VersionedUri,
+        properties: EntityProperties,
+        property_source: Option<String>,
+        link_data: Option<LinkData>,
+        draft_id: Option<DraftId>,
+    ) -> Result<EntityMetadata, InsertionError> {
+        let _timer = metrics::store_operation("create", "entities");
+
+        let entity_id = EntityId::new(
+            owned_by_id,
+            entity_uuid.unwrap_or_else(|| EntityUuid::new(Uuid::new_v4())),
+        );
+
+        let (entity_type_version_id, properties) = self
+            .run({
+                let entity_type_id = entity_type_id.clone();
+                move |session| -> Result<_, InsertionError> {
+                    let entity_type_version_id = session
+                        .version_id_by_uri(&entity_type_id, OntologyKind::EntityType)
+                        .change_context(InsertionError)?;
+
+                    session
+                        .validate_entity_properties(&entity_type_id, &properties)
+                        .change_context(InsertionError)?;
+
+                    Ok((entity_type_version_id, properties))
+                }
+            })
+            .await?;
+
+        let properties = serde_json::to_value(properties)
+            .into_report()
+            .change_context(InsertionError)?;
+
+        let now = Timestamp::<()>::now().truncate_to_microseconds();
+        let decision_time = decision_time
+            .map(|decision_time| decision_time.cast::<()>().truncate_to_microseconds());
+        let property_provenance = merge_property_provenance(
+            &properties,
+            None,
+            updated_by_id,
+            property_source.as_deref(),
+            now.cast(),
+        )
+        .into_report()
+        .change_context(InsertionError)?;
+
+        let transaction = self.transaction().await.change_context(InsertionError)?;
+
+        let (entity_record_id, (decision_time, transaction_time)) = transaction
+            .run(move |session| {
+                insert_entity_id(session.connection, entity_id, link_data.as_ref())?;
+
+                let entity_record_id = insert_edition(
+                    session.connection,
+                    entity_type_version_id,
+                    &properties,
+                    &property_provenance,
+                    link_data.as_ref().and_then(LinkData::left_to_right_order),
+                    link_data.as_ref().and_then(LinkData::right_to_left_order),
+                    updated_by_id,
+                    archived,
+                )?;
+
+                let versions = match draft_id {
+                    None => {
+                        let versions = (
+                            VersionTimespan {
+                                start: decision_time.unwrap_or(now),
+                                end: None,
+                            },
+                            VersionTimespan {
+                                start: now,
+                                end: None,
+                            },
+                        );
+                        insert_version(
+                            session.connection,
+                            entity_id,
+                            entity_record_id,
+                            versions.0,
+                            versions.1,
+                        )?;
+                        versions
+                    }
+                    Some(draft_id) => upsert_draft_edition(
+                        session.connection,
+                        draft_id,
+                        entity_id,
+                        entity_record_id,
+                        decision_time,
+                        now,
+                    )?,
+                };
+
+                Ok::<_, rusqlite::Error>((entity_record_id, versions))
+            })
+            .await
+            .into_report()
+            .change_context(InsertionError)?;
+
+        transaction.commit().await.change_context(InsertionError)?;
+
+        Ok(EntityMetadata::new(
+            EntityEditionId::new(entity_id, EntityRecordId::new(entity_record_id)),
+            EntityVersion::new(
+                VersionTimespan::from_anonymous(decision_time),
+                VersionTimespan::from_anonymous(transaction_time),
+            ),
+            entity_type_id,
+            ProvenanceMetadata::new(updated_by_id),
+            archived,
+            draft_id,
+        ))
+    }
+
+    #[doc(hidden)]
+    #[cfg(feature = "__internal_bench")]
+    async fn insert_entities_batched_by_type(
+        &mut self,
+        entities: impl IntoIterator<
+            Item = (
+                OwnedById,
+                Option<EntityUuid>,
+                EntityProperties,
+                Option<LinkData>,
+                Option<Timestamp<DecisionTime>>,
+            ),
+            IntoIter: Send,
+        > + Send,
+        actor_id: UpdatedById,
+        entity_type_id: &VersionedUri,
+    ) -> Result<Vec<EntityMetadata>, InsertionError> {
+        let transaction = self.transaction().await.change_context(InsertionError)?;
+
+        let entities = entities.into_iter().collect::<Vec<_>>();
+        let entity_type_id = entity_type_id.clone();
+        let now = Timestamp::<()>::now().truncate_to_microseconds();
+        let entities = transaction
+            .run(move |session| {
+                // Using one entity type per entity would result in more lookups, which results in a
+                // more complex logic and/or be inefficient.
+                // Please see the documentation for this function on the trait for more information.
+                let entity_type_version_id = session
+                    .version_id_by_uri(&entity_type_id, OntologyKind::EntityType)
+                    .change_context(InsertionError)?;
+
+                entities
+                    .into_iter()
+                    .map(
+                        |(owned_by_id, entity_uuid, properties, link_data, decision_time)| {
+                            let entity_id = EntityId::new(
+                                owned_by_id,
+                                entity_uuid.unwrap_or_else(|| EntityUuid::new(Uuid::new_v4())),
+                            );
+                            let properties = serde_json::to_value(properties)
+                                .into_report()
+                                .change_context(InsertionError)?;
+                            let property_provenance = merge_property_provenance(
+                                &properties,
+                                None,
+                                actor_id,
+                                None,
+                                now.cast(),
+                            )
+                            .into_report()
+                            .change_context(InsertionError)?;
+                            let decision_time = VersionTimespan {
+                                start: decision_time.map_or(now, |decision_time| {
+                                    decision_time.cast::<()>().truncate_to_microseconds()
+                                }),
+                                end: None,
+                            };
+                            let transaction_time = VersionTimespan {
+                                start: now,
+                                end: None,
+                            };
+
+                            insert_entity_id(session.connection, entity_id, link_data.as_ref())
+                                .and_then(|()| {
+                                    insert_edition(
+                                        session.connection,
+                                        entity_type_version_id,
+                                        &properties,
+                                        &property_provenance,
+                                        link_data.as_ref().and_then(LinkData::left_to_right_order),
+                                        link_data.as_ref().and_then(LinkData::right_to_left_order),
+                                        actor_id,
+                                        false,
+                                    )
+                                })
+                                .and_then(|entity_record_id| {
+                                    insert_version(
+                                        session.connection,
+                                        entity_id,
+                                        entity_record_id,
+                                        decision_time,
+                                        transaction_time,
+                                    )
+                                    .map(|()| entity_record_id)
+                                })
+                                .into_report()
+                                .change_context(InsertionError)
+                                .map(|entity_record_id| {
+                                    EntityMetadata::new(
+                                        EntityEditionId::new(
+                                            entity_id,
+                                            EntityRecordId::new(entity_record_id),
+                                        ),
+                                        EntityVersion::new(
+                                            VersionTimespan::from_anonymous(decision_time),
+                                            VersionTimespan::from_anonymous(transaction_time),
+                                        ),
+                                        entity_type_id.clone(),
+                                        ProvenanceMetadata::new(actor_id),
+                                        false,
+                                        None,
+                                    )
+                                })
+                        },
+                    )
+                    .collect::<Result<Vec<_>, InsertionError>>()
+            })
+            .await?;
+
+        transaction.commit().await.change_context(InsertionError)?;
+
+        Ok(entities)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_entity(&self, query: &StructuralQuery<Entity>) -> Result<Subgraph, QueryError> {
+        let _timer = metrics::store_operation("get", "entities");
+
+        let StructuralQuery {
+            ref filter,
+            graph_resolve_depths,
+            ref time_projection,
+            draft_id,
+        } = *query;
+
+        let mut subgraph = Subgraph::new(
+            graph_resolve_depths,
+            time_projection.clone(),
+            time_projection.clone().resolve(),
+        );
+        subgraph.draft_id = draft_id;
+        let statement =
+            SelectStatement::compile(filter, &subgraph.resolved_time_projection, draft_id);
+
+        self.run(move |session| {
+            let graph_resolve_depths =
+                session.limit_resolve_depths(&mut subgraph, graph_resolve_depths)?;
+            let mut dependency_context = DependencyContext::default();
+            let time_axis = subgraph.resolved_time_projection.time_axis();
+
+            for entity in statement.read(session.connection)? {
+                let vertex_id = entity.vertex_id(time_axis);
+                if !session.reserve_vertex::<Entity>(&mut subgraph, &vertex_id)? {
+                    break;
+                }
+
+                // Insert the vertex into the subgraph to avoid another lookup when traversing it
+                subgraph.insert(&vertex_id, entity);
+
+                session.traverse_entity(
+                    vertex_id,
+                    &mut dependency_context,
+                    &mut subgraph,
+                    graph_resolve_depths,
+                )?;
+
+                subgraph.roots.insert(vertex_id.into());
+            }
+
+            Ok(subgraph)
+        })
+        .await
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn changed_entity_ids(
+        &self,
+        from: Timestamp<TransactionTime>,
+        to: Timestamp<TransactionTime>,
+    ) -> Result<Vec<EntityId>, QueryError> {
+        let _timer = metrics::store_operation("read", "entity_versions");
+
+        self.run(move |session| {
+            session
+                .connection
+                .prepare_cached(
+                    r#"
+                        SELECT DISTINCT owned_by_id, entity_uuid
+                        FROM entity_versions
+                        WHERE transaction_time_start > ?1 AND transaction_time_start <= ?2;
+                    "#,
+                )?
+                .query_map(params![from, to], |row| {
+                    Ok(entity_id_from_row(row.get(0)?, row.get(1)?))
+                })?
+                .collect::<rusqlite::Result<_>>()
+        })
+        .await
+        .into_report()
+        .change_context(QueryError)
+    }
+
+    #[tracing::instrument(level = "info", skip(self, properties))]
+    async fn update_entity(
+        &mut self,
+        entity_id: EntityId,
+        decision_time: Option<Timestamp<DecisionTime>>,
+        updated_by_id: UpdatedById,
+        archived: bool,
+        entity_type_id: VersionedUri,
+        properties: EntityProperties,
+        property_source: Option<String>,
+        link_order: EntityLinkOrder,
+        draft_id: Option<DraftId>,
+    ) -> Result<EntityMetadata, UpdateError> {
+        let _timer = metrics::store_operation("update", "entities");
+
+        let (entity_type_version_id, properties) = self
+            .run({
+                let entity_type_id = entity_type_id.clone();
+                move |session| -> Result<_, UpdateError> {
+                    let entity_type_version_id = session
+                        .version_id_by_uri(&entity_type_id, OntologyKind::EntityType)
+                        .change_context(UpdateError)?;
+
+                    session
+                        .validate_entity_properties(&entity_type_id, &properties)
+                        .change_context(UpdateError)?;
+
+                    Ok((entity_type_version_id, properties))
+                }
+            })
+            .await?;
+
+        let properties = serde_json::to_value(properties)
+            .into_report()
+            .change_context(UpdateError)?;
+
+        // The transaction is required, so the previous edition cannot change between reading its
+        // properties and replacing it.
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        let entity_exists: bool = transaction
+            .run(move |session| {
+                session.connection.query_row(
+                    r#"
+                        SELECT EXISTS (
+                            SELECT 1 FROM entity_ids WHERE owned_by_id = ?1 AND entity_uuid = ?2
+                        );
+                    "#,
+                    params![
+                        entity_id.owned_by_id().as_uuid(),
+                        entity_id.entity_uuid().as_uuid()
+                    ],
+                    |row| row.get(0),
+                )
+            })
+            .await
+            .into_report()
+            .change_context(UpdateError)?;
+
+        if !entity_exists {
+            return Err(Report::new(EntityDoesNotExist)
+                .attach(entity_id)
+                .change_context(UpdateError));
+        }
+
+        let now = Timestamp::<()>::now().truncate_to_microseconds();
+        // Drafts keep an unspecified decision time, so it is set when the draft is published.
+        let decision_time = decision_time
+            .map(|decision_time| decision_time.cast::<()>().truncate_to_microseconds());
+
+        let previous = transaction
+            .run(move |session| {
+                if let Some(draft_id) = draft_id {
+                    if let Some(previous) =
+                        read_draft_properties(session.connection, draft_id, entity_id)?
+                    {
+                        return Ok(Some(previous));
+                    }
+                }
+                read_live_properties(
+                    session.connection,
+                    entity_id,
+                    decision_time.unwrap_or(now),
+                    now,
+                )
+            })
+            .await
+            .into_report()
+            .change_context(UpdateError)?;
+
+        // An entity can only be updated in a draft if it exists in the draft or is live
+        if draft_id.is_some() && previous.is_none() {
+            return Err(Report::new(RaceConditionOnUpdate)
+                .attach(entity_id)
+                .change_context(UpdateError));
+        }
+
+        let property_provenance = merge_property_provenance(
+            &properties,
+            previous.as_ref(),
+            updated_by_id,
+            property_source.as_deref(),
+            now.cast(),
+        )
+        .into_report()
+        .change_context(UpdateError)?;
+
+        let updated = transaction
+            .run(move |session| {
+                let entity_record_id = insert_edition(
+                    session.connection,
+                    entity_type_version_id,
+                    &properties,
+                    &property_provenance,
+                    link_order.left_to_right(),
+                    link_order.right_to_left(),
+                    updated_by_id,
+                    archived,
+                )?;
+
+                let versions = match draft_id {
+                    None => replace_live_edition(
+                        session.connection,
+                        entity_id,
+                        entity_record_id,
+                        decision_time.unwrap_or(now),
+                        now,
+                    )?,
+                    Some(draft_id) => Some(upsert_draft_edition(
+                        session.connection,
+                        draft_id,
+                        entity_id,
+                        entity_record_id,
+                        decision_time,
+                        now,
+                    )?),
+                };
+
+                Ok::<_, rusqlite::Error>(versions.map(|versions| (entity_record_id, versions)))
+            })
+            .await
+            .into_report()
+            .change_context(UpdateError)?;
+
+        let Some((entity_record_id, (decision_time, transaction_time))) = updated else {
+            return Err(Report::new(RaceConditionOnUpdate)
+                .attach(entity_id)
+                .change_context(UpdateError));
+        };
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        Ok(EntityMetadata::new(
+            EntityEditionId::new(entity_id, EntityRecordId::new(entity_record_id)),
+            EntityVersion::new(
+                VersionTimespan::from_anonymous(decision_time),
+                VersionTimespan::from_anonymous(transaction_time),
+            ),
+            entity_type_id,
+            ProvenanceMetadata::new(updated_by_id),
+            archived,
+            draft_id,
+        ))
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn create_draft(&mut self, owned_by_id: OwnedById) -> Result<DraftId, InsertionError> {
+        let _timer = metrics::store_operation("create", "drafts");
+
+        let draft_id = DraftId::new(Uuid::new_v4());
+
+        self.run(move |session| {
+            session.connection.execute(
+                "INSERT INTO drafts (draft_id, owned_by_id) VALUES (?1, ?2);",
+                [draft_id.as_uuid(), owned_by_id.as_uuid()],
+            )
+        })
+        .await
+        .into_report()
+        .change_context(InsertionError)?;
+
+        Ok(draft_id)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn publish_draft(
+        &mut self,
+        draft_id: DraftId,
+    ) -> Result<Vec<EntityEditionId>, UpdateError> {
+        let _timer = metrics::store_operation("publish", "drafts");
+
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        let now = Timestamp::<()>::now().truncate_to_microseconds();
+        transaction
+            .run(move |session| {
+                session.lock_draft(draft_id)?;
+                session.check_draft_conflicts(draft_id, now)
+            })
+            .await?;
+
+        let edition_ids = transaction
+            .run(move |session| {
+                let drafts = session
+                    .connection
+                    .prepare_cached(
+                        r#"
+                            SELECT owned_by_id, entity_uuid, entity_record_id, decision_time
+                            FROM entity_drafts
+                            WHERE draft_id = ?1;
+                        "#,
+                    )?
+                    .query_map([draft_id.as_uuid()], |row| {
+                        Ok((
+                            entity_id_from_row(row.get(0)?, row.get(1)?),
+                            row.get::<_, i64>(2)?,
+                            row.get::<_, Option<Timestamp<()>>>(3)?,
+                        ))
+                    })?
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+
+                for &(entity_id, entity_record_id, decision_time) in &drafts {
+                    let decision_time = decision_time.unwrap_or(now);
+
+                    // Entities, which are live already, are updated in the same way as in
+                    // `update_entity`, so the history is kept.
+                    if replace_live_edition(
+                        session.connection,
+                        entity_id,
+                        entity_record_id,
+                        decision_time,
+                        now,
+                    )?
+                    .is_none()
+                    {
+                        // Entities, which were created in the draft, get their first version.
+                        insert_version(
+                            session.connection,
+                            entity_id,
+                            entity_record_id,
+                            VersionTimespan {
+                                start: decision_time,
+                                end: None,
+                            },
+                            VersionTimespan {
+                                start: now,
+                                end: None,
+                            },
+                        )?;
+                    }
+                }
+
+                session
+                    .connection
+                    .execute("DELETE FROM entity_drafts WHERE draft_id = ?1;", [
+                        draft_id.as_uuid()
+                    ])?;
+                session
+                    .connection
+                    .execute("DELETE FROM drafts WHERE draft_id = ?1;", [
+                        draft_id.as_uuid()
+                    ])?;
+
+                Ok::<_, rusqlite::Error>(
+                    drafts
+                        .into_iter()
+                        .map(|(entity_id, entity_record_id, _)| {
+                            EntityEditionId::new(entity_id, EntityRecordId::new(entity_record_id))
+                        })
+                        .collect(),
+                )
+            })
+            .await
+            .into_report()
+            .change_context(UpdateError)?;
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        Ok(edition_ids)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn discard_draft(&mut self, draft_id: DraftId) -> Result<(), UpdateError> {
+        let _timer = metrics::store_operation("discard", "drafts");
+
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+        transaction
+            .run(move |session| session.lock_draft(draft_id))
+            .await?;
+
+        transaction
+            .run(move |session| {
+                // Link entities, which only existed in the draft, may refer to other entities,
+                // which only existed in the draft, so the references are checked on commit.
+                session
+                    .connection
+                    .pragma_update(None, "defer_foreign_keys", true)?;
+
+                let discarded = session
+                    .connection
+                    .prepare_cached(
+                        r#"
+                            SELECT owned_by_id, entity_uuid, entity_record_id
+                            FROM entity_drafts
+                            WHERE draft_id = ?1;
+                        "#,
+                    )?
+                    .query_map([draft_id.as_uuid()], |row| {
+                        Ok((
+                            row.get::<_, Uuid>(0)?,
+                            row.get::<_, Uuid>(1)?,
+                            row.get::<_, i64>(2)?,
+                        ))
+                    })?
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+
+                session
+                    .connection
+                    .execute("DELETE FROM entity_drafts WHERE draft_id = ?1;", [
+                        draft_id.as_uuid()
+                    ])?;
+
+                // Removes the editions of the draft and the entities, which only existed in the
+                // draft.
+                for (owned_by_id, entity_uuid, entity_record_id) in discarded {
+                    session.connection.execute(
+                        "DELETE FROM entity_editions WHERE entity_record_id = ?1;",
+                        [entity_record_id],
+                    )?;
+                    session.connection.execute(
+                        r#"
+                            DELETE FROM entity_ids
+                            WHERE owned_by_id = ?1
+                              AND entity_uuid = ?2
+                              AND NOT EXISTS (
+                                SELECT 1
+                                FROM entity_versions
+                                WHERE entity_versions.owned_by_id = entity_ids.owned_by_id
+                                  AND entity_versions.entity_uuid = entity_ids.entity_uuid
+                              );
+                        "#,
+                        [owned_by_id, entity_uuid],
+                    )?;
+                }
+
+                session
+                    .connection
+                    .execute("DELETE FROM drafts WHERE draft_id = ?1;", [
+                        draft_id.as_uuid()
+                    ])?;
+
+                Ok::<_, rusqlite::Error>(())
+            })
+            .await
+            .into_report()
+            .change_context(UpdateError)?;
+
+        transaction.commit().await.change_context(UpdateError)
+    }
+}
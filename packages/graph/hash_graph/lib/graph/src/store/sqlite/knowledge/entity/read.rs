@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+use error_stack::Result;
+
+use crate::{
+    identifier::time::TimeProjection,
+    knowledge::Entity,
+    metrics,
+    store::{
+        crud,
+        query::Filter,
+        sqlite::{query::SelectStatement, AsConnection},
+        QueryError, SqliteStore,
+    },
+};
+
+#[async_trait]
+impl<C: AsConnection> crud::Read<Entity> for SqliteStore<C> {
+    #[tracing::instrument(level = "info", skip(self, filter))]
+    async fn read(
+        &self,
+        filter: &Filter<Entity>,
+        time_projection: &TimeProjection,
+    ) -> Result<Vec<Entity>, QueryError> {
+        let _timer = metrics::store_operation("read", "entities");
+
+        let statement = SelectStatement::compile(filter, time_projection, None);
+        self.run(move |session| statement.read(session.connection))
+            .await
+    }
+}
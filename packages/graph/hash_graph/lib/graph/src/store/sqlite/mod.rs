@@ -0,0 +1,835 @@
+mod knowledge;
+mod ontology;
+mod pool;
+mod query;
+mod webhook;
+
+use std::{
+    collections::hash_map::RawEntryMut,
+    marker::PhantomData,
+    panic,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use async_trait::async_trait;
+use error_stack::{IntoReport, Report, Result, ResultExt};
+use rusqlite::{params, Connection};
+use type_system::{
+    uri::{BaseUri, VersionedUri},
+    DataTypeReference, EntityType, EntityTypeReference, PropertyType, PropertyTypeReference,
+};
+use uuid::Uuid;
+
+use self::ontology::{OntologyKind, SqliteOntologyRecord};
+pub use self::pool::{SqliteConnectionManager, SqliteStorePool};
+use crate::{
+    identifier::{
+        account::AccountId,
+        ontology::OntologyTypeEditionId,
+        time::{Timestamp, TransactionTime},
+    },
+    knowledge::EntityProperties,
+    metrics,
+    ontology::{
+        validation::PropertyValidator, ConstraintViolation, DataTypeWithMetadata,
+        EntityTypeWithMetadata, OntologyElementMetadata, OntologyType, OntologyTypeWithMetadata,
+        PropertyTypeWithMetadata,
+    },
+    provenance::{OwnedById, ProvenanceMetadata, UpdatedById},
+    store::{
+        AccountStore, BaseUriAlreadyExists, BaseUriDoesNotExist, InsertionError, QueryError,
+        QueryLimitExceeded, QueryLimits, Record, Store, StoreError, Transaction, UpdateError,
+    },
+    subgraph::{edges::GraphResolveDepths, Subgraph, SubgraphIndex},
+};
+
+/// The schema of the database, which is applied when the [`SqliteStorePool`] is created.
+const SCHEMA: &str = include_str!("schema.sql");
+
+/// The columns the store relies on, given as `(table, column)`.
+///
+/// The [`SCHEMA`] only creates missing tables, so a database file created by an older version
+/// keeps its old tables. The readiness check requires these schema objects, which the most recent
+/// changes to the schema introduced, to exist.
+const REQUIRED_COLUMNS: &[(&str, &str)] = &[
+    ("ontology_types", "external"),
+    ("ontology_types", "transaction_time_start"),
+    ("entity_editions", "property_provenance"),
+    ("drafts", "draft_id"),
+    ("entity_drafts", "base_entity_record_id"),
+    ("webhooks", "filter"),
+    ("webhook_dead_letters", "failed_at"),
+];
+
+/// Locks the connection.
+///
+/// A poisoned lock is recovered, as an open transaction is rolled back when it's dropped.
+fn lock(connection: &Mutex<Connection>) -> MutexGuard<'_, Connection> {
+    connection.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Runs `operation` on a thread of the runtime, which is allowed to block.
+///
+/// A panic in `operation` is resumed on the calling task.
+async fn spawn_blocking<T>(operation: impl FnOnce() -> T + Send + 'static) -> T
+where
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(operation)
+        .await
+        .unwrap_or_else(|error| panic::resume_unwind(error.into_panic()))
+}
+
+/// Runs `operation` on the locked `connection` on a thread, which is allowed to block.
+///
+/// The lock is released when `operation` returns, so it's never held across an `.await`.
+async fn run_blocking<T>(
+    connection: &Arc<Mutex<Connection>>,
+    operation: impl FnOnce(&Connection) -> T + Send + 'static,
+) -> T
+where
+    T: Send + 'static,
+{
+    let connection = Arc::clone(connection);
+    spawn_blocking(move || operation(&lock(&connection))).await
+}
+
+/// Provides access to the SQLite [`Connection`] a [`SqliteStore`] operates on.
+pub trait AsConnection: Send + Sync {
+    fn as_connection(&self) -> &Arc<Mutex<Connection>>;
+
+    /// Returns the number of transactions the connection is currently nested in.
+    fn transaction_depth(&self) -> usize;
+}
+
+/// A connection to a SQLite database, which is managed by the [`SqliteStorePool`].
+///
+/// The connection is shared with the blocking threads the operations of a [`SqliteStore`] run on.
+pub struct SqliteConnection(Arc<Mutex<Connection>>);
+
+impl SqliteConnection {
+    #[must_use]
+    pub fn new(connection: Connection) -> Self {
+        Self(Arc::new(Mutex::new(connection)))
+    }
+}
+
+impl AsConnection for SqliteConnection {
+    fn as_connection(&self) -> &Arc<Mutex<Connection>> {
+        &self.0
+    }
+
+    fn transaction_depth(&self) -> usize {
+        0
+    }
+}
+
+/// Returns the statement, which rolls back a transaction nested `depth` levels deep.
+const fn rollback_statement(depth: usize) -> &'static str {
+    if depth == 1 {
+        "ROLLBACK;"
+    } else {
+        "ROLLBACK TO graph_transaction; RELEASE graph_transaction;"
+    }
+}
+
+/// A transaction on a [`SqliteConnection`].
+///
+/// The outermost transaction is started as `IMMEDIATE` transaction, so the write lock of the
+/// database is acquired when the transaction begins. Nested transactions are implemented as
+/// savepoints. A transaction, which is dropped without being committed, is rolled back on the
+/// dropping thread, as dropping cannot wait for a blocking thread.
+pub struct SqliteTransaction<'t> {
+    connection: Arc<Mutex<Connection>>,
+    depth: usize,
+    finished: bool,
+    /// The store the transaction was started on cannot be used until the transaction is finished.
+    _store: PhantomData<&'t mut ()>,
+}
+
+impl SqliteTransaction<'_> {
+    async fn begin(connection: &Arc<Mutex<Connection>>, depth: usize) -> rusqlite::Result<Self> {
+        run_blocking(connection, move |connection| {
+            connection.execute_batch(if depth == 1 {
+                "BEGIN IMMEDIATE;"
+            } else {
+                "SAVEPOINT graph_transaction;"
+            })
+        })
+        .await?;
+
+        Ok(Self {
+            connection: Arc::clone(connection),
+            depth,
+            finished: false,
+            _store: PhantomData,
+        })
+    }
+
+    async fn commit(&mut self) -> rusqlite::Result<()> {
+        self.finished = true;
+        let depth = self.depth;
+        run_blocking(&self.connection, move |connection| {
+            let result = connection.execute_batch(if depth == 1 {
+                "COMMIT;"
+            } else {
+                "RELEASE graph_transaction;"
+            });
+
+            // A failed commit leaves the transaction open, so it's rolled back to release the
+            // lock.
+            if result.is_err() {
+                if let Err(error) = connection.execute_batch(rollback_statement(depth)) {
+                    tracing::error!(
+                        %error,
+                        "Could not roll back the transaction after a failed commit"
+                    );
+                }
+            }
+            result
+        })
+        .await
+    }
+
+    async fn rollback(&mut self) -> rusqlite::Result<()> {
+        self.finished = true;
+        let depth = self.depth;
+        run_blocking(&self.connection, move |connection| {
+            connection.execute_batch(rollback_statement(depth))
+        })
+        .await
+    }
+}
+
+impl Drop for SqliteTransaction<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            if let Err(error) = lock(&self.connection).execute_batch(rollback_statement(self.depth))
+            {
+                tracing::error!(%error, "Could not roll back the dropped transaction");
+            }
+        }
+    }
+}
+
+impl AsConnection for SqliteTransaction<'_> {
+    fn as_connection(&self) -> &Arc<Mutex<Connection>> {
+        &self.connection
+    }
+
+    fn transaction_depth(&self) -> usize {
+        self.depth
+    }
+}
+
+/// A SQLite-backed store.
+///
+/// SQLite only provides a synchronous API, so every operation runs on a thread of the runtime,
+/// which is allowed to block, see [`Session`]. [`Filter`]s are compiled to SQL, which is evaluated
+/// on the records pinned by the time projection of a query. The store is meant for local
+/// development and for embedding the graph into desktop applications, deployments serving many
+/// concurrent requests should use the [`PostgresStore`].
+///
+/// [`Filter`]: crate::store::query::Filter
+/// [`PostgresStore`]: crate::store::PostgresStore
+pub struct SqliteStore<C> {
+    connection: C,
+    limits: QueryLimits,
+}
+
+#[async_trait]
+impl<C: AsConnection> Store for SqliteStore<C> {
+    type Transaction<'t>
+    where
+        C: 't,
+    = SqliteStore<SqliteTransaction<'t>>;
+
+    async fn transaction(&mut self) -> Result<Self::Transaction<'_>, StoreError> {
+        let depth = self.connection.transaction_depth() + 1;
+        Ok(SqliteStore::with_query_limits(
+            SqliteTransaction::begin(self.connection.as_connection(), depth)
+                .await
+                .into_report()
+                .change_context(StoreError)?,
+            self.limits,
+        ))
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn check_readiness(&self) -> Result<(), StoreError> {
+        let missing_columns = self
+            .run(|session| {
+                let mut statement = session.connection.prepare_cached(
+                    "SELECT NOT EXISTS(SELECT 1 FROM pragma_table_info(?1) WHERE name = ?2);",
+                )?;
+                let mut missing_columns = Vec::new();
+                for &(table, column) in REQUIRED_COLUMNS {
+                    if statement.query_row([table, column], |row| row.get(0))? {
+                        missing_columns.push((table, column));
+                    }
+                }
+                Ok::<_, rusqlite::Error>(missing_columns)
+            })
+            .await
+            .into_report()
+            .change_context(StoreError)
+            .attach_printable("could not read the database schema")?;
+
+        let mut result: Result<(), StoreError> = Ok(());
+        for (table, column) in missing_columns {
+            let report = Report::new(StoreError).attach_printable(format!(
+                "the column `{table}.{column}` is missing, the database was created by an older \
+                 version"
+            ));
+            match &mut result {
+                Err(error) => error.extend_one(report),
+                Ok(()) => result = Err(report),
+            }
+        }
+
+        result
+    }
+}
+
+#[async_trait]
+impl Transaction for SqliteStore<SqliteTransaction<'_>> {
+    async fn commit(mut self) -> Result<(), StoreError> {
+        self.connection
+            .commit()
+            .await
+            .into_report()
+            .change_context(StoreError)
+    }
+
+    async fn rollback(mut self) -> Result<(), StoreError> {
+        self.connection
+            .rollback()
+            .await
+            .into_report()
+            .change_context(StoreError)
+    }
+}
+
+impl<C> SqliteStore<C>
+where
+    C: AsConnection,
+{
+    /// Creates a new `SqliteStore` object.
+    #[must_use]
+    pub const fn new(connection: C) -> Self {
+        Self::with_query_limits(connection, QueryLimits::unlimited())
+    }
+
+    /// Creates a new `SqliteStore` object, which applies the specified [`QueryLimits`] to
+    /// structural queries.
+    #[must_use]
+    pub const fn with_query_limits(connection: C, limits: QueryLimits) -> Self {
+        Self { connection, limits }
+    }
+
+    /// Runs `operation` on a [`Session`] of the store on a thread, which is allowed to block.
+    ///
+    /// The connection is locked while `operation` runs, so every operation of the store passes
+    /// all its work in a single closure.
+    async fn run<T>(&self, operation: impl FnOnce(&Session<'_>) -> T + Send + 'static) -> T
+    where
+        T: Send + 'static,
+    {
+        let limits = self.limits;
+        run_blocking(self.connection.as_connection(), move |connection| {
+            operation(&Session { connection, limits })
+        })
+        .await
+    }
+}
+
+/// The locked connection of a [`SqliteStore`] on a thread, which is allowed to block.
+///
+/// The operations of the store, which consist of multiple statements, are implemented on the
+/// session, so they run without switching threads in between.
+struct Session<'c> {
+    connection: &'c Connection,
+    limits: QueryLimits,
+}
+
+impl Session<'_> {
+    /// Reads the ontology type identified by `uri`.
+    ///
+    /// # Errors
+    ///
+    /// - if the ontology type does not exist
+    #[tracing::instrument(level = "debug", skip(self))]
+    fn read_ontology_type<T>(&self, uri: &VersionedUri) -> Result<T, QueryError>
+    where
+        T: SqliteOntologyRecord,
+    {
+        let (schema, owned_by_id, updated_by_id, external) = self
+            .connection
+            .query_row(
+                r#"
+                    SELECT schema, owned_by_id, updated_by_id, external
+                    FROM ontology_types
+                    WHERE base_uri = ?1 AND version = ?2 AND kind = ?3;
+                "#,
+                params![
+                    uri.base_uri().as_str(),
+                    i64::from(uri.version()),
+                    T::KIND.as_str()
+                ],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .into_report()
+            .change_context(QueryError)
+            .attach_printable_lazy(|| uri.clone())?;
+
+        ontology::ontology_type_from_row(schema, owned_by_id, updated_by_id, external)
+    }
+
+    /// Validates `properties` against the constraints of the data types, which the property
+    /// types of the entity type refer to.
+    ///
+    /// # Errors
+    ///
+    /// - [`ConstraintViolation`], if a property value does not satisfy its property type
+    /// - if reading the required ontology types failed
+    ///
+    /// [`ConstraintViolation`]: crate::ontology::ConstraintViolation
+    #[tracing::instrument(level = "debug", skip(self, properties))]
+    fn validate_entity_properties(
+        &self,
+        entity_type_id: &VersionedUri,
+        properties: &EntityProperties,
+    ) -> Result<(), ConstraintViolation> {
+        let mut validator = PropertyValidator::new(entity_type_id.clone());
+        loop {
+            let entity_type_ids = validator.missing_entity_types();
+            let property_type_ids = validator.missing_property_types();
+            let data_type_ids = validator.missing_data_types();
+            if entity_type_ids.is_empty()
+                && property_type_ids.is_empty()
+                && data_type_ids.is_empty()
+            {
+                break;
+            }
+
+            for uri in entity_type_ids {
+                let entity_type: EntityTypeWithMetadata = self
+                    .read_ontology_type(&uri)
+                    .change_context(ConstraintViolation)?;
+                validator.insert_entity_type(entity_type.inner().clone())?;
+            }
+            for uri in property_type_ids {
+                let property_type: PropertyTypeWithMetadata = self
+                    .read_ontology_type(&uri)
+                    .change_context(ConstraintViolation)?;
+                validator.insert_property_type(property_type.inner().clone())?;
+            }
+            for uri in data_type_ids {
+                let data_type: DataTypeWithMetadata = self
+                    .read_ontology_type(&uri)
+                    .change_context(ConstraintViolation)?;
+                validator.insert_data_type(data_type.inner().clone())?;
+            }
+        }
+
+        validator.validate(properties)
+    }
+
+    /// Caps the `requested` depths at the maximum depths of the [`QueryLimits`].
+    ///
+    /// If a depth was capped, the `subgraph` is flagged as incomplete.
+    ///
+    /// # Errors
+    ///
+    /// - [`QueryLimitExceeded`], if a requested depth exceeds the limit and truncating subgraphs is
+    ///   disabled
+    fn limit_resolve_depths(
+        &self,
+        subgraph: &mut Subgraph,
+        requested: GraphResolveDepths,
+    ) -> Result<GraphResolveDepths, QueryError> {
+        let (depths, exceeded) = self.limits.clamp_resolve_depths(requested);
+        if exceeded {
+            if !self.limits.truncate_subgraphs() {
+                return Err(Report::new(QueryLimitExceeded)
+                    .attach_printable(format!(
+                        "maximum resolve depths: {:?}",
+                        self.limits.max_resolve_depths()
+                    ))
+                    .change_context(QueryError));
+            }
+            subgraph.incomplete = true;
+        }
+        subgraph.depths = depths;
+        Ok(depths)
+    }
+
+    /// Checks if the vertex may be added to the `subgraph` without exceeding the maximum number
+    /// of vertices of the [`QueryLimits`].
+    ///
+    /// Returns `true` if the vertex is already contained in the subgraph or if the limit is not
+    /// reached yet. Otherwise, `false` is returned and the subgraph is flagged as incomplete.
+    ///
+    /// # Errors
+    ///
+    /// - [`QueryLimitExceeded`], if the limit is reached and truncating subgraphs is disabled
+    fn reserve_vertex<R: Record>(
+        &self,
+        subgraph: &mut Subgraph,
+        vertex_id: &R::VertexId,
+    ) -> Result<bool, QueryError> {
+        let Some(max_vertices) = self.limits.max_vertices() else {
+            return Ok(true);
+        };
+
+        if subgraph.vertices.len() < max_vertices
+            || matches!(
+                vertex_id.subgraph_vertex_entry(subgraph),
+                RawEntryMut::Occupied(_)
+            )
+        {
+            return Ok(true);
+        }
+
+        if !self.limits.truncate_subgraphs() {
+            return Err(Report::new(QueryLimitExceeded)
+                .attach_printable(format!("maximum number of vertices: {max_vertices}"))
+                .change_context(QueryError));
+        }
+
+        subgraph.incomplete = true;
+        Ok(false)
+    }
+
+    /// Checks if the specified [`BaseUri`] exists in the database.
+    ///
+    /// # Errors
+    ///
+    /// - if checking for the [`BaseUri`] failed.
+    #[tracing::instrument(level = "debug", skip(self))]
+    fn contains_base_uri(&self, base_uri: &BaseUri) -> Result<bool, QueryError> {
+        self.connection
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM ontology_types WHERE base_uri = ?1);",
+                [base_uri.as_str()],
+                |row| row.get(0),
+            )
+            .into_report()
+            .change_context(QueryError)
+            .attach_printable_lazy(|| base_uri.clone())
+    }
+
+    /// Inserts the specified ontology type.
+    ///
+    /// A new version id is created for the [`VersionedUri`] of the type.
+    ///
+    /// # Errors
+    ///
+    /// - If the [`BaseUri`] already exists
+    #[tracing::instrument(level = "info", skip(self, database_type))]
+    fn create<T>(
+        &self,
+        database_type: T,
+        owned_by_id: OwnedById,
+        provenance_metadata: ProvenanceMetadata,
+    ) -> Result<(Uuid, OntologyElementMetadata), InsertionError>
+    where
+        T: OntologyType<WithMetadata: SqliteOntologyRecord>,
+    {
+        let uri = database_type.id().clone();
+
+        if self
+            .contains_base_uri(uri.base_uri())
+            .change_context(InsertionError)?
+        {
+            return Err(Report::new(BaseUriAlreadyExists)
+                .attach_printable(uri.base_uri().clone())
+                .change_context(InsertionError));
+        }
+
+        let version_id = Uuid::new_v4();
+        self.insert_with_id(version_id, database_type, owned_by_id, provenance_metadata)?;
+
+        Ok((
+            version_id,
+            OntologyElementMetadata::new(
+                OntologyTypeEditionId::from(&uri),
+                provenance_metadata,
+                owned_by_id,
+            ),
+        ))
+    }
+
+    /// Inserts a new version of the specified ontology type.
+    ///
+    /// The new version is owned by the owner of the previous version.
+    ///
+    /// # Errors
+    ///
+    /// - If the [`BaseUri`] does not already exist
+    #[tracing::instrument(level = "info", skip(self, database_type))]
+    fn update<T>(
+        &self,
+        database_type: T,
+        updated_by_id: UpdatedById,
+    ) -> Result<(Uuid, OntologyElementMetadata), UpdateError>
+    where
+        T: OntologyType<WithMetadata: SqliteOntologyRecord>,
+    {
+        let uri = database_type.id().clone();
+
+        if !self
+            .contains_base_uri(uri.base_uri())
+            .change_context(UpdateError)?
+        {
+            return Err(Report::new(BaseUriDoesNotExist)
+                .attach_printable(uri.base_uri().clone())
+                .change_context(UpdateError));
+        }
+
+        let owned_by_id = OwnedById::new(AccountId::new(
+            self.connection
+                .query_row(
+                    r#"
+                        SELECT owned_by_id
+                        FROM ontology_types
+                        WHERE base_uri = ?1
+                        ORDER BY version DESC
+                        LIMIT 1;
+                    "#,
+                    [uri.base_uri().as_str()],
+                    |row| row.get(0),
+                )
+                .into_report()
+                .change_context(UpdateError)
+                .attach_printable_lazy(|| uri.base_uri().clone())?,
+        ));
+
+        let version_id = Uuid::new_v4();
+        let provenance_metadata = ProvenanceMetadata::new(updated_by_id);
+        self.insert_with_id(version_id, database_type, owned_by_id, provenance_metadata)
+            .change_context(UpdateError)?;
+
+        Ok((
+            version_id,
+            OntologyElementMetadata::new(
+                OntologyTypeEditionId::from(&uri),
+                provenance_metadata,
+                owned_by_id,
+            ),
+        ))
+    }
+
+    /// Inserts an ontology type identified by `version_id`, and associated with an [`OwnedById`]
+    /// and [`ProvenanceMetadata`], into the database.
+    ///
+    /// # Errors
+    ///
+    /// - if inserting failed, e.g. because the [`VersionedUri`] already exists.
+    #[tracing::instrument(level = "debug", skip(self, database_type))]
+    fn insert_with_id<T>(
+        &self,
+        version_id: Uuid,
+        database_type: T,
+        owned_by_id: OwnedById,
+        provenance_metadata: ProvenanceMetadata,
+    ) -> Result<(), InsertionError>
+    where
+        T: OntologyType<WithMetadata: SqliteOntologyRecord>,
+    {
+        let uri = database_type.id().clone();
+        let value = serde_json::to_value(T::Representation::from(database_type))
+            .into_report()
+            .change_context(InsertionError)?;
+
+        self.connection
+            .execute(
+                r#"
+                    INSERT INTO ontology_types (
+                        version_id,
+                        kind,
+                        base_uri,
+                        version,
+                        schema,
+                        owned_by_id,
+                        updated_by_id,
+                        external,
+                        transaction_time_start
+                    )
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9);
+                "#,
+                params![
+                    version_id,
+                    <T::WithMetadata as SqliteOntologyRecord>::KIND.as_str(),
+                    uri.base_uri().as_str(),
+                    i64::from(uri.version()),
+                    value,
+                    owned_by_id.as_uuid(),
+                    provenance_metadata.updated_by_id().as_uuid(),
+                    provenance_metadata.is_external(),
+                    Timestamp::<TransactionTime>::now().truncate_to_microseconds(),
+                ],
+            )
+            .into_report()
+            .change_context(InsertionError)
+            .attach_printable_lazy(|| uri.clone())?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "debug", skip(self, property_type))]
+    fn insert_property_type_references(
+        &self,
+        property_type: &PropertyType,
+        version_id: Uuid,
+    ) -> Result<(), InsertionError> {
+        let property_type_ids = self
+            .reference_ids(
+                property_type
+                    .property_type_references()
+                    .into_iter()
+                    .map(PropertyTypeReference::uri),
+                OntologyKind::PropertyType,
+            )
+            .change_context(InsertionError)
+            .attach_printable("Could not find referenced property types")?;
+        self.insert_references(version_id, &property_type_ids)?;
+
+        let data_type_ids = self
+            .reference_ids(
+                property_type
+                    .data_type_references()
+                    .into_iter()
+                    .map(DataTypeReference::uri),
+                OntologyKind::DataType,
+            )
+            .change_context(InsertionError)
+            .attach_printable("Could not find referenced data types")?;
+        self.insert_references(version_id, &data_type_ids)
+    }
+
+    #[tracing::instrument(level = "debug", skip(self, entity_type))]
+    fn insert_entity_type_references(
+        &self,
+        entity_type: &EntityType,
+        version_id: Uuid,
+    ) -> Result<(), InsertionError> {
+        let property_type_ids = self
+            .reference_ids(
+                entity_type
+                    .property_type_references()
+                    .into_iter()
+                    .map(PropertyTypeReference::uri),
+                OntologyKind::PropertyType,
+            )
+            .change_context(InsertionError)
+            .attach_printable("Could not find referenced property types")?;
+        self.insert_references(version_id, &property_type_ids)?;
+
+        let entity_type_ids = self
+            .reference_ids(
+                entity_type
+                    .link_mappings()
+                    .into_keys()
+                    .chain(
+                        entity_type
+                            .link_mappings()
+                            .into_values()
+                            .flatten()
+                            .flatten(),
+                    )
+                    .chain(entity_type.inherits_from().all_of())
+                    .map(EntityTypeReference::uri),
+                OntologyKind::EntityType,
+            )
+            .change_context(InsertionError)
+            .attach_printable("Could not find referenced entity types")?;
+        self.insert_references(version_id, &entity_type_ids)
+    }
+
+    fn insert_references(
+        &self,
+        source_version_id: Uuid,
+        target_version_ids: &[Uuid],
+    ) -> Result<(), InsertionError> {
+        let mut statement = self
+            .connection
+            .prepare_cached(
+                r#"
+                    INSERT INTO ontology_type_references (source_version_id, target_version_id)
+                    VALUES (?1, ?2);
+                "#,
+            )
+            .into_report()
+            .change_context(InsertionError)?;
+        for target_version_id in target_version_ids {
+            statement
+                .execute([source_version_id, *target_version_id])
+                .into_report()
+                .change_context(InsertionError)?;
+        }
+        Ok(())
+    }
+
+    fn reference_ids<'u>(
+        &self,
+        uris: impl IntoIterator<Item = &'u VersionedUri>,
+        kind: OntologyKind,
+    ) -> Result<Vec<Uuid>, QueryError> {
+        uris.into_iter()
+            .map(|uri| self.version_id_by_uri(uri, kind))
+            .collect()
+    }
+
+    /// Fetches the version id of the specified [`VersionedUri`].
+    ///
+    /// # Errors:
+    ///
+    /// - if the ontology type of the specified `kind` referred to by `uri` does not exist.
+    #[tracing::instrument(level = "debug", skip(self))]
+    fn version_id_by_uri(
+        &self,
+        uri: &VersionedUri,
+        kind: OntologyKind,
+    ) -> Result<Uuid, QueryError> {
+        self.connection
+            .query_row(
+                r#"
+                    SELECT version_id
+                    FROM ontology_types
+                    WHERE base_uri = ?1 AND version = ?2 AND kind = ?3;
+                "#,
+                params![
+                    uri.base_uri().as_str(),
+                    i64::from(uri.version()),
+                    kind.as_str()
+                ],
+                |row| row.get(0),
+            )
+            .into_report()
+            .change_context(QueryError)
+            .attach_printable_lazy(|| uri.clone())
+    }
+}
+
+#[async_trait]
+impl<C: AsConnection> AccountStore for SqliteStore<C> {
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn insert_account_id(&mut self, account_id: AccountId) -> Result<(), InsertionError> {
+        let _timer = metrics::store_operation("create", "accounts");
+
+        self.run(move |session| {
+            session
+                .connection
+                .execute("INSERT INTO accounts (account_id) VALUES (?1);", [
+                    account_id.as_uuid(),
+                ])
+        })
+        .await
+        .into_report()
+        .change_context(InsertionError)
+        .attach_printable(account_id)?;
+
+        Ok(())
+    }
+}
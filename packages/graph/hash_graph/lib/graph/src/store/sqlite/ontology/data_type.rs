@@ -0,0 +1,184 @@
+use async_trait::async_trait;
+use error_stack::{Result, ResultExt};
+use type_system::DataType;
+
+use crate::{
+    identifier::ontology::OntologyTypeEditionId,
+    metrics,
+    ontology::{DataTypeConstraints, DataTypeWithMetadata, OntologyElementMetadata},
+    provenance::{OwnedById, ProvenanceMetadata, UpdatedById},
+    store::{
+        postgres::{DependencyContext, DependencyStatus},
+        sqlite::{
+            ontology::get_or_read_ontology_type, query::SelectStatement, AsConnection, Session,
+        },
+        DataTypeStore, InsertionError, QueryError, Record, SqliteStore, Store, Transaction,
+        UpdateError,
+    },
+    subgraph::{edges::GraphResolveDepths, query::StructuralQuery, Subgraph},
+};
+
+impl<C: AsConnection> SqliteStore<C> {
+    /// Inserts the [`DataType`] in a new transaction.
+    #[tracing::instrument(level = "debug", skip(self, data_type))]
+    async fn insert_data_type(
+        &mut self,
+        data_type: DataType,
+        owned_by_id: OwnedById,
+        provenance_metadata: ProvenanceMetadata,
+    ) -> Result<OntologyElementMetadata, InsertionError> {
+        DataTypeConstraints::from_data_type(&data_type).change_context(InsertionError)?;
+
+        let transaction = self.transaction().await.change_context(InsertionError)?;
+
+        let (_, metadata) = transaction
+            .run(move |session| session.create(data_type, owned_by_id, provenance_metadata))
+            .await?;
+
+        transaction.commit().await.change_context(InsertionError)?;
+
+        Ok(metadata)
+    }
+}
+
+impl Session<'_> {
+    /// Internal method to read a [`DataTypeWithMetadata`] into a [`DependencyContext`].
+    ///
+    /// This is used to recursively resolve a type, so the result can be reused.
+    #[tracing::instrument(level = "trace", skip(self, dependency_context, subgraph))]
+    pub(crate) fn traverse_data_type(
+        &self,
+        data_type_id: &OntologyTypeEditionId,
+        dependency_context: &mut DependencyContext,
+        subgraph: &mut Subgraph,
+        current_resolve_depth: GraphResolveDepths,
+    ) -> Result<(), QueryError> {
+        let dependency_status = dependency_context
+            .ontology_dependency_map
+            .insert(data_type_id, current_resolve_depth);
+
+        let _data_type = match dependency_status {
+            DependencyStatus::Unresolved => {
+                if !self.reserve_vertex::<DataTypeWithMetadata>(subgraph, data_type_id)? {
+                    return Ok(());
+                }
+
+                get_or_read_ontology_type::<DataTypeWithMetadata>(self, subgraph, data_type_id)?
+            }
+            DependencyStatus::Resolved => return Ok(()),
+        };
+
+        // Data types currently have no references to other types, so there is nothing to traverse
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<C: AsConnection> DataTypeStore for SqliteStore<C> {
+    #[tracing::instrument(level = "info", skip(self, data_type))]
+    async fn create_data_type(
+        &mut self,
+        data_type: DataType,
+        owned_by_id: OwnedById,
+        updated_by_id: UpdatedById,
+    ) -> Result<OntologyElementMetadata, InsertionError> {
+        let _timer = metrics::store_operation("create", "data_types");
+
+        self.insert_data_type(
+            data_type,
+            owned_by_id,
+            ProvenanceMetadata::new(updated_by_id),
+        )
+        .await
+    }
+
+    #[tracing::instrument(level = "info", skip(self, data_type))]
+    async fn import_data_type(
+        &mut self,
+        data_type: DataType,
+        owned_by_id: OwnedById,
+        updated_by_id: UpdatedById,
+    ) -> Result<OntologyElementMetadata, InsertionError> {
+        let _timer = metrics::store_operation("import", "data_types");
+
+        self.insert_data_type(
+            data_type,
+            owned_by_id,
+            ProvenanceMetadata::new_external(updated_by_id),
+        )
+        .await
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_data_type(
+        &self,
+        query: &StructuralQuery<DataTypeWithMetadata>,
+    ) -> Result<Subgraph, QueryError> {
+        let _timer = metrics::store_operation("get", "data_types");
+
+        let StructuralQuery {
+            ref filter,
+            graph_resolve_depths,
+            ref time_projection,
+            draft_id: _,
+        } = *query;
+
+        let mut subgraph = Subgraph::new(
+            graph_resolve_depths,
+            time_projection.clone(),
+            time_projection.clone().resolve(),
+        );
+        let statement = SelectStatement::compile(filter, &subgraph.resolved_time_projection, None);
+
+        self.run(move |session| {
+            let graph_resolve_depths =
+                session.limit_resolve_depths(&mut subgraph, graph_resolve_depths)?;
+            let mut dependency_context = DependencyContext::default();
+            let time_axis = subgraph.resolved_time_projection.time_axis();
+
+            for data_type in statement.read(session.connection)? {
+                let vertex_id = data_type.vertex_id(time_axis);
+                if !session.reserve_vertex::<DataTypeWithMetadata>(&mut subgraph, &vertex_id)? {
+                    break;
+                }
+
+                // Insert the vertex into the subgraph to avoid another lookup when traversing it
+                subgraph.insert(&vertex_id, data_type);
+
+                session.traverse_data_type(
+                    &vertex_id,
+                    &mut dependency_context,
+                    &mut subgraph,
+                    graph_resolve_depths,
+                )?;
+
+                subgraph.roots.insert(vertex_id.into());
+            }
+
+            Ok(subgraph)
+        })
+        .await
+    }
+
+    #[tracing::instrument(level = "info", skip(self, data_type))]
+    async fn update_data_type(
+        &mut self,
+        data_type: DataType,
+        updated_by_id: UpdatedById,
+    ) -> Result<OntologyElementMetadata, UpdateError> {
+        let _timer = metrics::store_operation("update", "data_types");
+
+        DataTypeConstraints::from_data_type(&data_type).change_context(UpdateError)?;
+
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        let (_, metadata) = transaction
+            .run(move |session| session.update::<DataType>(data_type, updated_by_id))
+            .await?;
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        Ok(metadata)
+    }
+}
@@ -0,0 +1,417 @@
+use async_trait::async_trait;
+use error_stack::{Result, ResultExt};
+use type_system::{EntityType, EntityTypeReference, PropertyTypeReference};
+
+use crate::{
+    identifier::ontology::OntologyTypeEditionId,
+    metrics,
+    ontology::{
+        EntityTypeWithMetadata, OntologyElementMetadata, OntologyTypeWithMetadata,
+        PropertyTypeWithMetadata,
+    },
+    provenance::{OwnedById, ProvenanceMetadata, UpdatedById},
+    store::{
+        postgres::{DependencyContext, DependencyStatus},
+        sqlite::{
+            ontology::get_or_read_ontology_type, query::SelectStatement, AsConnection, Session,
+        },
+        EntityTypeStore, InsertionError, QueryError, Record, SqliteStore, Store, Transaction,
+        UpdateError,
+    },
+    subgraph::{
+        edges::{
+            Edge, GraphResolveDepths, OntologyEdgeKind, OntologyOutwardEdges,
+            OutgoingEdgeResolveDepth, OutwardEdge,
+        },
+        query::StructuralQuery,
+        Subgraph,
+    },
+};
+
+impl<C: AsConnection> SqliteStore<C> {
+    /// Inserts the [`EntityType`] and its references in a new transaction.
+    #[tracing::instrument(level = "debug", skip(self, entity_type))]
+    async fn insert_entity_type(
+        &mut self,
+        entity_type: EntityType,
+        owned_by_id: OwnedById,
+        provenance_metadata: ProvenanceMetadata,
+    ) -> Result<OntologyElementMetadata, InsertionError> {
+        let transaction = self.transaction().await.change_context(InsertionError)?;
+
+        // This clone is currently necessary because we extract the references as we insert them.
+        // We can only insert them after the type has been created, and so we currently extract them
+        // after as well. See `insert_entity_type_references` taking `&entity_type`
+        let metadata = transaction
+            .run(move |session| -> Result<_, InsertionError> {
+                let (version_id, metadata) =
+                    session.create(entity_type.clone(), owned_by_id, provenance_metadata)?;
+
+                session
+                    .insert_entity_type_references(&entity_type, version_id)
+                    .change_context(InsertionError)
+                    .attach_printable_lazy(|| {
+                        format!(
+                            "could not insert references for entity type: {}",
+                            entity_type.id()
+                        )
+                    })
+                    .attach_lazy(|| entity_type.clone())?;
+
+                Ok(metadata)
+            })
+            .await?;
+
+        transaction.commit().await.change_context(InsertionError)?;
+
+        Ok(metadata)
+    }
+}
+
+impl Session<'_> {
+    /// Internal method to read a [`EntityTypeWithMetadata`] into a [`DependencyContext`].
+    ///
+    /// This is used to recursively resolve a type, so the result can be reused.
+    #[tracing::instrument(level = "trace", skip(self, dependency_context, subgraph))]
+    pub(crate) fn traverse_entity_type(
+        &self,
+        entity_type_id: &OntologyTypeEditionId,
+        dependency_context: &mut DependencyContext,
+        subgraph: &mut Subgraph,
+        current_resolve_depth: GraphResolveDepths,
+    ) -> Result<(), QueryError> {
+        let dependency_status = dependency_context
+            .ontology_dependency_map
+            .insert(entity_type_id, current_resolve_depth);
+
+        let entity_type = match dependency_status {
+            DependencyStatus::Unresolved => {
+                if !self.reserve_vertex::<EntityTypeWithMetadata>(subgraph, entity_type_id)? {
+                    return Ok(());
+                }
+
+                get_or_read_ontology_type::<EntityTypeWithMetadata>(self, subgraph, entity_type_id)?
+            }
+            DependencyStatus::Resolved => return Ok(()),
+        };
+
+        // Collecting references before traversing further to avoid having a shared
+        // reference to the subgraph when borrowing it mutably
+        let property_type_ref_uris = (current_resolve_depth.constrains_properties_on.outgoing > 0)
+            .then(|| {
+                entity_type
+                    .inner()
+                    .property_type_references()
+                    .into_iter()
+                    .map(PropertyTypeReference::uri)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            });
+
+        let inherits_from_type_ref_uris =
+            (current_resolve_depth.inherits_from.outgoing > 0).then(|| {
+                entity_type
+                    .inner()
+                    .inherits_from()
+                    .all_of()
+                    .iter()
+                    .map(EntityTypeReference::uri)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            });
+
+        let link_mappings = (current_resolve_depth.constrains_links_on.outgoing > 0
+            || current_resolve_depth
+                .constrains_link_destinations_on
+                .outgoing
+                > 0)
+        .then(|| {
+            entity_type
+                .inner()
+                .link_mappings()
+                .into_iter()
+                .map(|(entity_type_ref, destinations)| {
+                    (
+                        entity_type_ref.uri().clone(),
+                        destinations
+                            .into_iter()
+                            .flatten()
+                            .map(EntityTypeReference::uri)
+                            .cloned()
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        });
+
+        if let Some(property_type_ref_uris) = property_type_ref_uris {
+            for property_type_ref_uri in property_type_ref_uris {
+                if !self.reserve_vertex::<PropertyTypeWithMetadata>(
+                    subgraph,
+                    &OntologyTypeEditionId::from(&property_type_ref_uri),
+                )? {
+                    continue;
+                }
+
+                subgraph.edges.insert(Edge::Ontology {
+                    vertex_id: entity_type_id.clone(),
+                    outward_edge: OntologyOutwardEdges::ToOntology(OutwardEdge {
+                        kind: OntologyEdgeKind::ConstrainsPropertiesOn,
+                        reversed: false,
+                        right_endpoint: OntologyTypeEditionId::from(&property_type_ref_uri),
+                    }),
+                });
+
+                self.traverse_property_type(
+                    &OntologyTypeEditionId::from(&property_type_ref_uri),
+                    dependency_context,
+                    subgraph,
+                    GraphResolveDepths {
+                        constrains_properties_on: OutgoingEdgeResolveDepth {
+                            outgoing: current_resolve_depth.constrains_properties_on.outgoing - 1,
+                            ..current_resolve_depth.constrains_properties_on
+                        },
+                        ..current_resolve_depth
+                    },
+                )?;
+            }
+        }
+
+        if let Some(inherits_from_type_ref_uris) = inherits_from_type_ref_uris {
+            for inherits_from_type_ref_uri in inherits_from_type_ref_uris {
+                if !self.reserve_vertex::<EntityTypeWithMetadata>(
+                    subgraph,
+                    &OntologyTypeEditionId::from(&inherits_from_type_ref_uri),
+                )? {
+                    continue;
+                }
+
+                subgraph.edges.insert(Edge::Ontology {
+                    vertex_id: entity_type_id.clone(),
+                    outward_edge: OntologyOutwardEdges::ToOntology(OutwardEdge {
+                        kind: OntologyEdgeKind::InheritsFrom,
+                        reversed: false,
+                        right_endpoint: OntologyTypeEditionId::from(&inherits_from_type_ref_uri),
+                    }),
+                });
+
+                self.traverse_entity_type(
+                    &OntologyTypeEditionId::from(&inherits_from_type_ref_uri),
+                    dependency_context,
+                    subgraph,
+                    GraphResolveDepths {
+                        inherits_from: OutgoingEdgeResolveDepth {
+                            outgoing: current_resolve_depth.inherits_from.outgoing - 1,
+                            ..current_resolve_depth.inherits_from
+                        },
+                        ..current_resolve_depth
+                    },
+                )?;
+            }
+        }
+
+        if let Some(link_mappings) = link_mappings {
+            for (link_type_uri, destination_type_uris) in link_mappings {
+                if current_resolve_depth.constrains_links_on.outgoing > 0 {
+                    if !self.reserve_vertex::<EntityTypeWithMetadata>(
+                        subgraph,
+                        &OntologyTypeEditionId::from(&link_type_uri),
+                    )? {
+                        continue;
+                    }
+
+                    subgraph.edges.insert(Edge::Ontology {
+                        vertex_id: entity_type_id.clone(),
+                        outward_edge: OntologyOutwardEdges::ToOntology(OutwardEdge {
+                            kind: OntologyEdgeKind::ConstrainsLinksOn,
+                            reversed: false,
+                            right_endpoint: OntologyTypeEditionId::from(&link_type_uri),
+                        }),
+                    });
+
+                    self.traverse_entity_type(
+                        &OntologyTypeEditionId::from(&link_type_uri),
+                        dependency_context,
+                        subgraph,
+                        GraphResolveDepths {
+                            constrains_links_on: OutgoingEdgeResolveDepth {
+                                outgoing: current_resolve_depth.constrains_links_on.outgoing - 1,
+                                ..current_resolve_depth.constrains_links_on
+                            },
+                            ..current_resolve_depth
+                        },
+                    )?;
+
+                    if current_resolve_depth
+                        .constrains_link_destinations_on
+                        .outgoing
+                        > 0
+                    {
+                        for destination_type_uri in destination_type_uris {
+                            if !self.reserve_vertex::<EntityTypeWithMetadata>(
+                                subgraph,
+                                &OntologyTypeEditionId::from(&destination_type_uri),
+                            )? {
+                                continue;
+                            }
+
+                            subgraph.edges.insert(Edge::Ontology {
+                                vertex_id: entity_type_id.clone(),
+                                outward_edge: OntologyOutwardEdges::ToOntology(OutwardEdge {
+                                    kind: OntologyEdgeKind::ConstrainsLinkDestinationsOn,
+                                    reversed: false,
+                                    right_endpoint: OntologyTypeEditionId::from(
+                                        &destination_type_uri,
+                                    ),
+                                }),
+                            });
+
+                            self.traverse_entity_type(
+                                &OntologyTypeEditionId::from(&destination_type_uri),
+                                dependency_context,
+                                subgraph,
+                                GraphResolveDepths {
+                                    constrains_link_destinations_on: OutgoingEdgeResolveDepth {
+                                        outgoing: current_resolve_depth
+                                            .constrains_link_destinations_on
+                                            .outgoing
+                                            - 1,
+                                        ..current_resolve_depth.constrains_link_destinations_on
+                                    },
+                                    ..current_resolve_depth
+                                },
+                            )?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<C: AsConnection> EntityTypeStore for SqliteStore<C> {
+    #[tracing::instrument(level = "info", skip(self, entity_type))]
+    async fn create_entity_type(
+        &mut self,
+        entity_type: EntityType,
+        owned_by_id: OwnedById,
+        updated_by_id: UpdatedById,
+    ) -> Result<OntologyElementMetadata, InsertionError> {
+        let _timer = metrics::store_operation("create", "entity_types");
+
+        self.insert_entity_type(
+            entity_type,
+            owned_by_id,
+            ProvenanceMetadata::new(updated_by_id),
+        )
+        .await
+    }
+
+    #[tracing::instrument(level = "info", skip(self, entity_type))]
+    async fn import_entity_type(
+        &mut self,
+        entity_type: EntityType,
+        owned_by_id: OwnedById,
+        updated_by_id: UpdatedById,
+    ) -> Result<OntologyElementMetadata, InsertionError> {
+        let _timer = metrics::store_operation("import", "entity_types");
+
+        self.insert_entity_type(
+            entity_type,
+            owned_by_id,
+            ProvenanceMetadata::new_external(updated_by_id),
+        )
+        .await
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_entity_type(
+        &self,
+        query: &StructuralQuery<EntityTypeWithMetadata>,
+    ) -> Result<Subgraph, QueryError> {
+        let _timer = metrics::store_operation("get", "entity_types");
+
+        let StructuralQuery {
+            ref filter,
+            graph_resolve_depths,
+            ref time_projection,
+            draft_id: _,
+        } = *query;
+
+        let mut subgraph = Subgraph::new(
+            graph_resolve_depths,
+            time_projection.clone(),
+            time_projection.clone().resolve(),
+        );
+        let statement = SelectStatement::compile(filter, &subgraph.resolved_time_projection, None);
+
+        self.run(move |session| {
+            let graph_resolve_depths =
+                session.limit_resolve_depths(&mut subgraph, graph_resolve_depths)?;
+            let mut dependency_context = DependencyContext::default();
+            let time_axis = subgraph.resolved_time_projection.time_axis();
+
+            for entity_type in statement.read(session.connection)? {
+                let vertex_id = entity_type.vertex_id(time_axis);
+                if !session.reserve_vertex::<EntityTypeWithMetadata>(&mut subgraph, &vertex_id)? {
+                    break;
+                }
+
+                // Insert the vertex into the subgraph to avoid another lookup when traversing it
+                subgraph.insert(&vertex_id, entity_type);
+
+                session.traverse_entity_type(
+                    &vertex_id,
+                    &mut dependency_context,
+                    &mut subgraph,
+                    graph_resolve_depths,
+                )?;
+
+                subgraph.roots.insert(vertex_id.into());
+            }
+
+            Ok(subgraph)
+        })
+        .await
+    }
+
+    #[tracing::instrument(level = "info", skip(self, entity_type))]
+    async fn update_entity_type(
+        &mut self,
+        entity_type: EntityType,
+        updated_by: UpdatedById,
+    ) -> Result<OntologyElementMetadata, UpdateError> {
+        let _timer = metrics::store_operation("update", "entity_types");
+
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        // This clone is currently necessary because we extract the references as we insert them.
+        // We can only insert them after the type has been created, and so we currently extract them
+        // after as well. See `insert_entity_type_references` taking `&entity_type`
+        let metadata = transaction
+            .run(move |session| -> Result<_, UpdateError> {
+                let (version_id, metadata) =
+                    session.update::<EntityType>(entity_type.clone(), updated_by)?;
+
+                session
+                    .insert_entity_type_references(&entity_type, version_id)
+                    .change_context(UpdateError)
+                    .attach_printable_lazy(|| {
+                        format!(
+                            "could not insert references for entity type: {}",
+                            entity_type.id()
+                        )
+                    })
+                    .attach_lazy(|| entity_type.clone())?;
+
+                Ok(metadata)
+            })
+            .await?;
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        Ok(metadata)
+    }
+}
@@ -0,0 +1,185 @@
+mod data_type;
+mod entity_type;
+mod property_type;
+
+use std::collections::hash_map::RawEntryMut;
+
+use async_trait::async_trait;
+use error_stack::{IntoReport, Result, ResultExt};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ValueRef};
+use type_system::uri::VersionedUri;
+use uuid::Uuid;
+
+use crate::{
+    identifier::{account::AccountId, ontology::OntologyTypeEditionId, time::TimeProjection},
+    metrics,
+    ontology::{
+        DataTypeWithMetadata, EntityTypeWithMetadata, OntologyElementMetadata, OntologyType,
+        OntologyTypeWithMetadata, PropertyTypeWithMetadata,
+    },
+    provenance::{OwnedById, ProvenanceMetadata, UpdatedById},
+    store::{
+        crud::Read,
+        query::Filter,
+        sqlite::{
+            query::{SelectStatement, SqliteQueryPath},
+            AsConnection, Session,
+        },
+        QueryError, SqliteStore,
+    },
+    subgraph::{Subgraph, SubgraphIndex},
+};
+
+/// The kind of an ontology type, all kinds are stored in the same table.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum OntologyKind {
+    DataType,
+    PropertyType,
+    EntityType,
+}
+
+impl OntologyKind {
+    /// Returns the value stored in the `kind` column.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::DataType => "data_type",
+            Self::PropertyType => "property_type",
+            Self::EntityType => "entity_type",
+        }
+    }
+
+    /// Returns the name of the table, which stores this kind in the Postgres store.
+    ///
+    /// This is used to report the same metrics as the Postgres store.
+    #[must_use]
+    pub const fn table(self) -> &'static str {
+        match self {
+            Self::DataType => "data_types",
+            Self::PropertyType => "property_types",
+            Self::EntityType => "entity_types",
+        }
+    }
+}
+
+impl FromSql for OntologyKind {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value.as_str()? {
+            "data_type" => Ok(Self::DataType),
+            "property_type" => Ok(Self::PropertyType),
+            "entity_type" => Ok(Self::EntityType),
+            kind => Err(FromSqlError::Other(
+                format!("unknown ontology kind `{kind}`").into(),
+            )),
+        }
+    }
+}
+
+/// An ontology type, which is stored in the `ontology_types` table.
+pub trait SqliteOntologyRecord:
+    OntologyTypeWithMetadata<VertexId = OntologyTypeEditionId> + Send + Sync + 'static
+{
+    const KIND: OntologyKind;
+}
+
+impl SqliteOntologyRecord for DataTypeWithMetadata {
+    const KIND: OntologyKind = OntologyKind::DataType;
+}
+
+impl SqliteOntologyRecord for PropertyTypeWithMetadata {
+    const KIND: OntologyKind = OntologyKind::PropertyType;
+}
+
+impl SqliteOntologyRecord for EntityTypeWithMetadata {
+    const KIND: OntologyKind = OntologyKind::EntityType;
+}
+
+/// Creates an ontology type from the columns of a row in the `ontology_types` table.
+///
+/// # Errors
+///
+/// - if the schema is not a valid ontology type
+pub fn ontology_type_from_row<T>(
+    schema: serde_json::Value,
+    owned_by_id: Uuid,
+    updated_by_id: Uuid,
+    external: bool,
+) -> Result<T, QueryError>
+where
+    T: OntologyTypeWithMetadata,
+{
+    let record_repr: <T::OntologyType as OntologyType>::Representation =
+        serde_json::from_value(schema)
+            .into_report()
+            .change_context(QueryError)?;
+    let record = T::OntologyType::try_from(record_repr)
+        .into_report()
+        .change_context(QueryError)?;
+    let updated_by_id = UpdatedById::new(AccountId::new(updated_by_id));
+    let provenance_metadata = if external {
+        ProvenanceMetadata::new_external(updated_by_id)
+    } else {
+        ProvenanceMetadata::new(updated_by_id)
+    };
+    let edition_id = OntologyTypeEditionId::from(record.id());
+
+    Ok(T::new(
+        record,
+        OntologyElementMetadata::new(
+            edition_id,
+            provenance_metadata,
+            OwnedById::new(AccountId::new(owned_by_id)),
+        ),
+    ))
+}
+
+/// Looks up an ontology type in the subgraph or reads it and inserts it if it is not yet in the
+/// subgraph.
+///
+/// # Errors
+///
+/// - if the ontology type does not exist
+fn get_or_read_ontology_type<'r, T>(
+    session: &Session<'_>,
+    subgraph: &'r mut Subgraph,
+    vertex_id: &OntologyTypeEditionId,
+) -> Result<&'r T, QueryError>
+where
+    T: SqliteOntologyRecord,
+{
+    Ok(
+        match <OntologyTypeEditionId as SubgraphIndex<T>>::subgraph_vertex_entry(
+            vertex_id, subgraph,
+        ) {
+            RawEntryMut::Occupied(entry) => entry.into_mut(),
+            RawEntryMut::Vacant(entry) => {
+                entry
+                    .insert(
+                        vertex_id.clone(),
+                        session.read_ontology_type(&VersionedUri::from(vertex_id))?,
+                    )
+                    .1
+            }
+        },
+    )
+}
+
+#[async_trait]
+impl<C: AsConnection, T> Read<T> for SqliteStore<C>
+where
+    T: SqliteOntologyRecord,
+    for<'p> T::QueryPath<'p>: SqliteQueryPath,
+{
+    #[tracing::instrument(level = "info", skip(self, filter))]
+    async fn read(
+        &self,
+        filter: &Filter<T>,
+        time_projection: &TimeProjection,
+    ) -> Result<Vec<T>, QueryError> {
+        let _timer = metrics::store_operation("read", T::KIND.table());
+
+        let statement = SelectStatement::compile(filter, time_projection, None);
+        self.run(move |session| statement.read(session.connection))
+            .await
+    }
+}
@@ -0,0 +1,320 @@
+use async_trait::async_trait;
+use error_stack::{Result, ResultExt};
+use type_system::{DataTypeReference, PropertyType, PropertyTypeReference};
+
+use crate::{
+    identifier::ontology::OntologyTypeEditionId,
+    metrics,
+    ontology::{
+        DataTypeWithMetadata, OntologyElementMetadata, OntologyTypeWithMetadata,
+        PropertyTypeWithMetadata,
+    },
+    provenance::{OwnedById, ProvenanceMetadata, UpdatedById},
+    store::{
+        postgres::{DependencyContext, DependencyStatus},
+        sqlite::{
+            ontology::get_or_read_ontology_type, query::SelectStatement, AsConnection, Session,
+        },
+        InsertionError, PropertyTypeStore, QueryError, Record, SqliteStore, Store, Transaction,
+        UpdateError,
+    },
+    subgraph::{
+        edges::{
+            Edge, GraphResolveDepths, OntologyEdgeKind, OntologyOutwardEdges,
+            OutgoingEdgeResolveDepth, OutwardEdge,
+        },
+        query::StructuralQuery,
+        Subgraph,
+    },
+};
+
+impl<C: AsConnection> SqliteStore<C> {
+    /// Inserts the [`PropertyType`] and its references in a new transaction.
+    #[tracing::instrument(level = "debug", skip(self, property_type))]
+    async fn insert_property_type(
+        &mut self,
+        property_type: PropertyType,
+        owned_by_id: OwnedById,
+        provenance_metadata: ProvenanceMetadata,
+    ) -> Result<OntologyElementMetadata, InsertionError> {
+        let transaction = self.transaction().await.change_context(InsertionError)?;
+
+        // This clone is currently necessary because we extract the references as we insert them.
+        // We can only insert them after the type has been created, and so we currently extract them
+        // after as well. See `insert_property_type_references` taking `&property_type`
+        let metadata = transaction
+            .run(move |session| -> Result<_, InsertionError> {
+                let (version_id, metadata) =
+                    session.create(property_type.clone(), owned_by_id, provenance_metadata)?;
+
+                session
+                    .insert_property_type_references(&property_type, version_id)
+                    .change_context(InsertionError)
+                    .attach_printable_lazy(|| {
+                        format!(
+                            "could not insert references for property type: {}",
+                            property_type.id()
+                        )
+                    })
+                    .attach_lazy(|| property_type.clone())?;
+
+                Ok(metadata)
+            })
+            .await?;
+
+        transaction.commit().await.change_context(InsertionError)?;
+
+        Ok(metadata)
+    }
+}
+
+impl Session<'_> {
+    /// Internal method to read a [`PropertyTypeWithMetadata`] into a [`DependencyContext`].
+    ///
+    /// This is used to recursively resolve a type, so the result can be reused.
+    #[tracing::instrument(level = "trace", skip(self, dependency_context, subgraph))]
+    pub(crate) fn traverse_property_type(
+        &self,
+        property_type_id: &OntologyTypeEditionId,
+        dependency_context: &mut DependencyContext,
+        subgraph: &mut Subgraph,
+        current_resolve_depth: GraphResolveDepths,
+    ) -> Result<(), QueryError> {
+        let dependency_status = dependency_context
+            .ontology_dependency_map
+            .insert(property_type_id, current_resolve_depth);
+
+        let property_type = match dependency_status {
+            DependencyStatus::Unresolved => {
+                if !self.reserve_vertex::<PropertyTypeWithMetadata>(subgraph, property_type_id)? {
+                    return Ok(());
+                }
+
+                get_or_read_ontology_type::<PropertyTypeWithMetadata>(
+                    self,
+                    subgraph,
+                    property_type_id,
+                )?
+            }
+            DependencyStatus::Resolved => return Ok(()),
+        };
+
+        // Collecting references before traversing further to avoid having a shared
+        // reference to the subgraph when borrowing it mutably
+        let data_type_ref_uris =
+            (current_resolve_depth.constrains_values_on.outgoing > 0).then(|| {
+                property_type
+                    .inner()
+                    .data_type_references()
+                    .into_iter()
+                    .map(DataTypeReference::uri)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            });
+
+        let property_type_ref_uris = (current_resolve_depth.constrains_properties_on.outgoing > 0)
+            .then(|| {
+                property_type
+                    .inner()
+                    .property_type_references()
+                    .into_iter()
+                    .map(PropertyTypeReference::uri)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            });
+
+        if let Some(data_type_ref_uris) = data_type_ref_uris {
+            for data_type_ref in data_type_ref_uris {
+                if !self.reserve_vertex::<DataTypeWithMetadata>(
+                    subgraph,
+                    &OntologyTypeEditionId::from(&data_type_ref),
+                )? {
+                    continue;
+                }
+
+                subgraph.edges.insert(Edge::Ontology {
+                    vertex_id: property_type_id.clone(),
+                    outward_edge: OntologyOutwardEdges::ToOntology(OutwardEdge {
+                        kind: OntologyEdgeKind::ConstrainsValuesOn,
+                        reversed: false,
+                        right_endpoint: OntologyTypeEditionId::from(&data_type_ref),
+                    }),
+                });
+
+                self.traverse_data_type(
+                    &OntologyTypeEditionId::from(&data_type_ref),
+                    dependency_context,
+                    subgraph,
+                    GraphResolveDepths {
+                        constrains_values_on: OutgoingEdgeResolveDepth {
+                            outgoing: current_resolve_depth.constrains_values_on.outgoing - 1,
+                            ..current_resolve_depth.constrains_values_on
+                        },
+                        ..current_resolve_depth
+                    },
+                )?;
+            }
+        }
+
+        if let Some(property_type_ref_uris) = property_type_ref_uris {
+            for property_type_ref_uri in property_type_ref_uris {
+                if !self.reserve_vertex::<PropertyTypeWithMetadata>(
+                    subgraph,
+                    &OntologyTypeEditionId::from(&property_type_ref_uri),
+                )? {
+                    continue;
+                }
+
+                subgraph.edges.insert(Edge::Ontology {
+                    vertex_id: property_type_id.clone(),
+                    outward_edge: OntologyOutwardEdges::ToOntology(OutwardEdge {
+                        kind: OntologyEdgeKind::ConstrainsPropertiesOn,
+                        reversed: false,
+                        right_endpoint: OntologyTypeEditionId::from(&property_type_ref_uri),
+                    }),
+                });
+
+                self.traverse_property_type(
+                    &OntologyTypeEditionId::from(&property_type_ref_uri),
+                    dependency_context,
+                    subgraph,
+                    GraphResolveDepths {
+                        constrains_properties_on: OutgoingEdgeResolveDepth {
+                            outgoing: current_resolve_depth.constrains_properties_on.outgoing - 1,
+                            ..current_resolve_depth.constrains_properties_on
+                        },
+                        ..current_resolve_depth
+                    },
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<C: AsConnection> PropertyTypeStore for SqliteStore<C> {
+    #[tracing::instrument(level = "info", skip(self, property_type))]
+    async fn create_property_type(
+        &mut self,
+        property_type: PropertyType,
+        owned_by_id: OwnedById,
+        updated_by_id: UpdatedById,
+    ) -> Result<OntologyElementMetadata, InsertionError> {
+        let _timer = metrics::store_operation("create", "property_types");
+
+        self.insert_property_type(
+            property_type,
+            owned_by_id,
+            ProvenanceMetadata::new(updated_by_id),
+        )
+        .await
+    }
+
+    #[tracing::instrument(level = "info", skip(self, property_type))]
+    async fn import_property_type(
+        &mut self,
+        property_type: PropertyType,
+        owned_by_id: OwnedById,
+        updated_by_id: UpdatedById,
+    ) -> Result<OntologyElementMetadata, InsertionError> {
+        let _timer = metrics::store_operation("import", "property_types");
+
+        self.insert_property_type(
+            property_type,
+            owned_by_id,
+            ProvenanceMetadata::new_external(updated_by_id),
+        )
+        .await
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_property_type(
+        &self,
+        query: &StructuralQuery<PropertyTypeWithMetadata>,
+    ) -> Result<Subgraph, QueryError> {
+        let _timer = metrics::store_operation("get", "property_types");
+
+        let StructuralQuery {
+            ref filter,
+            graph_resolve_depths,
+            ref time_projection,
+            draft_id: _,
+        } = *query;
+
+        let mut subgraph = Subgraph::new(
+            graph_resolve_depths,
+            time_projection.clone(),
+            time_projection.clone().resolve(),
+        );
+        let statement = SelectStatement::compile(filter, &subgraph.resolved_time_projection, None);
+
+        self.run(move |session| {
+            let graph_resolve_depths =
+                session.limit_resolve_depths(&mut subgraph, graph_resolve_depths)?;
+            let mut dependency_context = DependencyContext::default();
+            let time_axis = subgraph.resolved_time_projection.time_axis();
+
+            for property_type in statement.read(session.connection)? {
+                let vertex_id = property_type.vertex_id(time_axis);
+                if !session.reserve_vertex::<PropertyTypeWithMetadata>(&mut subgraph, &vertex_id)? {
+                    break;
+                }
+
+                // Insert the vertex into the subgraph to avoid another lookup when traversing it
+                subgraph.insert(&vertex_id, property_type);
+
+                session.traverse_property_type(
+                    &vertex_id,
+                    &mut dependency_context,
+                    &mut subgraph,
+                    graph_resolve_depths,
+                )?;
+
+                subgraph.roots.insert(vertex_id.into());
+            }
+
+            Ok(subgraph)
+        })
+        .await
+    }
+
+    #[tracing::instrument(level = "info", skip(self, property_type))]
+    async fn update_property_type(
+        &mut self,
+        property_type: PropertyType,
+        updated_by: UpdatedById,
+    ) -> Result<OntologyElementMetadata, UpdateError> {
+        let _timer = metrics::store_operation("update", "property_types");
+
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        // This clone is currently necessary because we extract the references as we insert them.
+        // We can only insert them after the type has been created, and so we currently extract them
+        // after as well. See `insert_property_type_references` taking `&property_type`
+        let metadata = transaction
+            .run(move |session| -> Result<_, UpdateError> {
+                let (version_id, metadata) =
+                    session.update::<PropertyType>(property_type.clone(), updated_by)?;
+
+                session
+                    .insert_property_type_references(&property_type, version_id)
+                    .change_context(UpdateError)
+                    .attach_printable_lazy(|| {
+                        format!(
+                            "could not insert references for property type: {}",
+                            property_type.id()
+                        )
+                    })
+                    .attach_lazy(|| property_type.clone())?;
+
+                Ok(metadata)
+            })
+            .await?;
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        Ok(metadata)
+    }
+}
@@ -0,0 +1,174 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use bb8_postgres::bb8::{ErrorSink, ManageConnection, Pool, PooledConnection, RunError};
+use error_stack::{IntoReport, Result, ResultExt};
+use rusqlite::{Connection, Error, OpenFlags};
+
+use crate::store::{
+    sqlite::{run_blocking, spawn_blocking, AsConnection, SqliteConnection, SCHEMA},
+    DatabaseConnectionInfo, QueryLimits, SqliteStore, StoreError, StorePool,
+};
+
+/// The time a connection waits for the lock of the database before returning an error.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Opens connections to a SQLite database file for a connection pool.
+///
+/// Only the settings, which are local to a connection, are applied when a connection is opened.
+/// The schema and the journal mode are stored in the database file, so they are applied once when
+/// the [`SqliteStorePool`] is created.
+#[derive(Debug, Clone)]
+pub struct SqliteConnectionManager {
+    path: PathBuf,
+}
+
+impl SqliteConnectionManager {
+    fn open(path: &Path) -> rusqlite::Result<Connection> {
+        let connection = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_WRITE
+                | OpenFlags::SQLITE_OPEN_CREATE
+                | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        connection.busy_timeout(BUSY_TIMEOUT)?;
+        connection.pragma_update(None, "foreign_keys", true)?;
+        Ok(connection)
+    }
+}
+
+#[async_trait]
+impl ManageConnection for SqliteConnectionManager {
+    type Connection = SqliteConnection;
+    type Error = Error;
+
+    async fn connect(&self) -> rusqlite::Result<Self::Connection> {
+        let path = self.path.clone();
+        Ok(SqliteConnection::new(
+            spawn_blocking(move || Self::open(&path)).await?,
+        ))
+    }
+
+    async fn is_valid(&self, connection: &mut Self::Connection) -> rusqlite::Result<()> {
+        run_blocking(connection.as_connection(), |connection| {
+            connection.execute_batch("SELECT 1;")
+        })
+        .await
+    }
+
+    fn has_broken(&self, _connection: &mut Self::Connection) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct ErrorLogger;
+
+impl ErrorSink<Error> for ErrorLogger {
+    fn sink(&self, error: Error) {
+        tracing::error!(%error, "Store connection pool has encountered an error");
+    }
+
+    fn boxed_clone(&self) -> Box<dyn ErrorSink<Error>> {
+        Box::new(*self)
+    }
+}
+
+/// A pool of connections to a SQLite database file.
+///
+/// SQLite serializes concurrent transactions with the lock of the database, readers are not
+/// blocked by a writer as the database is operated in write-ahead logging mode.
+pub struct SqliteStorePool {
+    pool: Pool<SqliteConnectionManager>,
+    query_limits: QueryLimits,
+}
+
+impl SqliteStorePool {
+    /// Creates a new `SqliteStorePool` for the database file at the path specified as `database`
+    /// in `db_info`.
+    ///
+    /// The file is created if it does not exist yet and the schema is applied to it.
+    ///
+    /// # Errors
+    ///
+    /// - if the database could not be opened
+    /// - if the schema could not be applied
+    pub async fn new(db_info: &DatabaseConnectionInfo) -> Result<Self, StoreError> {
+        Self::with_query_limits(db_info, QueryLimits::default()).await
+    }
+
+    /// Creates a new `SqliteStorePool`, which applies the [`QueryLimits`] to the acquired stores.
+    ///
+    /// # Errors
+    ///
+    /// - if the database could not be opened
+    /// - if the schema could not be applied
+    pub async fn with_query_limits(
+        db_info: &DatabaseConnectionInfo,
+        query_limits: QueryLimits,
+    ) -> Result<Self, StoreError> {
+        let manager = SqliteConnectionManager {
+            path: PathBuf::from(db_info.database()),
+        };
+
+        tracing::debug!(path=%manager.path.display(), "Applying schema to SQLite database");
+        let connection = manager
+            .connect()
+            .await
+            .into_report()
+            .change_context(StoreError)
+            .attach_printable_lazy(|| manager.path.display().to_string())?;
+        run_blocking(connection.as_connection(), |connection| {
+            connection.pragma_update(None, "journal_mode", "WAL")?;
+            connection.execute_batch(SCHEMA)
+        })
+        .await
+        .into_report()
+        .change_context(StoreError)
+        .attach_printable("could not apply the schema")?;
+
+        tracing::debug!(path=%manager.path.display(), "Creating connection pool to SQLite");
+        let pool = Pool::builder()
+            .error_sink(Box::new(ErrorLogger))
+            .build(manager)
+            .await
+            .into_report()
+            .change_context(StoreError)?;
+
+        Ok(Self { pool, query_limits })
+    }
+}
+
+#[async_trait]
+impl StorePool for SqliteStorePool {
+    type Error = RunError<Error>;
+    type Store<'pool> = SqliteStore<PooledConnection<'pool, SqliteConnectionManager>>;
+
+    async fn acquire(&self) -> Result<Self::Store<'_>, Self::Error> {
+        Ok(SqliteStore::with_query_limits(
+            self.pool.get().await?,
+            self.query_limits,
+        ))
+    }
+
+    async fn acquire_owned(&self) -> Result<Self::Store<'static>, Self::Error> {
+        Ok(SqliteStore::with_query_limits(
+            self.pool.get_owned().await?,
+            self.query_limits,
+        ))
+    }
+}
+
+impl AsConnection for PooledConnection<'_, SqliteConnectionManager> {
+    fn as_connection(&self) -> &Arc<Mutex<Connection>> {
+        (**self).as_connection()
+    }
+
+    fn transaction_depth(&self) -> usize {
+        0
+    }
+}
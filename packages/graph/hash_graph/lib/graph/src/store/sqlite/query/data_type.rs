@@ -0,0 +1,26 @@
+use crate::{
+    ontology::DataTypeQueryPath,
+    store::sqlite::query::{Column, Relation, SqliteQueryPath},
+};
+
+impl SqliteQueryPath for DataTypeQueryPath {
+    fn relations(&self) -> Vec<Relation> {
+        vec![]
+    }
+
+    fn terminating_column(&self) -> Column<'static> {
+        match self {
+            Self::BaseUri => Column::BaseUri,
+            Self::Version => Column::Version,
+            Self::VersionId => Column::VersionId,
+            Self::OwnedById => Column::OwnedById,
+            Self::UpdatedById => Column::UpdatedById,
+            Self::External => Column::External,
+            Self::Schema => Column::Schema(None),
+            Self::VersionedUri => Column::Schema(Some("$id")),
+            Self::Title => Column::Schema(Some("title")),
+            Self::Type => Column::Schema(Some("type")),
+            Self::Description => Column::Schema(Some("description")),
+        }
+    }
+}
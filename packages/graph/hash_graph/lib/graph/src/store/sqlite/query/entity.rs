@@ -0,0 +1,64 @@
+use std::iter::once;
+
+use crate::{
+    knowledge::EntityQueryPath,
+    store::sqlite::query::{Column, Relation, SqliteQueryPath},
+};
+
+impl SqliteQueryPath for EntityQueryPath<'_> {
+    fn relations(&self) -> Vec<Relation> {
+        match self {
+            Self::LeftEntity(path) | Self::RightEntity(path)
+                if **path == EntityQueryPath::Uuid || **path == EntityQueryPath::OwnedById =>
+            {
+                vec![]
+            }
+            Self::Type(path) => once(Relation::EntityType).chain(path.relations()).collect(),
+            Self::LeftEntity(path) => once(Relation::LeftEntity).chain(path.relations()).collect(),
+            Self::RightEntity(path) => once(Relation::RightEntity)
+                .chain(path.relations())
+                .collect(),
+            Self::OutgoingLinks(path) => once(Relation::OutgoingLinks)
+                .chain(path.relations())
+                .collect(),
+            Self::IncomingLinks(path) => once(Relation::IncomingLinks)
+                .chain(path.relations())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    fn terminating_column(&self) -> Column<'_> {
+        match self {
+            Self::Uuid => Column::EntityUuid,
+            Self::RecordId => Column::RecordId,
+            Self::DecisionTime => Column::DecisionTime,
+            Self::TransactionTime => Column::TransactionTime,
+            Self::ProjectedTime => Column::ProjectedTime,
+            Self::Archived => Column::Archived,
+            Self::DraftId => Column::DraftId,
+            Self::Type(path) => path.terminating_column(),
+            Self::OwnedById => Column::OwnedById,
+            Self::UpdatedById => Column::UpdatedById,
+            Self::LeftEntity(path) if **path == EntityQueryPath::Uuid => Column::LeftEntityUuid,
+            Self::LeftEntity(path) if **path == EntityQueryPath::OwnedById => {
+                Column::LeftEntityOwnedById
+            }
+            Self::RightEntity(path) if **path == EntityQueryPath::Uuid => Column::RightEntityUuid,
+            Self::RightEntity(path) if **path == EntityQueryPath::OwnedById => {
+                Column::RightEntityOwnedById
+            }
+            Self::LeftEntity(path)
+            | Self::RightEntity(path)
+            | Self::IncomingLinks(path)
+            | Self::OutgoingLinks(path) => path.terminating_column(),
+            Self::LeftToRightOrder => Column::LeftToRightOrder,
+            Self::RightToLeftOrder => Column::RightToLeftOrder,
+            Self::Properties(path) => Column::Properties(path.as_deref()),
+            Self::PropertyProvenance(path) => Column::PropertyProvenance(
+                path.as_ref()
+                    .map(|(property, field)| (property.as_ref(), field.as_str())),
+            ),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use std::iter::once;
+
+use crate::{
+    ontology::EntityTypeQueryPath,
+    store::sqlite::query::{Column, Relation, SqliteQueryPath},
+};
+
+impl SqliteQueryPath for EntityTypeQueryPath {
+    fn relations(&self) -> Vec<Relation> {
+        match self {
+            Self::Properties(path) => once(Relation::PropertyTypeReferences)
+                .chain(path.relations())
+                .collect(),
+            Self::Links(path) => once(Relation::EntityTypeLinks)
+                .chain(path.relations())
+                .collect(),
+            Self::InheritsFrom(path) => once(Relation::EntityTypeInheritance)
+                .chain(path.relations())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    fn terminating_column(&self) -> Column<'static> {
+        match self {
+            Self::BaseUri => Column::BaseUri,
+            Self::Version => Column::Version,
+            Self::VersionId => Column::VersionId,
+            Self::OwnedById => Column::OwnedById,
+            Self::UpdatedById => Column::UpdatedById,
+            Self::External => Column::External,
+            Self::Schema => Column::Schema(None),
+            Self::VersionedUri => Column::Schema(Some("$id")),
+            Self::Title => Column::Schema(Some("title")),
+            Self::Description => Column::Schema(Some("description")),
+            Self::Default => Column::Schema(Some("default")),
+            Self::Examples => Column::Schema(Some("examples")),
+            Self::Required => Column::Schema(Some("required")),
+            Self::RequiredLinks => Column::Schema(Some("requiredLinks")),
+            Self::Links(path) | Self::InheritsFrom(path) => path.terminating_column(),
+            Self::Properties(path) => path.terminating_column(),
+        }
+    }
+}
@@ -0,0 +1,926 @@
+//! Compilation of [`Filter`]s to SQL for the SQLite store.
+//!
+//! SQLite has no equivalent of the range types and `jsonb` operators, which are used to compile
+//! filters for Postgres. Instead, the records pinned by a [`TimeProjection`] are selected in common
+//! table expressions, which the compiled filter is evaluated on. The semantics follow the Postgres
+//! compiler: every distinct chain of [`Relation`]s in a filter is joined once, and a record matches
+//! if any combination of joined records satisfies the filter. Comparisons with `NULL` are unknown,
+//! as in SQL.
+
+mod data_type;
+mod entity;
+mod entity_type;
+mod property_type;
+
+use std::{collections::HashMap, marker::PhantomData, str::FromStr};
+
+use error_stack::{IntoReport, Result, ResultExt};
+use rusqlite::{params_from_iter, types::FromSql, Connection, Row, ToSql};
+use serde_json::Value as JsonValue;
+use type_system::uri::VersionedUri;
+use uuid::Uuid;
+
+use crate::{
+    identifier::{
+        account::AccountId,
+        knowledge::{EntityEditionId, EntityId, EntityRecordId, EntityVersion},
+        time::{TimeAxis, TimeProjection, Timespan, TimespanBound, Timestamp, VersionTimespan},
+    },
+    knowledge::{DraftId, Entity, EntityUuid, LinkData, LinkOrder},
+    provenance::{OwnedById, ProvenanceMetadata, UpdatedById},
+    store::{
+        query::{Filter, FilterExpression, Parameter},
+        sqlite::ontology::{ontology_type_from_row, OntologyKind, SqliteOntologyRecord},
+        QueryError, Record,
+    },
+};
+
+/// A relation between two records, which is used to join records when evaluating a path.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Relation {
+    /// The entity type of an entity.
+    EntityType,
+    /// The left entity of a link entity.
+    LeftEntity,
+    /// The right entity of a link entity.
+    RightEntity,
+    /// The link entities, which have the entity as left entity.
+    OutgoingLinks,
+    /// The link entities, which have the entity as right entity.
+    IncomingLinks,
+    /// The data types referenced by a property type.
+    DataTypeReferences,
+    /// The property types referenced by a property type or an entity type.
+    PropertyTypeReferences,
+    /// The link entity types referenced in the `links` of an entity type.
+    EntityTypeLinks,
+    /// The entity types an entity type inherits from.
+    EntityTypeInheritance,
+}
+
+impl Relation {
+    /// Returns the records, which are joined by the relation.
+    const fn target(self) -> Records {
+        match self {
+            Self::EntityType | Self::EntityTypeLinks | Self::EntityTypeInheritance => {
+                Records::OntologyTypes(OntologyKind::EntityType)
+            }
+            Self::DataTypeReferences => Records::OntologyTypes(OntologyKind::DataType),
+            Self::PropertyTypeReferences => Records::OntologyTypes(OntologyKind::PropertyType),
+            Self::LeftEntity | Self::RightEntity | Self::OutgoingLinks | Self::IncomingLinks => {
+                Records::Entities
+            }
+        }
+    }
+}
+
+/// The value of a record, which is compared in a filter.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Column<'p> {
+    BaseUri,
+    Version,
+    VersionId,
+    OwnedById,
+    UpdatedById,
+    External,
+    /// The schema of an ontology type or the text of one of its top-level fields.
+    Schema(Option<&'static str>),
+    EntityUuid,
+    RecordId,
+    DecisionTime,
+    TransactionTime,
+    /// The start of the edition on the time axis of the image of the time projection.
+    ProjectedTime,
+    Archived,
+    DraftId,
+    LeftEntityUuid,
+    LeftEntityOwnedById,
+    RightEntityUuid,
+    RightEntityOwnedById,
+    LeftToRightOrder,
+    RightToLeftOrder,
+    /// The properties of an entity or the text of a single property.
+    Properties(Option<&'p str>),
+    /// The provenance of the properties of an entity or the text of a field of the provenance of a
+    /// single property.
+    PropertyProvenance(Option<(&'p str, &'static str)>),
+}
+
+/// A query path, which can be compiled to SQL.
+pub trait SqliteQueryPath {
+    /// Returns the relations that are required to access the path.
+    fn relations(&self) -> Vec<Relation>;
+
+    /// Returns the column the path is pointing to.
+    fn terminating_column(&self) -> Column<'_>;
+}
+
+/// The records pinned by a [`TimeProjection`], which are selected in a common table expression.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Records {
+    OntologyTypes(OntologyKind),
+    Entities,
+}
+
+impl Records {
+    /// The name of the common table expression, which selects the records.
+    const fn table(self) -> &'static str {
+        match self {
+            Self::OntologyTypes(_) => "pinned_ontology_types",
+            Self::Entities => "pinned_entities",
+        }
+    }
+}
+
+/// A [`Record`], which can be read with a [`SelectStatement`].
+pub trait SqliteRecord: Record + 'static {
+    /// The records, which are selected.
+    const RECORDS: Records;
+
+    /// The columns of the records, which are required to create a record.
+    const COLUMNS: &'static str;
+
+    /// The column, which identifies a record.
+    ///
+    /// A record is only returned once, even if it's pinned multiple times, e.g. if the decision
+    /// time of an entity edition was changed in a later transaction.
+    const KEY: &'static str;
+
+    /// Creates the record from a row, which contains the [`COLUMNS`].
+    ///
+    /// # Errors
+    ///
+    /// - if the stored record could not be parsed
+    ///
+    /// [`COLUMNS`]: Self::COLUMNS
+    fn from_row(row: &Row<'_>) -> Result<Self, QueryError>;
+}
+
+fn column<T: FromSql>(row: &Row<'_>, index: usize) -> Result<T, QueryError> {
+    row.get(index).into_report().change_context(QueryError)
+}
+
+impl<T: SqliteOntologyRecord> SqliteRecord for T {
+    const COLUMNS: &'static str = "t0.schema, t0.owned_by_id, t0.updated_by_id, t0.external";
+    const KEY: &'static str = "t0.version_id";
+    const RECORDS: Records = Records::OntologyTypes(T::KIND);
+
+    fn from_row(row: &Row<'_>) -> Result<Self, QueryError> {
+        ontology_type_from_row(
+            column(row, 0)?,
+            column(row, 1)?,
+            column(row, 2)?,
+            column(row, 3)?,
+        )
+    }
+}
+
+impl SqliteRecord for Entity {
+    // The edition, which is pinned first in the transaction time, is returned if an edition is
+    // pinned multiple times.
+    const COLUMNS: &'static str = r#"
+        t0.owned_by_id,
+        t0.entity_uuid,
+        t0.entity_record_id,
+        t0.decision_time_start,
+        t0.decision_time_end,
+        t0.transaction_time_start,
+        t0.transaction_time_end,
+        t0.draft_id,
+        t0.entity_type_id,
+        t0.properties,
+        t0.property_provenance,
+        t0.left_owned_by_id,
+        t0.left_entity_uuid,
+        t0.right_owned_by_id,
+        t0.right_entity_uuid,
+        t0.left_to_right_order,
+        t0.right_to_left_order,
+        t0.updated_by_id,
+        t0.archived,
+        MIN(t0.transaction_time_start)
+    "#;
+    const KEY: &'static str = "t0.entity_record_id";
+    const RECORDS: Records = Records::Entities;
+
+    fn from_row(row: &Row<'_>) -> Result<Self, QueryError> {
+        let left_entity_id = column::<Option<Uuid>>(row, 11)?.zip(column::<Option<Uuid>>(row, 12)?);
+        let right_entity_id =
+            column::<Option<Uuid>>(row, 13)?.zip(column::<Option<Uuid>>(row, 14)?);
+        let link_data = match (left_entity_id, right_entity_id) {
+            (Some(left_entity_id), Some(right_entity_id)) => Some(LinkData::new(
+                entity_id(left_entity_id),
+                entity_id(right_entity_id),
+                column::<Option<i32>>(row, 15)?.map(LinkOrder::new),
+                column::<Option<i32>>(row, 16)?.map(LinkOrder::new),
+            )),
+            (None, None) => None,
+            _ => unreachable!(
+                "It's not possible to have a link entity with the left entityId or right entityId \
+                 unspecified"
+            ),
+        };
+
+        Ok(Self::new(
+            serde_json::from_value(column::<JsonValue>(row, 9)?)
+                .into_report()
+                .change_context(QueryError)?,
+            serde_json::from_value(column::<JsonValue>(row, 10)?)
+                .into_report()
+                .change_context(QueryError)?,
+            link_data,
+            EntityEditionId::new(
+                entity_id((column(row, 0)?, column(row, 1)?)),
+                EntityRecordId::new(column(row, 2)?),
+            ),
+            EntityVersion::new(
+                VersionTimespan::from_anonymous(VersionTimespan::<()> {
+                    start: column(row, 3)?,
+                    end: column(row, 4)?,
+                }),
+                VersionTimespan::from_anonymous(VersionTimespan::<()> {
+                    start: column(row, 5)?,
+                    end: column(row, 6)?,
+                }),
+            ),
+            VersionedUri::from_str(&column::<String>(row, 8)?)
+                .into_report()
+                .change_context(QueryError)?,
+            ProvenanceMetadata::new(UpdatedById::new(AccountId::new(column(row, 17)?))),
+            column(row, 18)?,
+            column::<Option<Uuid>>(row, 7)?.map(DraftId::new),
+        ))
+    }
+}
+
+fn entity_id((owned_by_id, entity_uuid): (Uuid, Uuid)) -> EntityId {
+    EntityId::new(
+        OwnedById::new(AccountId::new(owned_by_id)),
+        EntityUuid::new(entity_uuid),
+    )
+}
+
+/// A compiled `SELECT` statement, which reads the records of type `R` matching a [`Filter`].
+///
+/// The statement owns its parameters, so it can be compiled on the calling task and read on a
+/// thread, which is allowed to block.
+pub struct SelectStatement<R> {
+    sql: String,
+    parameters: Vec<Box<dyn ToSql + Send>>,
+    record: PhantomData<fn() -> R>,
+}
+
+impl<R: SqliteRecord> SelectStatement<R> {
+    /// Compiles the `filter` for the records pinned by the `time_projection`.
+    ///
+    /// If a `draft_id` is specified, the editions of the draft replace the live editions of the
+    /// entities changed in the draft.
+    ///
+    /// # Panics
+    ///
+    /// - if the image of the `time_projection` is bounded
+    pub fn compile<'p>(
+        filter: &Filter<'p, R>,
+        time_projection: &TimeProjection,
+        draft_id: Option<DraftId>,
+    ) -> Self
+    where
+        R::QueryPath<'p>: SqliteQueryPath,
+    {
+        assert_eq!(
+            time_projection.image(),
+            Timespan {
+                start: TimespanBound::Unbounded,
+                end: TimespanBound::Unbounded
+            },
+            "custom time projection images are not supported yet"
+        );
+
+        let mut compiler = Compiler::new(time_projection, draft_id, R::RECORDS);
+        compiler.add_joins(filter);
+        let condition = compiler.condition(filter);
+        let root_condition = match R::RECORDS {
+            Records::OntologyTypes(kind) => format!("t0.kind = '{}'", kind.as_str()),
+            Records::Entities => "1".to_owned(),
+        };
+        let joins = compiler
+            .joins
+            .iter()
+            .enumerate()
+            .map(|(index, &(parent, relation))| join(index + 1, parent, relation))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut tables = Vec::new();
+        if compiler
+            .records
+            .iter()
+            .any(|records| matches!(records, Records::OntologyTypes(_)))
+        {
+            tables.push(compiler.pinned_ontology_types());
+        }
+        if compiler.records.contains(&Records::Entities) {
+            tables.push(compiler.pinned_entities());
+        }
+
+        Self {
+            sql: format!(
+                "WITH {tables}\nSELECT {columns}\nFROM {table} AS t0\n{joins}\nWHERE \
+                 {root_condition} AND {condition}\nGROUP BY {key}\nORDER BY {key};",
+                tables = tables.join(",\n"),
+                columns = R::COLUMNS,
+                table = R::RECORDS.table(),
+                key = R::KEY,
+            ),
+            parameters: compiler.parameters,
+            record: PhantomData,
+        }
+    }
+
+    /// Reads the records, which match the compiled filter.
+    ///
+    /// # Errors
+    ///
+    /// - if reading from the database failed
+    /// - if a stored record could not be parsed
+    pub fn read(&self, connection: &Connection) -> Result<Vec<R>, QueryError> {
+        let mut statement = connection
+            .prepare_cached(&self.sql)
+            .into_report()
+            .change_context(QueryError)
+            .attach_printable_lazy(|| self.sql.clone())?;
+        let mut rows = statement
+            .query(params_from_iter(&self.parameters))
+            .into_report()
+            .change_context(QueryError)?;
+
+        let mut records = Vec::new();
+        while let Some(row) = rows.next().into_report().change_context(QueryError)? {
+            records.push(R::from_row(row)?);
+        }
+        Ok(records)
+    }
+}
+
+/// Returns the join of the records, which are related to the records of the `parent` join.
+fn join(index: usize, parent: usize, relation: Relation) -> String {
+    let table = relation.target().table();
+    match relation {
+        Relation::EntityType => format!(
+            "INNER JOIN {table} AS t{index} ON t{index}.version_id = \
+             t{parent}.entity_type_version_id"
+        ),
+        Relation::LeftEntity | Relation::RightEntity => {
+            let side = if relation == Relation::LeftEntity {
+                "left"
+            } else {
+                "right"
+            };
+            format!(
+                "INNER JOIN {table} AS t{index} ON t{index}.owned_by_id = \
+                 t{parent}.{side}_owned_by_id AND t{index}.entity_uuid = \
+                 t{parent}.{side}_entity_uuid"
+            )
+        }
+        Relation::OutgoingLinks | Relation::IncomingLinks => {
+            let side = if relation == Relation::OutgoingLinks {
+                "left"
+            } else {
+                "right"
+            };
+            format!(
+                "INNER JOIN {table} AS t{index} ON t{index}.{side}_owned_by_id = \
+                 t{parent}.owned_by_id AND t{index}.{side}_entity_uuid = t{parent}.entity_uuid"
+            )
+        }
+        Relation::DataTypeReferences
+        | Relation::PropertyTypeReferences
+        | Relation::EntityTypeLinks
+        | Relation::EntityTypeInheritance => {
+            let Records::OntologyTypes(kind) = relation.target() else {
+                unreachable!("references always point to ontology types")
+            };
+            // Only the references, which are listed in the respective field of the schema, are
+            // joined for entity types.
+            let reference = match relation {
+                Relation::EntityTypeLinks => format!(
+                    " AND EXISTS (SELECT 1 FROM json_each(t{parent}.schema, '$.links') AS links \
+                     WHERE links.key = t{index}.versioned_uri)"
+                ),
+                Relation::EntityTypeInheritance => format!(
+                    " AND EXISTS (SELECT 1 FROM json_each(t{parent}.schema, '$.allOf') AS parents \
+                     WHERE json_extract(parents.value, '$.\"$ref\"') = t{index}.versioned_uri)"
+                ),
+                _ => String::new(),
+            };
+            format!(
+                "INNER JOIN ontology_type_references AS r{index} ON r{index}.source_version_id = \
+                 t{parent}.version_id\nINNER JOIN {table} AS t{index} ON t{index}.version_id = \
+                 r{index}.target_version_id AND t{index}.kind = '{kind}'{reference}",
+                kind = kind.as_str()
+            )
+        }
+    }
+}
+
+/// Compiles a [`Filter`] to the condition of a [`SelectStatement`].
+struct Compiler<'t> {
+    time_projection: &'t TimeProjection,
+    draft_id: Option<DraftId>,
+    /// The parent join and the relation to it for every join except the root.
+    joins: Vec<(usize, Relation)>,
+    /// The records of every join, the root record has the index `0`.
+    records: Vec<Records>,
+    indices: HashMap<Vec<Relation>, usize>,
+    parameters: Vec<Box<dyn ToSql + Send>>,
+    kernel: Option<String>,
+    now: Option<String>,
+}
+
+impl<'t> Compiler<'t> {
+    fn new(time_projection: &'t TimeProjection, draft_id: Option<DraftId>, root: Records) -> Self {
+        Self {
+            time_projection,
+            draft_id,
+            joins: Vec::new(),
+            records: vec![root],
+            indices: HashMap::from([(Vec::new(), 0)]),
+            parameters: Vec::new(),
+            kernel: None,
+            now: None,
+        }
+    }
+
+    /// Adds a parameter to the statement and returns its placeholder.
+    fn parameter(&mut self, value: impl ToSql + Send + 'static) -> String {
+        self.parameters.push(Box::new(value));
+        format!("?{}", self.parameters.len())
+    }
+
+    /// Returns the placeholder of the kernel of the time projection.
+    fn kernel(&mut self) -> String {
+        if let Some(kernel) = &self.kernel {
+            return kernel.clone();
+        }
+        let kernel = self.parameter(self.time_projection.kernel());
+        self.kernel = Some(kernel.clone());
+        kernel
+    }
+
+    /// Returns the placeholder of the current time, which determines the latest editions.
+    fn now(&mut self) -> String {
+        if let Some(now) = &self.now {
+            return now.clone();
+        }
+        let now = self.parameter(Timestamp::<()>::now().truncate_to_microseconds());
+        self.now = Some(now.clone());
+        now
+    }
+
+    /// Selects the ontology types pinned by the time projection.
+    ///
+    /// Only versions, which were known at the kernel, are pinned if the decision time is projected.
+    fn pinned_ontology_types(&mut self) -> String {
+        let kernel =
+            (self.time_projection.time_axis() == TimeAxis::DecisionTime).then(|| self.kernel());
+        let pinned = |table: &str| {
+            kernel.as_ref().map_or_else(
+                || "1".to_owned(),
+                |kernel| format!("{table}.transaction_time_start <= {kernel}"),
+            )
+        };
+
+        format!(
+            r#"
+                pinned_ontology_types AS (
+                    SELECT
+                        version_id,
+                        kind,
+                        base_uri,
+                        version,
+                        schema,
+                        owned_by_id,
+                        updated_by_id,
+                        external,
+                        json_extract(schema, '$."$id"') AS versioned_uri,
+                        version = (
+                            SELECT MAX(versions.version)
+                            FROM ontology_types AS versions
+                            WHERE versions.base_uri = ontology_types.base_uri
+                              AND {pinned_versions}
+                        ) AS is_latest
+                    FROM ontology_types
+                    WHERE {pinned_types}
+                )
+            "#,
+            pinned_versions = pinned("versions"),
+            pinned_types = pinned("ontology_types"),
+        )
+    }
+
+    /// Selects the entity editions pinned by the time projection.
+    ///
+    /// The kernel pins the time axis, which is not projected. If a draft is specified, the editions
+    /// of the draft replace the live editions of the entities changed in the draft.
+    fn pinned_entities(&mut self) -> String {
+        let pinned_axis = match self.time_projection.time_axis() {
+            TimeAxis::DecisionTime => "transaction_time",
+            TimeAxis::TransactionTime => "decision_time",
+        };
+        let kernel = self.kernel();
+        let draft_id = self.parameter(self.draft_id.map(DraftId::as_uuid));
+
+        format!(
+            r#"
+                pinned_entities AS (
+                    SELECT
+                        versions.owned_by_id,
+                        versions.entity_uuid,
+                        versions.entity_record_id,
+                        versions.decision_time_start,
+                        versions.decision_time_end,
+                        versions.transaction_time_start,
+                        versions.transaction_time_end,
+                        versions.draft_id,
+                        entity_editions.entity_type_version_id,
+                        json_extract(ontology_types.schema, '$."$id"') AS entity_type_id,
+                        entity_editions.properties,
+                        entity_editions.property_provenance,
+                        entity_ids.left_owned_by_id,
+                        entity_ids.left_entity_uuid,
+                        entity_ids.right_owned_by_id,
+                        entity_ids.right_entity_uuid,
+                        entity_editions.left_to_right_order,
+                        entity_editions.right_to_left_order,
+                        entity_editions.updated_by_id,
+                        entity_editions.archived
+                    FROM (
+                        SELECT
+                            owned_by_id,
+                            entity_uuid,
+                            entity_record_id,
+                            decision_time_start,
+                            decision_time_end,
+                            transaction_time_start,
+                            transaction_time_end,
+                            NULL AS draft_id
+                        FROM entity_versions
+                        WHERE NOT EXISTS (
+                            SELECT 1
+                            FROM entity_drafts
+                            WHERE entity_drafts.draft_id = {draft_id}
+                              AND entity_drafts.owned_by_id = entity_versions.owned_by_id
+                              AND entity_drafts.entity_uuid = entity_versions.entity_uuid
+                        )
+                        UNION ALL
+                        SELECT
+                            owned_by_id,
+                            entity_uuid,
+                            entity_record_id,
+                            COALESCE(decision_time, transaction_time_start),
+                            NULL,
+                            transaction_time_start,
+                            NULL,
+                            draft_id
+                        FROM entity_drafts
+                        WHERE draft_id = {draft_id}
+                    ) AS versions
+                    JOIN entity_editions
+                      ON entity_editions.entity_record_id = versions.entity_record_id
+                    JOIN entity_ids
+                      ON entity_ids.owned_by_id = versions.owned_by_id
+                     AND entity_ids.entity_uuid = versions.entity_uuid
+                    JOIN ontology_types
+                      ON ontology_types.version_id = entity_editions.entity_type_version_id
+                    WHERE versions.{pinned_axis}_start <= {kernel}
+                      AND (
+                        versions.{pinned_axis}_end IS NULL
+                        OR versions.{pinned_axis}_end > {kernel}
+                      )
+                )
+            "#
+        )
+    }
+
+    /// Adds the joins, which are required to evaluate the `filter`.
+    ///
+    /// Every distinct chain of relations is joined once, chains sharing a prefix share the joins of
+    /// the prefix.
+    fn add_joins<'p, R>(&mut self, filter: &Filter<'p, R>)
+    where
+        R: Record,
+        R::QueryPath<'p>: SqliteQueryPath,
+    {
+        match filter {
+            Filter::All(filters) | Filter::Any(filters) => {
+                for filter in filters {
+                    self.add_joins(filter);
+                }
+            }
+            Filter::Not(filter) => self.add_joins(filter),
+            Filter::Equal(lhs, rhs) | Filter::NotEqual(lhs, rhs) => {
+                for expression in [lhs, rhs].into_iter().flatten() {
+                    if let FilterExpression::Path(path) = expression {
+                        let relations = path.relations();
+                        for depth in 1..=relations.len() {
+                            if !self.indices.contains_key(&relations[..depth]) {
+                                let parent = self.indices[&relations[..depth - 1]];
+                                self.joins.push((parent, relations[depth - 1]));
+                                self.records.push(relations[depth - 1].target());
+                                self.indices
+                                    .insert(relations[..depth].to_vec(), self.joins.len());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn condition<'p, R>(&mut self, filter: &Filter<'p, R>) -> String
+    where
+        R: Record,
+        R::QueryPath<'p>: SqliteQueryPath,
+    {
+        match filter {
+            Filter::All(filters) => self.conditions(filters, " AND ", "1"),
+            Filter::Any(filters) => self.conditions(filters, " OR ", "0"),
+            Filter::Not(filter) => format!("NOT {}", self.condition(filter)),
+            Filter::Equal(lhs, rhs) => match (lhs, rhs) {
+                (Some(lhs), Some(rhs)) => {
+                    let lhs = self.operand(lhs);
+                    let rhs = self.operand(rhs);
+                    Operand::equals(&lhs, &rhs)
+                }
+                (Some(expression), None) | (None, Some(expression)) => {
+                    self.operand(expression).is_null()
+                }
+                (None, None) => "1".to_owned(),
+            },
+            Filter::NotEqual(lhs, rhs) => match (lhs, rhs) {
+                (Some(lhs), Some(rhs)) => {
+                    let lhs = self.operand(lhs);
+                    let rhs = self.operand(rhs);
+                    format!("NOT {}", Operand::equals(&lhs, &rhs))
+                }
+                (Some(expression), None) | (None, Some(expression)) => {
+                    format!("NOT {}", self.operand(expression).is_null())
+                }
+                (None, None) => "0".to_owned(),
+            },
+        }
+    }
+
+    fn conditions<'p, R>(
+        &mut self,
+        filters: &[Filter<'p, R>],
+        operator: &str,
+        empty: &str,
+    ) -> String
+    where
+        R: Record,
+        R::QueryPath<'p>: SqliteQueryPath,
+    {
+        if filters.is_empty() {
+            return empty.to_owned();
+        }
+        let conditions = filters
+            .iter()
+            .map(|filter| self.condition(filter))
+            .collect::<Vec<_>>();
+        format!("({})", conditions.join(operator))
+    }
+
+    fn operand<'p, R>(&mut self, expression: &FilterExpression<'p, R>) -> Operand
+    where
+        R: Record,
+        R::QueryPath<'p>: SqliteQueryPath,
+    {
+        match expression {
+            FilterExpression::Path(path) => {
+                let index = self.indices[&path.relations()];
+                self.column(index, path.terminating_column())
+            }
+            FilterExpression::Parameter(parameter) => match parameter {
+                Parameter::Boolean(boolean) => Operand::Boolean(self.parameter(*boolean)),
+                Parameter::Number(number) => Operand::Number(self.parameter(*number)),
+                Parameter::Text(text) => Operand::Text(self.parameter(text.to_string())),
+                Parameter::Uuid(uuid) => Operand::Uuid(self.parameter(*uuid)),
+                Parameter::SignedInteger(integer) => Operand::Integer(self.parameter(*integer)),
+                Parameter::Timestamp(timestamp) => Operand::Timestamp(self.parameter(*timestamp)),
+            },
+        }
+    }
+
+    /// Returns the text of the JSON value at the path of `keys`, as the `->>` operator in Postgres
+    /// does.
+    fn json_text(&mut self, json: &str, keys: &[&str]) -> String {
+        let path = self.parameter(
+            keys.iter()
+                .fold(String::from("$"), |path, key| format!(r#"{path}."{key}""#)),
+        );
+        format!(
+            "(CASE json_type({json}, {path}) WHEN 'text' THEN json_extract({json}, {path}) WHEN \
+             'true' THEN 'true' WHEN 'false' THEN 'false' WHEN 'null' THEN NULL ELSE \
+             CAST(json_extract({json}, {path}) AS TEXT) END)"
+        )
+    }
+
+    fn column(&mut self, index: usize, column: Column<'_>) -> Operand {
+        let table = format!("t{index}");
+        match self.records[index] {
+            Records::OntologyTypes(_) => match column {
+                Column::BaseUri => Operand::Text(format!("{table}.base_uri")),
+                Column::Version => Operand::Version {
+                    version: format!("{table}.version"),
+                    latest: format!("{table}.is_latest"),
+                },
+                Column::VersionId => Operand::Uuid(format!("{table}.version_id")),
+                Column::OwnedById => Operand::Uuid(format!("{table}.owned_by_id")),
+                Column::UpdatedById => Operand::Uuid(format!("{table}.updated_by_id")),
+                Column::External => Operand::Boolean(format!("{table}.external")),
+                Column::Schema(None) => Operand::Opaque,
+                Column::Schema(Some(field)) => {
+                    Operand::Text(self.json_text(&format!("{table}.schema"), &[field]))
+                }
+                _ => Operand::Null,
+            },
+            Records::Entities => match column {
+                Column::EntityUuid => Operand::Uuid(format!("{table}.entity_uuid")),
+                Column::OwnedById => Operand::Uuid(format!("{table}.owned_by_id")),
+                Column::UpdatedById => Operand::Uuid(format!("{table}.updated_by_id")),
+                Column::RecordId => Operand::Integer(format!("{table}.entity_record_id")),
+                Column::DecisionTime | Column::TransactionTime => Operand::Opaque,
+                Column::ProjectedTime => {
+                    let axis = match self.time_projection.time_axis() {
+                        TimeAxis::DecisionTime => "decision_time",
+                        TimeAxis::TransactionTime => "transaction_time",
+                    };
+                    let now = self.now();
+                    Operand::ProjectedTime {
+                        start: format!("{table}.{axis}_start"),
+                        latest: format!(
+                            "({table}.{axis}_start <= {now} AND ({table}.{axis}_end IS NULL OR \
+                             {table}.{axis}_end > {now}))"
+                        ),
+                    }
+                }
+                Column::Archived => Operand::Boolean(format!("{table}.archived")),
+                Column::DraftId => Operand::Uuid(format!("{table}.draft_id")),
+                Column::LeftEntityUuid => Operand::Uuid(format!("{table}.left_entity_uuid")),
+                Column::LeftEntityOwnedById => Operand::Uuid(format!("{table}.left_owned_by_id")),
+                Column::RightEntityUuid => Operand::Uuid(format!("{table}.right_entity_uuid")),
+                Column::RightEntityOwnedById => Operand::Uuid(format!("{table}.right_owned_by_id")),
+                Column::LeftToRightOrder => {
+                    Operand::Integer(format!("{table}.left_to_right_order"))
+                }
+                Column::RightToLeftOrder => {
+                    Operand::Integer(format!("{table}.right_to_left_order"))
+                }
+                Column::Properties(None) | Column::PropertyProvenance(None) => Operand::Opaque,
+                Column::Properties(Some(property)) => {
+                    Operand::Text(self.json_text(&format!("{table}.properties"), &[property]))
+                }
+                Column::PropertyProvenance(Some((property, field))) => Operand::Text(
+                    self.json_text(&format!("{table}.property_provenance"), &[property, field]),
+                ),
+                _ => Operand::Null,
+            },
+        }
+    }
+}
+
+/// An operand of a comparison, the variants hold the SQL expression of the operand.
+#[derive(Debug)]
+enum Operand {
+    /// A value, which is `NULL` for every record.
+    Null,
+    Boolean(String),
+    Integer(String),
+    Number(String),
+    Text(String),
+    Uuid(String),
+    Timestamp(String),
+    /// The version of an ontology type, which is equal to `"latest"` if it's the latest version.
+    Version {
+        version: String,
+        latest: String,
+    },
+    /// The projected time of an entity edition, which is equal to `"latest"` if the edition is
+    /// valid at the current time.
+    ProjectedTime {
+        start: String,
+        latest: String,
+    },
+    /// A value, which is not equal to any other value, e.g. a time interval or a JSON object.
+    Opaque,
+}
+
+impl Operand {
+    /// Returns the expression of the operand, if it may be `NULL`.
+    fn nullable(&self) -> Option<&str> {
+        match self {
+            Self::Boolean(expression)
+            | Self::Integer(expression)
+            | Self::Number(expression)
+            | Self::Text(expression)
+            | Self::Uuid(expression)
+            | Self::Timestamp(expression) => Some(expression.as_str()),
+            Self::Null | Self::Version { .. } | Self::ProjectedTime { .. } | Self::Opaque => None,
+        }
+    }
+
+    fn is_null(&self) -> String {
+        match self {
+            Self::Null => "1".to_owned(),
+            Self::Version { .. } | Self::ProjectedTime { .. } | Self::Opaque => "0".to_owned(),
+            Self::Boolean(expression)
+            | Self::Integer(expression)
+            | Self::Number(expression)
+            | Self::Text(expression)
+            | Self::Uuid(expression)
+            | Self::Timestamp(expression) => format!("({expression} IS NULL)"),
+        }
+    }
+
+    /// Returns `condition` unless any operand is `NULL`, in which case the comparison is unknown.
+    fn unless_null(lhs: &Self, rhs: &Self, condition: &str) -> String {
+        let checks = [lhs, rhs]
+            .into_iter()
+            .filter_map(Self::nullable)
+            .map(|expression| format!("{expression} IS NULL"))
+            .collect::<Vec<_>>();
+        if checks.is_empty() {
+            format!("({condition})")
+        } else {
+            format!(
+                "(CASE WHEN {} THEN NULL ELSE {condition} END)",
+                checks.join(" OR ")
+            )
+        }
+    }
+
+    /// Compares two operands for equality.
+    ///
+    /// The comparison is unknown if any operand is `NULL`. Text, which is extracted from JSON, is
+    /// compared to numbers and booleans by its textual representation.
+    fn equals(lhs: &Self, rhs: &Self) -> String {
+        Self::equals_ordered(lhs, rhs)
+            .or_else(|| Self::equals_ordered(rhs, lhs))
+            .unwrap_or_else(|| Self::unless_null(lhs, rhs, "0"))
+    }
+
+    /// Compares two operands for equality, if the comparison is defined in this order.
+    fn equals_ordered(lhs: &Self, rhs: &Self) -> Option<String> {
+        Some(match (lhs, rhs) {
+            (Self::Null, _) => "NULL".to_owned(),
+            (Self::Boolean(lhs), Self::Boolean(rhs))
+            | (
+                Self::Integer(lhs),
+                Self::Integer(rhs) | Self::Number(rhs) | Self::Version { version: rhs, .. },
+            )
+            | (Self::Number(lhs), Self::Number(rhs))
+            | (Self::Text(lhs), Self::Text(rhs))
+            | (Self::Uuid(lhs), Self::Uuid(rhs))
+            | (
+                Self::Timestamp(lhs),
+                Self::Timestamp(rhs) | Self::ProjectedTime { start: rhs, .. },
+            ) => format!("({lhs} = {rhs})"),
+            (Self::Text(text), Self::Number(number)) => Self::unless_null(
+                lhs,
+                rhs,
+                &format!(
+                    "CASE WHEN NOT json_valid({text}) THEN 0 WHEN json_type({text}) IN \
+                     ('integer', 'real') THEN CAST({text} AS REAL) = {number} ELSE 0 END"
+                ),
+            ),
+            (Self::Text(text), Self::Integer(integer)) => Self::unless_null(
+                lhs,
+                rhs,
+                &format!(
+                    "CASE WHEN NOT json_valid({text}) THEN 0 WHEN json_type({text}) = 'integer' \
+                     THEN CAST({text} AS INTEGER) = {integer} ELSE 0 END"
+                ),
+            ),
+            (Self::Text(text), Self::Boolean(boolean)) => Self::unless_null(
+                lhs,
+                rhs,
+                &format!("{text} = CASE WHEN {boolean} THEN 'true' ELSE 'false' END"),
+            ),
+            (Self::Text(text), Self::Uuid(uuid)) => Self::unless_null(
+                lhs,
+                rhs,
+                &format!("upper(replace({text}, '-', '')) = hex({uuid})"),
+            ),
+            (
+                Self::Text(text),
+                Self::Version { latest, .. } | Self::ProjectedTime { latest, .. },
+            ) => Self::unless_null(
+                lhs,
+                rhs,
+                &format!("CASE WHEN {text} = 'latest' THEN {latest} ELSE 0 END"),
+            ),
+            _ => return None,
+        })
+    }
+}
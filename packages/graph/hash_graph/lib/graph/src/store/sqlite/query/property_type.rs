@@ -0,0 +1,37 @@
+use std::iter::once;
+
+use crate::{
+    ontology::PropertyTypeQueryPath,
+    store::sqlite::query::{Column, Relation, SqliteQueryPath},
+};
+
+impl SqliteQueryPath for PropertyTypeQueryPath {
+    fn relations(&self) -> Vec<Relation> {
+        match self {
+            Self::DataTypes(path) => once(Relation::DataTypeReferences)
+                .chain(path.relations())
+                .collect(),
+            Self::PropertyTypes(path) => once(Relation::PropertyTypeReferences)
+                .chain(path.relations())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    fn terminating_column(&self) -> Column<'static> {
+        match self {
+            Self::BaseUri => Column::BaseUri,
+            Self::Version => Column::Version,
+            Self::VersionId => Column::VersionId,
+            Self::OwnedById => Column::OwnedById,
+            Self::UpdatedById => Column::UpdatedById,
+            Self::External => Column::External,
+            Self::Schema => Column::Schema(None),
+            Self::VersionedUri => Column::Schema(Some("$id")),
+            Self::Title => Column::Schema(Some("title")),
+            Self::Description => Column::Schema(Some("description")),
+            Self::DataTypes(path) => path.terminating_column(),
+            Self::PropertyTypes(path) => path.terminating_column(),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+-- The schema of the SQLite store, which mirrors the Postgres migrations.
+--
+-- UUIDs are stored as 16 byte blobs, JSON values as text and timestamps as RFC 3339 text with
+-- microsecond precision, so they are ordered chronologically when compared. Time intervals are
+-- stored as two columns `*_start` and `*_end`, where an `*_end` of `NULL` is unbounded.
+
+CREATE TABLE IF NOT EXISTS accounts (
+    account_id BLOB PRIMARY KEY
+);
+
+-- Data types, property types and entity types share one table, so a base URI can only be used
+-- by one kind of type, as in Postgres.
+CREATE TABLE IF NOT EXISTS ontology_types (
+    version_id BLOB PRIMARY KEY,
+    kind TEXT NOT NULL CHECK (kind IN ('data_type', 'property_type', 'entity_type')),
+    base_uri TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    schema TEXT NOT NULL,
+    owned_by_id BLOB NOT NULL REFERENCES accounts,
+    updated_by_id BLOB NOT NULL REFERENCES accounts,
+    external INTEGER NOT NULL DEFAULT FALSE,
+    transaction_time_start TEXT NOT NULL,
+    UNIQUE (base_uri, version)
+);
+
+CREATE TABLE IF NOT EXISTS ontology_type_references (
+    source_version_id BLOB NOT NULL REFERENCES ontology_types,
+    target_version_id BLOB NOT NULL REFERENCES ontology_types
+);
+
+CREATE TABLE IF NOT EXISTS entity_ids (
+    owned_by_id BLOB NOT NULL REFERENCES accounts,
+    entity_uuid BLOB NOT NULL,
+    left_owned_by_id BLOB,
+    left_entity_uuid BLOB,
+    right_owned_by_id BLOB,
+    right_entity_uuid BLOB,
+    PRIMARY KEY (owned_by_id, entity_uuid),
+    FOREIGN KEY (left_owned_by_id, left_entity_uuid) REFERENCES entity_ids,
+    FOREIGN KEY (right_owned_by_id, right_entity_uuid) REFERENCES entity_ids,
+    CHECK (
+        left_entity_uuid IS NULL AND right_entity_uuid IS NULL
+            AND left_owned_by_id IS NULL AND right_owned_by_id IS NULL
+        OR left_entity_uuid IS NOT NULL AND right_entity_uuid IS NOT NULL
+            AND left_owned_by_id IS NOT NULL AND right_owned_by_id IS NOT NULL
+    )
+);
+
+CREATE TABLE IF NOT EXISTS entity_editions (
+    entity_record_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    entity_type_version_id BLOB NOT NULL REFERENCES ontology_types,
+    properties TEXT NOT NULL,
+    property_provenance TEXT NOT NULL,
+    left_to_right_order INTEGER,
+    right_to_left_order INTEGER,
+    updated_by_id BLOB NOT NULL REFERENCES accounts,
+    archived INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS entity_versions (
+    owned_by_id BLOB NOT NULL,
+    entity_uuid BLOB NOT NULL,
+    entity_record_id INTEGER NOT NULL REFERENCES entity_editions,
+    decision_time_start TEXT NOT NULL,
+    decision_time_end TEXT,
+    transaction_time_start TEXT NOT NULL,
+    transaction_time_end TEXT,
+    FOREIGN KEY (owned_by_id, entity_uuid) REFERENCES entity_ids,
+    CHECK (decision_time_start <= transaction_time_start)
+);
+
+CREATE INDEX IF NOT EXISTS entity_versions_entity_id
+    ON entity_versions (owned_by_id, entity_uuid);
+
+CREATE TABLE IF NOT EXISTS drafts (
+    draft_id BLOB PRIMARY KEY,
+    owned_by_id BLOB NOT NULL REFERENCES accounts
+);
+
+CREATE TABLE IF NOT EXISTS entity_drafts (
+    draft_id BLOB NOT NULL REFERENCES drafts,
+    owned_by_id BLOB NOT NULL,
+    entity_uuid BLOB NOT NULL,
+    entity_record_id INTEGER NOT NULL REFERENCES entity_editions,
+    -- The live edition when the entity was first changed in the draft, `NULL` if the entity was
+    -- created in the draft
+    base_entity_record_id INTEGER REFERENCES entity_editions,
+    decision_time TEXT,
+    transaction_time_start TEXT NOT NULL,
+    PRIMARY KEY (draft_id, owned_by_id, entity_uuid),
+    FOREIGN KEY (owned_by_id, entity_uuid) REFERENCES entity_ids
+);
+
+CREATE TABLE IF NOT EXISTS webhooks (
+    webhook_id BLOB PRIMARY KEY,
+    owned_by_id BLOB NOT NULL REFERENCES accounts,
+    target_url TEXT NOT NULL,
+    secret TEXT NOT NULL,
+    filter TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS webhook_dead_letters (
+    webhook_id BLOB NOT NULL REFERENCES webhooks ON DELETE CASCADE,
+    owned_by_id BLOB NOT NULL,
+    entity_uuid BLOB NOT NULL,
+    entity_record_id INTEGER NOT NULL REFERENCES entity_editions,
+    payload TEXT NOT NULL,
+    attempts INTEGER NOT NULL,
+    error TEXT NOT NULL,
+    failed_at TEXT NOT NULL,
+    FOREIGN KEY (owned_by_id, entity_uuid) REFERENCES entity_ids
+);
@@ -0,0 +1,196 @@
+use async_trait::async_trait;
+use error_stack::{IntoReport, Result, ResultExt};
+use uuid::Uuid;
+
+use crate::{
+    identifier::{
+        account::AccountId,
+        knowledge::{EntityEditionId, EntityId, EntityRecordId},
+        time::Timestamp,
+    },
+    knowledge::EntityUuid,
+    metrics,
+    provenance::OwnedById,
+    store::{
+        sqlite::AsConnection,
+        webhook::{DeadLetter, Webhook, WebhookId, WebhookStore},
+        InsertionError, QueryError, SqliteStore,
+    },
+};
+
+#[async_trait]
+impl<C: AsConnection> WebhookStore for SqliteStore<C> {
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn create_webhook(&mut self, webhook: &Webhook) -> Result<(), InsertionError> {
+        let _timer = metrics::store_operation("create", "webhooks");
+
+        webhook
+            .validate_target_url()
+            .change_context(InsertionError)
+            .attach_printable(webhook.webhook_id())?;
+
+        let webhook_id = webhook.webhook_id();
+        let webhook = webhook.clone();
+        self.run(move |session| {
+            session.connection.execute(
+                r#"
+                    INSERT INTO webhooks (webhook_id, owned_by_id, target_url, secret, filter)
+                    VALUES (?1, ?2, ?3, ?4, ?5);
+                "#,
+                rusqlite::params![
+                    webhook.webhook_id().as_uuid(),
+                    webhook.owned_by_id().as_uuid(),
+                    webhook.target_url(),
+                    webhook.secret(),
+                    webhook.filter(),
+                ],
+            )
+        })
+        .await
+        .into_report()
+        .change_context(InsertionError)
+        .attach_printable(webhook_id)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn webhooks(&self) -> Result<Vec<Webhook>, QueryError> {
+        let _timer = metrics::store_operation("read", "webhooks");
+
+        let rows = self
+            .run(move |session| {
+                session
+                    .connection
+                    .prepare_cached(
+                        r#"
+                            SELECT webhook_id, owned_by_id, target_url, secret, filter
+                            FROM webhooks;
+                        "#,
+                    )?
+                    .query_map([], |row| {
+                        Ok((
+                            row.get::<_, Uuid>(0)?,
+                            row.get::<_, Uuid>(1)?,
+                            row.get(2)?,
+                            row.get(3)?,
+                            row.get(4)?,
+                        ))
+                    })?
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .await
+            .into_report()
+            .change_context(QueryError)?;
+
+        rows.into_iter()
+            .map(|(webhook_id, owned_by_id, target_url, secret, filter)| {
+                Webhook::new(
+                    WebhookId::new(webhook_id),
+                    OwnedById::new(AccountId::new(owned_by_id)),
+                    target_url,
+                    secret,
+                    filter,
+                )
+                .change_context(QueryError)
+            })
+            .collect()
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn insert_dead_letter(&mut self, dead_letter: &DeadLetter) -> Result<(), InsertionError> {
+        let _timer = metrics::store_operation("create", "webhook_dead_letters");
+
+        let webhook_id = dead_letter.webhook_id();
+        let entity_edition_id = dead_letter.entity_edition_id();
+        let dead_letter = dead_letter.clone();
+        self.run(move |session| {
+            session.connection.execute(
+                r#"
+                    INSERT INTO webhook_dead_letters (
+                        webhook_id,
+                        owned_by_id,
+                        entity_uuid,
+                        entity_record_id,
+                        payload,
+                        attempts,
+                        error,
+                        failed_at
+                    )
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);
+                "#,
+                rusqlite::params![
+                    dead_letter.webhook_id().as_uuid(),
+                    entity_edition_id.base_id().owned_by_id().as_uuid(),
+                    entity_edition_id.base_id().entity_uuid().as_uuid(),
+                    entity_edition_id.record_id().as_i64(),
+                    dead_letter.payload(),
+                    i64::from(dead_letter.attempts()),
+                    dead_letter.error(),
+                    Timestamp::<()>::now().truncate_to_microseconds(),
+                ],
+            )
+        })
+        .await
+        .into_report()
+        .change_context(InsertionError)
+        .attach_printable(webhook_id)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn dead_letters(&self, webhook_id: WebhookId) -> Result<Vec<DeadLetter>, QueryError> {
+        let _timer = metrics::store_operation("read", "webhook_dead_letters");
+
+        let rows = self
+            .run(move |session| {
+                session
+                    .connection
+                    .prepare_cached(
+                        r#"
+                            SELECT owned_by_id, entity_uuid, entity_record_id, payload, attempts, error
+                            FROM webhook_dead_letters
+                            WHERE webhook_id = ?1
+                            ORDER BY failed_at, rowid;
+                        "#,
+                    )?
+                    .query_map([webhook_id.as_uuid()], |row| {
+                        Ok((
+                            row.get::<_, Uuid>(0)?,
+                            row.get::<_, Uuid>(1)?,
+                            row.get::<_, i64>(2)?,
+                            row.get(3)?,
+                            row.get::<_, i64>(4)?,
+                            row.get(5)?,
+                        ))
+                    })?
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .await
+            .into_report()
+            .change_context(QueryError)?;
+
+        rows.into_iter()
+            .map(
+                |(owned_by_id, entity_uuid, entity_record_id, payload, attempts, error)| {
+                    Ok(DeadLetter::new(
+                        webhook_id,
+                        EntityEditionId::new(
+                            EntityId::new(
+                                OwnedById::new(AccountId::new(owned_by_id)),
+                                EntityUuid::new(entity_uuid),
+                            ),
+                            EntityRecordId::new(entity_record_id),
+                        ),
+                        payload,
+                        u32::try_from(attempts)
+                            .into_report()
+                            .change_context(QueryError)?,
+                        error,
+                    ))
+                },
+            )
+            .collect()
+    }
+}
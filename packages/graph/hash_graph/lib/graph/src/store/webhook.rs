@@ -0,0 +1,858 @@
+//! Push delivery of entity changes to registered webhooks.
+//!
+//! A [`Webhook`] is registered with a [`Filter`] over entities and a target URL. After an entity
+//! was created or updated, the [`WebhookDispatcher`] evaluates the filter of every registered
+//! webhook against the new edition of the entity and sends a signed [`WebhookPayload`] to each
+//! matching target. Every attempt is bounded by a request timeout and failed deliveries are retried
+//! with an exponential backoff. When the retries are exhausted, the delivery is stored as a
+//! [`DeadLetter`].
+//!
+//! Targets, which resolve to a loopback, private, link-local or otherwise non-public address, are
+//! not contacted unless the dispatcher explicitly [allows private targets].
+//!
+//! [allows private targets]: WebhookDispatcher::allow_private_targets
+
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use error_stack::{ensure, IntoReport, Report, Result, ResultExt};
+use futures::future::join_all;
+use hmac::{Hmac, Mac};
+use hyper::{
+    client::{connect::Connect, HttpConnector},
+    header::CONTENT_TYPE,
+    Body, Client, Request, Uri,
+};
+use postgres_types::{FromSql, ToSql};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    identifier::{knowledge::EntityEditionId, time::UnresolvedTimeProjection},
+    knowledge::Entity,
+    provenance::OwnedById,
+    store::{crud::Read, query::Filter, InsertionError, QueryError, StorePool},
+};
+
+/// The header containing the signature of a [`WebhookPayload`].
+///
+/// The value is `sha256=` followed by the hex-encoded HMAC-SHA256 of the request body, keyed with
+/// the secret of the [`Webhook`].
+pub const SIGNATURE_HEADER: &str = "x-hash-signature-256";
+
+#[derive(Debug)]
+#[must_use]
+pub struct InvalidWebhookFilter;
+
+impl fmt::Display for InvalidWebhookFilter {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("The filter of the webhook is not a valid entity filter")
+    }
+}
+
+impl error_stack::Context for InvalidWebhookFilter {}
+
+#[derive(Debug)]
+#[must_use]
+pub struct InvalidWebhookTargetUrl;
+
+impl fmt::Display for InvalidWebhookTargetUrl {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("The target URL of the webhook is not a valid HTTP or HTTPS URL")
+    }
+}
+
+impl error_stack::Context for InvalidWebhookTargetUrl {}
+
+#[derive(Debug)]
+#[must_use]
+pub struct DeliveryError;
+
+impl fmt::Display for DeliveryError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Could not deliver webhook payload")
+    }
+}
+
+impl error_stack::Context for DeliveryError {}
+
+#[derive(Debug)]
+#[must_use]
+pub struct DispatchError;
+
+impl fmt::Display for DispatchError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Could not dispatch entity change to webhooks")
+    }
+}
+
+impl error_stack::Context for DispatchError {}
+
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    ToSchema,
+    FromSql,
+    ToSql,
+)]
+#[repr(transparent)]
+#[postgres(transparent)]
+pub struct WebhookId(Uuid);
+
+impl WebhookId {
+    #[must_use]
+    pub const fn new(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+
+    #[must_use]
+    pub const fn as_uuid(self) -> Uuid {
+        self.0
+    }
+}
+
+impl fmt::Display for WebhookId {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{}", &self.0)
+    }
+}
+
+/// A registered subscription to changes of entities matching a [`Filter`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    webhook_id: WebhookId,
+    owned_by_id: OwnedById,
+    target_url: String,
+    #[serde(skip)]
+    secret: String,
+    #[schema(value_type = Object)]
+    filter: serde_json::Value,
+}
+
+impl Webhook {
+    /// Creates a new `Webhook`.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidWebhookFilter`], if `filter` cannot be parsed as a [`Filter`] over entities
+    pub fn new(
+        webhook_id: WebhookId,
+        owned_by_id: OwnedById,
+        target_url: String,
+        secret: String,
+        filter: serde_json::Value,
+    ) -> Result<Self, InvalidWebhookFilter> {
+        let webhook = Self {
+            webhook_id,
+            owned_by_id,
+            target_url,
+            secret,
+            filter,
+        };
+        webhook.parse_filter()?;
+        Ok(webhook)
+    }
+
+    #[must_use]
+    pub const fn webhook_id(&self) -> WebhookId {
+        self.webhook_id
+    }
+
+    #[must_use]
+    pub const fn owned_by_id(&self) -> OwnedById {
+        self.owned_by_id
+    }
+
+    #[must_use]
+    pub fn target_url(&self) -> &str {
+        &self.target_url
+    }
+
+    #[must_use]
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    #[must_use]
+    pub const fn filter(&self) -> &serde_json::Value {
+        &self.filter
+    }
+
+    /// Checks that the target URL is an absolute `http` or `https` URL.
+    ///
+    /// The addresses the target resolves to are not checked here, as they may change until the
+    /// webhook is triggered. The [`WebhookDispatcher`] checks them before every delivery attempt.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidWebhookTargetUrl`], if the target URL cannot be parsed or uses another scheme
+    pub fn validate_target_url(&self) -> Result<(), InvalidWebhookTargetUrl> {
+        let uri = self
+            .target_url
+            .parse::<Uri>()
+            .into_report()
+            .change_context(InvalidWebhookTargetUrl)
+            .attach_printable_lazy(|| self.target_url.clone())?;
+
+        ensure!(
+            matches!(uri.scheme_str(), Some("http" | "https")) && uri.authority().is_some(),
+            Report::new(InvalidWebhookTargetUrl).attach_printable(self.target_url.clone())
+        );
+
+        Ok(())
+    }
+
+    fn parse_filter(&self) -> Result<Filter<'_, Entity>, InvalidWebhookFilter> {
+        let mut filter = Filter::deserialize(&self.filter)
+            .into_report()
+            .change_context(InvalidWebhookFilter)?;
+        filter
+            .convert_parameters()
+            .change_context(InvalidWebhookFilter)?;
+        Ok(filter)
+    }
+}
+
+/// The body sent to a [`Webhook`] for a matching entity edition.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPayload<'e> {
+    webhook_id: WebhookId,
+    entity: &'e Entity,
+}
+
+/// A delivery, which could not be completed after all retries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadLetter {
+    webhook_id: WebhookId,
+    entity_edition_id: EntityEditionId,
+    payload: serde_json::Value,
+    attempts: u32,
+    error: String,
+}
+
+impl DeadLetter {
+    #[must_use]
+    pub const fn new(
+        webhook_id: WebhookId,
+        entity_edition_id: EntityEditionId,
+        payload: serde_json::Value,
+        attempts: u32,
+        error: String,
+    ) -> Self {
+        Self {
+            webhook_id,
+            entity_edition_id,
+            payload,
+            attempts,
+            error,
+        }
+    }
+
+    #[must_use]
+    pub const fn webhook_id(&self) -> WebhookId {
+        self.webhook_id
+    }
+
+    #[must_use]
+    pub const fn entity_edition_id(&self) -> EntityEditionId {
+        self.entity_edition_id
+    }
+
+    #[must_use]
+    pub const fn payload(&self) -> &serde_json::Value {
+        &self.payload
+    }
+
+    #[must_use]
+    pub const fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    #[must_use]
+    pub fn error(&self) -> &str {
+        &self.error
+    }
+}
+
+/// Describes the API of a store implementation for [`Webhook`]s.
+#[async_trait]
+pub trait WebhookStore: Read<Entity> {
+    /// Registers the specified [`Webhook`].
+    ///
+    /// # Errors
+    ///
+    /// - [`InvalidWebhookTargetUrl`], if the target URL is not an `http` or `https` URL
+    /// - if the account referred to by the `owned_by_id` of the [`Webhook`] does not exist
+    /// - if a [`Webhook`] with the same [`WebhookId`] already exists
+    async fn create_webhook(&mut self, webhook: &Webhook) -> Result<(), InsertionError>;
+
+    /// Returns all registered [`Webhook`]s.
+    ///
+    /// # Errors
+    ///
+    /// - if reading from the store failed
+    async fn webhooks(&self) -> Result<Vec<Webhook>, QueryError>;
+
+    /// Stores a delivery, which could not be completed.
+    ///
+    /// # Errors
+    ///
+    /// - if the [`Webhook`] referred to by the [`DeadLetter`] does not exist
+    async fn insert_dead_letter(&mut self, dead_letter: &DeadLetter) -> Result<(), InsertionError>;
+
+    /// Returns the deliveries to the [`Webhook`] identified by `webhook_id`, which could not be
+    /// completed.
+    ///
+    /// # Errors
+    ///
+    /// - if reading from the store failed
+    async fn dead_letters(&self, webhook_id: WebhookId) -> Result<Vec<DeadLetter>, QueryError>;
+}
+
+/// Controls how long a delivery attempt may take and how often and how fast a failed delivery is
+/// retried.
+///
+/// The delay before the `n`-th retry is `initial_backoff * 2^(n-1)`, capped at `max_backoff`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+pub struct WebhookRetryPolicy {
+    /// The maximum number of delivery attempts of a webhook payload, including the first one.
+    #[cfg_attr(
+        feature = "clap",
+        clap(
+            long = "webhook-max-attempts",
+            default_value_t = 5,
+            env = "HASH_GRAPH_WEBHOOK_MAX_ATTEMPTS"
+        )
+    )]
+    max_attempts: u32,
+
+    /// The number of milliseconds to wait before the first retry of a webhook delivery.
+    #[cfg_attr(
+        feature = "clap",
+        clap(
+            long = "webhook-initial-backoff",
+            default_value_t = 1000,
+            env = "HASH_GRAPH_WEBHOOK_INITIAL_BACKOFF"
+        )
+    )]
+    initial_backoff: u64,
+
+    /// The maximum number of milliseconds to wait between two attempts of a webhook delivery.
+    #[cfg_attr(
+        feature = "clap",
+        clap(
+            long = "webhook-max-backoff",
+            default_value_t = 60_000,
+            env = "HASH_GRAPH_WEBHOOK_MAX_BACKOFF"
+        )
+    )]
+    max_backoff: u64,
+
+    /// The number of milliseconds to wait for the response of a webhook target before the attempt
+    /// is considered failed.
+    #[cfg_attr(
+        feature = "clap",
+        clap(
+            long = "webhook-request-timeout",
+            default_value_t = 10_000,
+            env = "HASH_GRAPH_WEBHOOK_REQUEST_TIMEOUT"
+        )
+    )]
+    request_timeout: u64,
+}
+
+impl Default for WebhookRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: 1000,
+            max_backoff: 60_000,
+            request_timeout: 10_000,
+        }
+    }
+}
+
+impl WebhookRetryPolicy {
+    #[must_use]
+    pub const fn new(
+        max_attempts: u32,
+        initial_backoff: Duration,
+        max_backoff: Duration,
+        request_timeout: Duration,
+    ) -> Self {
+        Self {
+            max_attempts,
+            initial_backoff: initial_backoff.as_millis() as u64,
+            max_backoff: max_backoff.as_millis() as u64,
+            request_timeout: request_timeout.as_millis() as u64,
+        }
+    }
+
+    #[must_use]
+    pub const fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    #[must_use]
+    pub const fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout)
+    }
+
+    /// Returns the delay before the `retry`-th retry, starting at `1`.
+    #[must_use]
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2_u64.saturating_pow(retry.saturating_sub(1));
+        Duration::from_millis(
+            self.initial_backoff
+                .saturating_mul(factor)
+                .min(self.max_backoff),
+        )
+    }
+}
+
+/// Signs `body` with `secret` as described in [`SIGNATURE_HEADER`].
+#[must_use]
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take a key of any size");
+    mac.update(body);
+
+    let signature = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!("sha256={signature}")
+}
+
+/// Returns if `address` is reachable on the public internet.
+///
+/// Loopback, unspecified, private, shared, link-local, documentation, broadcast and multicast
+/// addresses are not public. IPv4 addresses embedded in IPv6 addresses are checked as IPv4
+/// addresses.
+#[must_use]
+pub fn is_public_address(address: IpAddr) -> bool {
+    match address {
+        IpAddr::V4(address) => is_public_ipv4_address(address),
+        IpAddr::V6(address) => is_public_ipv6_address(address),
+    }
+}
+
+const fn is_public_ipv4_address(address: Ipv4Addr) -> bool {
+    let [first, second, ..] = address.octets();
+    // 100.64.0.0/10 is reserved for carrier-grade NAT
+    let is_shared = first == 100 && (second & 0b1100_0000) == 64;
+
+    !(address.is_loopback()
+        || address.is_unspecified()
+        || address.is_private()
+        || address.is_link_local()
+        || address.is_documentation()
+        || address.is_broadcast()
+        || address.is_multicast()
+        || is_shared)
+}
+
+fn is_public_ipv6_address(address: Ipv6Addr) -> bool {
+    if let Some(address) = address.to_ipv4_mapped() {
+        return is_public_ipv4_address(address);
+    }
+
+    let first_segment = address.segments()[0];
+    // fc00::/7 are unique local addresses and fe80::/10 are link-local addresses
+    let is_unique_local = (first_segment & 0xFE00) == 0xFC00;
+    let is_link_local = (first_segment & 0xFFC0) == 0xFE80;
+
+    !(address.is_loopback()
+        || address.is_unspecified()
+        || address.is_multicast()
+        || is_unique_local
+        || is_link_local)
+}
+
+/// Delivers entity changes to the registered [`Webhook`]s.
+///
+/// The dispatcher uses a plain HTTP connector by default, a client with a different connector
+/// (e.g. one supporting TLS) can be provided with [`with_client`].
+///
+/// Only targets resolving to public addresses are contacted, so a webhook cannot be used to reach
+/// services on the network of the graph. Local receivers (e.g. in tests) have to be enabled with
+/// [`allow_private_targets`].
+///
+/// [`with_client`]: Self::with_client
+/// [`allow_private_targets`]: Self::allow_private_targets
+#[derive(Debug, Clone)]
+pub struct WebhookDispatcher<C = HttpConnector> {
+    client: Client<C>,
+    retry_policy: WebhookRetryPolicy,
+    allow_private_targets: bool,
+}
+
+impl WebhookDispatcher {
+    #[must_use]
+    pub fn new(retry_policy: WebhookRetryPolicy) -> Self {
+        Self::with_client(Client::new(), retry_policy)
+    }
+}
+
+impl<C> WebhookDispatcher<C>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
+    #[must_use]
+    pub const fn with_client(client: Client<C>, retry_policy: WebhookRetryPolicy) -> Self {
+        Self {
+            client,
+            retry_policy,
+            allow_private_targets: false,
+        }
+    }
+
+    /// Sets whether targets resolving to non-public addresses, e.g. `localhost`, are contacted.
+    ///
+    /// This is disabled by default and should only be enabled if the webhook receivers are
+    /// trusted, e.g. in tests or local deployments.
+    #[must_use]
+    pub const fn allow_private_targets(mut self, allow_private_targets: bool) -> Self {
+        self.allow_private_targets = allow_private_targets;
+        self
+    }
+
+    /// Resolves the host of the target of the `webhook` and checks that every resolved address is
+    /// public.
+    async fn check_target_addresses(&self, webhook: &Webhook) -> Result<(), DeliveryError> {
+        if self.allow_private_targets {
+            return Ok(());
+        }
+
+        let uri = webhook
+            .target_url()
+            .parse::<Uri>()
+            .into_report()
+            .change_context(DeliveryError)?;
+        let host = uri
+            .host()
+            .ok_or_else(|| Report::new(DeliveryError).attach_printable("missing host"))?;
+        // IPv6 literals are enclosed in brackets
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        let port = uri
+            .port_u16()
+            .unwrap_or(if uri.scheme_str() == Some("https") {
+                443
+            } else {
+                80
+            });
+
+        let addresses = tokio::net::lookup_host((host, port))
+            .await
+            .into_report()
+            .change_context(DeliveryError)
+            .attach_printable_lazy(|| format!("could not resolve `{host}`"))?;
+        for address in addresses {
+            ensure!(
+                is_public_address(address.ip()),
+                Report::new(DeliveryError).attach_printable(format!(
+                    "the webhook target resolves to the non-public address {}",
+                    address.ip()
+                ))
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Sends the signed `body` to the target of the `webhook` once.
+    ///
+    /// The attempt fails if the target resolves to a non-public address, unless private targets
+    /// are allowed, or if it does not respond within the request timeout of the
+    /// [`WebhookRetryPolicy`].
+    async fn send(&self, webhook: &Webhook, body: &[u8]) -> Result<(), DeliveryError> {
+        self.check_target_addresses(webhook).await?;
+
+        let request = Request::post(webhook.target_url())
+            .header(CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, sign(webhook.secret(), body))
+            .body(Body::from(body.to_vec()))
+            .into_report()
+            .change_context(DeliveryError)?;
+        let response = tokio::time::timeout(
+            self.retry_policy.request_timeout(),
+            self.client.request(request),
+        )
+        .await
+        .into_report()
+        .change_context(DeliveryError)
+        .attach_printable("the webhook target did not respond in time")?
+        .into_report()
+        .change_context(DeliveryError)?;
+
+        let status = response.status();
+        ensure!(
+            status.is_success(),
+            Report::new(DeliveryError)
+                .attach_printable(format!("unexpected status code: {status}"))
+        );
+
+        Ok(())
+    }
+
+    /// Delivers `entity` to the `webhook` and retries according to the [`WebhookRetryPolicy`].
+    ///
+    /// Returns the [`DeadLetter`] to store, if all attempts failed.
+    async fn deliver(
+        &self,
+        webhook: &Webhook,
+        entity: &Entity,
+    ) -> core::result::Result<(), DeadLetter> {
+        let payload = serde_json::to_value(WebhookPayload {
+            webhook_id: webhook.webhook_id(),
+            entity,
+        })
+        .expect("entity should be serializable");
+        let body = payload.to_string();
+
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let error = match self.send(webhook, body.as_bytes()).await {
+                Ok(()) => return Ok(()),
+                Err(report) => report,
+            };
+
+            tracing::warn!(
+                webhook_id = %webhook.webhook_id(),
+                attempt,
+                error = ?error,
+                "Could not deliver webhook payload"
+            );
+            if attempt >= self.retry_policy.max_attempts() {
+                return Err(DeadLetter::new(
+                    webhook.webhook_id(),
+                    entity.metadata().edition_id(),
+                    payload,
+                    attempt,
+                    format!("{error:?}"),
+                ));
+            }
+            tokio::time::sleep(self.retry_policy.backoff(attempt)).await;
+        }
+    }
+
+    /// Sends every `(webhook, entity)` delivery concurrently.
+    ///
+    /// Returns the [`DeadLetter`]s of the deliveries, which failed after all retries.
+    async fn deliver_all(&self, deliveries: &[(Webhook, Entity)]) -> Vec<DeadLetter> {
+        join_all(
+            deliveries
+                .iter()
+                .map(|(webhook, entity)| self.deliver(webhook, entity)),
+        )
+        .await
+        .into_iter()
+        .filter_map(core::result::Result::err)
+        .inspect(|dead_letter| {
+            tracing::error!(
+                webhook_id = %dead_letter.webhook_id(),
+                "Giving up on webhook delivery after {} attempts",
+                dead_letter.attempts()
+            );
+        })
+        .collect()
+    }
+
+    /// Delivers the entity edition identified by `edition_id` to all [`Webhook`]s, whose filter
+    /// matches it.
+    ///
+    /// The deliveries run concurrently. Deliveries, which failed after all retries, are stored in
+    /// the `store` as [`DeadLetter`]s. The `store` is borrowed until all deliveries are finished,
+    /// [`dispatch_from_pool`] should be used instead if the store is a pooled connection.
+    ///
+    /// # Errors
+    ///
+    /// - if the registered [`Webhook`]s could not be read
+    /// - if a filter could not be evaluated
+    /// - if a [`DeadLetter`] could not be stored
+    ///
+    /// [`dispatch_from_pool`]: Self::dispatch_from_pool
+    pub async fn dispatch<S>(
+        &self,
+        store: &mut S,
+        edition_id: EntityEditionId,
+    ) -> Result<(), DispatchError>
+    where
+        S: WebhookStore + Send,
+    {
+        let deliveries = matching_deliveries(store, edition_id).await?;
+        let dead_letters = self.deliver_all(&deliveries).await;
+        insert_dead_letters(store, &dead_letters).await
+    }
+
+    /// Delivers the entity edition identified by `edition_id` to all [`Webhook`]s, whose filter
+    /// matches it, without holding a store of the `pool` while delivering.
+    ///
+    /// The matching webhooks and the entity are read before the deliveries start. They are read
+    /// from the primary database, as a read replica may not contain the new edition yet. A store is
+    /// only acquired again to insert the [`DeadLetter`]s of deliveries, which failed after all
+    /// retries.
+    ///
+    /// # Errors
+    ///
+    /// - if a store could not be acquired from the `pool`
+    /// - if the registered [`Webhook`]s could not be read
+    /// - if a filter could not be evaluated
+    /// - if a [`DeadLetter`] could not be stored
+    pub async fn dispatch_from_pool<P>(
+        &self,
+        pool: &P,
+        edition_id: EntityEditionId,
+    ) -> Result<(), DispatchError>
+    where
+        P: StorePool,
+    {
+        let deliveries = {
+            let store = pool
+                .acquire()
+                .await
+                .map_err(|report| report.change_context(DispatchError))?;
+            matching_deliveries(&store, edition_id).await?
+        };
+
+        let dead_letters = self.deliver_all(&deliveries).await;
+        if dead_letters.is_empty() {
+            return Ok(());
+        }
+
+        let mut store = pool
+            .acquire()
+            .await
+            .map_err(|report| report.change_context(DispatchError))?;
+        insert_dead_letters(&mut store, &dead_letters).await
+    }
+}
+
+/// Reads the [`Webhook`]s, whose filter matches the entity edition identified by `edition_id`,
+/// together with the entity.
+async fn matching_deliveries<S>(
+    store: &S,
+    edition_id: EntityEditionId,
+) -> Result<Vec<(Webhook, Entity)>, DispatchError>
+where
+    S: WebhookStore,
+{
+    let time_projection = UnresolvedTimeProjection::default().resolve();
+
+    let mut deliveries = Vec::new();
+    for webhook in store.webhooks().await.change_context(DispatchError)? {
+        let filter = Filter::All(vec![
+            webhook.parse_filter().change_context(DispatchError)?,
+            Filter::for_entity_by_edition_id(edition_id),
+        ]);
+        let entity = Read::<Entity>::read(store, &filter, &time_projection)
+            .await
+            .change_context(DispatchError)?
+            .pop();
+        if let Some(entity) = entity {
+            deliveries.push((webhook, entity));
+        }
+    }
+
+    Ok(deliveries)
+}
+
+/// Stores the `dead_letters` and reports every one, which could not be stored.
+async fn insert_dead_letters<S>(
+    store: &mut S,
+    dead_letters: &[DeadLetter],
+) -> Result<(), DispatchError>
+where
+    S: WebhookStore + Send,
+{
+    let mut result: Result<(), DispatchError> = Ok(());
+    for dead_letter in dead_letters {
+        if let Err(report) = store.insert_dead_letter(dead_letter).await {
+            let report = report.change_context(DispatchError);
+            match &mut result {
+                Err(error) => error.extend_one(report),
+                Ok(()) => result = Err(report),
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff() {
+        let policy = WebhookRetryPolicy::new(
+            5,
+            Duration::from_millis(100),
+            Duration::from_millis(500),
+            Duration::from_secs(1),
+        );
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff(4), Duration::from_millis(500));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn public_addresses() {
+        for address in [
+            "127.0.0.1",
+            "0.0.0.0",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+        ] {
+            let address = address.parse().expect("invalid address");
+            assert!(!is_public_address(address), "{address} is public");
+        }
+
+        for address in ["93.184.216.34", "100.128.0.1", "2606:2800:220:1::1"] {
+            let address = address.parse().expect("invalid address");
+            assert!(is_public_address(address), "{address} is not public");
+        }
+    }
+
+    #[test]
+    fn signature() {
+        // Test vector 2 of RFC 4231
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -0,0 +1,674 @@
+use alloc::vec::{self, Vec};
+use core::{cmp::Ordering, fmt, marker::PhantomData, ops::Bound, slice};
+
+use crate::{
+    bounds::{LowerBound, LowerBoundHelper, UpperBound, UpperBoundHelper},
+    Interval,
+};
+
+/// A set of points represented by intervals.
+///
+/// The intervals are kept sorted by their lower bound, do not overlap and are not adjacent to each
+/// other. Inserting an interval coalesces it with all intervals it overlaps or is adjacent to, so
+/// two sets containing the same points always consist of the same intervals.
+///
+/// The intervals are stored in a contiguous, sorted [`Vec`]:
+///
+/// - Lookups are done by binary search and take logarithmic time.
+/// - [`insert`] and [`remove`] find the affected intervals in logarithmic time, but shift all
+///   following intervals, so they take linear time in the number of intervals in the set.
+/// - [`Extend`] and [`FromIterator`] sort the new intervals and merge them with the set in a single
+///   pass, so adding `m` intervals to a set of `n` intervals takes `O(m log m + n)` time instead of
+///   `O(m * n)` for repeated insertions.
+/// - [`union`], [`intersection`] and [`difference`] take linear time in the number of intervals in
+///   both sets.
+///
+/// [`insert`]: Self::insert
+/// [`remove`]: Self::remove
+/// [`union`]: Self::union
+/// [`intersection`]: Self::intersection
+/// [`difference`]: Self::difference
+pub struct IntervalSet<I, T> {
+    intervals: Vec<I>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<I, T> IntervalSet<I, T> {
+    /// Creates an empty set.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of disjoint intervals in this set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /// Returns `true` if the set does not contain any points.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Returns an iterator over the disjoint intervals of this set in ascending order.
+    pub fn iter(&self) -> slice::Iter<'_, I> {
+        self.intervals.iter()
+    }
+
+    /// Returns the disjoint intervals of this set in ascending order.
+    #[must_use]
+    pub fn as_slice(&self) -> &[I] {
+        &self.intervals
+    }
+}
+
+impl<I, T> IntervalSet<I, T>
+where
+    I: Interval<T>,
+    T: PartialOrd,
+{
+    /// Adds all points of `interval` to the set.
+    ///
+    /// This takes linear time in the number of intervals in the set. Use [`Extend`] to add many
+    /// intervals at once.
+    pub fn insert(&mut self, interval: I) {
+        let start = self
+            .intervals
+            .partition_point(|existing| is_separated(existing, &interval));
+        let end = start
+            + self.intervals[start..]
+                .partition_point(|existing| !is_separated(&interval, existing));
+
+        let merged = self
+            .intervals
+            .drain(start..end)
+            .fold(interval, merge_intervals);
+        self.intervals.insert(start, merged);
+    }
+
+    /// Removes all points of `interval` from the set.
+    ///
+    /// This takes linear time in the number of intervals in the set. Use [`difference`] to remove
+    /// many intervals at once.
+    ///
+    /// [`difference`]: Self::difference
+    pub fn remove(&mut self, interval: &I)
+    where
+        I: Clone,
+    {
+        let start = self.intervals.partition_point(|existing| {
+            existing.upper_bound().cmp_lower(interval.lower_bound()) == Ordering::Less
+        });
+        let end = start
+            + self.intervals[start..].partition_point(|existing| {
+                existing.lower_bound().cmp_upper(interval.upper_bound()) != Ordering::Greater
+            });
+
+        let remaining = self
+            .intervals
+            .drain(start..end)
+            .flat_map(|existing| existing.difference(interval.clone()))
+            .collect::<Vec<_>>();
+        self.intervals.splice(start..start, remaining);
+    }
+
+    /// Returns the interval of this set, which contains `point`.
+    #[must_use]
+    pub fn get(&self, point: &T) -> Option<&I> {
+        let index = self
+            .intervals
+            .partition_point(|interval| lower_bound_contains(interval.lower_bound(), point));
+        let interval = self.intervals.get(index.checked_sub(1)?)?;
+        upper_bound_contains(interval.upper_bound(), point).then_some(interval)
+    }
+
+    /// Returns `true` if `point` is contained in this set.
+    #[must_use]
+    pub fn contains_point(&self, point: &T) -> bool {
+        self.get(point).is_some()
+    }
+
+    /// Returns `true` if all points of `interval` are contained in this set.
+    #[must_use]
+    pub fn contains(&self, interval: &impl Interval<T>) -> bool {
+        let index = self.intervals.partition_point(|existing| {
+            existing.lower_bound().cmp_lower(interval.lower_bound()) != Ordering::Greater
+        });
+        index
+            .checked_sub(1)
+            .and_then(|index| self.intervals.get(index))
+            .map_or(false, |existing| {
+                existing.upper_bound().cmp_upper(interval.upper_bound()) != Ordering::Less
+            })
+    }
+
+    /// Returns `true` if any point of `interval` is contained in this set.
+    #[must_use]
+    pub fn overlaps(&self, interval: &impl Interval<T>) -> bool {
+        let index = self.intervals.partition_point(|existing| {
+            existing.upper_bound().cmp_lower(interval.lower_bound()) == Ordering::Less
+        });
+        self.intervals
+            .get(index)
+            .map_or(false, |existing| existing.overlaps(interval))
+    }
+
+    /// Returns a set containing all points, which are contained in either set.
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        let mut intervals = Vec::with_capacity(self.len() + other.len());
+        let mut lhs = self.intervals.into_iter().peekable();
+        let mut rhs = other.intervals.into_iter().peekable();
+
+        loop {
+            let next = match (lhs.peek(), rhs.peek()) {
+                (Some(lhs_interval), Some(rhs_interval))
+                    if lhs_interval
+                        .lower_bound()
+                        .cmp_lower(rhs_interval.lower_bound())
+                        == Ordering::Greater =>
+                {
+                    rhs.next()
+                }
+                (Some(_), _) => lhs.next(),
+                (None, _) => rhs.next(),
+            };
+            let Some(next) = next else {
+                break;
+            };
+
+            match intervals.pop() {
+                Some(last) if !is_separated(&last, &next) => {
+                    intervals.push(merge_intervals(last, next));
+                }
+                Some(last) => {
+                    intervals.push(last);
+                    intervals.push(next);
+                }
+                None => intervals.push(next),
+            }
+        }
+
+        Self::from_normalized(intervals)
+    }
+
+    /// Returns a set containing all points, which are contained in both sets.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self
+    where
+        I: Clone,
+    {
+        let mut intervals = Vec::new();
+        let (mut lhs_index, mut rhs_index) = (0, 0);
+
+        while let (Some(lhs), Some(rhs)) = (
+            self.intervals.get(lhs_index),
+            other.intervals.get(rhs_index),
+        ) {
+            if let Some(intersection) = lhs.clone().intersect(rhs.clone()) {
+                intervals.push(intersection);
+            }
+
+            // The interval ending first cannot overlap with any further interval of the other set
+            if lhs.upper_bound().cmp_upper(rhs.upper_bound()) == Ordering::Less {
+                lhs_index += 1;
+            } else {
+                rhs_index += 1;
+            }
+        }
+
+        Self::from_normalized(intervals)
+    }
+
+    /// Returns a set containing all points of this set, which are not contained in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self
+    where
+        I: Clone,
+    {
+        let mut intervals = Vec::with_capacity(self.len());
+        let mut rhs_start = 0;
+
+        for lhs in &self.intervals {
+            // Skip all intervals, which end before the current interval starts
+            while other.intervals.get(rhs_start).map_or(false, |rhs| {
+                rhs.upper_bound().cmp_lower(lhs.lower_bound()) == Ordering::Less
+            }) {
+                rhs_start += 1;
+            }
+
+            let mut remaining = Some(lhs.clone());
+            let mut rhs_index = rhs_start;
+            while let Some(current) = remaining.take() {
+                match other.intervals.get(rhs_index) {
+                    Some(rhs)
+                        if rhs.lower_bound().cmp_upper(current.upper_bound())
+                            != Ordering::Greater =>
+                    {
+                        // Everything before `rhs` is final, everything after it may overlap with
+                        // the next interval of `other`.
+                        for piece in current.difference(rhs.clone()) {
+                            if piece.upper_bound().cmp_lower(rhs.lower_bound()) == Ordering::Less {
+                                intervals.push(piece);
+                            } else {
+                                remaining = Some(piece);
+                            }
+                        }
+                        rhs_index += 1;
+                    }
+                    _ => intervals.push(current),
+                }
+            }
+        }
+
+        Self::from_normalized(intervals)
+    }
+
+    /// Returns a set containing all points, which are not contained in this set.
+    #[must_use]
+    pub fn complement(&self) -> Self
+    where
+        I: Clone,
+    {
+        Self::from(I::from_bounds(
+            <I::LowerBound as LowerBound<T>>::from_bound(Bound::Unbounded),
+            <I::UpperBound as UpperBound<T>>::from_bound(Bound::Unbounded),
+        ))
+        .difference(self)
+    }
+
+    /// Creates a set from intervals, which are already sorted, non-overlapping and non-adjacent.
+    const fn from_normalized(intervals: Vec<I>) -> Self {
+        Self {
+            intervals,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Returns `true` if `lhs` ends before `rhs` starts and there is a gap between them.
+fn is_separated<T: PartialOrd>(lhs: &impl Interval<T>, rhs: &impl Interval<T>) -> bool {
+    lhs.upper_bound().cmp_lower(rhs.lower_bound()) == Ordering::Less
+        && !lhs.upper_bound().is_adjacent_to(rhs.lower_bound())
+}
+
+fn merge_intervals<I: Interval<T>, T: PartialOrd>(lhs: I, rhs: I) -> I {
+    lhs.merge(rhs)
+        .next()
+        .expect("merging two intervals always results in one interval")
+}
+
+fn lower_bound_contains<T: PartialOrd>(bound: &impl LowerBound<T>, point: &T) -> bool {
+    match bound.as_bound() {
+        Bound::Included(value) => value <= point,
+        Bound::Excluded(value) => value < point,
+        Bound::Unbounded => true,
+    }
+}
+
+fn upper_bound_contains<T: PartialOrd>(bound: &impl UpperBound<T>, point: &T) -> bool {
+    match bound.as_bound() {
+        Bound::Included(value) => point <= value,
+        Bound::Excluded(value) => point < value,
+        Bound::Unbounded => true,
+    }
+}
+
+impl<I, T> Default for IntervalSet<I, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Clone, T> Clone for IntervalSet<I, T> {
+    fn clone(&self) -> Self {
+        Self {
+            intervals: self.intervals.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<I: fmt::Debug, T> fmt::Debug for IntervalSet<I, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_set().entries(&self.intervals).finish()
+    }
+}
+
+impl<I: PartialEq, T> PartialEq for IntervalSet<I, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.intervals == other.intervals
+    }
+}
+
+impl<I: Eq, T> Eq for IntervalSet<I, T> {}
+
+impl<I, T> From<I> for IntervalSet<I, T>
+where
+    I: Interval<T>,
+    T: PartialOrd,
+{
+    fn from(interval: I) -> Self {
+        Self::from_normalized(alloc::vec![interval])
+    }
+}
+
+impl<I, T> FromIterator<I> for IntervalSet<I, T>
+where
+    I: Interval<T>,
+    T: PartialOrd,
+{
+    fn from_iter<It: IntoIterator<Item = I>>(iter: It) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<I, T> Extend<I> for IntervalSet<I, T>
+where
+    I: Interval<T>,
+    T: PartialOrd,
+{
+    fn extend<It: IntoIterator<Item = I>>(&mut self, iter: It) {
+        let mut intervals = iter.into_iter().collect::<Vec<_>>();
+        intervals.sort_by(|lhs, rhs| lhs.lower_bound().cmp_lower(rhs.lower_bound()));
+
+        // As the intervals are sorted, an interval can only be coalesced with the previous one
+        let mut normalized: Vec<I> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match normalized.pop() {
+                Some(last) if !is_separated(&last, &interval) => {
+                    normalized.push(merge_intervals(last, interval));
+                }
+                Some(last) => {
+                    normalized.push(last);
+                    normalized.push(interval);
+                }
+                None => normalized.push(interval),
+            }
+        }
+
+        *self = core::mem::take(self).union(Self::from_normalized(normalized));
+    }
+}
+
+impl<I, T> IntoIterator for IntervalSet<I, T> {
+    type IntoIter = vec::IntoIter<I>;
+    type Item = I;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.into_iter()
+    }
+}
+
+impl<'a, I, T> IntoIterator for &'a IntervalSet<I, T> {
+    type IntoIter = slice::Iter<'a, I>;
+    type Item = &'a I;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use core::ops::{Bound, RangeBounds};
+
+    use super::IntervalSet;
+    use crate::{bounds::LowerBoundHelper, Interval, IntervalBounds};
+
+    type Set = IntervalSet<IntervalBounds<u32>, u32>;
+
+    /// Every bound used in the exhaustive tests is one of these values.
+    ///
+    /// The points in between and around the values are probed as well, so sampling all points
+    /// from `0` to `10` is sufficient to compare two sets.
+    const VALUES: [u32; 4] = [2, 4, 6, 8];
+    const POINTS: core::ops::RangeInclusive<u32> = 0..=10;
+
+    fn bounds() -> impl Iterator<Item = Bound<u32>> + Clone {
+        core::iter::once(Bound::Unbounded).chain(
+            VALUES
+                .into_iter()
+                .flat_map(|value| [Bound::Included(value), Bound::Excluded(value)]),
+        )
+    }
+
+    /// Returns every valid interval, which can be built from the [`VALUES`].
+    fn intervals() -> Vec<IntervalBounds<u32>> {
+        bounds()
+            .flat_map(|lower| bounds().map(move |upper| (lower, upper)))
+            .filter(|(lower, upper)| !lower.cmp_upper(upper).is_gt())
+            .map(|(lower, upper)| IntervalBounds::from_bounds(lower, upper))
+            .collect()
+    }
+
+    fn assert_points(set: &Set, expected: impl Fn(u32) -> bool, operation: &str) {
+        for window in set.as_slice().windows(2) {
+            assert!(
+                !window[0].overlaps(&window[1]) && !window[0].is_adjacent_to(&window[1]),
+                "{operation}: {set:?} is not coalesced"
+            );
+            assert!(
+                window[0]
+                    .lower_bound()
+                    .cmp_lower(window[1].lower_bound())
+                    .is_lt(),
+                "{operation}: {set:?} is not sorted"
+            );
+        }
+
+        for point in POINTS {
+            assert_eq!(
+                set.contains_point(&point),
+                expected(point),
+                "{operation}: unexpected lookup of {point} in {set:?}"
+            );
+            assert_eq!(
+                set.iter().any(|interval| interval.contains(&point)),
+                expected(point),
+                "{operation}: unexpected intervals for {point} in {set:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn insert() {
+        let intervals = intervals();
+        for lhs in &intervals {
+            for rhs in &intervals {
+                let set = Set::from_iter([*lhs, *rhs]);
+                assert_points(
+                    &set,
+                    |point| lhs.contains(&point) || rhs.contains(&point),
+                    "insert",
+                );
+                assert_eq!(set, Set::from_iter([*rhs, *lhs]), "insertion order matters");
+
+                for third in &intervals {
+                    let mut set = set.clone();
+                    set.insert(*third);
+                    assert_points(
+                        &set,
+                        |point| {
+                            lhs.contains(&point) || rhs.contains(&point) || third.contains(&point)
+                        },
+                        "insert",
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn remove() {
+        let intervals = intervals();
+        for lhs in &intervals {
+            for rhs in &intervals {
+                let mut set = Set::from(*lhs);
+                set.remove(rhs);
+                assert_points(
+                    &set,
+                    |point| lhs.contains(&point) && !rhs.contains(&point),
+                    "remove",
+                );
+
+                for third in &intervals {
+                    let mut set = Set::from_iter([*lhs, *rhs]);
+                    set.remove(third);
+                    assert_points(
+                        &set,
+                        |point| {
+                            (lhs.contains(&point) || rhs.contains(&point))
+                                && !third.contains(&point)
+                        },
+                        "remove",
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn set_operations() {
+        let intervals = intervals();
+        for first in &intervals {
+            for second in &intervals {
+                let pair = Set::from_iter([*first, *second]);
+                let in_pair = |point| first.contains(&point) || second.contains(&point);
+
+                for other in &intervals {
+                    let single = Set::from(*other);
+                    let in_single = |point| other.contains(&point);
+
+                    assert_points(
+                        &pair.clone().union(single.clone()),
+                        |point| in_pair(point) || in_single(point),
+                        "union",
+                    );
+                    assert_points(
+                        &single.clone().union(pair.clone()),
+                        |point| in_pair(point) || in_single(point),
+                        "union",
+                    );
+                    assert_points(
+                        &pair.intersection(&single),
+                        |point| in_pair(point) && in_single(point),
+                        "intersection",
+                    );
+                    assert_points(
+                        &single.intersection(&pair),
+                        |point| in_pair(point) && in_single(point),
+                        "intersection",
+                    );
+                    assert_points(
+                        &pair.difference(&single),
+                        |point| in_pair(point) && !in_single(point),
+                        "difference",
+                    );
+                    assert_points(
+                        &single.difference(&pair),
+                        |point| in_single(point) && !in_pair(point),
+                        "difference",
+                    );
+                }
+
+                assert_points(&pair.complement(), |point| !in_pair(point), "complement");
+            }
+        }
+    }
+
+    #[test]
+    fn containment() {
+        let intervals = intervals();
+        for first in &intervals {
+            for second in &intervals {
+                let set = Set::from_iter([*first, *second]);
+                let in_set = |point| first.contains(&point) || second.contains(&point);
+
+                for other in &intervals {
+                    assert_eq!(
+                        set.contains(other),
+                        POINTS.filter(|point| other.contains(point)).all(in_set),
+                        "{set:?} containing {other:?}"
+                    );
+                    assert_eq!(
+                        set.overlaps(other),
+                        POINTS.filter(|point| other.contains(point)).any(in_set),
+                        "{set:?} overlapping {other:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn extend() {
+        let intervals = intervals();
+        for first in &intervals {
+            for second in &intervals {
+                for third in &intervals {
+                    let mut extended = Set::from(*first);
+                    extended.extend([*second, *third]);
+
+                    let mut inserted = Set::from(*first);
+                    inserted.insert(*second);
+                    inserted.insert(*third);
+
+                    assert_eq!(extended, inserted, "extending differs from inserting");
+                }
+            }
+        }
+
+        let mut set = Set::new();
+        set.extend(intervals.iter().copied());
+        assert_points(&set, |_| true, "extend");
+    }
+
+    #[test]
+    fn coalesces_intervals() {
+        let set = Set::from_iter([
+            IntervalBounds::from_bounds(Bound::Included(6), Bound::Excluded(8)),
+            IntervalBounds::from_bounds(Bound::Included(0), Bound::Excluded(2)),
+            IntervalBounds::from_bounds(Bound::Included(2), Bound::Included(4)),
+        ]);
+
+        assert_eq!(set.as_slice(), [
+            IntervalBounds::from_bounds(Bound::Included(0), Bound::Included(4)),
+            IntervalBounds::from_bounds(Bound::Included(6), Bound::Excluded(8)),
+        ]);
+        assert_eq!(
+            set.get(&3),
+            Some(&IntervalBounds::from_bounds(
+                Bound::Included(0),
+                Bound::Included(4)
+            ))
+        );
+        assert_eq!(set.get(&5), None);
+        assert_eq!(set.get(&8), None);
+    }
+
+    #[test]
+    fn remove_splits_interval() {
+        let mut set = Set::from(IntervalBounds::from_bounds(
+            Bound::Unbounded,
+            Bound::Unbounded,
+        ));
+        set.remove(&IntervalBounds::from_bounds(
+            Bound::Included(2),
+            Bound::Excluded(4),
+        ));
+
+        assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![
+            IntervalBounds::from_bounds(Bound::Unbounded, Bound::Excluded(2)),
+            IntervalBounds::from_bounds(Bound::Included(4), Bound::Unbounded),
+        ]);
+    }
+}
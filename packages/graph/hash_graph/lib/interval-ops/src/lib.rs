@@ -8,11 +8,13 @@ extern crate alloc;
 mod bounds;
 mod interval;
 mod interval_bounds;
+mod interval_set;
 
 pub use self::{
     bounds::{LowerBound, UpperBound},
     interval::Interval,
     interval_bounds::IntervalBounds,
+    interval_set::IntervalSet,
 };
 
 #[inline(never)]
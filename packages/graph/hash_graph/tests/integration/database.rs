@@ -0,0 +1,702 @@
+//! Helpers shared by the integration tests of every [`Store`] implementation.
+
+use std::borrow::Cow;
+
+use error_stack::Result;
+use graph::{
+    identifier::{
+        account::AccountId,
+        knowledge::{EntityEditionId, EntityId},
+        ontology::OntologyTypeEditionId,
+        time::{
+            TimespanBound, Timestamp, TransactionTime, UnresolvedImage, UnresolvedKernel,
+            UnresolvedProjection, UnresolvedTimeProjection,
+        },
+        EntityVertexId, GraphElementVertexId,
+    },
+    knowledge::{
+        DraftId, Entity, EntityDiff, EntityLinkOrder, EntityMetadata, EntityProperties,
+        EntityQueryPath, EntityUuid, LinkData,
+    },
+    ontology::{
+        DataTypeWithMetadata, EntityTypeQueryPath, EntityTypeWithMetadata, OntologyElementMetadata,
+        PropertyTypeWithMetadata,
+    },
+    provenance::{OwnedById, UpdatedById},
+    store::{
+        fetcher::{FetchError, OntologyTypeFetcher, OntologyTypeReference},
+        query::{Filter, FilterExpression, Parameter},
+        webhook::{DeadLetter, DispatchError, Webhook, WebhookDispatcher, WebhookId},
+        InsertionError, QueryError, Store, StoreError, Transaction, UpdateError,
+    },
+    subgraph::{edges::GraphResolveDepths, query::StructuralQuery, Subgraph},
+};
+use type_system::{repr, uri::VersionedUri, DataType, EntityType, PropertyType};
+use uuid::Uuid;
+
+pub struct DatabaseApi<S> {
+    store: S,
+    account_id: AccountId,
+}
+
+impl<S: Transaction + Send> DatabaseApi<S> {
+    pub async fn commit(self) -> Result<(), StoreError> {
+        self.store.commit().await
+    }
+}
+
+// TODO: Add get_all_* methods
+impl<S: Store + Send> DatabaseApi<S> {
+    /// Inserts a new account and the provided types through `store`.
+    pub async fn seed<D, P, E>(
+        mut store: S,
+        data_types: D,
+        property_types: P,
+        entity_types: E,
+    ) -> Result<Self, InsertionError>
+    where
+        D: IntoIterator<Item = &'static str>,
+        P: IntoIterator<Item = &'static str>,
+        E: IntoIterator<Item = &'static str>,
+    {
+        let account_id = AccountId::new(Uuid::new_v4());
+        store
+            .insert_account_id(account_id)
+            .await
+            .expect("could not insert account id");
+
+        for data_type_str in data_types {
+            let data_type_repr: repr::DataType = serde_json::from_str(data_type_str)
+                .expect("could not parse data type representation");
+            store
+                .create_data_type(
+                    DataType::try_from(data_type_repr).expect("could not parse data type"),
+                    OwnedById::new(account_id),
+                    UpdatedById::new(account_id),
+                )
+                .await?;
+        }
+
+        for property_type_str in property_types {
+            let property_type_repr: repr::PropertyType = serde_json::from_str(property_type_str)
+                .expect("could not parse property type representation");
+            store
+                .create_property_type(
+                    PropertyType::try_from(property_type_repr)
+                        .expect("could not parse property type"),
+                    OwnedById::new(account_id),
+                    UpdatedById::new(account_id),
+                )
+                .await?;
+        }
+
+        for entity_type_str in entity_types {
+            let entity_type_repr: repr::EntityType = serde_json::from_str(entity_type_str)
+                .expect("could not parse entity type representation");
+            store
+                .create_entity_type(
+                    EntityType::try_from(entity_type_repr).expect("could not parse entity type"),
+                    OwnedById::new(account_id),
+                    UpdatedById::new(account_id),
+                )
+                .await?;
+        }
+
+        Ok(Self { store, account_id })
+    }
+
+    /// Starts a transaction, which acts on behalf of the same account.
+    pub async fn transaction(&mut self) -> Result<DatabaseApi<S::Transaction<'_>>, StoreError> {
+        Ok(DatabaseApi {
+            store: self.store.transaction().await?,
+            account_id: self.account_id,
+        })
+    }
+
+    pub async fn import_missing_types<C>(
+        &mut self,
+        fetcher: &OntologyTypeFetcher<C>,
+        references: impl IntoIterator<Item = OntologyTypeReference> + Send,
+    ) -> Result<Vec<OntologyElementMetadata>, FetchError>
+    where
+        C: hyper::client::connect::Connect + Clone + Send + Sync + 'static,
+    {
+        fetcher
+            .import_missing(
+                &mut self.store,
+                references,
+                OwnedById::new(self.account_id),
+                UpdatedById::new(self.account_id),
+            )
+            .await
+    }
+
+    pub async fn create_webhook(
+        &mut self,
+        target_url: impl Into<String> + Send,
+        secret: impl Into<String> + Send,
+        filter: serde_json::Value,
+    ) -> Result<Webhook, InsertionError> {
+        let webhook = Webhook::new(
+            WebhookId::new(Uuid::new_v4()),
+            OwnedById::new(self.account_id),
+            target_url.into(),
+            secret.into(),
+            filter,
+        )
+        .expect("invalid webhook filter");
+        self.store.create_webhook(&webhook).await?;
+        Ok(webhook)
+    }
+
+    pub async fn dispatch_to_webhooks<C>(
+        &mut self,
+        dispatcher: &WebhookDispatcher<C>,
+        edition_id: EntityEditionId,
+    ) -> Result<(), DispatchError>
+    where
+        C: hyper::client::connect::Connect + Clone + Send + Sync + 'static,
+    {
+        dispatcher.dispatch(&mut self.store, edition_id).await
+    }
+
+    pub async fn get_dead_letters(
+        &self,
+        webhook_id: WebhookId,
+    ) -> Result<Vec<DeadLetter>, QueryError> {
+        self.store.dead_letters(webhook_id).await
+    }
+
+    pub async fn create_data_type(
+        &mut self,
+        data_type: DataType,
+    ) -> Result<OntologyElementMetadata, InsertionError> {
+        self.store
+            .create_data_type(
+                data_type,
+                OwnedById::new(self.account_id),
+                UpdatedById::new(self.account_id),
+            )
+            .await
+    }
+
+    pub async fn get_data_type(
+        &mut self,
+        uri: &VersionedUri,
+    ) -> Result<DataTypeWithMetadata, QueryError> {
+        Ok(self
+            .get_data_type_at(uri, None)
+            .await?
+            .expect("no data type found"))
+    }
+
+    /// Returns the data type as it was known at `transaction_time`, or the latest if `None`.
+    pub async fn get_data_type_at(
+        &mut self,
+        uri: &VersionedUri,
+        transaction_time: Option<Timestamp<TransactionTime>>,
+    ) -> Result<Option<DataTypeWithMetadata>, QueryError> {
+        Ok(self
+            .store
+            .get_data_type(&StructuralQuery {
+                filter: Filter::for_versioned_uri(uri),
+                graph_resolve_depths: GraphResolveDepths::default(),
+                time_projection: UnresolvedTimeProjection::DecisionTime(UnresolvedProjection {
+                    kernel: UnresolvedKernel::new(transaction_time),
+                    image: UnresolvedImage::new(
+                        Some(TimespanBound::Unbounded),
+                        Some(TimespanBound::Unbounded),
+                    ),
+                }),
+                draft_id: None,
+            })
+            .await?
+            .vertices
+            .data_types
+            .remove(&OntologyTypeEditionId::from(uri)))
+    }
+
+    pub async fn update_data_type(
+        &mut self,
+        data_type: DataType,
+    ) -> Result<OntologyElementMetadata, UpdateError> {
+        self.store
+            .update_data_type(data_type, UpdatedById::new(self.account_id))
+            .await
+    }
+
+    pub async fn create_property_type(
+        &mut self,
+        property_type: PropertyType,
+    ) -> Result<OntologyElementMetadata, InsertionError> {
+        self.store
+            .create_property_type(
+                property_type,
+                OwnedById::new(self.account_id),
+                UpdatedById::new(self.account_id),
+            )
+            .await
+    }
+
+    pub async fn get_property_type(
+        &mut self,
+        uri: &VersionedUri,
+    ) -> Result<PropertyTypeWithMetadata, QueryError> {
+        Ok(self
+            .store
+            .get_property_type(&StructuralQuery {
+                filter: Filter::for_versioned_uri(uri),
+                graph_resolve_depths: GraphResolveDepths::default(),
+                time_projection: UnresolvedTimeProjection::DecisionTime(UnresolvedProjection {
+                    kernel: UnresolvedKernel::new(None),
+                    image: UnresolvedImage::new(
+                        Some(TimespanBound::Unbounded),
+                        Some(TimespanBound::Unbounded),
+                    ),
+                }),
+                draft_id: None,
+            })
+            .await?
+            .vertices
+            .property_types
+            .remove(&OntologyTypeEditionId::from(uri))
+            .expect("no property type found"))
+    }
+
+    pub async fn update_property_type(
+        &mut self,
+        property_type: PropertyType,
+    ) -> Result<OntologyElementMetadata, UpdateError> {
+        self.store
+            .update_property_type(property_type, UpdatedById::new(self.account_id))
+            .await
+    }
+
+    pub async fn create_entity_type(
+        &mut self,
+        entity_type: EntityType,
+    ) -> Result<OntologyElementMetadata, InsertionError> {
+        self.store
+            .create_entity_type(
+                entity_type,
+                OwnedById::new(self.account_id),
+                UpdatedById::new(self.account_id),
+            )
+            .await
+    }
+
+    pub async fn get_entity_type(
+        &mut self,
+        uri: &VersionedUri,
+    ) -> Result<EntityTypeWithMetadata, QueryError> {
+        Ok(self
+            .store
+            .get_entity_type(&StructuralQuery {
+                filter: Filter::for_versioned_uri(uri),
+                graph_resolve_depths: GraphResolveDepths::default(),
+                time_projection: UnresolvedTimeProjection::DecisionTime(UnresolvedProjection {
+                    kernel: UnresolvedKernel::new(None),
+                    image: UnresolvedImage::new(
+                        Some(TimespanBound::Unbounded),
+                        Some(TimespanBound::Unbounded),
+                    ),
+                }),
+                draft_id: None,
+            })
+            .await?
+            .vertices
+            .entity_types
+            .remove(&OntologyTypeEditionId::from(uri))
+            .expect("no entity type found"))
+    }
+
+    pub async fn get_entity_type_subgraph(
+        &mut self,
+        uri: &VersionedUri,
+        graph_resolve_depths: GraphResolveDepths,
+    ) -> Result<Subgraph, QueryError> {
+        self.store
+            .get_entity_type(&StructuralQuery {
+                filter: Filter::for_versioned_uri(uri),
+                graph_resolve_depths,
+                time_projection: UnresolvedTimeProjection::DecisionTime(UnresolvedProjection {
+                    kernel: UnresolvedKernel::new(None),
+                    image: UnresolvedImage::new(
+                        Some(TimespanBound::Unbounded),
+                        Some(TimespanBound::Unbounded),
+                    ),
+                }),
+                draft_id: None,
+            })
+            .await
+    }
+
+    pub async fn update_entity_type(
+        &mut self,
+        entity_type: EntityType,
+    ) -> Result<OntologyElementMetadata, UpdateError> {
+        self.store
+            .update_entity_type(entity_type, UpdatedById::new(self.account_id))
+            .await
+    }
+
+    pub async fn create_entity(
+        &mut self,
+        properties: EntityProperties,
+        entity_type_id: VersionedUri,
+        entity_uuid: Option<EntityUuid>,
+    ) -> Result<EntityMetadata, InsertionError> {
+        self.store
+            .create_entity(
+                OwnedById::new(self.account_id),
+                entity_uuid,
+                None,
+                UpdatedById::new(self.account_id),
+                false,
+                entity_type_id,
+                properties,
+                None,
+                None,
+                None,
+            )
+            .await
+    }
+
+    pub async fn get_entity(
+        &self,
+        entity_id: EntityId,
+        timestamp: Timestamp<TransactionTime>,
+    ) -> Result<Entity, QueryError> {
+        let entity_vertex_id = EntityVertexId::new(entity_id, timestamp.cast());
+        Ok(self
+            .store
+            .get_entity(&StructuralQuery {
+                filter: Entity::create_filter_for_vertex_id(&entity_vertex_id),
+                graph_resolve_depths: GraphResolveDepths::default(),
+                time_projection: UnresolvedTimeProjection::DecisionTime(UnresolvedProjection {
+                    kernel: UnresolvedKernel::new(None),
+                    image: UnresolvedImage::new(
+                        Some(TimespanBound::Unbounded),
+                        Some(TimespanBound::Unbounded),
+                    ),
+                }),
+                draft_id: None,
+            })
+            .await?
+            .vertices
+            .entities
+            .remove(&entity_vertex_id)
+            .expect("no entity found"))
+    }
+
+    pub async fn update_entity(
+        &mut self,
+        entity_id: EntityId,
+        properties: EntityProperties,
+        entity_type_id: VersionedUri,
+        property_source: Option<&str>,
+        link_order: EntityLinkOrder,
+    ) -> Result<EntityMetadata, UpdateError> {
+        self.store
+            .update_entity(
+                entity_id,
+                None,
+                UpdatedById::new(self.account_id),
+                false,
+                entity_type_id,
+                properties,
+                property_source.map(str::to_owned),
+                link_order,
+                None,
+            )
+            .await
+    }
+
+    pub async fn diff_entities(
+        &self,
+        from: Timestamp<TransactionTime>,
+        to: Timestamp<TransactionTime>,
+    ) -> Result<EntityDiff, QueryError> {
+        self.store.diff_entities(from, to, None).await
+    }
+
+    pub async fn create_draft(&mut self) -> Result<DraftId, InsertionError> {
+        self.store
+            .create_draft(OwnedById::new(self.account_id))
+            .await
+    }
+
+    pub async fn create_draft_entity(
+        &mut self,
+        properties: EntityProperties,
+        entity_type_id: VersionedUri,
+        draft_id: DraftId,
+    ) -> Result<EntityMetadata, InsertionError> {
+        self.store
+            .create_entity(
+                OwnedById::new(self.account_id),
+                None,
+                None,
+                UpdatedById::new(self.account_id),
+                false,
+                entity_type_id,
+                properties,
+                None,
+                None,
+                Some(draft_id),
+            )
+            .await
+    }
+
+    pub async fn update_draft_entity(
+        &mut self,
+        entity_id: EntityId,
+        properties: EntityProperties,
+        entity_type_id: VersionedUri,
+        draft_id: DraftId,
+    ) -> Result<EntityMetadata, UpdateError> {
+        self.store
+            .update_entity(
+                entity_id,
+                None,
+                UpdatedById::new(self.account_id),
+                false,
+                entity_type_id,
+                properties,
+                None,
+                EntityLinkOrder::new(None, None),
+                Some(draft_id),
+            )
+            .await
+    }
+
+    pub async fn publish_draft(
+        &mut self,
+        draft_id: DraftId,
+    ) -> Result<Vec<EntityEditionId>, UpdateError> {
+        self.store.publish_draft(draft_id).await
+    }
+
+    pub async fn discard_draft(&mut self, draft_id: DraftId) -> Result<(), UpdateError> {
+        self.store.discard_draft(draft_id).await
+    }
+
+    /// Returns the latest edition of every entity, including the editions of the draft if one is
+    /// passed.
+    pub async fn get_latest_entities(
+        &self,
+        draft_id: Option<DraftId>,
+    ) -> Result<Vec<Entity>, QueryError> {
+        let mut subgraph = self
+            .store
+            .get_entity(&StructuralQuery {
+                filter: Filter::Equal(
+                    Some(FilterExpression::Path(EntityQueryPath::ProjectedTime)),
+                    Some(FilterExpression::Parameter(Parameter::Text(Cow::Borrowed(
+                        "latest",
+                    )))),
+                ),
+                graph_resolve_depths: GraphResolveDepths::default(),
+                time_projection: UnresolvedTimeProjection::DecisionTime(UnresolvedProjection {
+                    kernel: UnresolvedKernel::new(None),
+                    image: UnresolvedImage::new(
+                        Some(TimespanBound::Unbounded),
+                        Some(TimespanBound::Unbounded),
+                    ),
+                }),
+                draft_id,
+            })
+            .await?;
+
+        Ok(subgraph
+            .roots
+            .into_iter()
+            .filter_map(|edition_id| match edition_id {
+                GraphElementVertexId::Ontology(_) => None,
+                GraphElementVertexId::KnowledgeGraph(edition_id) => {
+                    subgraph.vertices.entities.remove(&edition_id)
+                }
+            })
+            .collect())
+    }
+
+    async fn create_link_entity(
+        &mut self,
+        properties: EntityProperties,
+        entity_type_id: VersionedUri,
+        entity_uuid: Option<EntityUuid>,
+        left_entity_id: EntityId,
+        right_entity_id: EntityId,
+    ) -> Result<EntityMetadata, InsertionError> {
+        self.store
+            .create_entity(
+                OwnedById::new(self.account_id),
+                entity_uuid,
+                None,
+                UpdatedById::new(self.account_id),
+                false,
+                entity_type_id,
+                properties,
+                None,
+                Some(LinkData::new(left_entity_id, right_entity_id, None, None)),
+                None,
+            )
+            .await
+    }
+
+    pub async fn get_link_entity_target(
+        &self,
+        source_entity_id: EntityId,
+        link_type_id: VersionedUri,
+    ) -> Result<Entity, QueryError> {
+        let filter = Filter::All(vec![
+            Filter::Equal(
+                Some(FilterExpression::Path(EntityQueryPath::LeftEntity(
+                    Box::new(EntityQueryPath::Uuid),
+                ))),
+                Some(FilterExpression::Parameter(Parameter::Uuid(
+                    source_entity_id.entity_uuid().as_uuid(),
+                ))),
+            ),
+            Filter::Equal(
+                Some(FilterExpression::Path(EntityQueryPath::LeftEntity(
+                    Box::new(EntityQueryPath::OwnedById),
+                ))),
+                Some(FilterExpression::Parameter(Parameter::Uuid(
+                    source_entity_id.owned_by_id().as_uuid(),
+                ))),
+            ),
+            Filter::Equal(
+                Some(FilterExpression::Path(EntityQueryPath::Type(
+                    EntityTypeQueryPath::BaseUri,
+                ))),
+                Some(FilterExpression::Parameter(Parameter::Text(Cow::Borrowed(
+                    link_type_id.base_uri().as_str(),
+                )))),
+            ),
+            Filter::Equal(
+                Some(FilterExpression::Path(EntityQueryPath::Type(
+                    EntityTypeQueryPath::Version,
+                ))),
+                Some(FilterExpression::Parameter(Parameter::SignedInteger(
+                    link_type_id.version().into(),
+                ))),
+            ),
+        ]);
+
+        let mut subgraph = self
+            .store
+            .get_entity(&StructuralQuery {
+                filter,
+                graph_resolve_depths: GraphResolveDepths::default(),
+                time_projection: UnresolvedTimeProjection::DecisionTime(UnresolvedProjection {
+                    kernel: UnresolvedKernel::new(None),
+                    image: UnresolvedImage::new(
+                        Some(TimespanBound::Unbounded),
+                        Some(TimespanBound::Unbounded),
+                    ),
+                }),
+                draft_id: None,
+            })
+            .await?;
+
+        let roots = subgraph
+            .roots
+            .into_iter()
+            .filter_map(|edition_id| match edition_id {
+                GraphElementVertexId::Ontology(_) => None,
+                GraphElementVertexId::KnowledgeGraph(edition_id) => {
+                    subgraph.vertices.entities.remove(&edition_id)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        match roots.len() {
+            1 => Ok(roots.into_iter().next().unwrap()),
+            len => panic!("unexpected number of entities found, expected 1 but received {len}"),
+        }
+    }
+
+    pub async fn get_latest_entity_links(
+        &self,
+        source_entity_id: EntityId,
+    ) -> Result<Vec<Entity>, QueryError> {
+        let filter = Filter::All(vec![
+            Filter::Equal(
+                Some(FilterExpression::Path(EntityQueryPath::LeftEntity(
+                    Box::new(EntityQueryPath::Uuid),
+                ))),
+                Some(FilterExpression::Parameter(Parameter::Uuid(
+                    source_entity_id.entity_uuid().as_uuid(),
+                ))),
+            ),
+            Filter::Equal(
+                Some(FilterExpression::Path(EntityQueryPath::LeftEntity(
+                    Box::new(EntityQueryPath::OwnedById),
+                ))),
+                Some(FilterExpression::Parameter(Parameter::Uuid(
+                    source_entity_id.owned_by_id().as_uuid(),
+                ))),
+            ),
+            Filter::Equal(
+                Some(FilterExpression::Path(EntityQueryPath::ProjectedTime)),
+                Some(FilterExpression::Parameter(Parameter::Text(Cow::Borrowed(
+                    "latest",
+                )))),
+            ),
+            Filter::Equal(
+                Some(FilterExpression::Path(EntityQueryPath::Archived)),
+                Some(FilterExpression::Parameter(Parameter::Boolean(false))),
+            ),
+        ]);
+
+        let mut subgraph = self
+            .store
+            .get_entity(&StructuralQuery {
+                filter,
+                graph_resolve_depths: GraphResolveDepths::default(),
+                time_projection: UnresolvedTimeProjection::DecisionTime(UnresolvedProjection {
+                    kernel: UnresolvedKernel::new(None),
+                    image: UnresolvedImage::new(
+                        Some(TimespanBound::Unbounded),
+                        Some(TimespanBound::Unbounded),
+                    ),
+                }),
+                draft_id: None,
+            })
+            .await?;
+
+        Ok(subgraph
+            .roots
+            .into_iter()
+            .filter_map(|edition_id| match edition_id {
+                GraphElementVertexId::Ontology(_) => None,
+                GraphElementVertexId::KnowledgeGraph(edition_id) => {
+                    subgraph.vertices.entities.remove(&edition_id)
+                }
+            })
+            .collect())
+    }
+
+    async fn archive_entity(
+        &mut self,
+        entity_id: EntityId,
+        properties: EntityProperties,
+        entity_type_id: VersionedUri,
+        link_order: EntityLinkOrder,
+    ) -> Result<EntityMetadata, UpdateError> {
+        self.store
+            .update_entity(
+                entity_id,
+                None,
+                UpdatedById::new(self.account_id),
+                true,
+                entity_type_id,
+                properties,
+                None,
+                link_order,
+                None,
+            )
+            .await
+    }
+}
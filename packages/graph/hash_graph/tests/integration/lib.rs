@@ -1 +1,4 @@
+mod database;
 mod postgres;
+#[cfg(feature = "sqlite")]
+mod sqlite;
@@ -1,7 +1,7 @@
-use graph::ontology::OntologyTypeWithMetadata;
+use graph::{identifier::time::Timestamp, ontology::OntologyTypeWithMetadata};
 use type_system::{repr, DataType};
 
-use crate::postgres::DatabaseTestWrapper;
+use super::DatabaseTestWrapper;
 
 #[tokio::test]
 async fn insert() {
@@ -88,3 +88,37 @@ async fn update() {
     assert_eq!(&object_dt_v1, returned_object_dt_v1.inner());
     assert_eq!(&object_dt_v2, returned_object_dt_v2.inner());
 }
+
+#[tokio::test]
+async fn query_before_creation() {
+    let data_type_repr: repr::DataType =
+        serde_json::from_str(graph_test_data::data_type::EMPTY_LIST_V1)
+            .expect("could not parse data type representation");
+    let empty_list_dt = DataType::try_from(data_type_repr).expect("could not parse data type");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([], [], [])
+        .await
+        .expect("could not seed database");
+
+    api.create_data_type(empty_list_dt.clone())
+        .await
+        .expect("could not create data type");
+
+    let before_creation = "2000-01-01T00:00:00Z"
+        .parse()
+        .expect("could not parse timestamp");
+    let data_type = api
+        .get_data_type_at(empty_list_dt.id(), Some(before_creation))
+        .await
+        .expect("could not query data type");
+    assert!(data_type.is_none());
+
+    let data_type = api
+        .get_data_type_at(empty_list_dt.id(), Some(Timestamp::now()))
+        .await
+        .expect("could not query data type")
+        .expect("no data type found");
+    assert_eq!(data_type.inner(), &empty_list_dt);
+}
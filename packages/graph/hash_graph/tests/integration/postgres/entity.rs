@@ -1,8 +1,21 @@
-use graph::knowledge::{EntityLinkOrder, EntityProperties};
+use std::time::Duration;
+
+use graph::{
+    identifier::time::Timestamp,
+    knowledge::{EntityLinkOrder, EntityProperties},
+    ontology::ConstraintViolation,
+    store::error::DraftPublishConflict,
+};
 use graph_test_data::{data_type, entity, entity_type, property_type};
-use type_system::uri::{BaseUri, VersionedUri};
+use serde_json::json;
+use type_system::{
+    repr,
+    uri::{BaseUri, VersionedUri},
+    EntityType,
+};
+use uuid::Uuid;
 
-use crate::postgres::DatabaseTestWrapper;
+use super::DatabaseTestWrapper;
 
 #[tokio::test]
 async fn insert() {
@@ -121,6 +134,7 @@ async fn update() {
                     .expect("couldn't construct Base URI"),
                 1,
             ),
+            None,
             EntityLinkOrder::new(None, None),
         )
         .await
@@ -136,3 +150,531 @@ async fn update() {
 
     assert_eq!(entity_v2.properties(), &page_v2);
 }
+
+#[tokio::test]
+async fn property_provenance() {
+    let page_v1: EntityProperties =
+        serde_json::from_str(entity::PAGE_V1).expect("could not parse entity");
+    let page_v2: EntityProperties =
+        serde_json::from_str(entity::PAGE_V2).expect("could not parse entity");
+    let page_type_id = VersionedUri::new(
+        BaseUri::new("https://blockprotocol.org/@alice/types/entity-type/page/".to_owned())
+            .expect("couldn't construct Base URI"),
+        1,
+    );
+    let text_property =
+        BaseUri::new("https://blockprotocol.org/@alice/types/property-type/text/".to_owned())
+            .expect("couldn't construct Base URI");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([data_type::TEXT_V1], [property_type::TEXT_V1], [
+            entity_type::PAGE_V1,
+        ])
+        .await
+        .expect("could not seed database:");
+
+    let v1_metadata = api
+        .create_entity(page_v1, page_type_id.clone(), None)
+        .await
+        .expect("could not create entity");
+    let entity_v1 = api
+        .get_entity(
+            v1_metadata.edition_id().base_id(),
+            v1_metadata.version().transaction_time().start,
+        )
+        .await
+        .expect("could not get entity");
+    let v1_provenance = entity_v1
+        .property_provenance()
+        .get(&text_property)
+        .expect("no provenance for property");
+    assert_eq!(
+        v1_provenance.updated_at(),
+        v1_metadata.version().transaction_time().start
+    );
+    assert_eq!(v1_provenance.source(), None);
+
+    // Changing the value attributes the property to the update
+    let v2_metadata = api
+        .update_entity(
+            v1_metadata.edition_id().base_id(),
+            page_v2.clone(),
+            page_type_id.clone(),
+            Some("https://example.com/"),
+            EntityLinkOrder::new(None, None),
+        )
+        .await
+        .expect("could not update entity");
+    let entity_v2 = api
+        .get_entity(
+            v2_metadata.edition_id().base_id(),
+            v2_metadata.version().transaction_time().start,
+        )
+        .await
+        .expect("could not get entity");
+    let v2_provenance = entity_v2
+        .property_provenance()
+        .get(&text_property)
+        .expect("no provenance for property");
+    assert_eq!(
+        v2_provenance.updated_at(),
+        v2_metadata.version().transaction_time().start
+    );
+    assert_eq!(v2_provenance.source(), Some("https://example.com/"));
+
+    // Keeping the value keeps the provenance
+    let v3_metadata = api
+        .update_entity(
+            v2_metadata.edition_id().base_id(),
+            page_v2,
+            page_type_id,
+            None,
+            EntityLinkOrder::new(None, None),
+        )
+        .await
+        .expect("could not update entity");
+    let entity_v3 = api
+        .get_entity(
+            v3_metadata.edition_id().base_id(),
+            v3_metadata.version().transaction_time().start,
+        )
+        .await
+        .expect("could not get entity");
+    assert_eq!(
+        entity_v3.property_provenance(),
+        entity_v2.property_provenance()
+    );
+}
+
+#[tokio::test]
+async fn publish_draft() {
+    let page_v1: EntityProperties =
+        serde_json::from_str(entity::PAGE_V1).expect("could not parse entity");
+    let page_v2: EntityProperties =
+        serde_json::from_str(entity::PAGE_V2).expect("could not parse entity");
+    let page_type_id = VersionedUri::new(
+        BaseUri::new("https://blockprotocol.org/@alice/types/entity-type/page/".to_owned())
+            .expect("couldn't construct Base URI"),
+        1,
+    );
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([data_type::TEXT_V1], [property_type::TEXT_V1], [
+            entity_type::PAGE_V1,
+        ])
+        .await
+        .expect("could not seed database:");
+
+    let live_metadata = api
+        .create_entity(page_v1.clone(), page_type_id.clone(), None)
+        .await
+        .expect("could not create entity");
+
+    let draft_id = api.create_draft().await.expect("could not create draft");
+    let updated_metadata = api
+        .update_draft_entity(
+            live_metadata.edition_id().base_id(),
+            page_v2.clone(),
+            page_type_id.clone(),
+            draft_id,
+        )
+        .await
+        .expect("could not update entity in draft");
+    assert_eq!(updated_metadata.draft_id(), Some(draft_id));
+    let created_metadata = api
+        .create_draft_entity(page_v2.clone(), page_type_id, draft_id)
+        .await
+        .expect("could not create entity in draft");
+
+    // Without the draft, only the live edition is visible
+    let entities = api
+        .get_latest_entities(None)
+        .await
+        .expect("could not get entities");
+    assert_eq!(entities.len(), 1);
+    assert_eq!(entities[0].properties(), &page_v1);
+    assert_eq!(entities[0].metadata().draft_id(), None);
+
+    // With the draft, the draft editions replace the live editions
+    let mut entities = api
+        .get_latest_entities(Some(draft_id))
+        .await
+        .expect("could not get entities");
+    entities.sort_by_key(|entity| entity.metadata().edition_id().record_id());
+    assert_eq!(entities.len(), 2);
+    assert_eq!(
+        entities[0].metadata().edition_id(),
+        updated_metadata.edition_id()
+    );
+    assert_eq!(
+        entities[1].metadata().edition_id(),
+        created_metadata.edition_id()
+    );
+    assert!(
+        entities.iter().all(|entity| entity.properties() == &page_v2
+            && entity.metadata().draft_id() == Some(draft_id))
+    );
+
+    let mut published = api
+        .publish_draft(draft_id)
+        .await
+        .expect("could not publish draft");
+    published.sort_by_key(|edition_id| edition_id.record_id());
+    assert_eq!(published, [
+        updated_metadata.edition_id(),
+        created_metadata.edition_id()
+    ]);
+
+    let entities = api
+        .get_latest_entities(None)
+        .await
+        .expect("could not get entities");
+    assert_eq!(entities.len(), 2);
+    assert!(entities.iter().all(|entity| entity.properties() == &page_v2
+        && entity.metadata().draft_id().is_none()));
+
+    api.publish_draft(draft_id)
+        .await
+        .expect_err("could publish draft twice");
+}
+
+#[tokio::test]
+async fn publish_outdated_draft() {
+    let page_v1: EntityProperties =
+        serde_json::from_str(entity::PAGE_V1).expect("could not parse entity");
+    let page_v2: EntityProperties =
+        serde_json::from_str(entity::PAGE_V2).expect("could not parse entity");
+    let page_type_id = VersionedUri::new(
+        BaseUri::new("https://blockprotocol.org/@alice/types/entity-type/page/".to_owned())
+            .expect("couldn't construct Base URI"),
+        1,
+    );
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([data_type::TEXT_V1], [property_type::TEXT_V1], [
+            entity_type::PAGE_V1,
+        ])
+        .await
+        .expect("could not seed database:");
+
+    let live_metadata = api
+        .create_entity(page_v1.clone(), page_type_id.clone(), None)
+        .await
+        .expect("could not create entity");
+    let entity_id = live_metadata.edition_id().base_id();
+
+    let draft_id = api.create_draft().await.expect("could not create draft");
+    api.update_draft_entity(entity_id, page_v2.clone(), page_type_id.clone(), draft_id)
+        .await
+        .expect("could not update entity in draft");
+
+    let updated_metadata = api
+        .update_entity(
+            entity_id,
+            page_v2,
+            page_type_id,
+            None,
+            EntityLinkOrder::new(None, None),
+        )
+        .await
+        .expect("could not update live entity");
+
+    let report = api
+        .publish_draft(draft_id)
+        .await
+        .expect_err("could publish draft based on an outdated edition");
+    assert!(report.contains::<DraftPublishConflict>());
+
+    // Nothing was published, so the live update is kept
+    let entities = api
+        .get_latest_entities(None)
+        .await
+        .expect("could not get entities");
+    assert_eq!(entities.len(), 1);
+    assert_eq!(
+        entities[0].metadata().edition_id(),
+        updated_metadata.edition_id()
+    );
+}
+
+#[tokio::test]
+async fn publish_draft_during_update() {
+    let database = DatabaseTestWrapper::new().await;
+    let mut publisher = database
+        .seed_committed()
+        .await
+        .expect("could not seed database");
+    let mut updater = database
+        .seed_committed()
+        .await
+        .expect("could not seed database");
+
+    // The changes are visible to other connections and are kept after the test, so the entity
+    // type has to be unique
+    let entity_type_id = VersionedUri::new(
+        BaseUri::new(format!(
+            "https://example.com/@{}/types/entity-type/empty/",
+            Uuid::new_v4()
+        ))
+        .expect("couldn't construct Base URI"),
+        1,
+    );
+    let entity_type_repr: repr::EntityType = serde_json::from_value(json!({
+        "kind": "entityType",
+        "$id": entity_type_id.to_string(),
+        "type": "object",
+        "title": "Empty",
+        "properties": {}
+    }))
+    .expect("could not parse entity type representation");
+    publisher
+        .create_entity_type(
+            EntityType::try_from(entity_type_repr).expect("could not parse entity type"),
+        )
+        .await
+        .expect("could not create entity type");
+
+    let live_metadata = publisher
+        .create_entity(EntityProperties::empty(), entity_type_id.clone(), None)
+        .await
+        .expect("could not create entity");
+    let entity_id = live_metadata.edition_id().base_id();
+
+    let draft_id = publisher
+        .create_draft()
+        .await
+        .expect("could not create draft");
+    publisher
+        .update_draft_entity(
+            entity_id,
+            EntityProperties::empty(),
+            entity_type_id.clone(),
+            draft_id,
+        )
+        .await
+        .expect("could not update entity in draft");
+
+    let mut transaction = updater
+        .transaction()
+        .await
+        .expect("could not start transaction");
+    transaction
+        .update_entity(
+            entity_id,
+            EntityProperties::empty(),
+            entity_type_id,
+            None,
+            EntityLinkOrder::new(None, None),
+        )
+        .await
+        .expect("could not update live entity");
+
+    // The update is not committed yet, so publishing has to wait for it
+    let publish = tokio::spawn(async move { publisher.publish_draft(draft_id).await });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(
+        !publish.is_finished(),
+        "the draft was published while the live entity was updated"
+    );
+
+    transaction.commit().await.expect("could not commit update");
+
+    let report = publish
+        .await
+        .expect("could not join publishing task")
+        .expect_err("could publish draft based on an outdated edition");
+    assert!(report.contains::<DraftPublishConflict>());
+}
+
+#[tokio::test]
+async fn discard_draft() {
+    let page_v1: EntityProperties =
+        serde_json::from_str(entity::PAGE_V1).expect("could not parse entity");
+    let page_v2: EntityProperties =
+        serde_json::from_str(entity::PAGE_V2).expect("could not parse entity");
+    let page_type_id = VersionedUri::new(
+        BaseUri::new("https://blockprotocol.org/@alice/types/entity-type/page/".to_owned())
+            .expect("couldn't construct Base URI"),
+        1,
+    );
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([data_type::TEXT_V1], [property_type::TEXT_V1], [
+            entity_type::PAGE_V1,
+        ])
+        .await
+        .expect("could not seed database:");
+
+    let live_metadata = api
+        .create_entity(page_v1.clone(), page_type_id.clone(), None)
+        .await
+        .expect("could not create entity");
+
+    let draft_id = api.create_draft().await.expect("could not create draft");
+    api.update_draft_entity(
+        live_metadata.edition_id().base_id(),
+        page_v2.clone(),
+        page_type_id.clone(),
+        draft_id,
+    )
+    .await
+    .expect("could not update entity in draft");
+    api.create_draft_entity(page_v2, page_type_id, draft_id)
+        .await
+        .expect("could not create entity in draft");
+
+    api.discard_draft(draft_id)
+        .await
+        .expect("could not discard draft");
+
+    let entities = api
+        .get_latest_entities(None)
+        .await
+        .expect("could not get entities");
+    assert_eq!(entities.len(), 1);
+    assert_eq!(
+        entities[0].metadata().edition_id(),
+        live_metadata.edition_id()
+    );
+    assert_eq!(entities[0].properties(), &page_v1);
+
+    api.discard_draft(draft_id)
+        .await
+        .expect_err("could discard draft twice");
+}
+
+#[tokio::test]
+async fn diff() {
+    let page_v1: EntityProperties =
+        serde_json::from_str(entity::PAGE_V1).expect("could not parse entity");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([data_type::TEXT_V1], [property_type::TEXT_V1], [
+            entity_type::PAGE_V1,
+        ])
+        .await
+        .expect("could not seed database:");
+
+    let metadata = api
+        .create_entity(
+            page_v1,
+            VersionedUri::new(
+                BaseUri::new("https://blockprotocol.org/@alice/types/entity-type/page/".to_owned())
+                    .expect("couldn't construct Base URI"),
+                1,
+            ),
+            None,
+        )
+        .await
+        .expect("could not create entity");
+
+    let before_creation = "2000-01-01T00:00:00Z"
+        .parse()
+        .expect("could not parse timestamp");
+    let diff = api
+        .diff_entities(before_creation, Timestamp::now())
+        .await
+        .expect("could not diff entities");
+
+    assert_eq!(diff.created(), [metadata.edition_id()]);
+    assert!(diff.updated().is_empty());
+    assert!(diff.archived().is_empty());
+
+    let created_at = metadata.version().transaction_time().start;
+    let diff = api
+        .diff_entities(created_at, created_at)
+        .await
+        .expect("could not diff entities");
+
+    assert!(diff.is_empty());
+
+    let diff = api
+        .diff_entities(created_at, Timestamp::now())
+        .await
+        .expect("could not diff entities");
+
+    assert!(diff.is_empty());
+}
+
+const PERCENTAGE_DT: &str = r#"{
+  "kind": "dataType",
+  "$id": "https://example.com/@alice/types/data-type/percentage/v/1",
+  "title": "Percentage",
+  "type": "number",
+  "minimum": 0,
+  "maximum": 100
+}"#;
+
+const PROGRESS_PT: &str = r#"{
+  "kind": "propertyType",
+  "$id": "https://example.com/@alice/types/property-type/progress/v/1",
+  "title": "Progress",
+  "oneOf": [
+    {
+      "$ref": "https://example.com/@alice/types/data-type/percentage/v/1"
+    }
+  ]
+}"#;
+
+const TASK_ET: &str = r#"{
+  "kind": "entityType",
+  "$id": "https://example.com/@alice/types/entity-type/task/v/1",
+  "type": "object",
+  "title": "Task",
+  "properties": {
+    "https://example.com/@alice/types/property-type/progress/": {
+      "$ref": "https://example.com/@alice/types/property-type/progress/v/1"
+    }
+  }
+}"#;
+
+#[tokio::test]
+async fn reject_constraint_violation() {
+    let valid: EntityProperties = serde_json::from_str(
+        r#"{ "https://example.com/@alice/types/property-type/progress/": 42 }"#,
+    )
+    .expect("could not parse entity");
+    let invalid: EntityProperties = serde_json::from_str(
+        r#"{ "https://example.com/@alice/types/property-type/progress/": 142 }"#,
+    )
+    .expect("could not parse entity");
+    let task_et_id = VersionedUri::new(
+        BaseUri::new("https://example.com/@alice/types/entity-type/task/".to_owned())
+            .expect("couldn't construct Base URI"),
+        1,
+    );
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([PERCENTAGE_DT], [PROGRESS_PT], [TASK_ET])
+        .await
+        .expect("could not seed database");
+
+    let report = api
+        .create_entity(invalid.clone(), task_et_id.clone(), None)
+        .await
+        .expect_err("could create entity violating its data type constraints");
+    assert!(report.contains::<ConstraintViolation>());
+
+    let metadata = api
+        .create_entity(valid, task_et_id.clone(), None)
+        .await
+        .expect("could not create entity");
+
+    let report = api
+        .update_entity(
+            metadata.edition_id().base_id(),
+            invalid,
+            task_et_id,
+            None,
+            EntityLinkOrder::new(None, None),
+        )
+        .await
+        .expect_err("could update entity violating its data type constraints");
+    assert!(report.contains::<ConstraintViolation>());
+}
@@ -0,0 +1,158 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    io,
+    net::SocketAddr,
+    pin::Pin,
+    str::FromStr,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::{extract::OriginalUri, http::StatusCode, Extension, Router};
+use graph::{
+    ontology::OntologyTypeWithMetadata,
+    store::fetcher::{DomainNotAllowed, OntologyTypeFetcher, OntologyTypeReference},
+};
+use graph_test_data::{data_type, entity_type, property_type};
+use hyper::{service::Service, Client, Uri};
+use serde_json::Value;
+use tokio::net::TcpStream;
+use type_system::{repr, uri::VersionedUri, EntityType};
+
+use super::DatabaseTestWrapper;
+
+/// Connects every request to the stand-in server, regardless of the requested host.
+#[derive(Clone)]
+struct StandInConnector(SocketAddr);
+
+impl Service<Uri> for StandInConnector {
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+    type Response = TcpStream;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _uri: Uri) -> Self::Future {
+        Box::pin(TcpStream::connect(self.0))
+    }
+}
+
+/// Serves the provided ontology type schemas at the path of their `$id`.
+fn serve_types(
+    schemas: impl IntoIterator<Item = &'static str>,
+) -> OntologyTypeFetcher<StandInConnector> {
+    let schemas = schemas
+        .into_iter()
+        .map(|schema| {
+            let value: Value = serde_json::from_str(schema).expect("could not parse schema");
+            let id = value["$id"].as_str().expect("schema has no `$id`");
+            let path = Uri::from_str(id)
+                .expect("could not parse `$id`")
+                .path()
+                .to_owned();
+            (path, schema)
+        })
+        .collect::<HashMap<_, _>>();
+
+    let router = Router::new()
+        .fallback(
+            |Extension(schemas): Extension<Arc<HashMap<String, &'static str>>>,
+             OriginalUri(uri): OriginalUri| async move {
+                schemas
+                    .get(uri.path())
+                    .copied()
+                    .ok_or(StatusCode::NOT_FOUND)
+            },
+        )
+        .layer(Extension(Arc::new(schemas)));
+
+    let server = axum::Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0)))
+        .serve(router.into_make_service());
+    let address = server.local_addr();
+    tokio::spawn(server);
+
+    OntologyTypeFetcher::with_client(Client::builder().build(StandInConnector(address)), [
+        "blockprotocol.org",
+    ])
+}
+
+#[tokio::test]
+async fn import_missing_types() {
+    let fetcher = serve_types([
+        data_type::TEXT_V1,
+        property_type::NAME_V1,
+        entity_type::LINK_V1,
+        entity_type::link::FRIEND_OF_V1,
+    ]);
+
+    let person_et_repr: repr::EntityType = serde_json::from_str(entity_type::PERSON_V1)
+        .expect("could not parse entity type representation");
+    let person_et = EntityType::try_from(person_et_repr).expect("could not parse entity type");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([], [], [])
+        .await
+        .expect("could not seed database");
+
+    let imported = api
+        .import_missing_types(&fetcher, OntologyTypeReference::of_entity_type(&person_et))
+        .await
+        .expect("could not import missing types");
+    assert_eq!(imported.len(), 4);
+    assert!(
+        imported
+            .iter()
+            .all(|metadata| metadata.provenance_metadata().is_external())
+    );
+
+    let metadata = api
+        .create_entity_type(person_et)
+        .await
+        .expect("could not create entity type");
+    assert!(!metadata.provenance_metadata().is_external());
+
+    let name_pt = api
+        .get_property_type(
+            &VersionedUri::from_str(
+                "https://blockprotocol.org/@alice/types/property-type/name/v/1",
+            )
+            .expect("invalid versioned URI"),
+        )
+        .await
+        .expect("could not get property type");
+    assert!(name_pt.metadata().provenance_metadata().is_external());
+
+    let imported = api
+        .import_missing_types(&fetcher, [OntologyTypeReference::PropertyType(
+            name_pt.inner().id().clone(),
+        )])
+        .await
+        .expect("could not import missing types");
+    assert!(imported.is_empty());
+}
+
+#[tokio::test]
+async fn reject_domain_not_allowed() {
+    let fetcher = OntologyTypeFetcher::new(["example.com"]);
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([], [], [])
+        .await
+        .expect("could not seed database");
+
+    let report = api
+        .import_missing_types(&fetcher, [OntologyTypeReference::DataType(
+            VersionedUri::from_str(
+                "https://blockprotocol.org/@blockprotocol/types/data-type/text/v/1",
+            )
+            .expect("invalid versioned URI"),
+        )])
+        .await
+        .expect_err("could import type from a domain which is not allowed");
+    assert!(report.contains::<DomainNotAllowed>());
+}
@@ -2,7 +2,7 @@ use graph::ontology::OntologyTypeWithMetadata;
 use graph_test_data::{data_type, property_type};
 use type_system::{repr, PropertyType};
 
-use crate::postgres::DatabaseTestWrapper;
+use super::DatabaseTestWrapper;
 
 #[tokio::test]
 async fn insert() {
@@ -0,0 +1,198 @@
+use graph::{
+    store::{QueryLimitExceeded, QueryLimits},
+    subgraph::{
+        edges::{GraphResolveDepths, OntologyOutwardEdges, OutgoingEdgeResolveDepth},
+        Subgraph,
+    },
+};
+use graph_test_data::{data_type, entity_type, property_type};
+use type_system::uri::{BaseUri, VersionedUri};
+
+use super::DatabaseTestWrapper;
+
+fn person_type_id() -> VersionedUri {
+    VersionedUri::new(
+        BaseUri::new("https://blockprotocol.org/@alice/types/entity-type/person/".to_owned())
+            .expect("couldn't construct Base URI"),
+        1,
+    )
+}
+
+fn resolve_depths(constrains_properties_on: u8, constrains_values_on: u8) -> GraphResolveDepths {
+    GraphResolveDepths {
+        constrains_properties_on: OutgoingEdgeResolveDepth {
+            outgoing: constrains_properties_on,
+            incoming: 0,
+        },
+        constrains_values_on: OutgoingEdgeResolveDepth {
+            outgoing: constrains_values_on,
+            incoming: 0,
+        },
+        ..GraphResolveDepths::default()
+    }
+}
+
+fn query_limits(
+    max_constrains_properties_on_depth: u8,
+    max_vertices: Option<usize>,
+    truncate_subgraphs: bool,
+) -> QueryLimits {
+    let max_resolve_depths = QueryLimits::default().max_resolve_depths();
+    QueryLimits::new(
+        GraphResolveDepths {
+            constrains_properties_on: OutgoingEdgeResolveDepth {
+                outgoing: max_constrains_properties_on_depth,
+                incoming: 0,
+            },
+            ..max_resolve_depths
+        },
+        max_vertices,
+        None,
+        truncate_subgraphs,
+    )
+}
+
+#[tokio::test]
+async fn exceeding_depth_fails() {
+    let mut database = DatabaseTestWrapper::with_query_limits(query_limits(1, None, false)).await;
+    let mut api = database
+        .seed([data_type::TEXT_V1], [property_type::NAME_V1], [
+            entity_type::LINK_V1,
+            entity_type::link::FRIEND_OF_V1,
+            entity_type::PERSON_V1,
+        ])
+        .await
+        .expect("could not seed database");
+
+    let report = api
+        .get_entity_type_subgraph(&person_type_id(), resolve_depths(255, 0))
+        .await
+        .expect_err("query should exceed the depth limit");
+    assert!(report.contains::<QueryLimitExceeded>());
+}
+
+#[tokio::test]
+async fn exceeding_depth_truncates() {
+    let mut database = DatabaseTestWrapper::with_query_limits(query_limits(1, None, true)).await;
+    let mut api = database
+        .seed([data_type::TEXT_V1], [property_type::NAME_V1], [
+            entity_type::LINK_V1,
+            entity_type::link::FRIEND_OF_V1,
+            entity_type::PERSON_V1,
+        ])
+        .await
+        .expect("could not seed database");
+
+    let subgraph = api
+        .get_entity_type_subgraph(&person_type_id(), resolve_depths(255, 0))
+        .await
+        .expect("could not query entity type");
+
+    assert!(subgraph.incomplete);
+    assert_eq!(subgraph.depths.constrains_properties_on.outgoing, 1);
+    assert_eq!(subgraph.vertices.entity_types.len(), 1);
+    assert_eq!(subgraph.vertices.property_types.len(), 1);
+}
+
+#[tokio::test]
+async fn exceeding_vertices_truncates() {
+    let mut database =
+        DatabaseTestWrapper::with_query_limits(query_limits(u8::MAX, Some(2), true)).await;
+    let mut api = database
+        .seed([data_type::TEXT_V1], [property_type::NAME_V1], [
+            entity_type::LINK_V1,
+            entity_type::link::FRIEND_OF_V1,
+            entity_type::PERSON_V1,
+        ])
+        .await
+        .expect("could not seed database");
+
+    let subgraph = api
+        .get_entity_type_subgraph(&person_type_id(), resolve_depths(1, 1))
+        .await
+        .expect("could not query entity type");
+
+    assert!(subgraph.incomplete);
+    assert_eq!(subgraph.vertices.len(), 2);
+    assert!(subgraph.vertices.data_types.is_empty());
+}
+
+fn contains_ontology_vertex(subgraph: &Subgraph, edge: &OntologyOutwardEdges) -> bool {
+    match edge {
+        OntologyOutwardEdges::ToOntology(edge) => {
+            subgraph
+                .vertices
+                .data_types
+                .contains_key(&edge.right_endpoint)
+                || subgraph
+                    .vertices
+                    .property_types
+                    .contains_key(&edge.right_endpoint)
+                || subgraph
+                    .vertices
+                    .entity_types
+                    .contains_key(&edge.right_endpoint)
+        }
+        OntologyOutwardEdges::ToKnowledgeGraph(edge) => subgraph
+            .vertices
+            .entities
+            .contains_key(&edge.right_endpoint),
+    }
+}
+
+#[tokio::test]
+async fn truncated_subgraph_has_no_dangling_edges() {
+    let mut database =
+        DatabaseTestWrapper::with_query_limits(query_limits(u8::MAX, Some(2), true)).await;
+    let mut api = database
+        .seed([data_type::TEXT_V1], [property_type::NAME_V1], [
+            entity_type::LINK_V1,
+            entity_type::link::FRIEND_OF_V1,
+            entity_type::PERSON_V1,
+        ])
+        .await
+        .expect("could not seed database");
+
+    let subgraph = api
+        .get_entity_type_subgraph(&person_type_id(), resolve_depths(1, 1))
+        .await
+        .expect("could not query entity type");
+
+    assert!(subgraph.incomplete);
+    assert!(!subgraph.edges.ontology.is_empty());
+    for (vertex_id, edges) in &subgraph.edges.ontology {
+        assert!(
+            subgraph.vertices.property_types.contains_key(vertex_id)
+                || subgraph.vertices.entity_types.contains_key(vertex_id),
+            "edge source {vertex_id:?} is not a vertex of the subgraph"
+        );
+        for edge in edges {
+            assert!(
+                contains_ontology_vertex(&subgraph, edge),
+                "edge {edge:?} points to a vertex outside of the subgraph"
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn within_limits_is_complete() {
+    let mut database =
+        DatabaseTestWrapper::with_query_limits(query_limits(1, Some(3), false)).await;
+    let mut api = database
+        .seed([data_type::TEXT_V1], [property_type::NAME_V1], [
+            entity_type::LINK_V1,
+            entity_type::link::FRIEND_OF_V1,
+            entity_type::PERSON_V1,
+        ])
+        .await
+        .expect("could not seed database");
+
+    let subgraph = api
+        .get_entity_type_subgraph(&person_type_id(), resolve_depths(1, 1))
+        .await
+        .expect("could not query entity type");
+
+    assert!(!subgraph.incomplete);
+    assert_eq!(subgraph.vertices.len(), 3);
+}
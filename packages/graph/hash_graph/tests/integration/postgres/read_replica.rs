@@ -0,0 +1,106 @@
+use std::time::Duration;
+
+use graph::{
+    identifier::account::AccountId,
+    store::{
+        AccountStore, AsClient, DatabaseConnectionInfo, DatabaseType, PostgresStorePool,
+        ReadReplicaConfig, StorePool,
+    },
+};
+use tokio_postgres::{GenericClient, NoTls};
+use uuid::Uuid;
+
+// These tests require a second Postgres instance with the same credentials to be running on
+// `REPLICA_PORT`. It doesn't need to replicate the primary database as only the routing is tested.
+const PRIMARY_PORT: u16 = 5432;
+const REPLICA_PORT: u16 = 5433;
+
+fn connection_info(port: u16) -> DatabaseConnectionInfo {
+    DatabaseConnectionInfo::new(
+        DatabaseType::Postgres,
+        "graph".to_owned(),
+        "graph".to_owned(),
+        "localhost".to_owned(),
+        port,
+        "graph".to_owned(),
+    )
+}
+
+async fn server_port(store: &impl AsClient) -> u16 {
+    let port: i32 = store
+        .as_client()
+        .query_one("SELECT inet_server_port();", &[])
+        .await
+        .expect("could not query server port")
+        .get(0);
+    u16::try_from(port).expect("port out of range")
+}
+
+#[tokio::test]
+async fn reads_are_routed_to_replica() {
+    let pool = PostgresStorePool::with_read_replicas(
+        &connection_info(PRIMARY_PORT),
+        &ReadReplicaConfig::new(vec![connection_info(REPLICA_PORT)], None),
+        NoTls,
+    )
+    .await
+    .expect("could not connect to database");
+
+    let store = pool.acquire().await.expect("could not acquire store");
+    assert_eq!(server_port(&store).await, PRIMARY_PORT);
+    drop(store);
+
+    let store = pool.acquire_read().await.expect("could not acquire store");
+    assert_eq!(server_port(&store).await, REPLICA_PORT);
+}
+
+#[tokio::test]
+async fn reads_without_replica_use_primary() {
+    let pool = PostgresStorePool::new(&connection_info(PRIMARY_PORT), NoTls)
+        .await
+        .expect("could not connect to database");
+
+    let store = pool.acquire_read().await.expect("could not acquire store");
+    assert_eq!(server_port(&store).await, PRIMARY_PORT);
+}
+
+#[tokio::test]
+async fn read_your_writes_falls_back_to_primary() {
+    let window = Duration::from_millis(500);
+    let pool = PostgresStorePool::with_read_replicas(
+        &connection_info(PRIMARY_PORT),
+        &ReadReplicaConfig::new(vec![connection_info(REPLICA_PORT)], Some(window)),
+        NoTls,
+    )
+    .await
+    .expect("could not connect to database");
+
+    // No write happened yet, so the replica is used
+    let store = pool.acquire_read().await.expect("could not acquire store");
+    assert_eq!(server_port(&store).await, REPLICA_PORT);
+    drop(store);
+
+    // Acquiring a store for writing alone does not affect the routing
+    let store = pool.acquire().await.expect("could not acquire store");
+    drop(store);
+    let store = pool.acquire_read().await.expect("could not acquire store");
+    assert_eq!(server_port(&store).await, REPLICA_PORT);
+    drop(store);
+
+    let mut store = pool.acquire().await.expect("could not acquire store");
+    store
+        .insert_account_id(AccountId::new(Uuid::new_v4()))
+        .await
+        .expect("could not insert account id");
+    drop(store);
+
+    // Directly after a write was committed, reads are sent to the primary
+    let store = pool.acquire_read().await.expect("could not acquire store");
+    assert_eq!(server_port(&store).await, PRIMARY_PORT);
+    drop(store);
+
+    tokio::time::sleep(window * 2).await;
+
+    let store = pool.acquire_read().await.expect("could not acquire store");
+    assert_eq!(server_port(&store).await, REPLICA_PORT);
+}
@@ -0,0 +1,262 @@
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use axum::{
+    body::Bytes,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+    Extension, Router,
+};
+use graph::{
+    knowledge::EntityProperties,
+    store::webhook::{
+        sign, InvalidWebhookTargetUrl, WebhookDispatcher, WebhookRetryPolicy, SIGNATURE_HEADER,
+    },
+};
+use graph_test_data::{data_type, entity, entity_type, property_type};
+use serde_json::{json, Value};
+use type_system::uri::{BaseUri, VersionedUri};
+
+use super::DatabaseTestWrapper;
+
+type Deliveries = Arc<Mutex<Vec<(Option<String>, Bytes)>>>;
+
+/// Starts a server, which records the requests to `/ok` and rejects the requests to `/fail`.
+fn receive_webhooks() -> (SocketAddr, Deliveries) {
+    let deliveries = Deliveries::default();
+
+    let router =
+        Router::new()
+            .route(
+                "/ok",
+                post(
+                    |Extension(deliveries): Extension<Deliveries>,
+                     headers: HeaderMap,
+                     body: Bytes| async move {
+                        let signature = headers
+                            .get(SIGNATURE_HEADER)
+                            .and_then(|value| value.to_str().ok())
+                            .map(str::to_owned);
+                        deliveries
+                            .lock()
+                            .expect("could not lock deliveries")
+                            .push((signature, body));
+                        StatusCode::OK
+                    },
+                ),
+            )
+            .route("/fail", post(|| async { StatusCode::SERVICE_UNAVAILABLE }))
+            .layer(Extension(Arc::clone(&deliveries)));
+
+    let server = axum::Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0)))
+        .serve(router.into_make_service());
+    let address = server.local_addr();
+    tokio::spawn(server);
+
+    (address, deliveries)
+}
+
+fn entity_type_filter(base_uri: &str) -> Value {
+    json!({
+        "equal": [
+            { "path": ["type", "baseUri"] },
+            { "parameter": base_uri }
+        ]
+    })
+}
+
+fn retry_policy() -> WebhookRetryPolicy {
+    WebhookRetryPolicy::new(
+        2,
+        Duration::from_millis(10),
+        Duration::from_millis(10),
+        Duration::from_secs(1),
+    )
+}
+
+#[tokio::test]
+async fn deliver_matching_entities() {
+    let (address, deliveries) = receive_webhooks();
+    let dispatcher = WebhookDispatcher::new(retry_policy()).allow_private_targets(true);
+
+    let person: EntityProperties =
+        serde_json::from_str(entity::PERSON_A_V1).expect("could not parse entity");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([data_type::TEXT_V1], [property_type::NAME_V1], [
+            entity_type::LINK_V1,
+            entity_type::link::FRIEND_OF_V1,
+            entity_type::PERSON_V1,
+            entity_type::ORGANIZATION_V1,
+        ])
+        .await
+        .expect("could not seed database");
+
+    let person_webhook = api
+        .create_webhook(
+            format!("http://{address}/ok"),
+            "secret",
+            entity_type_filter("https://blockprotocol.org/@alice/types/entity-type/person/"),
+        )
+        .await
+        .expect("could not create webhook");
+    let organization_webhook = api
+        .create_webhook(
+            format!("http://{address}/ok"),
+            "secret",
+            entity_type_filter("https://blockprotocol.org/@alice/types/entity-type/organization/"),
+        )
+        .await
+        .expect("could not create webhook");
+    let failing_webhook = api
+        .create_webhook(
+            format!("http://{address}/fail"),
+            "secret",
+            entity_type_filter("https://blockprotocol.org/@alice/types/entity-type/person/"),
+        )
+        .await
+        .expect("could not create webhook");
+
+    let metadata = api
+        .create_entity(
+            person.clone(),
+            VersionedUri::new(
+                BaseUri::new(
+                    "https://blockprotocol.org/@alice/types/entity-type/person/".to_owned(),
+                )
+                .expect("couldn't construct Base URI"),
+                1,
+            ),
+            None,
+        )
+        .await
+        .expect("could not create entity");
+
+    api.dispatch_to_webhooks(&dispatcher, metadata.edition_id())
+        .await
+        .expect("could not dispatch entity to webhooks");
+
+    let deliveries = deliveries
+        .lock()
+        .expect("could not lock deliveries")
+        .clone();
+    assert_eq!(deliveries.len(), 1);
+    let (signature, body) = &deliveries[0];
+    assert_eq!(signature.as_deref(), Some(sign("secret", body).as_str()));
+
+    let payload: Value = serde_json::from_slice(body).expect("could not parse payload");
+    assert_eq!(
+        payload["webhookId"],
+        json!(person_webhook.webhook_id().as_uuid())
+    );
+    assert_eq!(
+        serde_json::from_value::<EntityProperties>(payload["entity"]["properties"].clone())
+            .expect("could not parse properties"),
+        person
+    );
+
+    assert!(
+        api.get_dead_letters(person_webhook.webhook_id())
+            .await
+            .expect("could not read dead letters")
+            .is_empty()
+    );
+    assert!(
+        api.get_dead_letters(organization_webhook.webhook_id())
+            .await
+            .expect("could not read dead letters")
+            .is_empty()
+    );
+
+    let dead_letters = api
+        .get_dead_letters(failing_webhook.webhook_id())
+        .await
+        .expect("could not read dead letters");
+    assert_eq!(dead_letters.len(), 1);
+    assert_eq!(dead_letters[0].attempts(), 2);
+    assert_eq!(dead_letters[0].entity_edition_id(), metadata.edition_id());
+}
+
+#[tokio::test]
+async fn reject_invalid_target_url() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([], [], [])
+        .await
+        .expect("could not seed database");
+
+    for target_url in ["ftp://localhost/webhook", "/webhook", "not a url"] {
+        let report = api
+            .create_webhook(target_url, "secret", json!({ "all": [] }))
+            .await
+            .expect_err("created webhook with invalid target URL");
+        assert!(
+            report.contains::<InvalidWebhookTargetUrl>(),
+            "unexpected error for `{target_url}`: {report:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn reject_private_target_address() {
+    let (address, deliveries) = receive_webhooks();
+    let dispatcher = WebhookDispatcher::new(retry_policy());
+
+    let person: EntityProperties =
+        serde_json::from_str(entity::PERSON_A_V1).expect("could not parse entity");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([data_type::TEXT_V1], [property_type::NAME_V1], [
+            entity_type::LINK_V1,
+            entity_type::link::FRIEND_OF_V1,
+            entity_type::PERSON_V1,
+        ])
+        .await
+        .expect("could not seed database");
+
+    let webhook = api
+        .create_webhook(
+            format!("http://localhost:{}/ok", address.port()),
+            "secret",
+            entity_type_filter("https://blockprotocol.org/@alice/types/entity-type/person/"),
+        )
+        .await
+        .expect("could not create webhook");
+
+    let metadata = api
+        .create_entity(
+            person,
+            VersionedUri::new(
+                BaseUri::new(
+                    "https://blockprotocol.org/@alice/types/entity-type/person/".to_owned(),
+                )
+                .expect("couldn't construct Base URI"),
+                1,
+            ),
+            None,
+        )
+        .await
+        .expect("could not create entity");
+
+    api.dispatch_to_webhooks(&dispatcher, metadata.edition_id())
+        .await
+        .expect("could not dispatch entity to webhooks");
+
+    assert!(
+        deliveries
+            .lock()
+            .expect("could not lock deliveries")
+            .is_empty()
+    );
+    let dead_letters = api
+        .get_dead_letters(webhook.webhook_id())
+        .await
+        .expect("could not read dead letters");
+    assert_eq!(dead_letters.len(), 1);
+    assert!(dead_letters[0].error().contains("non-public address"));
+}
@@ -0,0 +1,125 @@
+//! Runs the integration tests written for the Postgres store against the SQLite store.
+
+#[path = "../postgres/data_type.rs"]
+mod data_type;
+#[path = "../postgres/entity.rs"]
+mod entity;
+#[path = "../postgres/entity_type.rs"]
+mod entity_type;
+#[path = "../postgres/fetcher.rs"]
+mod fetcher;
+#[path = "../postgres/links.rs"]
+mod links;
+#[path = "../postgres/property_type.rs"]
+mod property_type;
+#[path = "../postgres/query_limits.rs"]
+mod query_limits;
+#[path = "../postgres/webhook.rs"]
+mod webhook;
+
+use error_stack::Result;
+use graph::store::{
+    DatabaseConnectionInfo, DatabaseType, InsertionError, QueryLimits, SqliteStore,
+    SqliteStorePool, SqliteTransaction, Store, StorePool,
+};
+use tempfile::TempDir;
+
+use crate::database::DatabaseApi;
+
+pub struct DatabaseTestWrapper {
+    connection: <SqliteStorePool as StorePool>::Store<'static>,
+    pool: SqliteStorePool,
+    // The database file is removed when the directory is dropped, so it's dropped last.
+    _directory: TempDir,
+}
+
+impl DatabaseTestWrapper {
+    pub async fn new() -> Self {
+        Self::with_query_limits(QueryLimits::default()).await
+    }
+
+    pub async fn with_query_limits(query_limits: QueryLimits) -> Self {
+        let directory = TempDir::new().expect("could not create a temporary directory");
+
+        let connection_info = DatabaseConnectionInfo::new(
+            DatabaseType::Sqlite,
+            String::new(),
+            String::new(),
+            String::new(),
+            0,
+            directory
+                .path()
+                .join("graph.sqlite")
+                .to_str()
+                .expect("temporary directory is not valid UTF-8")
+                .to_owned(),
+        );
+
+        let pool = SqliteStorePool::with_query_limits(&connection_info, query_limits)
+            .await
+            .expect("could not open database");
+
+        let connection = pool
+            .acquire_owned()
+            .await
+            .expect("could not acquire a database connection");
+
+        Self {
+            connection,
+            pool,
+            _directory: directory,
+        }
+    }
+
+    pub async fn seed<D, P, E>(
+        &mut self,
+        data_types: D,
+        property_types: P,
+        entity_types: E,
+    ) -> Result<DatabaseApi<SqliteStore<SqliteTransaction<'_>>>, InsertionError>
+    where
+        D: IntoIterator<Item = &'static str>,
+        P: IntoIterator<Item = &'static str>,
+        E: IntoIterator<Item = &'static str>,
+    {
+        let store = self
+            .connection
+            .transaction()
+            .await
+            .expect("could not start test transaction");
+
+        DatabaseApi::seed(store, data_types, property_types, entity_types).await
+    }
+
+    /// Inserts a new account through a separate connection without a surrounding transaction.
+    ///
+    /// Unlike [`seed`], every change is committed directly and is visible to other connections.
+    ///
+    /// [`seed`]: Self::seed
+    pub async fn seed_committed(
+        &self,
+    ) -> Result<DatabaseApi<<SqliteStorePool as StorePool>::Store<'static>>, InsertionError> {
+        let store = self
+            .pool
+            .acquire_owned()
+            .await
+            .expect("could not acquire a database connection");
+
+        DatabaseApi::seed(store, [], [], []).await
+    }
+}
+
+#[tokio::test]
+async fn can_connect() {
+    DatabaseTestWrapper::new().await;
+}
+
+#[tokio::test]
+async fn schema_is_ready() {
+    DatabaseTestWrapper::new()
+        .await
+        .connection
+        .check_readiness()
+        .await
+        .expect("the schema is not up-to-date");
+}
@@ -65,6 +65,11 @@ export const up = (pgm: MigrationBuilder): void => {
         type: "UUID",
         references: "version_ids",
       },
+      transaction_time: {
+        type: "tstzrange",
+        notNull: true,
+        default: pgm.func("tstzrange(now(), NULL, '[)')"),
+      },
     },
     {
       ifNotExists: true,
@@ -100,6 +105,11 @@ export const up = (pgm: MigrationBuilder): void => {
         notNull: true,
         references: "accounts",
       },
+      external: {
+        type: "BOOLEAN",
+        notNull: true,
+        default: false,
+      },
     },
     {
       ifNotExists: true,
@@ -128,6 +138,11 @@ export const up = (pgm: MigrationBuilder): void => {
         notNull: true,
         references: "accounts",
       },
+      external: {
+        type: "BOOLEAN",
+        notNull: true,
+        default: false,
+      },
     },
     {
       ifNotExists: true,
@@ -156,6 +171,11 @@ export const up = (pgm: MigrationBuilder): void => {
         notNull: true,
         references: "accounts",
       },
+      external: {
+        type: "BOOLEAN",
+        notNull: true,
+        default: false,
+      },
     },
     {
       ifNotExists: true,
@@ -317,6 +337,12 @@ export const up = (pgm: MigrationBuilder): void => {
         type: "JSONB",
         notNull: true,
       },
+      // The provenance of each property, keyed by the base URI of the property, see
+      // `merge_property_provenance`
+      property_provenance: {
+        type: "JSONB",
+        notNull: true,
+      },
       left_to_right_order: {
         // TODO: this is where we could do fractional indexing
         //  https://app.asana.com/0/1200211978612931/1202085856561975/f
@@ -395,6 +421,81 @@ export const up = (pgm: MigrationBuilder): void => {
     },
   );
 
+  pgm.createTable(
+    "drafts",
+    {
+      draft_id: {
+        type: "UUID",
+        primaryKey: true,
+      },
+      owned_by_id: {
+        type: "UUID",
+        notNull: true,
+        references: "accounts",
+      },
+    },
+    {
+      ifNotExists: true,
+    },
+  );
+
+  // Editions of entities, which were created or updated in a draft. They are not part of
+  // `entity_versions` until the draft is published, see `publish_draft`.
+  pgm.createTable(
+    "entity_drafts",
+    {
+      draft_id: {
+        type: "UUID",
+        notNull: true,
+        references: "drafts",
+      },
+      owned_by_id: {
+        type: "UUID",
+        notNull: true,
+      },
+      entity_uuid: {
+        type: "UUID",
+        notNull: true,
+      },
+      entity_record_id: {
+        type: "BIGINT",
+        notNull: true,
+        references: "entity_editions",
+      },
+      // The live edition the entity had when it was first changed in the draft, `NULL` if the
+      // entity was created in the draft. Publishing fails if the live edition changed since.
+      base_entity_record_id: {
+        type: "BIGINT",
+        notNull: false,
+        references: "entity_editions",
+      },
+      // The decision time the edition will be published with. If not specified, the time of
+      // publishing is used.
+      decision_time: {
+        type: "TIMESTAMP WITH TIME ZONE",
+        notNull: false,
+      },
+      transaction_time: {
+        type: "tstzrange",
+        notNull: true,
+      },
+    },
+    {
+      ifNotExists: true,
+    },
+  );
+
+  pgm.addConstraint("entity_drafts", "entity_drafts_primary_key", {
+    primaryKey: ["draft_id", "owned_by_id", "entity_uuid"],
+  });
+
+  pgm.addConstraint("entity_drafts", "entity_drafts_reference", {
+    foreignKeys: {
+      references: "entity_ids",
+      columns: ["owned_by_id", "entity_uuid"],
+    },
+  });
+
   pgm.createView(
     "entities",
     {},
@@ -408,19 +509,123 @@ export const up = (pgm: MigrationBuilder): void => {
       entity_editions.entity_type_version_id,
       entity_editions.updated_by_id,
       entity_editions.properties,
+      entity_editions.property_provenance,
       entity_editions.archived,
       entity_ids.left_owned_by_id,
       entity_ids.left_entity_uuid,
       entity_editions.left_to_right_order,
       entity_ids.right_owned_by_id,
       entity_ids.right_entity_uuid,
-      entity_editions.right_to_left_order
+      entity_editions.right_to_left_order,
+      NULL::UUID AS draft_id
     FROM entity_versions
     JOIN entity_editions ON entity_versions.entity_record_id = entity_editions.entity_record_id
     JOIN entity_ids ON entity_versions.owned_by_id = entity_ids.owned_by_id AND entity_versions.entity_uuid = entity_ids.entity_uuid
     `,
   );
 
+  // The entities as they are seen from within each draft: the editions of the draft replace the
+  // live editions of the same entity. Queries, which include a draft, read from this view instead
+  // of `entities` filtered by `visible_in_draft_id`.
+  pgm.createView(
+    "draft_entities",
+    {},
+    `
+    SELECT
+      entities.*,
+      drafts.draft_id AS visible_in_draft_id
+    FROM drafts
+    CROSS JOIN entities
+    WHERE NOT EXISTS (
+      SELECT 1
+      FROM entity_drafts
+      WHERE entity_drafts.draft_id = drafts.draft_id
+        AND entity_drafts.owned_by_id = entities.owned_by_id
+        AND entity_drafts.entity_uuid = entities.entity_uuid
+    )
+    UNION ALL
+    SELECT
+      entity_drafts.entity_record_id,
+      entity_drafts.owned_by_id,
+      entity_drafts.entity_uuid,
+      tstzrange(
+        COALESCE(entity_drafts.decision_time, lower(entity_drafts.transaction_time)),
+        NULL,
+        '[)'
+      ) AS decision_time,
+      entity_drafts.transaction_time,
+      entity_editions.entity_type_version_id,
+      entity_editions.updated_by_id,
+      entity_editions.properties,
+      entity_editions.property_provenance,
+      entity_editions.archived,
+      entity_ids.left_owned_by_id,
+      entity_ids.left_entity_uuid,
+      entity_editions.left_to_right_order,
+      entity_ids.right_owned_by_id,
+      entity_ids.right_entity_uuid,
+      entity_editions.right_to_left_order,
+      entity_drafts.draft_id,
+      entity_drafts.draft_id AS visible_in_draft_id
+    FROM entity_drafts
+    JOIN entity_editions ON entity_drafts.entity_record_id = entity_editions.entity_record_id
+    JOIN entity_ids ON entity_drafts.owned_by_id = entity_ids.owned_by_id AND entity_drafts.entity_uuid = entity_ids.entity_uuid
+    `,
+  );
+
+  pgm.createFunction(
+    "merge_property_provenance",
+    [
+      {
+        name: "_properties",
+        type: "JSONB",
+      },
+      {
+        name: "_old_properties",
+        type: "JSONB",
+      },
+      {
+        name: "_old_property_provenance",
+        type: "JSONB",
+      },
+      {
+        name: "_updated_by_id",
+        type: "UUID",
+      },
+      {
+        name: "_source",
+        type: "TEXT",
+      },
+    ],
+    {
+      returns: "JSONB",
+      language: "sql",
+      behavior: "STABLE",
+      replace: true,
+    },
+    `
+    -- Properties, which kept their value, keep their provenance, all other properties are
+    -- attributed to the current update.
+    SELECT COALESCE(
+      jsonb_object_agg(
+        property.key,
+        CASE
+          WHEN _old_properties -> property.key = property.value
+            AND _old_property_provenance ? property.key
+          THEN _old_property_provenance -> property.key
+          ELSE jsonb_strip_nulls(jsonb_build_object(
+            'updatedById', _updated_by_id,
+            'updatedAt', now(),
+            'source', _source
+          ))
+        END
+      ),
+      '{}'::JSONB
+    )
+    FROM jsonb_each(_properties) AS property;
+    `,
+  );
+
   pgm.createFunction(
     "create_entity",
     [
@@ -452,6 +657,10 @@ export const up = (pgm: MigrationBuilder): void => {
         name: "_properties",
         type: "JSONB",
       },
+      {
+        name: "_property_source",
+        type: "TEXT",
+      },
       {
         name: "_left_owned_by_id",
         type: "UUID",
@@ -476,6 +685,10 @@ export const up = (pgm: MigrationBuilder): void => {
         name: "_right_to_left_order",
         type: "INTEGER",
       },
+      {
+        name: "_draft_id",
+        type: "UUID",
+      },
     ],
     {
       returns:
@@ -487,7 +700,8 @@ export const up = (pgm: MigrationBuilder): void => {
     DECLARE
       _entity_record_id BIGINT;
     BEGIN
-      IF _decision_time IS NULL THEN _decision_time := now(); END IF;
+      -- Drafts keep an unspecified decision time, so it is set when the draft is published.
+      IF _decision_time IS NULL AND _draft_id IS NULL THEN _decision_time := now(); END IF;
   
       INSERT INTO entity_ids (
         owned_by_id,
@@ -511,6 +725,7 @@ export const up = (pgm: MigrationBuilder): void => {
         archived,
         entity_type_version_id,
         properties,
+        property_provenance,
         left_to_right_order,
         right_to_left_order
       ) VALUES (
@@ -518,24 +733,52 @@ export const up = (pgm: MigrationBuilder): void => {
         _archived,
         _entity_type_version_id,
         _properties,
+        merge_property_provenance(_properties, NULL, NULL, _updated_by_id, _property_source),
         _left_to_right_order,
         _right_to_left_order
       ) RETURNING entity_editions.entity_record_id INTO _entity_record_id;
 
-      RETURN QUERY
-      INSERT INTO entity_versions (
-        owned_by_id,
-        entity_uuid,
-        entity_record_id,
-        decision_time,
-        transaction_time
-      ) VALUES (
-        _owned_by_id,
-        _entity_uuid,
-        _entity_record_id,
-        tstzrange(_decision_time, NULL, '[)'),
-        tstzrange(now(), NULL, '[)')
-      ) RETURNING entity_versions.entity_record_id, entity_versions.decision_time, entity_versions.transaction_time;
+      IF _draft_id IS NULL THEN
+        RETURN QUERY
+        INSERT INTO entity_versions (
+          owned_by_id,
+          entity_uuid,
+          entity_record_id,
+          decision_time,
+          transaction_time
+        ) VALUES (
+          _owned_by_id,
+          _entity_uuid,
+          _entity_record_id,
+          tstzrange(_decision_time, NULL, '[)'),
+          tstzrange(now(), NULL, '[)')
+        ) RETURNING entity_versions.entity_record_id, entity_versions.decision_time, entity_versions.transaction_time;
+      ELSE
+        RETURN QUERY
+        INSERT INTO entity_drafts (
+          draft_id,
+          owned_by_id,
+          entity_uuid,
+          entity_record_id,
+          decision_time,
+          transaction_time
+        ) VALUES (
+          _draft_id,
+          _owned_by_id,
+          _entity_uuid,
+          _entity_record_id,
+          _decision_time,
+          tstzrange(now(), NULL, '[)')
+        )
+        RETURNING
+          entity_drafts.entity_record_id,
+          tstzrange(
+            COALESCE(entity_drafts.decision_time, lower(entity_drafts.transaction_time)),
+            NULL,
+            '[)'
+          ),
+          entity_drafts.transaction_time;
+      END IF;
     END
     `,
   );
@@ -571,6 +814,10 @@ export const up = (pgm: MigrationBuilder): void => {
         name: "_properties",
         type: "JSONB",
       },
+      {
+        name: "_property_source",
+        type: "TEXT",
+      },
       {
         name: "_left_to_right_order",
         type: "INTEGER",
@@ -579,6 +826,10 @@ export const up = (pgm: MigrationBuilder): void => {
         name: "_right_to_left_order",
         type: "INTEGER",
       },
+      {
+        name: "_draft_id",
+        type: "UUID",
+      },
     ],
     {
       returns:
@@ -589,14 +840,55 @@ export const up = (pgm: MigrationBuilder): void => {
     `
     DECLARE
       _new_entity_record_id BIGINT;
+      _base_entity_record_id BIGINT;
+      _old_properties JSONB;
+      _old_property_provenance JSONB;
+      _in_draft BOOLEAN := FALSE;
     BEGIN
-      IF _decision_time IS NULL THEN _decision_time := now(); END IF;
+      -- Drafts keep an unspecified decision time, so it is set when the draft is published.
+      IF _decision_time IS NULL AND _draft_id IS NULL THEN _decision_time := now(); END IF;
+
+      -- Live updates lock the entity, so a draft, which is published concurrently, either sees
+      -- this update when checking for conflicts or is published before this update.
+      IF _draft_id IS NULL THEN
+        PERFORM 1
+        FROM entity_ids
+        WHERE entity_ids.owned_by_id = _owned_by_id
+          AND entity_ids.entity_uuid = _entity_uuid
+        FOR UPDATE;
+      END IF;
+
+      IF _draft_id IS NOT NULL THEN
+        SELECT entity_editions.properties, entity_editions.property_provenance
+        INTO _old_properties, _old_property_provenance
+        FROM entity_drafts
+        JOIN entity_editions ON entity_drafts.entity_record_id = entity_editions.entity_record_id
+        WHERE entity_drafts.draft_id = _draft_id
+          AND entity_drafts.owned_by_id = _owned_by_id
+          AND entity_drafts.entity_uuid = _entity_uuid;
+        _in_draft := FOUND;
+      END IF;
+
+      IF NOT _in_draft THEN
+        SELECT entity_versions.entity_record_id, entity_editions.properties, entity_editions.property_provenance
+        INTO _base_entity_record_id, _old_properties, _old_property_provenance
+        FROM entity_versions
+        JOIN entity_editions ON entity_versions.entity_record_id = entity_editions.entity_record_id
+        WHERE entity_versions.owned_by_id = _owned_by_id
+          AND entity_versions.entity_uuid = _entity_uuid
+          AND entity_versions.decision_time @> COALESCE(_decision_time, now())
+          AND entity_versions.transaction_time @> now();
+
+        -- An entity can only be updated in a draft if it exists in the draft or is live
+        IF _draft_id IS NOT NULL AND NOT FOUND THEN RETURN; END IF;
+      END IF;
 
       INSERT INTO entity_editions (
         updated_by_id,
         archived,
         entity_type_version_id,
         properties,
+        property_provenance,
         left_to_right_order,
         right_to_left_order
       ) VALUES (
@@ -604,21 +896,62 @@ export const up = (pgm: MigrationBuilder): void => {
         _archived,
         _entity_type_version_id,
         _properties,
+        merge_property_provenance(
+          _properties,
+          _old_properties,
+          _old_property_provenance,
+          _updated_by_id,
+          _property_source
+        ),
         _left_to_right_order,
         _right_to_left_order
       )
       RETURNING entity_editions.entity_record_id INTO _new_entity_record_id;
-  
-      RETURN QUERY
-      UPDATE entity_versions
-      SET decision_time = tstzrange(_decision_time, upper(entity_versions.decision_time), '[)'),
-          transaction_time = tstzrange(now(), NULL, '[)'),
-          entity_record_id = _new_entity_record_id
-      WHERE entity_versions.owned_by_id = _owned_by_id
-        AND entity_versions.entity_uuid = _entity_uuid
-        AND entity_versions.decision_time @> _decision_time
-        AND entity_versions.transaction_time @> now()
-      RETURNING entity_versions.entity_record_id, entity_versions.decision_time, entity_versions.transaction_time;
+
+      IF _draft_id IS NULL THEN
+        RETURN QUERY
+        UPDATE entity_versions
+        SET decision_time = tstzrange(_decision_time, upper(entity_versions.decision_time), '[)'),
+            transaction_time = tstzrange(now(), NULL, '[)'),
+            entity_record_id = _new_entity_record_id
+        WHERE entity_versions.owned_by_id = _owned_by_id
+          AND entity_versions.entity_uuid = _entity_uuid
+          AND entity_versions.decision_time @> _decision_time
+          AND entity_versions.transaction_time @> now()
+        RETURNING entity_versions.entity_record_id, entity_versions.decision_time, entity_versions.transaction_time;
+      ELSE
+        RETURN QUERY
+        INSERT INTO entity_drafts (
+          draft_id,
+          owned_by_id,
+          entity_uuid,
+          entity_record_id,
+          base_entity_record_id,
+          decision_time,
+          transaction_time
+        ) VALUES (
+          _draft_id,
+          _owned_by_id,
+          _entity_uuid,
+          _new_entity_record_id,
+          _base_entity_record_id,
+          _decision_time,
+          tstzrange(now(), NULL, '[)')
+        )
+        -- The base edition is kept from the first change of the entity in the draft
+        ON CONFLICT (draft_id, owned_by_id, entity_uuid) DO UPDATE
+        SET entity_record_id = EXCLUDED.entity_record_id,
+            decision_time = EXCLUDED.decision_time,
+            transaction_time = EXCLUDED.transaction_time
+        RETURNING
+          entity_drafts.entity_record_id,
+          tstzrange(
+            COALESCE(entity_drafts.decision_time, lower(entity_drafts.transaction_time)),
+            NULL,
+            '[)'
+          ),
+          entity_drafts.transaction_time;
+      END IF;
     END
     `,
   );
@@ -674,6 +1007,194 @@ export const up = (pgm: MigrationBuilder): void => {
     level: "ROW",
     function: "update_entity_version_trigger",
   });
+
+  pgm.createFunction(
+    "publish_draft",
+    [
+      {
+        name: "_draft_id",
+        type: "UUID",
+      },
+    ],
+    {
+      returns:
+        "TABLE (owned_by_id UUID, entity_uuid UUID, entity_record_id BIGINT)",
+      language: "plpgsql",
+      replace: true,
+    },
+    `
+    DECLARE
+      _draft RECORD;
+      _decision_time TIMESTAMP WITH TIME ZONE;
+    BEGIN
+      FOR _draft IN
+        SELECT entity_drafts.owned_by_id, entity_drafts.entity_uuid, entity_drafts.entity_record_id, entity_drafts.decision_time
+        FROM entity_drafts
+        WHERE entity_drafts.draft_id = _draft_id
+      LOOP
+        _decision_time := COALESCE(_draft.decision_time, now());
+
+        -- Entities, which are live already, are updated in the same way as in \`update_entity\`,
+        -- so the history is kept by \`update_entity_version_trigger\`.
+        UPDATE entity_versions
+        SET decision_time = tstzrange(_decision_time, upper(entity_versions.decision_time), '[)'),
+            transaction_time = tstzrange(now(), NULL, '[)'),
+            entity_record_id = _draft.entity_record_id
+        WHERE entity_versions.owned_by_id = _draft.owned_by_id
+          AND entity_versions.entity_uuid = _draft.entity_uuid
+          AND entity_versions.decision_time @> _decision_time
+          AND entity_versions.transaction_time @> now();
+
+        -- Entities, which were created in the draft, get their first version.
+        IF NOT FOUND THEN
+          INSERT INTO entity_versions (
+            owned_by_id,
+            entity_uuid,
+            entity_record_id,
+            decision_time,
+            transaction_time
+          ) VALUES (
+            _draft.owned_by_id,
+            _draft.entity_uuid,
+            _draft.entity_record_id,
+            tstzrange(_decision_time, NULL, '[)'),
+            tstzrange(now(), NULL, '[)')
+          );
+        END IF;
+
+        owned_by_id := _draft.owned_by_id;
+        entity_uuid := _draft.entity_uuid;
+        entity_record_id := _draft.entity_record_id;
+        RETURN NEXT;
+      END LOOP;
+
+      DELETE FROM entity_drafts WHERE entity_drafts.draft_id = _draft_id;
+      DELETE FROM drafts WHERE drafts.draft_id = _draft_id;
+    END
+    `,
+  );
+
+  pgm.createFunction(
+    "discard_draft",
+    [
+      {
+        name: "_draft_id",
+        type: "UUID",
+      },
+    ],
+    {
+      returns: "VOID",
+      language: "plpgsql",
+      replace: true,
+    },
+    `
+    BEGIN
+      -- Removes the editions of the draft and the entities, which only existed in the draft.
+      WITH discarded AS (
+        DELETE FROM entity_drafts
+        WHERE entity_drafts.draft_id = _draft_id
+        RETURNING entity_drafts.owned_by_id, entity_drafts.entity_uuid, entity_drafts.entity_record_id
+      ), discarded_editions AS (
+        DELETE FROM entity_editions
+        WHERE entity_editions.entity_record_id IN (SELECT discarded.entity_record_id FROM discarded)
+      )
+      DELETE FROM entity_ids
+      WHERE (entity_ids.owned_by_id, entity_ids.entity_uuid) IN (
+          SELECT discarded.owned_by_id, discarded.entity_uuid FROM discarded
+        )
+        AND NOT EXISTS (
+          SELECT 1
+          FROM entity_versions
+          WHERE entity_versions.owned_by_id = entity_ids.owned_by_id
+            AND entity_versions.entity_uuid = entity_ids.entity_uuid
+        );
+
+      DELETE FROM drafts WHERE drafts.draft_id = _draft_id;
+    END
+    `,
+  );
+
+  pgm.createTable(
+    "webhooks",
+    {
+      webhook_id: {
+        type: "UUID",
+        primaryKey: true,
+      },
+      owned_by_id: {
+        type: "UUID",
+        notNull: true,
+        references: "accounts",
+      },
+      target_url: {
+        type: "TEXT",
+        notNull: true,
+      },
+      secret: {
+        type: "TEXT",
+        notNull: true,
+      },
+      filter: {
+        type: "JSONB",
+        notNull: true,
+      },
+    },
+    {
+      ifNotExists: true,
+    },
+  );
+
+  pgm.createTable(
+    "webhook_dead_letters",
+    {
+      webhook_id: {
+        type: "UUID",
+        notNull: true,
+        references: "webhooks",
+        onDelete: "CASCADE",
+      },
+      owned_by_id: {
+        type: "UUID",
+        notNull: true,
+      },
+      entity_uuid: {
+        type: "UUID",
+        notNull: true,
+      },
+      entity_record_id: {
+        type: "BIGINT",
+        notNull: true,
+        references: "entity_editions",
+      },
+      payload: {
+        type: "JSONB",
+        notNull: true,
+      },
+      attempts: {
+        type: "BIGINT",
+        notNull: true,
+      },
+      error: {
+        type: "TEXT",
+        notNull: true,
+      },
+      failed_at: {
+        type: "TIMESTAMP WITH TIME ZONE",
+        notNull: true,
+        default: pgm.func("now()"),
+      },
+    },
+    {
+      ifNotExists: true,
+    },
+  );
+
+  pgm.addConstraint("webhook_dead_letters", "webhook_dead_letters_reference", {
+    foreignKeys: {
+      references: "entity_ids",
+      columns: ["owned_by_id", "entity_uuid"],
+    },
+  });
 };
 
 // A down migration would cause data loss.
@@ -690,14 +1211,21 @@ DROP TABLE IF EXISTS entity_type_entity_type_references CASCADE;
 DROP TABLE IF EXISTS entity_ids CASCADE;
 DROP TABLE IF EXISTS entity_editions CASCADE;
 DROP TABLE IF EXISTS entity_versions CASCADE;
+DROP TABLE IF EXISTS drafts CASCADE;
+DROP TABLE IF EXISTS entity_drafts CASCADE;
 DROP TABLE IF EXISTS accounts CASCADE;
 DROP TABLE IF EXISTS base_uris CASCADE;
 DROP TABLE IF EXISTS type_ids CASCADE;
 DROP TABLE IF EXISTS version_ids CASCADE;
+DROP TABLE IF EXISTS webhooks CASCADE;
+DROP TABLE IF EXISTS webhook_dead_letters CASCADE;
 */
 
 /* Drop all functions:
+DROP FUNCTION IF EXISTS discard_draft;
+DROP FUNCTION IF EXISTS publish_draft;
 DROP FUNCTION IF EXISTS update_entity_version_trigger;
 DROP FUNCTION IF EXISTS update_entity;
 DROP FUNCTION IF EXISTS create_entity;
+DROP FUNCTION IF EXISTS merge_property_provenance;
  */
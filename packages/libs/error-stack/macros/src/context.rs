@@ -0,0 +1,251 @@
+//! Implementation of `#[derive(Context)]`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Error, Fields, Index, LitStr, Member, Result};
+
+use crate::format::{binding, FormatString};
+
+/// The struct itself or a single variant of an enum.
+struct Shape {
+    /// Path used to match the shape, either `Self` or `Self::Variant`.
+    path: TokenStream,
+    display: FormatString,
+    provide: Vec<Member>,
+}
+
+impl Shape {
+    fn new(path: TokenStream, fields: &Fields, display: &LitStr) -> Result<Self> {
+        let display = FormatString::parse(display)?;
+        let members = fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                field
+                    .ident
+                    .clone()
+                    .map_or_else(|| Member::Unnamed(Index::from(index)), Member::Named)
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(unknown) = display
+            .fields
+            .iter()
+            .find(|member| !members.contains(member))
+        {
+            let name = match unknown {
+                Member::Named(ident) => ident.to_string(),
+                Member::Unnamed(index) => index.index.to_string(),
+            };
+
+            return Err(Error::new_spanned(
+                &display.literal,
+                format!("invalid format string: there is no field `{name}`"),
+            ));
+        }
+
+        let mut provide = Vec::new();
+        for (member, field) in members.into_iter().zip(fields) {
+            reject_attribute(&field.attrs, "display", "fields")?;
+
+            if provide_attribute(&field.attrs)? {
+                provide.push(member);
+            }
+        }
+
+        Ok(Self {
+            path,
+            display,
+            provide,
+        })
+    }
+
+    /// Returns a pattern, which binds `members` to the names returned by [`binding`].
+    fn pattern(&self, members: &[Member]) -> TokenStream {
+        let path = &self.path;
+        let fields = members.iter().map(|member| match member {
+            Member::Named(ident) => quote!(#ident),
+            Member::Unnamed(_) => {
+                let binding = binding(member);
+                quote!(#member: #binding)
+            }
+        });
+
+        quote!(#path { #(#fields,)* .. })
+    }
+}
+
+pub(crate) fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    reject_attribute(&input.attrs, "provide", "types")?;
+
+    let shapes = match &input.data {
+        Data::Struct(data) => {
+            let display = display_attribute(&input.attrs)?.ok_or_else(|| {
+                Error::new_spanned(&input.ident, "missing `#[display(\"...\")]` attribute")
+            })?;
+
+            vec![Shape::new(quote!(Self), &data.fields, &display)?]
+        }
+        Data::Enum(data) => {
+            // The attribute on the enum is used for all variants without their own attribute
+            let fallback = display_attribute(&input.attrs)?;
+
+            data.variants
+                .iter()
+                .map(|variant| {
+                    reject_attribute(&variant.attrs, "provide", "variants")?;
+
+                    let display = match display_attribute(&variant.attrs)? {
+                        Some(display) => display,
+                        None => fallback.clone().ok_or_else(|| {
+                            Error::new_spanned(
+                                &variant.ident,
+                                "missing `#[display(\"...\")]` attribute on the variant or the \
+                                 enum",
+                            )
+                        })?,
+                    };
+
+                    let ident = &variant.ident;
+                    Shape::new(quote!(Self::#ident), &variant.fields, &display)
+                })
+                .collect::<Result<Vec<_>>>()?
+        }
+        Data::Union(data) => {
+            return Err(Error::new_spanned(
+                data.union_token,
+                "`Context` cannot be derived for unions",
+            ));
+        }
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let display = expand_display(&shapes);
+    let provide = expand_provide(&shapes);
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::fmt::Display for #ident #ty_generics #where_clause {
+            fn fmt(&self, __formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #display
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::error_stack::Context for #ident #ty_generics #where_clause {
+            #provide
+        }
+    })
+}
+
+fn expand_display(shapes: &[Shape]) -> TokenStream {
+    if shapes.is_empty() {
+        return quote!(match *self {});
+    }
+
+    let arms = shapes.iter().map(|shape| {
+        let pattern = shape.pattern(&shape.display.fields);
+        let literal = &shape.display.literal;
+        let arguments = shape.display.fields.iter().map(|member| {
+            let binding = binding(member);
+            quote!(#binding = #binding)
+        });
+
+        quote!(#pattern => ::core::write!(__formatter, #literal #(, #arguments)*),)
+    });
+
+    quote!(match self { #(#arms)* })
+}
+
+/// Implements `Context::provide` if any field is annotated with `#[provide]`.
+fn expand_provide(shapes: &[Shape]) -> TokenStream {
+    if shapes.iter().all(|shape| shape.provide.is_empty()) {
+        return TokenStream::new();
+    }
+
+    let arms = shapes
+        .iter()
+        .filter(|shape| !shape.provide.is_empty())
+        .map(|shape| {
+            let pattern = shape.pattern(&shape.provide);
+            let bindings = shape.provide.iter().map(binding);
+
+            quote!(#pattern => { #(__demand.provide_ref(#bindings);)* })
+        });
+    let fallback = shapes
+        .iter()
+        .any(|shape| shape.provide.is_empty())
+        .then(|| quote!(_ => {}));
+
+    quote! {
+        fn provide<'__demand>(&'__demand self, __demand: &mut ::core::any::Demand<'__demand>) {
+            match self {
+                #(#arms)*
+                #fallback
+            }
+        }
+    }
+}
+
+fn display_attribute(attributes: &[Attribute]) -> Result<Option<LitStr>> {
+    let mut display = None;
+
+    for attribute in attributes {
+        if !attribute.path.is_ident("display") {
+            continue;
+        }
+
+        if display.is_some() {
+            return Err(Error::new_spanned(
+                attribute,
+                "duplicate `#[display(\"...\")]` attribute",
+            ));
+        }
+
+        display = Some(attribute.parse_args::<LitStr>()?);
+    }
+
+    Ok(display)
+}
+
+fn provide_attribute(attributes: &[Attribute]) -> Result<bool> {
+    let mut provide = false;
+
+    for attribute in attributes {
+        if !attribute.path.is_ident("provide") {
+            continue;
+        }
+
+        if !attribute.tokens.is_empty() {
+            return Err(Error::new_spanned(
+                &attribute.tokens,
+                "`#[provide]` does not take any arguments",
+            ));
+        }
+
+        if !cfg!(feature = "provide") {
+            return Err(Error::new_spanned(
+                attribute,
+                "`#[provide]` requires the `provide` feature of `error-stack-macros`",
+            ));
+        }
+
+        provide = true;
+    }
+
+    Ok(provide)
+}
+
+fn reject_attribute(attributes: &[Attribute], name: &str, position: &str) -> Result<()> {
+    attributes
+        .iter()
+        .find(|attribute| attribute.path.is_ident(name))
+        .map_or(Ok(()), |attribute| {
+            Err(Error::new_spanned(
+                attribute,
+                format!("`#[{name}]` is not supported on {position}"),
+            ))
+        })
+}
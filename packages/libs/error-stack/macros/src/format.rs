@@ -0,0 +1,167 @@
+//! Parsing of the format string passed to `#[display("...")]`.
+
+use proc_macro2::Ident;
+use quote::format_ident;
+use syn::{Error, Index, LitStr, Member, Result};
+
+/// A format string, which only refers to the fields of a struct or variant.
+///
+/// Named fields are referred to by their name (`{field}`), fields of tuple structs or variants by
+/// their index (`{0}`). The string is rewritten, so that every referenced field is passed to
+/// [`write!`] as a named argument, see [`binding`].
+pub(crate) struct FormatString {
+    pub(crate) literal: LitStr,
+    pub(crate) fields: Vec<Member>,
+}
+
+impl FormatString {
+    pub(crate) fn parse(literal: &LitStr) -> Result<Self> {
+        let value = literal.value();
+        let mut chars = value.chars().peekable();
+
+        let mut output = String::with_capacity(value.len());
+        let mut fields = Vec::new();
+
+        while let Some(char) = chars.next() {
+            match char {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    output.push_str("{{");
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    output.push_str("}}");
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(char) => placeholder.push(char),
+                            None => {
+                                return Err(Error::new_spanned(
+                                    literal,
+                                    "invalid format string: expected `}` but string was terminated",
+                                ));
+                            }
+                        }
+                    }
+
+                    output.push('{');
+                    parse_placeholder(literal, &placeholder, &mut output, &mut fields)?;
+                    output.push('}');
+                }
+                '}' => {
+                    return Err(Error::new_spanned(
+                        literal,
+                        "invalid format string: unmatched `}` found, use `}}` to escape it",
+                    ));
+                }
+                char => output.push(char),
+            }
+        }
+
+        Ok(Self {
+            literal: LitStr::new(&output, literal.span()),
+            fields,
+        })
+    }
+}
+
+/// Returns the name of the variable a field is bound to in the generated code.
+pub(crate) fn binding(member: &Member) -> Ident {
+    match member {
+        Member::Named(ident) => ident.clone(),
+        Member::Unnamed(index) => format_ident!("_{}", index.index),
+    }
+}
+
+fn parse_placeholder(
+    literal: &LitStr,
+    placeholder: &str,
+    output: &mut String,
+    fields: &mut Vec<Member>,
+) -> Result<()> {
+    let (argument, spec) = match placeholder.split_once(':') {
+        Some((argument, spec)) => (argument, Some(spec)),
+        None => (placeholder, None),
+    };
+
+    if argument.is_empty() {
+        return Err(Error::new_spanned(
+            literal,
+            "positional arguments are not supported, refer to a field by its name or index instead",
+        ));
+    }
+    output.push_str(&parse_argument(literal, argument, fields)?);
+
+    if let Some(spec) = spec {
+        output.push(':');
+        parse_spec(literal, spec, output, fields)?;
+    }
+
+    Ok(())
+}
+
+/// Parses the width and precision arguments (`name$`) of a format spec.
+fn parse_spec(
+    literal: &LitStr,
+    spec: &str,
+    output: &mut String,
+    fields: &mut Vec<Member>,
+) -> Result<()> {
+    let mut rest = spec;
+
+    // The first character is the fill character if followed by an alignment, it may be a `$`
+    let mut chars = spec.chars();
+    if let (Some(fill), Some('<' | '^' | '>')) = (chars.next(), chars.next()) {
+        let (head, tail) = rest.split_at(fill.len_utf8() + 1);
+        output.push_str(head);
+        rest = tail;
+    }
+
+    if rest.contains(".*") {
+        return Err(Error::new_spanned(
+            literal,
+            "positional arguments are not supported, refer to a field by its name or index instead",
+        ));
+    }
+
+    while let Some(position) = rest.find('$') {
+        let (head, tail) = rest.split_at(position);
+        let start = head
+            .trim_end_matches(|char: char| char.is_alphanumeric() || char == '_')
+            .len();
+
+        output.push_str(&head[..start]);
+        output.push_str(&parse_argument(literal, &head[start..], fields)?);
+        output.push('$');
+        rest = &tail[1..];
+    }
+    output.push_str(rest);
+
+    Ok(())
+}
+
+fn parse_argument(literal: &LitStr, argument: &str, fields: &mut Vec<Member>) -> Result<String> {
+    let member = if let Ok(index) = argument.parse::<u32>() {
+        Member::Unnamed(Index {
+            index,
+            span: literal.span(),
+        })
+    } else {
+        Member::Named(syn::parse_str(argument).map_err(|_error| {
+            Error::new_spanned(
+                literal,
+                format!("invalid format string: `{argument}` is not a valid field"),
+            )
+        })?)
+    };
+
+    let name = binding(&member).to_string();
+    if !fields.contains(&member) {
+        fields.push(member);
+    }
+
+    Ok(name)
+}
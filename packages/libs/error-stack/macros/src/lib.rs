@@ -1 +1,117 @@
 #![doc = include_str!("../README.md")]
+#![warn(
+    missing_docs,
+    unreachable_pub,
+    clippy::pedantic,
+    clippy::nursery,
+    clippy::undocumented_unsafe_blocks
+)]
+#![allow(clippy::redundant_pub_crate)] // This would otherwise clash with `unreachable_pub`
+#![allow(clippy::module_name_repetitions)]
+#![forbid(unsafe_code)]
+
+mod context;
+mod format;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Derives [`Display`] and [`Context`] for a struct or an enum.
+///
+/// The message is specified by the `#[display("...")]` attribute. It accepts a format string like
+/// [`format!`], which can refer to named fields by their name (`{field}`) and to the fields of
+/// tuple structs or variants by their index (`{0}`). Format specs are supported as well, including
+/// width and precision arguments (`{value:>width$}`).
+///
+/// For enums, every variant may specify its own message. If a variant does not, the attribute on
+/// the enum is used as a fallback.
+///
+/// ```
+/// use error_stack_macros::Context;
+///
+/// #[derive(Debug, Context)]
+/// #[display("could not parse configuration")]
+/// struct ConfigError;
+///
+/// #[derive(Debug, Context)]
+/// #[display("experiment {name:?} failed")]
+/// struct ExperimentError {
+///     name: String,
+/// }
+///
+/// #[derive(Debug, Context)]
+/// #[display("unknown error")]
+/// enum RunnerError {
+///     #[display("runner {0} exited with code {1}")]
+///     Exited(usize, i32),
+///     #[display("runner {id} timed out after {seconds:.1}s")]
+///     TimedOut {
+///         id: usize,
+///         seconds: f64,
+///     },
+///     Unknown,
+/// }
+///
+/// assert_eq!(ConfigError.to_string(), "could not parse configuration");
+///
+/// let error = ExperimentError {
+///     name: "foo".to_owned(),
+/// };
+/// assert_eq!(error.to_string(), r#"experiment "foo" failed"#);
+///
+/// assert_eq!(
+///     RunnerError::Exited(3, 1).to_string(),
+///     "runner 3 exited with code 1"
+/// );
+/// let error = RunnerError::TimedOut {
+///     id: 3,
+///     seconds: 2.5,
+/// };
+/// assert_eq!(error.to_string(), "runner 3 timed out after 2.5s");
+/// assert_eq!(RunnerError::Unknown.to_string(), "unknown error");
+/// ```
+///
+/// ## Provider API
+///
+/// With the `provide` feature, fields annotated with `#[provide]` are provided by
+/// [`Context::provide`], so they can be requested from a [`Report`] with [`Report::request_ref`].
+/// As the Provider API is unstable, this requires a nightly toolchain and the crate deriving
+/// `Context` has to enable `#![feature(provide_any)]`. Without the `provide` feature, `#[provide]`
+/// is rejected.
+///
+/// ```
+/// # #![cfg_attr(feature = "provide", feature(provide_any))]
+/// # #[cfg(feature = "provide")] {
+/// use error_stack_macros::Context;
+///
+/// #[derive(Debug)]
+/// pub struct Suggestion(&'static str);
+///
+/// #[derive(Debug, Context)]
+/// #[display("could not read the manifest")]
+/// struct ManifestError {
+///     #[provide]
+///     suggestion: Suggestion,
+/// }
+///
+/// let report = error_stack::Report::new(ManifestError {
+///     suggestion: Suggestion("create `manifest.json`"),
+/// });
+/// let suggestion = report.request_ref::<Suggestion>().next().unwrap();
+/// assert_eq!(suggestion.0, "create `manifest.json`");
+/// # }
+/// ```
+///
+/// [`Display`]: core::fmt::Display
+/// [`Context`]: https://docs.rs/error-stack/latest/error_stack/trait.Context.html
+/// [`Context::provide`]: https://docs.rs/error-stack/latest/error_stack/trait.Context.html#method.provide
+/// [`Report`]: https://docs.rs/error-stack/latest/error_stack/struct.Report.html
+/// [`Report::request_ref`]: https://docs.rs/error-stack/latest/error_stack/struct.Report.html#method.request_ref
+#[proc_macro_derive(Context, attributes(display, provide))]
+pub fn derive_context(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    context::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,9 @@
+#[cfg_attr(miri, ignore = "Miri does not support UI tests")]
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+
+    #[cfg(not(feature = "provide"))]
+    t.compile_fail("tests/ui/provide/*.rs");
+}
@@ -0,0 +1,161 @@
+#![cfg_attr(feature = "provide", feature(provide_any))]
+
+use core::fmt;
+
+use error_stack::Report;
+use error_stack_macros::Context;
+
+#[derive(Debug, Context)]
+#[display("unit struct")]
+struct Unit;
+
+#[derive(Debug, Context)]
+#[display("named {id} with {message:?}")]
+struct Named {
+    id: u32,
+    message: &'static str,
+}
+
+#[derive(Debug, Context)]
+#[display("tuple {1}: {0}")]
+struct Tuple(&'static str, u32);
+
+#[derive(Debug, Context)]
+#[display("fallback")]
+enum Enum {
+    #[display("unit variant")]
+    Unit,
+    #[display("tuple variant {0}")]
+    Tuple(u32),
+    #[display("named variant {id}")]
+    Named {
+        id: u32,
+    },
+    Other,
+}
+
+#[derive(Debug, Context)]
+#[display("{value:>width$.precision$}|")]
+struct Spec {
+    value: f64,
+    width: usize,
+    precision: usize,
+}
+
+#[derive(Debug, Context)]
+#[display("{{escaped}} {0:#x} {0:?}")]
+struct Escaped(u32);
+
+#[derive(Debug, Context)]
+#[display("generic {0}")]
+struct Generic<T: fmt::Display + fmt::Debug + Send + Sync + 'static>(T);
+
+#[derive(Debug, Context)]
+enum Empty {}
+
+#[test]
+fn structs() {
+    assert_eq!(Unit.to_string(), "unit struct");
+    assert_eq!(
+        Named {
+            id: 1,
+            message: "message"
+        }
+        .to_string(),
+        r#"named 1 with "message""#
+    );
+    assert_eq!(Tuple("message", 2).to_string(), "tuple 2: message");
+}
+
+#[test]
+fn enums() {
+    assert_eq!(Enum::Unit.to_string(), "unit variant");
+    assert_eq!(Enum::Tuple(1).to_string(), "tuple variant 1");
+    assert_eq!(Enum::Named { id: 2 }.to_string(), "named variant 2");
+    assert_eq!(Enum::Other.to_string(), "fallback");
+}
+
+#[test]
+fn format_spec() {
+    let spec = Spec {
+        value: 1.0,
+        width: 6,
+        precision: 2,
+    };
+    assert_eq!(spec.to_string(), "  1.00|");
+    assert_eq!(Escaped(255).to_string(), "{escaped} 0xff 255");
+}
+
+#[test]
+fn generics() {
+    assert_eq!(Generic("value").to_string(), "generic value");
+}
+
+#[test]
+fn report() {
+    fn assert_context<C: error_stack::Context>() {}
+    assert_context::<Empty>();
+
+    let report = Report::new(Unit).change_context(Enum::Tuple(1));
+
+    assert!(report.contains::<Unit>());
+    assert_eq!(report.to_string(), "tuple variant 1");
+}
+
+#[cfg(feature = "provide")]
+mod provide {
+    use error_stack::Report;
+    use error_stack_macros::Context;
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Suggestion(&'static str);
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct ExitCode(i32);
+
+    #[derive(Debug, Context)]
+    #[display("could not read {path}")]
+    struct ReadError {
+        path: &'static str,
+        #[provide]
+        suggestion: Suggestion,
+    }
+
+    #[derive(Debug, Context)]
+    enum RunnerError {
+        #[display("runner exited")]
+        Exited(#[provide] ExitCode, #[provide] Suggestion),
+        #[display("runner timed out")]
+        TimedOut,
+    }
+
+    #[test]
+    fn struct_fields() {
+        let report = Report::new(ReadError {
+            path: "manifest.json",
+            suggestion: Suggestion("create the manifest"),
+        });
+
+        assert_eq!(report.request_ref::<Suggestion>().collect::<Vec<_>>(), [
+            &Suggestion("create the manifest")
+        ]);
+    }
+
+    #[test]
+    fn enum_fields() {
+        let report = Report::new(RunnerError::Exited(
+            ExitCode(1),
+            Suggestion("check the logs"),
+        ));
+
+        assert_eq!(report.request_ref::<ExitCode>().collect::<Vec<_>>(), [
+            &ExitCode(1)
+        ]);
+        assert_eq!(report.request_ref::<Suggestion>().collect::<Vec<_>>(), [
+            &Suggestion("check the logs")
+        ]);
+
+        let report = Report::new(RunnerError::TimedOut);
+        assert_eq!(report.request_ref::<ExitCode>().count(), 0);
+    }
+}
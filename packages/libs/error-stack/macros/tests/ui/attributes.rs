@@ -0,0 +1,27 @@
+use error_stack_macros::Context;
+
+#[derive(Debug, Context)]
+#[display("provide with arguments")]
+struct ProvideArguments {
+    #[provide(ref)]
+    value: u32,
+}
+
+#[derive(Debug, Context)]
+#[display("provide on type")]
+#[provide]
+struct ProvideType;
+
+#[derive(Debug, Context)]
+#[display("display on field")]
+struct DisplayField {
+    #[display("field")]
+    value: u32,
+}
+
+#[derive(Context)]
+union Union {
+    value: u32,
+}
+
+fn main() {}
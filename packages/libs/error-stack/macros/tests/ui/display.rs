@@ -0,0 +1,36 @@
+use error_stack_macros::Context;
+
+#[derive(Debug, Context)]
+struct MissingAttribute;
+
+#[derive(Debug, Context)]
+#[display("no field {id}")]
+struct UnknownField {
+    name: String,
+}
+
+#[derive(Debug, Context)]
+#[display("tuple {1}")]
+struct UnknownIndex(u32);
+
+#[derive(Debug, Context)]
+#[display("positional {}")]
+struct Positional(u32);
+
+#[derive(Debug, Context)]
+#[display("unmatched }")]
+struct Unmatched;
+
+#[derive(Debug, Context)]
+#[display("first")]
+#[display("second")]
+struct Duplicate;
+
+#[derive(Debug, Context)]
+enum MissingVariantAttribute {
+    #[display("first")]
+    First,
+    Second,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use error_stack_macros::Context;
+
+#[derive(Debug)]
+struct Suggestion(&'static str);
+
+#[derive(Debug, Context)]
+#[display("provide without feature")]
+struct ProvideField {
+    #[provide]
+    suggestion: Suggestion,
+}
+
+fn main() {}
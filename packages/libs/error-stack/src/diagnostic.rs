@@ -0,0 +1,251 @@
+//! Attachments for compiler-style diagnostics.
+//!
+//! Errors, which are caused by user input like configuration files or manifests, are a lot easier
+//! to understand if they point at the offending input. The attachments in this module are rendered
+//! by the [`Debug`] output of a [`Report`] similar to the diagnostics of a compiler:
+//!
+//! - [`SourceSnippet`] renders an excerpt of the source with line numbers and every [`Label`]
+//!   underlining a span of it,
+//! - [`Help`] renders a message suggesting how to resolve the error, and
+//! - [`Code`] renders an error code, which can be looked up in the documentation.
+//!
+//! The rendering uses box-drawing characters and colors if the `pretty-print` feature is enabled,
+//! and ASCII characters otherwise. A hook installed for any of the attachments takes precedence
+//! over the built-in rendering.
+//!
+//! ```rust
+//! # // we only test with Rust 1.65, which means that `render()` is unused on earlier version
+//! # #![cfg_attr(not(rust_1_65), allow(dead_code, unused_variables, unused_imports))]
+//! use std::fmt;
+//!
+//! use error_stack::{
+//!     diagnostic::{Code, Help, Label, SourceSnippet},
+//!     Context, Report,
+//! };
+//!
+//! #[derive(Debug)]
+//! struct ManifestError;
+//!
+//! impl fmt::Display for ManifestError {
+//!     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+//!         fmt.write_str("invalid experiment manifest")
+//!     }
+//! }
+//!
+//! impl Context for ManifestError {}
+//!
+//! let source = r#"{
+//!     "name": "warmup",
+//!     "steps": "ten"
+//! }"#;
+//!
+//! # std::env::set_var("RUST_LIB_BACKTRACE", "0");
+//! let report = Report::new(ManifestError)
+//!     .attach(Code::new("E0042"))
+//!     .attach(
+//!         SourceSnippet::new(source)
+//!             .with_origin("experiments.json")
+//!             .with_label(Label::new(37..42).with_message("expected a number")),
+//!     )
+//!     .attach(Help::new("the number of steps needs to be an integer, e.g. `10`"));
+//!
+//! # owo_colors::set_override(false);
+//! # fn render(value: String) -> String {
+//! #     let backtrace = regex::Regex::new(r"backtrace no\. (\d+)\n(?:  .*\n)*  .*").unwrap();
+//! #     let backtrace_info = regex::Regex::new(r"backtrace( with (\d+) frames)? \((\d+)\)").unwrap();
+//! #
+//! #     let value = backtrace.replace_all(&value, "backtrace no. $1\n  [redacted]");
+//! #     let value = backtrace_info.replace_all(value.as_ref(), "backtrace ($3)");
+//! #
+//! #     ansi_to_html::convert_escaped(value.as_ref()).unwrap()
+//! # }
+//! #
+//! # #[cfg(rust_1_65)]
+//! # expect_test::expect_file![concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots/doc/diagnostic__doc.snap")].assert_eq(&render(format!("{report:?}")));
+//! #
+//! println!("{report:?}");
+//! ```
+//!
+//! <pre>
+#![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots/doc/diagnostic__doc.snap"))]
+//! </pre>
+//!
+//! [`Debug`]: core::fmt::Debug
+//! [`Report`]: crate::Report
+
+use alloc::{borrow::Cow, vec::Vec};
+use core::{fmt, ops::Range};
+
+use crate::fmt::diagnostic::{Charset, SnippetRenderer};
+
+/// An error code, which identifies the kind of error.
+///
+/// Rendered as `code: <code>` in the [`Debug`] output of a [`Report`].
+///
+/// [`Debug`]: core::fmt::Debug
+/// [`Report`]: crate::Report
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Code(Cow<'static, str>);
+
+impl Code {
+    /// Creates a new error code.
+    #[must_use]
+    pub fn new(code: impl Into<Cow<'static, str>>) -> Self {
+        Self(code.into())
+    }
+
+    /// Returns the error code.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(&self.0)
+    }
+}
+
+/// A message, which suggests how to resolve an error.
+///
+/// Rendered as `help: <message>` in the [`Debug`] output of a [`Report`].
+///
+/// [`Debug`]: core::fmt::Debug
+/// [`Report`]: crate::Report
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Help(Cow<'static, str>);
+
+impl Help {
+    /// Creates a new help message.
+    #[must_use]
+    pub fn new(message: impl Into<Cow<'static, str>>) -> Self {
+        Self(message.into())
+    }
+
+    /// Returns the help message.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Help {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(&self.0)
+    }
+}
+
+/// A span inside of a [`SourceSnippet`], which is underlined when rendered, optionally followed
+/// by a message.
+///
+/// The span is given as byte offsets into the source of the [`SourceSnippet`]. Spans, which are
+/// out of bounds, are truncated to the source.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Label {
+    span: Range<usize>,
+    message: Option<Cow<'static, str>>,
+}
+
+impl Label {
+    /// Creates a new label for the byte range `span`.
+    #[must_use]
+    pub const fn new(span: Range<usize>) -> Self {
+        Self {
+            span,
+            message: None,
+        }
+    }
+
+    /// Adds a message, which is displayed next to the underlined span.
+    #[must_use]
+    pub fn with_message(mut self, message: impl Into<Cow<'static, str>>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Returns the byte range of the label.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns the message of the label, if any.
+    #[must_use]
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
+
+impl fmt::Display for Label {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.message {
+            Some(message) => write!(fmt, "{message} at {:?}", self.span),
+            None => write!(fmt, "{:?}", self.span),
+        }
+    }
+}
+
+/// An excerpt of source code, like the contents of a configuration file, with [`Label`]s.
+///
+/// Only the lines covered by a label are rendered. If no label is added, all lines of the source
+/// are rendered. The [`Display`] implementation renders the snippet with ASCII characters and
+/// without colors.
+///
+/// [`Display`]: core::fmt::Display
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceSnippet {
+    origin: Option<Cow<'static, str>>,
+    source: Cow<'static, str>,
+    labels: Vec<Label>,
+}
+
+impl SourceSnippet {
+    /// Creates a new snippet for `source`.
+    #[must_use]
+    pub fn new(source: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            origin: None,
+            source: source.into(),
+            labels: Vec::new(),
+        }
+    }
+
+    /// Sets the origin of the source, like a path, which is displayed in front of the source.
+    #[must_use]
+    pub fn with_origin(mut self, origin: impl Into<Cow<'static, str>>) -> Self {
+        self.origin = Some(origin.into());
+        self
+    }
+
+    /// Adds a label to the snippet.
+    #[must_use]
+    pub fn with_label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    /// Returns the origin of the source, if any.
+    #[must_use]
+    pub fn origin(&self) -> Option<&str> {
+        self.origin.as_deref()
+    }
+
+    /// Returns the source.
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the labels of the snippet.
+    #[must_use]
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+}
+
+impl fmt::Display for SourceSnippet {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&SnippetRenderer::new(self, Charset::Ascii), fmt)
+    }
+}
@@ -0,0 +1,372 @@
+use alloc::{
+    borrow::Cow,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt::Write, panic::Location};
+use std::backtrace::Backtrace;
+
+use crate::fmt::HookContext;
+
+/// Symbol prefixes of the standard library, the test harness, common async runtimes, and
+/// `error-stack` itself.
+const RUNTIME_PREFIXES: &[&str] = &[
+    "std::",
+    "core::",
+    "alloc::",
+    "test::",
+    // function pointers called through traits of the standard library, e.g.
+    // `<fn() as core::ops::function::FnOnce<()>>::call_once`
+    "fn(",
+    "tokio::",
+    "futures::",
+    "futures_core::",
+    "futures_util::",
+    "futures_executor::",
+    "async_std::",
+    "error_stack::",
+    "__rust",
+    "__libc_start",
+    "_start",
+    "<unknown>",
+];
+
+/// The [`Location`]s rendered so far, recorded by the builtin hook for [`Location`].
+pub(crate) struct Locations(Vec<Location<'static>>);
+
+pub(crate) fn record_location(
+    location: &Location<'static>,
+    context: &mut HookContext<Location<'static>>,
+) {
+    match context.get_mut::<Locations>() {
+        Some(Locations(locations)) => locations.push(*location),
+        None => {
+            context.insert(Locations(Vec::from([*location])));
+        }
+    }
+}
+
+/// The backtraces rendered so far together with their number, used for deduplication.
+struct Rendered(Vec<(String, isize)>);
+
+struct BacktraceFrame<'a> {
+    index: Option<&'a str>,
+    symbol: &'a str,
+    location: Option<&'a str>,
+}
+
+impl BacktraceFrame<'_> {
+    /// The path of the symbol without leading references or angle brackets, e.g.
+    /// `<&core::pin::Pin<P> as core::future::Future>::poll` becomes `core::pin::Pin<P> as ...`.
+    fn path(&self) -> &str {
+        self.symbol
+            .trim_start_matches(|char| char == '<' || char == '&')
+            .trim_start_matches("dyn ")
+            .trim_start_matches("mut ")
+    }
+
+    /// Splits the location of the frame into its file and line.
+    fn file_and_line(&self) -> Option<(&str, u32)> {
+        let mut parts = self.location?.rsplitn(3, ':');
+        let _column = parts.next()?;
+        let line = parts.next()?.parse().ok()?;
+        let file = parts.next()?;
+
+        Some((file, line))
+    }
+
+    fn is_at(&self, location: &Location) -> bool {
+        self.file_and_line().map_or(false, |(file, line)| {
+            file.ends_with(location.file()) && line == location.line()
+        })
+    }
+
+    fn is_before(&self, location: &Location) -> bool {
+        self.file_and_line().map_or(false, |(file, line)| {
+            file.ends_with(location.file()) && line <= location.line()
+        })
+    }
+}
+
+/// Parses the [`Display`] output of a [`Backtrace`].
+///
+/// The individual frames of a [`Backtrace`] are only accessible on nightly, and their symbols are
+/// not accessible at all, which is why the output is parsed instead.
+///
+/// [`Display`]: core::fmt::Display
+fn parse(backtrace: &str) -> Vec<BacktraceFrame> {
+    let mut frames: Vec<BacktraceFrame> = Vec::new();
+
+    for line in backtrace.lines().map(str::trim_start) {
+        if let Some(location) = line.strip_prefix("at ") {
+            if let Some(frame) = frames.last_mut() {
+                frame.location = Some(location);
+            }
+
+            continue;
+        }
+
+        let frame = match line.split_once(": ") {
+            Some((index, symbol)) if index.bytes().all(|byte| byte.is_ascii_digit()) => {
+                BacktraceFrame {
+                    index: Some(index),
+                    symbol,
+                    location: None,
+                }
+            }
+            // symbols of inlined functions are not prefixed with an index
+            _ if !line.is_empty() => BacktraceFrame {
+                index: None,
+                symbol: line,
+                location: None,
+            },
+            _ => continue,
+        };
+        frames.push(frame);
+    }
+
+    frames
+}
+
+/// Configurable rendering of [`Backtrace`]s in the [`Debug`] output of a [`Report`].
+///
+/// By default, every frame of a [`Backtrace`] is rendered, which includes the frames of the
+/// standard library, async runtimes like `tokio`, and the frames of `error-stack` itself. A
+/// `BacktraceFilter` is installed as a [`Debug`] hook for [`Backtrace`] and is able to:
+///
+/// - only keep frames of specific crates ([`crate_prefix`]),
+/// - collapse consecutive frames of the runtime into a single line ([`collapse_runtime`]),
+/// - only keep the frames between the location the report has been created at and its outermost
+///   location ([`between_locations`]), and
+/// - render identical backtraces only once, e.g. those of sibling frames created in a loop and
+///   combined with [`Report::extend_one`] ([`deduplicate`]).
+///
+/// ```rust
+/// use std::backtrace::Backtrace;
+///
+/// use error_stack::{fmt::BacktraceFilter, Report};
+///
+/// Report::install_debug_hook::<Backtrace>(
+///     BacktraceFilter::new()
+///         .crate_prefix("graph")
+///         .crate_prefix("engine")
+///         .collapse_runtime(true)
+///         .deduplicate(true)
+///         .into_hook(),
+/// );
+/// ```
+///
+/// [`Debug`]: core::fmt::Debug
+/// [`Report`]: crate::Report
+/// [`Report::extend_one`]: crate::Report::extend_one
+/// [`crate_prefix`]: Self::crate_prefix
+/// [`collapse_runtime`]: Self::collapse_runtime
+/// [`between_locations`]: Self::between_locations
+/// [`deduplicate`]: Self::deduplicate
+#[derive(Debug, Clone, Default)]
+pub struct BacktraceFilter {
+    crates: Vec<Cow<'static, str>>,
+    runtime: Vec<Cow<'static, str>>,
+    collapse_runtime: bool,
+    between_locations: bool,
+    deduplicate: bool,
+}
+
+impl BacktraceFilter {
+    /// Creates a new filter, which renders every frame.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only keeps frames, whose symbol starts with `prefix`, like `my_crate::`.
+    ///
+    /// If called multiple times, frames matching any of the prefixes are kept.
+    #[must_use]
+    pub fn crate_prefix(mut self, prefix: impl Into<Cow<'static, str>>) -> Self {
+        self.crates.push(prefix.into());
+        self
+    }
+
+    /// Considers frames, whose symbol starts with `prefix`, as part of the runtime.
+    ///
+    /// The standard library, the test harness, `tokio`, `futures`, `async-std`, and `error-stack`
+    /// are always considered as part of the runtime.
+    #[must_use]
+    pub fn runtime_prefix(mut self, prefix: impl Into<Cow<'static, str>>) -> Self {
+        self.runtime.push(prefix.into());
+        self
+    }
+
+    /// Collapses consecutive frames of the runtime into a single line.
+    #[must_use]
+    pub const fn collapse_runtime(mut self, collapse: bool) -> Self {
+        self.collapse_runtime = collapse;
+        self
+    }
+
+    /// Only keeps the frames between the location a report has been created at and the outermost
+    /// location of the report.
+    ///
+    /// Locations are matched by their file and line. If a location cannot be found in the
+    /// backtrace, the frames are not trimmed at that end. This requires the builtin hook for
+    /// [`Location`] to be active, it has no effect if a custom hook for [`Location`] is installed.
+    #[must_use]
+    pub const fn between_locations(mut self, between: bool) -> Self {
+        self.between_locations = between;
+        self
+    }
+
+    /// Renders a backtrace only once if multiple frames of a report have identical backtraces
+    /// after filtering.
+    #[must_use]
+    pub const fn deduplicate(mut self, deduplicate: bool) -> Self {
+        self.deduplicate = deduplicate;
+        self
+    }
+
+    /// Converts the filter into a hook, which can be installed with
+    /// [`Report::install_debug_hook`].
+    ///
+    /// [`Report::install_debug_hook`]: crate::Report::install_debug_hook
+    pub fn into_hook(
+        self,
+    ) -> impl Fn(&Backtrace, &mut HookContext<Backtrace>) + Send + Sync + 'static {
+        move |backtrace: &Backtrace, context: &mut HookContext<Backtrace>| {
+            self.render(backtrace, context);
+        }
+    }
+
+    fn is_runtime(&self, frame: &BacktraceFrame) -> bool {
+        let path = frame.path();
+
+        RUNTIME_PREFIXES
+            .iter()
+            .copied()
+            .chain(self.runtime.iter().map(AsRef::as_ref))
+            .any(|prefix| path.starts_with(prefix))
+    }
+
+    fn is_kept(&self, frame: &BacktraceFrame) -> bool {
+        self.crates.is_empty()
+            || self
+                .crates
+                .iter()
+                .any(|prefix| frame.path().starts_with(prefix.as_ref()))
+    }
+
+    /// Removes all frames before the innermost and after the outermost location.
+    fn trim<'a, 'b>(
+        frames: &'a [BacktraceFrame<'b>],
+        locations: &[Location<'static>],
+    ) -> &'a [BacktraceFrame<'b>] {
+        let (Some(outermost), Some(innermost)) = (locations.first(), locations.last()) else {
+            return frames;
+        };
+
+        let start = frames
+            .iter()
+            .position(|frame| frame.is_at(innermost))
+            .unwrap_or_default();
+        let end = if locations.len() > 1 {
+            frames[start..]
+                .iter()
+                .position(|frame| frame.is_before(outermost))
+                .map_or(frames.len(), |position| start + position + 1)
+        } else {
+            frames.len()
+        };
+
+        &frames[start..end]
+    }
+
+    /// Renders the backtrace and returns the output together with the number of rendered frames.
+    fn format(&self, backtrace: &str, locations: &[Location<'static>]) -> (String, usize) {
+        let frames = parse(backtrace);
+        if frames.is_empty() {
+            return (backtrace.to_string(), 0);
+        }
+
+        let frames = if self.between_locations {
+            Self::trim(&frames, locations)
+        } else {
+            &frames
+        };
+
+        let mut output = String::new();
+        let mut rendered = 0;
+        let mut hidden = 0;
+
+        for frame in frames.iter().filter(|frame| self.is_kept(frame)) {
+            if self.collapse_runtime && self.is_runtime(frame) {
+                hidden += 1;
+                continue;
+            }
+
+            if hidden > 0 {
+                // writing to a `String` is infallible
+                let _ = writeln!(output, "      [{hidden} runtime frames hidden]");
+                hidden = 0;
+            }
+
+            match frame.index {
+                Some(index) => {
+                    let _ = writeln!(output, "{index:>4}: {}", frame.symbol);
+                }
+                None => {
+                    let _ = writeln!(output, "      {}", frame.symbol);
+                }
+            }
+            if let Some(location) = frame.location {
+                let _ = writeln!(output, "             at {location}");
+            }
+
+            rendered += 1;
+        }
+
+        if hidden > 0 {
+            let _ = writeln!(output, "      [{hidden} runtime frames hidden]");
+        }
+
+        (output, rendered)
+    }
+
+    fn render(&self, backtrace: &Backtrace, context: &mut HookContext<Backtrace>) {
+        let (output, frames) = {
+            let locations = context.cast::<Location<'static>>().get::<Locations>();
+            self.format(
+                &backtrace.to_string(),
+                locations.map_or(&[], |Locations(locations)| locations),
+            )
+        };
+
+        if self.deduplicate {
+            let previous = context.get::<Rendered>().and_then(|Rendered(rendered)| {
+                rendered
+                    .iter()
+                    .find(|(previous, _)| *previous == output)
+                    .map(|(_, idx)| *idx)
+            });
+
+            if let Some(idx) = previous {
+                context.push_body(format!("backtrace with {frames} frames ({})", idx + 1));
+                return;
+            }
+        }
+
+        let idx = context.increment_counter();
+
+        context.push_appendix(format!("backtrace no. {}\n{output}", idx + 1));
+        context.push_body(format!("backtrace with {frames} frames ({})", idx + 1));
+
+        if self.deduplicate {
+            match context.get_mut::<Rendered>() {
+                Some(Rendered(rendered)) => rendered.push((output, idx)),
+                None => {
+                    context.insert(Rendered(Vec::from([(output, idx)])));
+                }
+            }
+        }
+    }
+}
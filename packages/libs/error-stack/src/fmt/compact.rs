@@ -0,0 +1,87 @@
+//! Implementation of [`Format::Compact`].
+//!
+//! Every context is followed by the printable attachments leading up to it and the contexts it has
+//! been caused by:
+//!
+//! ```text
+//! context B: attachment B; caused by context A: attachment A, attachment A'
+//! ```
+//!
+//! Multiple sources are each wrapped in brackets and separated by a comma
+//! (`context B; caused by [context A], [context A']`). Opaque attachments are omitted.
+//!
+//! [`Format::Compact`]: crate::Format::Compact
+
+use alloc::string::{String, ToString};
+use core::{
+    fmt::{self, Display, Formatter, Write},
+    mem,
+};
+
+use crate::{AttachmentKind, Frame, FrameKind};
+
+/// Writes `value` on a single line by joining its lines with a space.
+fn write_line(fmt: &mut Formatter<'_>, value: &impl Display) -> fmt::Result {
+    for (idx, line) in value.to_string().lines().enumerate() {
+        if idx > 0 {
+            fmt.write_char(' ')?;
+        }
+        fmt.write_str(line)?;
+    }
+
+    Ok(())
+}
+
+pub(super) fn compact_frames(
+    fmt: &mut Formatter<'_>,
+    frames: &[Frame],
+    prefix: &[String],
+) -> fmt::Result {
+    if let [frame] = frames {
+        return compact_frame(fmt, frame, prefix);
+    }
+
+    for (idx, frame) in frames.iter().enumerate() {
+        if idx > 0 {
+            fmt.write_str(", ")?;
+        }
+
+        fmt.write_char('[')?;
+        compact_frame(fmt, frame, prefix)?;
+        fmt.write_char(']')?;
+    }
+
+    Ok(())
+}
+
+fn compact_frame(fmt: &mut Formatter<'_>, mut frame: &Frame, prefix: &[String]) -> fmt::Result {
+    let mut attachments = prefix.to_vec();
+
+    loop {
+        match frame.kind() {
+            FrameKind::Context(context) => {
+                write_line(fmt, &context)?;
+
+                // attachments are collected from the outermost to the innermost, but are displayed
+                // in the order they have been attached
+                for (idx, attachment) in mem::take(&mut attachments).iter().rev().enumerate() {
+                    fmt.write_str(if idx == 0 { ": " } else { ", " })?;
+                    write_line(fmt, &attachment)?;
+                }
+
+                if !frame.sources().is_empty() {
+                    fmt.write_str("; caused by ")?;
+                }
+            }
+            FrameKind::Attachment(AttachmentKind::Printable(attachment)) => {
+                attachments.push(attachment.to_string());
+            }
+            FrameKind::Attachment(AttachmentKind::Opaque(_)) => {}
+        }
+
+        match frame.sources() {
+            [source] => frame = source,
+            sources => return compact_frames(fmt, sources, &attachments),
+        }
+    }
+}
@@ -0,0 +1,377 @@
+//! Rendering of the attachments in [`crate::diagnostic`].
+
+#[cfg(not(any(feature = "std", feature = "hooks")))]
+use alloc::vec;
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "pretty-print")]
+use owo_colors::{OwoColorize, Stream};
+
+use crate::diagnostic::{Code, Help, Label, SourceSnippet};
+#[cfg(not(any(feature = "std", feature = "hooks")))]
+use crate::Frame;
+
+/// The characters used to render a [`SourceSnippet`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Charset {
+    Ascii,
+    Utf8,
+}
+
+impl Charset {
+    /// The charset used by the [`Debug`] output of a [`Report`].
+    ///
+    /// [`Debug`]: core::fmt::Debug
+    /// [`Report`]: crate::Report
+    pub(crate) const fn current() -> Self {
+        if cfg!(feature = "pretty-print") {
+            Self::Utf8
+        } else {
+            Self::Ascii
+        }
+    }
+
+    const fn vertical(self) -> &'static str {
+        match self {
+            Self::Ascii => "|",
+            Self::Utf8 => "│",
+        }
+    }
+
+    const fn arrow(self) -> &'static str {
+        match self {
+            Self::Ascii => "-->",
+            Self::Utf8 => " ╭─▶",
+        }
+    }
+
+    const fn underline(self) -> &'static str {
+        match self {
+            Self::Ascii => "^",
+            Self::Utf8 => "━",
+        }
+    }
+
+    const fn gap(self) -> &'static str {
+        match self {
+            Self::Ascii => "...",
+            Self::Utf8 => "⋮",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Highlight {
+    Gutter,
+    Label,
+}
+
+/// A line of the source, `start` is the byte offset of the line in the source.
+struct SourceLine<'a> {
+    start: usize,
+    text: &'a str,
+}
+
+/// A [`Label`] resolved against the source, `first` and `last` are the indices of the lines the
+/// label starts and ends on.
+struct Span<'a> {
+    start: usize,
+    end: usize,
+    first: usize,
+    last: usize,
+    message: Option<&'a str>,
+}
+
+fn source_lines(source: &str) -> Vec<SourceLine> {
+    let mut start = 0;
+    let mut lines: Vec<_> = source
+        .split('\n')
+        .map(|text| {
+            let line = SourceLine {
+                start,
+                text: text.strip_suffix('\r').unwrap_or(text),
+            };
+            start += text.len() + 1;
+            line
+        })
+        .collect();
+
+    // a trailing newline does not start a new line
+    if lines.len() > 1 && lines.last().map_or(false, |line| line.text.is_empty()) {
+        lines.pop();
+    }
+
+    lines
+}
+
+fn resolve<'a>(label: &'a Label, source: &str, lines: &[SourceLine]) -> Span<'a> {
+    let span = label.span();
+
+    let mut start = span.start.min(source.len());
+    let mut end = span.end.clamp(start, source.len());
+    while !source.is_char_boundary(start) {
+        start -= 1;
+    }
+    while !source.is_char_boundary(end) {
+        end += 1;
+    }
+
+    let line_of = |offset| {
+        lines
+            .iter()
+            .rposition(|line| line.start <= offset)
+            .unwrap_or_default()
+    };
+    let first = line_of(start);
+    let last = if end > start { line_of(end - 1) } else { first };
+
+    Span {
+        start,
+        end,
+        first,
+        last,
+        message: label.message(),
+    }
+}
+
+/// The display width of `text` up to the byte `offset`, tabs are expanded to four spaces.
+fn column(text: &str, offset: usize) -> usize {
+    text[..offset.min(text.len())]
+        .chars()
+        .map(|char| if char == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+/// Renders a [`SourceSnippet`] similar to the diagnostics of a compiler:
+///
+/// ```text
+///  --> experiments.json:3:14
+///   |
+/// 3 |     "steps": "ten"
+///   |              ^^^^^ expected a number
+/// ```
+#[derive(Copy, Clone)]
+pub(crate) struct SnippetRenderer<'a> {
+    snippet: &'a SourceSnippet,
+    charset: Charset,
+    #[cfg(feature = "pretty-print")]
+    color: bool,
+}
+
+impl<'a> SnippetRenderer<'a> {
+    pub(crate) const fn new(snippet: &'a SourceSnippet, charset: Charset) -> Self {
+        Self {
+            snippet,
+            charset,
+            #[cfg(feature = "pretty-print")]
+            color: false,
+        }
+    }
+
+    #[cfg(feature = "pretty-print")]
+    fn paint(self, highlight: Highlight, value: &str) -> String {
+        if !self.color {
+            return value.to_owned();
+        }
+
+        match highlight {
+            Highlight::Gutter => value.bright_blue().to_string(),
+            Highlight::Label => value.red().bold().to_string(),
+        }
+    }
+
+    // Reason: the signature needs to be the same as with `pretty-print`
+    #[cfg(not(feature = "pretty-print"))]
+    #[allow(clippy::unused_self)]
+    fn paint(self, _: Highlight, value: &str) -> String {
+        value.to_owned()
+    }
+
+    fn write_underline(
+        self,
+        fmt: &mut Formatter<'_>,
+        gutter: &str,
+        index: usize,
+        line: &SourceLine,
+        span: &Span,
+    ) -> fmt::Result {
+        let start = if index == span.first {
+            column(line.text, span.start - line.start)
+        } else {
+            0
+        };
+        let end = if index == span.last {
+            column(line.text, span.end.saturating_sub(line.start))
+        } else {
+            column(line.text, line.text.len())
+        };
+
+        let underline = self
+            .charset
+            .underline()
+            .repeat(end.saturating_sub(start).max(1));
+        write!(
+            fmt,
+            "\n{gutter} {} {:start$}{}",
+            self.paint(Highlight::Gutter, self.charset.vertical()),
+            "",
+            self.paint(Highlight::Label, &underline)
+        )?;
+
+        match span.message {
+            Some(message) if index == span.last => {
+                write!(fmt, " {}", self.paint(Highlight::Label, message))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Display for SnippetRenderer<'_> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        let source = self.snippet.source();
+        let lines = source_lines(source);
+
+        let mut spans: Vec<_> = self
+            .snippet
+            .labels()
+            .iter()
+            .map(|label| resolve(label, source, &lines))
+            .collect();
+        spans.sort_by_key(|span| (span.start, span.end));
+
+        // only lines covered by a label are displayed, if there are no labels, every line is
+        let mut shown: Vec<_> = if spans.is_empty() {
+            (0..lines.len()).collect()
+        } else {
+            spans
+                .iter()
+                .flat_map(|span| span.first..=span.last)
+                .collect()
+        };
+        shown.sort_unstable();
+        shown.dedup();
+
+        let width = shown
+            .last()
+            .map_or(1, |index| (index + 1).to_string().len());
+        let gutter = " ".repeat(width);
+        let vertical = self.paint(Highlight::Gutter, self.charset.vertical());
+
+        if let Some(origin) = self.snippet.origin() {
+            write!(
+                fmt,
+                "{gutter}{} {origin}",
+                self.paint(Highlight::Gutter, self.charset.arrow())
+            )?;
+
+            if let Some(span) = spans.first() {
+                let line = &lines[span.first];
+                let column = column(line.text, span.start - line.start) + 1;
+                write!(fmt, ":{}:{column}", span.first + 1)?;
+            }
+
+            fmt.write_str("\n")?;
+        }
+        write!(fmt, "{gutter} {vertical}")?;
+
+        let mut previous = None;
+        for index in shown {
+            if previous.map_or(false, |previous| index > previous + 1) {
+                write!(
+                    fmt,
+                    "\n{}",
+                    self.paint(Highlight::Gutter, self.charset.gap())
+                )?;
+            }
+            previous = Some(index);
+
+            let line = &lines[index];
+            let number = format!("{:>width$}", index + 1);
+            write!(
+                fmt,
+                "\n{} {vertical}",
+                self.paint(Highlight::Gutter, &number)
+            )?;
+            if !line.text.is_empty() {
+                write!(fmt, " {}", line.text.replace('\t', "    "))?;
+            }
+
+            for span in spans
+                .iter()
+                .filter(|span| span.first <= index && index <= span.last)
+            {
+                self.write_underline(fmt, &gutter, index, line, span)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "pretty-print")]
+fn keyword(name: &str) -> String {
+    name.if_supports_color(Stream::Stdout, OwoColorize::bold)
+        .to_string()
+}
+
+#[cfg(not(feature = "pretty-print"))]
+fn keyword(name: &str) -> String {
+    name.to_owned()
+}
+
+/// Renders a [`SourceSnippet`] for the [`Debug`] output of a [`Report`].
+///
+/// [`Debug`]: core::fmt::Debug
+/// [`Report`]: crate::Report
+#[cfg(feature = "pretty-print")]
+pub(crate) fn render_snippet(snippet: &SourceSnippet) -> String {
+    SnippetRenderer::new(snippet, Charset::current())
+        .if_supports_color(Stream::Stdout, |renderer| SnippetRenderer {
+            color: true,
+            ..*renderer
+        })
+        .to_string()
+}
+
+/// Renders a [`SourceSnippet`] for the [`Debug`] output of a [`Report`].
+///
+/// [`Debug`]: core::fmt::Debug
+/// [`Report`]: crate::Report
+#[cfg(not(feature = "pretty-print"))]
+pub(crate) fn render_snippet(snippet: &SourceSnippet) -> String {
+    SnippetRenderer::new(snippet, Charset::current()).to_string()
+}
+
+pub(crate) fn render_label(label: &Label) -> String {
+    format!("{}: {label}", keyword("label"))
+}
+
+pub(crate) fn render_help(help: &Help) -> String {
+    format!("{}: {help}", keyword("help"))
+}
+
+pub(crate) fn render_code(code: &Code) -> String {
+    format!("{}: {code}", keyword("code"))
+}
+
+/// Renders a frame if it is one of the attachments in [`crate::diagnostic`].
+///
+/// This is only used if hooks are unavailable, otherwise the attachments are rendered by the
+/// builtin hooks.
+#[cfg(not(any(feature = "std", feature = "hooks")))]
+pub(super) fn debug_diagnostic(frame: &Frame) -> Option<Vec<String>> {
+    frame
+        .downcast_ref::<SourceSnippet>()
+        .map(render_snippet)
+        .or_else(|| frame.downcast_ref::<Label>().map(render_label))
+        .or_else(|| frame.downcast_ref::<Help>().map(render_help))
+        .or_else(|| frame.downcast_ref::<Code>().map(render_code))
+        .map(|body| vec![body])
+}
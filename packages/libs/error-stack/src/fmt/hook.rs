@@ -458,8 +458,14 @@ mod default {
 
     #[cfg(feature = "pretty-print")]
     use crate::fmt::location::LocationDisplay;
+    #[cfg(feature = "remote")]
+    use crate::remote::RemoteLocation;
     use crate::{
-        fmt::hook::{into_boxed_hook, BoxedHook, HookContext},
+        diagnostic::{Code, Help, Label, SourceSnippet},
+        fmt::{
+            diagnostic::{render_code, render_help, render_label, render_snippet},
+            hook::{into_boxed_hook, BoxedHook, HookContext},
+        },
         Frame, Report,
     };
 
@@ -489,15 +495,43 @@ mod default {
 
             Report::install_debug_hook::<Location>(location);
 
+            #[cfg(feature = "remote")]
+            Report::install_debug_hook::<RemoteLocation>(remote_location);
+
             #[cfg(all(feature = "std", rust_1_65))]
             Report::install_debug_hook::<Backtrace>(backtrace);
 
             #[cfg(feature = "spantrace")]
             Report::install_debug_hook::<SpanTrace>(span_trace);
+
+            Report::install_debug_hook::<SourceSnippet>(|snippet, context| {
+                context.push_body(render_snippet(snippet));
+            });
+            Report::install_debug_hook::<Label>(|label, context| {
+                context.push_body(render_label(label));
+            });
+            Report::install_debug_hook::<Help>(|help, context| {
+                context.push_body(render_help(help));
+            });
+            Report::install_debug_hook::<Code>(|code, context| {
+                context.push_body(render_code(code));
+            });
         });
     }
 
     fn location(location: &Location<'static>, context: &mut HookContext<Location<'static>>) {
+        #[cfg(all(feature = "std", rust_1_65))]
+        crate::fmt::backtrace::record_location(location, context);
+
+        #[cfg(feature = "pretty-print")]
+        context.push_body(LocationDisplay::new(location).render());
+
+        #[cfg(not(feature = "pretty-print"))]
+        context.push_body(format!("at {location}"));
+    }
+
+    #[cfg(feature = "remote")]
+    fn remote_location(location: &RemoteLocation, context: &mut HookContext<RemoteLocation>) {
         #[cfg(feature = "pretty-print")]
         context.push_body(LocationDisplay::new(location).render());
 
@@ -0,0 +1,121 @@
+//! Implementation of [`Format::JsonLines`].
+//!
+//! The output has the same structure as the serialized [`Report`] with the `serde` feature, but is
+//! written on a single line and does not require `serde`:
+//!
+//! ```json
+//! [{"context":"context display output","attachments":["all","attachments"],"sources":[]}]
+//! ```
+//!
+//! Contexts and printable attachments are rendered using their [`Display`] output, [`Location`]s
+//! as `file:line:column`, and other opaque attachments using their type name.
+//!
+//! [`Format::JsonLines`]: crate::Format::JsonLines
+//! [`Report`]: crate::Report
+//! [`Display`]: core::fmt::Display
+//! [`Location`]: core::panic::Location
+
+use alloc::string::ToString;
+use core::{
+    fmt::{self, Display, Formatter, Write},
+    panic::Location,
+};
+
+use crate::{AttachmentKind, Frame, FrameKind};
+
+/// Writes `value` as a JSON string.
+fn write_string(fmt: &mut Formatter<'_>, value: &impl Display) -> fmt::Result {
+    fmt.write_char('"')?;
+
+    for char in value.to_string().chars() {
+        match char {
+            '"' => fmt.write_str("\\\"")?,
+            '\\' => fmt.write_str("\\\\")?,
+            '\n' => fmt.write_str("\\n")?,
+            '\r' => fmt.write_str("\\r")?,
+            '\t' => fmt.write_str("\\t")?,
+            char if char.is_control() => write!(fmt, "\\u{:04x}", char as u32)?,
+            char => fmt.write_char(char)?,
+        }
+    }
+
+    fmt.write_char('"')
+}
+
+fn write_attachment(fmt: &mut Formatter<'_>, frame: &Frame) -> fmt::Result {
+    match frame.kind() {
+        FrameKind::Context(context) => write_string(fmt, &context),
+        FrameKind::Attachment(AttachmentKind::Printable(attachment)) => {
+            write_string(fmt, &attachment)
+        }
+        FrameKind::Attachment(AttachmentKind::Opaque(_)) => {
+            match frame.downcast_ref::<Location<'static>>() {
+                Some(location) => write_string(fmt, location),
+                None => write_string(fmt, &frame.type_name()),
+            }
+        }
+    }
+}
+
+fn write_context(fmt: &mut Formatter<'_>, context: &Frame, attachments: &[&Frame]) -> fmt::Result {
+    fmt.write_str("{\"context\":")?;
+    write_attachment(fmt, context)?;
+
+    fmt.write_str(",\"attachments\":[")?;
+    // attachments are collected from the outermost to the innermost, but are displayed in the
+    // order they have been attached
+    for (idx, attachment) in attachments.iter().rev().enumerate() {
+        if idx > 0 {
+            fmt.write_char(',')?;
+        }
+        write_attachment(fmt, attachment)?;
+    }
+
+    fmt.write_str("],\"sources\":")?;
+    json_frames(fmt, context.sources())?;
+    fmt.write_char('}')
+}
+
+/// Writes the next contexts reachable from `current`, every attachment on the way is added to the
+/// attachments of the context.
+fn write_next<'a>(
+    fmt: &mut Formatter<'_>,
+    head: &[&'a Frame],
+    mut current: &'a Frame,
+    first: &mut bool,
+) -> fmt::Result {
+    let mut attachments = head.to_vec();
+
+    loop {
+        if let FrameKind::Context(_) = current.kind() {
+            if !*first {
+                fmt.write_char(',')?;
+            }
+            *first = false;
+
+            return write_context(fmt, current, &attachments);
+        }
+
+        attachments.push(current);
+        match current.sources() {
+            [source] => current = source,
+            sources => {
+                for source in sources {
+                    write_next(fmt, &attachments, source, first)?;
+                }
+
+                return Ok(());
+            }
+        }
+    }
+}
+
+pub(super) fn json_frames(fmt: &mut Formatter<'_>, frames: &[Frame]) -> fmt::Result {
+    let mut first = true;
+
+    fmt.write_char('[')?;
+    for frame in frames {
+        write_next(fmt, &[], frame, &mut first)?;
+    }
+    fmt.write_char(']')
+}
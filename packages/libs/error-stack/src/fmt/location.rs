@@ -3,14 +3,14 @@ use core::panic::Location;
 
 use owo_colors::{OwoColorize, Stream};
 
-pub(super) struct LocationDisplay<'a> {
-    location: &'a Location<'static>,
+pub(super) struct LocationDisplay<'a, L = Location<'static>> {
+    location: &'a L,
     supports_color: bool,
 }
 
-impl<'a> LocationDisplay<'a> {
+impl<'a, L: core::fmt::Display> LocationDisplay<'a, L> {
     #[must_use]
-    pub(super) const fn new(location: &'a Location<'static>) -> Self {
+    pub(super) const fn new(location: &'a L) -> Self {
         Self {
             location,
             supports_color: false,
@@ -27,7 +27,7 @@ impl<'a> LocationDisplay<'a> {
     }
 }
 
-impl<'a> core::fmt::Display for LocationDisplay<'a> {
+impl<'a, L: core::fmt::Display> core::fmt::Display for LocationDisplay<'a, L> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let location = self.location;
 
@@ -37,6 +37,9 @@
 //! the appendix with [`HookContext::push_appendix`], refer to the documentation of [`HookContext`]
 //! for further information.
 //!
+//! The rendering of [`Backtrace`]s can be customized without writing a hook from scratch by
+//! installing a [`BacktraceFilter`] through [`BacktraceFilter::into_hook`].
+//!
 //! ## Example
 //!
 //! ```rust
@@ -142,8 +145,13 @@
 //! [`atomic`]: std::sync::atomic
 //! [`Error::provide`]: core::error::Error::provide
 
+#[cfg(all(feature = "std", rust_1_65))]
+mod backtrace;
+mod compact;
+pub(crate) mod diagnostic;
 #[cfg(any(feature = "std", feature = "hooks"))]
 mod hook;
+mod json;
 #[cfg(feature = "pretty-print")]
 mod location;
 
@@ -161,17 +169,108 @@ use core::{
     mem,
 };
 
+#[cfg(all(feature = "std", rust_1_65))]
+pub use backtrace::BacktraceFilter;
+#[cfg(not(any(feature = "std", feature = "hooks")))]
+use diagnostic::debug_diagnostic;
 #[cfg(any(feature = "std", feature = "hooks"))]
 pub use hook::HookContext;
 #[cfg(any(feature = "std", feature = "hooks"))]
-pub(crate) use hook::{install_builtin_hooks, Format, Hooks};
+pub(crate) use hook::{install_builtin_hooks, Hooks};
 #[cfg(all(not(any(feature = "std", feature = "hooks")), feature = "pretty-print"))]
 use location::LocationDisplay;
 #[cfg(feature = "pretty-print")]
 use owo_colors::{OwoColorize, Stream, Style as OwOStyle};
 
+use self::{compact::compact_frames, json::json_frames};
 use crate::{AttachmentKind, Context, Frame, FrameKind, Report};
 
+/// The output format of a [`Report`].
+///
+/// The format used by the [`Debug`] implementation of [`Report`] can be changed globally with
+/// `Report::set_debug_format`, while [`Report::format`] selects the format for a single call.
+///
+/// ```rust
+/// use std::io::{Error, ErrorKind};
+///
+/// use error_stack::{Format, Report};
+///
+/// let report = Report::new(Error::from(ErrorKind::NotFound))
+///     .attach_printable("could not read the experiment manifest");
+///
+/// assert_eq!(
+///     report.format(Format::Compact).to_string(),
+///     "entity not found: could not read the experiment manifest"
+/// );
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum Format {
+    /// The multi-line tree, which is the default [`Debug`] output.
+    #[default]
+    Tree,
+    /// A single line, where every context is followed by its printable attachments and the
+    /// contexts it has been caused by, e.g. `context B: attachment; caused by context A`.
+    ///
+    /// Opaque attachments and locations are omitted, line breaks are replaced by a space.
+    Compact,
+    /// A single line of JSON with the same structure as the serialized [`Report`] with the
+    /// `serde` feature, which does not require `serde`:
+    ///
+    /// ```json
+    /// [{"context":"context display output","attachments":["all","attachments"],"sources":[]}]
+    /// ```
+    ///
+    /// Contexts and printable attachments are rendered using their [`Display`] output,
+    /// [`Location`]s as `file:line:column`, and other opaque attachments using their type name.
+    ///
+    /// [`Location`]: core::panic::Location
+    JsonLines,
+}
+
+/// A [`Report`] formatted in a specific [`Format`], created by [`Report::format`].
+#[must_use]
+pub struct FormattedReport<'a, C> {
+    report: &'a Report<C>,
+    format: Format,
+    normalized: bool,
+}
+
+impl<'a, C> FormattedReport<'a, C> {
+    pub(crate) const fn new(report: &'a Report<C>, format: Format) -> Self {
+        Self {
+            report,
+            format,
+            normalized: false,
+        }
+    }
+
+    /// Formats the report as [`Format::Tree`], but omits all frames, which differ between runs or
+    /// machines, see [`is_volatile`].
+    pub(crate) const fn normalized(report: &'a Report<C>) -> Self {
+        Self {
+            report,
+            format: Format::Tree,
+            normalized: true,
+        }
+    }
+}
+
+impl<C> Display for FormattedReport<'_, C> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self.format {
+            Format::Tree => debug_tree(self.report, fmt, self.normalized),
+            Format::Compact => compact_frames(fmt, self.report.current_frames(), &[]),
+            Format::JsonLines => json_frames(fmt, self.report.current_frames()),
+        }
+    }
+}
+
+impl<C> Debug for FormattedReport<'_, C> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, fmt)
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum Symbol {
     Vertical,
@@ -672,14 +771,41 @@ impl Opaque {
     }
 }
 
+/// Returns `true` if the frame is a [`Location`], [`Backtrace`], or [`SpanTrace`], which differ
+/// between runs or machines.
+///
+/// [`Location`]: core::panic::Location
+/// [`Backtrace`]: std::backtrace::Backtrace
+/// [`SpanTrace`]: tracing_error::SpanTrace
+fn is_volatile(frame: &Frame) -> bool {
+    #[cfg(all(rust_1_65, feature = "std"))]
+    if frame.is::<std::backtrace::Backtrace>() {
+        return true;
+    }
+
+    #[cfg(feature = "spantrace")]
+    if frame.is::<tracing_error::SpanTrace>() {
+        return true;
+    }
+
+    #[cfg(feature = "remote")]
+    if frame.is::<crate::remote::RemoteLocation>() {
+        return true;
+    }
+
+    frame.is::<core::panic::Location<'static>>()
+}
+
 fn debug_attachments_invoke<'a>(
     frames: impl IntoIterator<Item = &'a Frame>,
+    normalized: bool,
     #[cfg(any(feature = "std", feature = "hooks"))] context: &mut HookContext<Frame>,
 ) -> (Opaque, Vec<String>) {
     let mut opaque = Opaque::new();
 
     let body = frames
         .into_iter()
+        .filter(|frame| !(normalized && is_volatile(frame)))
         .map(|frame| match frame.kind() {
             #[cfg(any(feature = "std", feature = "hooks"))]
             FrameKind::Context(_) => Some(
@@ -702,19 +828,21 @@ fn debug_attachments_invoke<'a>(
             FrameKind::Context(_) => Some(vec![]),
             #[cfg(not(any(feature = "std", feature = "hooks")))]
             FrameKind::Attachment(AttachmentKind::Printable(attachment)) => {
-                Some(vec![attachment.to_string()])
+                Some(debug_diagnostic(frame).unwrap_or_else(|| vec![attachment.to_string()]))
             }
             #[cfg(all(not(any(feature = "std", feature = "hooks")), feature = "pretty-print"))]
             FrameKind::Attachment(AttachmentKind::Opaque(_)) => frame
                 .downcast_ref::<core::panic::Location<'static>>()
-                .map(|location| vec![LocationDisplay::new(location).render()]),
+                .map(|location| vec![LocationDisplay::new(location).render()])
+                .or_else(|| debug_diagnostic(frame)),
             #[cfg(all(
                 not(any(feature = "std", feature = "hooks")),
                 not(feature = "pretty-print")
             ))]
             FrameKind::Attachment(AttachmentKind::Opaque(_)) => frame
                 .downcast_ref::<core::panic::Location>()
-                .map(|location| vec![format!("at {location}")]),
+                .map(|location| vec![format!("at {location}")])
+                .or_else(|| debug_diagnostic(frame)),
         })
         .flat_map(|body| {
             body.unwrap_or_else(|| {
@@ -732,12 +860,14 @@ fn debug_attachments_invoke<'a>(
 fn debug_attachments<'a>(
     position: Position,
     frames: impl IntoIterator<Item = &'a Frame>,
+    normalized: bool,
     #[cfg(any(feature = "std", feature = "hooks"))] context: &mut HookContext<Frame>,
 ) -> Lines {
     let last = matches!(position, Position::Final);
 
     let (opaque, entries) = debug_attachments_invoke(
         frames,
+        normalized,
         #[cfg(any(feature = "std", feature = "hooks"))]
         context,
     );
@@ -864,6 +994,7 @@ fn debug_render(head: Lines, contexts: VecDeque<Lines>, sources: Vec<Lines>) ->
 fn debug_frame(
     root: &Frame,
     prefix: &[&Frame],
+    normalized: bool,
     #[cfg(any(feature = "std", feature = "hooks"))] context: &mut HookContext<Frame>,
 ) -> Vec<Lines> {
     let (stack, sources) = collect(root, prefix);
@@ -905,6 +1036,7 @@ fn debug_frame(
                     Position::Inner
                 },
                 once(head).chain(body),
+                normalized,
                 #[cfg(any(feature = "std", feature = "hooks"))]
                 context,
             );
@@ -921,6 +1053,7 @@ fn debug_frame(
                 debug_frame(
                     source,
                     &prefix,
+                    normalized,
                     #[cfg(any(feature = "std", feature = "hooks"))]
                     context,
                 )
@@ -942,72 +1075,81 @@ fn debug_frame(
     vec![debug_render(head, contexts, sources)]
 }
 
-impl<C> Debug for Report<C> {
-    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        #[cfg(any(feature = "std", feature = "hooks"))]
-        let mut context = HookContext::new(Format::new(fmt.alternate()));
+fn debug_tree<C>(report: &Report<C>, fmt: &mut Formatter<'_>, normalized: bool) -> fmt::Result {
+    #[cfg(any(feature = "std", feature = "hooks"))]
+    let mut context = HookContext::new(hook::Format::new(fmt.alternate()));
 
-        #[cfg_attr(not(any(feature = "std", feature = "hooks")), allow(unused_mut))]
-        let mut lines = self
-            .current_frames()
+    #[cfg_attr(not(any(feature = "std", feature = "hooks")), allow(unused_mut))]
+    let mut lines = report
+        .current_frames()
+        .iter()
+        .flat_map(|frame| {
+            debug_frame(
+                frame,
+                &[],
+                normalized,
+                #[cfg(any(feature = "std", feature = "hooks"))]
+                context.cast(),
+            )
+        })
+        .enumerate()
+        .flat_map(|(idx, lines)| {
+            if idx == 0 {
+                lines.into_vec()
+            } else {
+                lines
+                    .before(
+                        Line::new().push(Indent::no_group().visible(false).spacing(None).into()),
+                    )
+                    .into_vec()
+            }
+        })
+        .map(|line| line.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    #[cfg(any(feature = "std", feature = "hooks"))]
+    {
+        let appendix = context
+            .appendix()
             .iter()
-            .flat_map(|frame| {
-                debug_frame(
-                    frame,
-                    &[],
-                    #[cfg(any(feature = "std", feature = "hooks"))]
-                    context.cast(),
-                )
-            })
-            .enumerate()
-            .flat_map(|(idx, lines)| {
-                if idx == 0 {
-                    lines.into_vec()
-                } else {
-                    lines
-                        .before(
-                            Line::new()
-                                .push(Indent::no_group().visible(false).spacing(None).into()),
-                        )
-                        .into_vec()
-                }
-            })
-            .map(|line| line.to_string())
+            .map(
+                // remove all trailing newlines for a more uniform look
+                |snippet| snippet.trim_end_matches('\n').to_owned(),
+            )
             .collect::<Vec<_>>()
-            .join("\n");
-
-        #[cfg(any(feature = "std", feature = "hooks"))]
-        {
-            let appendix = context
-                .appendix()
-                .iter()
-                .map(
-                    // remove all trailing newlines for a more uniform look
-                    |snippet| snippet.trim_end_matches('\n').to_owned(),
-                )
-                .collect::<Vec<_>>()
-                .join("\n\n");
-
-            if !appendix.is_empty() {
-                // 44 is the size for the separation.
-                lines.reserve(44 + appendix.len());
+            .join("\n\n");
 
-                lines.push_str("\n\n");
-                #[cfg(feature = "pretty-print")]
-                {
-                    lines.push_str(&"━".repeat(40));
-                }
-                #[cfg(not(feature = "pretty-print"))]
-                {
-                    lines.push_str(&"=".repeat(40));
-                }
+        if !appendix.is_empty() {
+            // 44 is the size for the separation.
+            lines.reserve(44 + appendix.len());
 
-                lines.push_str("\n\n");
-                lines.push_str(&appendix);
+            lines.push_str("\n\n");
+            #[cfg(feature = "pretty-print")]
+            {
+                lines.push_str(&"━".repeat(40));
             }
+            #[cfg(not(feature = "pretty-print"))]
+            {
+                lines.push_str(&"=".repeat(40));
+            }
+
+            lines.push_str("\n\n");
+            lines.push_str(&appendix);
         }
+    }
+
+    fmt.write_str(&lines)
+}
+
+impl<C> Debug for Report<C> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        #[cfg(any(feature = "std", feature = "hooks"))]
+        let format = Report::debug_format();
+        #[cfg(not(any(feature = "std", feature = "hooks")))]
+        let format = Format::Tree;
 
-        fmt.write_str(&lines)
+        Display::fmt(&self.format(format), fmt)
     }
 }
 
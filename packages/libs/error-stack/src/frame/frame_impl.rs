@@ -13,6 +13,10 @@ pub(super) trait FrameImpl: Send + Sync + 'static {
 
     fn as_any_mut(&mut self) -> &mut dyn Any;
 
+    /// The type name of the held context or attachment, used if no other representation is
+    /// available.
+    fn type_name(&self) -> &'static str;
+
     /// Provide values which can then be requested.
     #[cfg(nightly)]
     fn provide<'a>(&'a self, demand: &mut Demand<'a>);
@@ -36,6 +40,10 @@ impl<C: Context> FrameImpl for ContextFrame<C> {
         &mut self.context
     }
 
+    fn type_name(&self) -> &'static str {
+        core::any::type_name::<C>()
+    }
+
     #[cfg(nightly)]
     fn provide<'a>(&'a self, demand: &mut Demand<'a>) {
         Context::provide(&self.context, demand);
@@ -60,6 +68,10 @@ impl<A: 'static + Send + Sync> FrameImpl for AttachmentFrame<A> {
         &mut self.attachment
     }
 
+    fn type_name(&self) -> &'static str {
+        core::any::type_name::<A>()
+    }
+
     #[cfg(nightly)]
     fn provide<'a>(&'a self, demand: &mut Demand<'a>) {
         demand.provide_ref(&self.attachment);
@@ -86,6 +98,10 @@ impl<A: 'static + fmt::Debug + fmt::Display + Send + Sync> FrameImpl
         &mut self.attachment
     }
 
+    fn type_name(&self) -> &'static str {
+        core::any::type_name::<A>()
+    }
+
     #[cfg(nightly)]
     fn provide<'a>(&'a self, demand: &mut Demand<'a>) {
         demand.provide_ref(&self.attachment);
@@ -133,6 +149,10 @@ impl FrameImpl for AnyhowContext {
         &mut self.0
     }
 
+    fn type_name(&self) -> &'static str {
+        core::any::type_name::<anyhow::Error>()
+    }
+
     #[cfg(nightly)]
     #[inline]
     fn provide<'a>(&'a self, demand: &mut Demand<'a>) {
@@ -180,6 +200,10 @@ impl FrameImpl for EyreContext {
         &mut self.0
     }
 
+    fn type_name(&self) -> &'static str {
+        core::any::type_name::<eyre::Report>()
+    }
+
     #[cfg(nightly)]
     #[inline]
     fn provide<'a>(&'a self, demand: &mut Demand<'a>) {
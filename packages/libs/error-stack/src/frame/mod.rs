@@ -95,6 +95,11 @@ impl Frame {
     pub fn type_id(&self) -> TypeId {
         self.frame.as_any().type_id()
     }
+
+    /// Returns the type name of the held context or attachment by this frame.
+    pub(crate) fn type_name(&self) -> &'static str {
+        self.frame.type_name()
+    }
 }
 
 #[cfg(nightly)]
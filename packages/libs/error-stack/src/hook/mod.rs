@@ -1,10 +1,14 @@
 pub(crate) mod context;
 
+#[cfg(feature = "serde")]
+use alloc::boxed::Box;
 use alloc::vec::Vec;
 
+#[cfg(feature = "serde")]
+use crate::serde::{into_boxed_hook, SerializeHooks};
 use crate::{
     fmt::{install_builtin_hooks, Hooks},
-    Report,
+    Format, Report,
 };
 
 #[cfg(feature = "std")]
@@ -17,6 +21,11 @@ type RwLock<T> = spin::rwlock::RwLock<T>;
 
 static FMT_HOOK: RwLock<Hooks> = RwLock::new(Hooks { inner: Vec::new() });
 
+static DEBUG_FORMAT: RwLock<Format> = RwLock::new(Format::Tree);
+
+#[cfg(feature = "serde")]
+static SERDE_HOOK: RwLock<SerializeHooks> = RwLock::new(SerializeHooks { inner: Vec::new() });
+
 impl Report<()> {
     /// Can be used to globally set a [`Debug`] format hook, for a specific type `T`.
     ///
@@ -181,4 +190,196 @@ impl Report<()> {
 
         closure(&hook)
     }
+
+    /// Can be used to globally set the [`Format`] used by the [`Debug`] implementation of
+    /// [`Report`].
+    ///
+    /// By default, reports are rendered as [`Format::Tree`]. Debug hooks are only used by
+    /// [`Format::Tree`]. To use a different format for a single call, use [`Report::format`].
+    ///
+    /// [`Debug`]: core::fmt::Debug
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::{Error, ErrorKind};
+    ///
+    /// use error_stack::{Format, Report};
+    ///
+    /// Report::set_debug_format(Format::Compact);
+    ///
+    /// let report = Report::new(Error::from(ErrorKind::InvalidInput))
+    ///     .attach_printable("the experiment has no steps");
+    ///
+    /// assert_eq!(
+    ///     format!("{report:?}"),
+    ///     "invalid input parameter: the experiment has no steps"
+    /// );
+    /// ```
+    #[cfg(any(feature = "std", feature = "hooks"))]
+    pub fn set_debug_format(format: Format) {
+        #[cfg(feature = "std")]
+        let mut lock = DEBUG_FORMAT.write().expect("should not be poisoned");
+
+        // The spin RwLock cannot panic
+        #[cfg(all(not(feature = "std"), feature = "hooks"))]
+        let mut lock = DEBUG_FORMAT.write();
+
+        *lock = format;
+    }
+
+    /// Returns the format that was previously set by [`set_debug_format`]
+    ///
+    /// [`set_debug_format`]: Self::set_debug_format
+    #[cfg(any(feature = "std", feature = "hooks"))]
+    pub(crate) fn debug_format() -> Format {
+        #[cfg(feature = "std")]
+        let format = *DEBUG_FORMAT.read().expect("should not be poisoned");
+
+        // The spin RwLock cannot panic
+        #[cfg(all(not(feature = "std"), feature = "hooks"))]
+        let format = *DEBUG_FORMAT.read();
+
+        format
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Report<()> {
+    /// Can be used to globally set a [`Serialize`] hook for a specific type `T`.
+    ///
+    /// Contexts and attachments of type `T` will be serialized using their [`Serialize`]
+    /// implementation instead of their [`Display`] output or type name. Installing a hook for a
+    /// type replaces any previously installed serialization hook for that type.
+    ///
+    /// [`Serialize`]: serde::Serialize
+    /// [`Display`]: core::fmt::Display
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fmt;
+    ///
+    /// use error_stack::{Context, Report};
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Debug, Serialize)]
+    /// struct ConfigError {
+    ///     path: &'static str,
+    /// }
+    ///
+    /// impl fmt::Display for ConfigError {
+    ///     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         fmt.write_str("could not read configuration")
+    ///     }
+    /// }
+    ///
+    /// impl Context for ConfigError {}
+    ///
+    /// #[derive(Serialize)]
+    /// struct Suggestion(&'static str);
+    ///
+    /// Report::install_serde_hook::<ConfigError>();
+    /// Report::install_serde_hook::<Suggestion>();
+    ///
+    /// let report = Report::new(ConfigError {
+    ///     path: "config.json",
+    /// })
+    /// .attach(Suggestion("does the file exist?"));
+    ///
+    /// let value = serde_json::to_value(&report)?;
+    ///
+    /// assert_eq!(value[0]["context"]["path"], "config.json");
+    /// assert_eq!(
+    ///     value[0]["attachments"]
+    ///         .as_array()
+    ///         .and_then(|attachments| attachments.last()),
+    ///     Some(&serde_json::json!("does the file exist?"))
+    /// );
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn install_serde_hook<T: serde::Serialize + Send + Sync + 'static>() {
+        crate::serde::install_builtin_hooks();
+
+        let hook = into_boxed_hook(|frame| {
+            frame
+                .downcast_ref::<T>()
+                .map(|value| Box::new(value) as Box<dyn erased_serde::Serialize + '_>)
+        });
+
+        #[cfg(feature = "std")]
+        let mut lock = SERDE_HOOK.write().expect("should not be poisoned");
+
+        // The spin RwLock cannot panic
+        #[cfg(all(not(feature = "std"), feature = "hooks"))]
+        let mut lock = SERDE_HOOK.write();
+
+        lock.insert::<T>(hook);
+    }
+
+    /// Can be used to globally set a [`Serialize`] hook for a specific type `T`, which converts
+    /// the value into a serializable representation first.
+    ///
+    /// This is useful for types, which do not implement [`Serialize`] themselves, like most
+    /// contexts from other crates. Installing a hook for a type replaces any previously installed
+    /// serialization hook for that type.
+    ///
+    /// [`Serialize`]: serde::Serialize
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::{Error, ErrorKind};
+    ///
+    /// use error_stack::Report;
+    ///
+    /// Report::install_serde_hook_with::<Error, _>(|error| format!("{:?}", error.kind()));
+    ///
+    /// let report = Report::new(Error::from(ErrorKind::NotFound));
+    ///
+    /// let value = serde_json::to_value(&report)?;
+    ///
+    /// assert_eq!(value[0]["context"], "NotFound");
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn install_serde_hook_with<T, U>(hook: impl Fn(&T) -> U + Send + Sync + 'static)
+    where
+        T: Send + Sync + 'static,
+        U: serde::Serialize + 'static,
+    {
+        crate::serde::install_builtin_hooks();
+
+        let hook = into_boxed_hook(move |frame| {
+            frame
+                .downcast_ref::<T>()
+                .map(|value| Box::new(hook(value)) as Box<dyn erased_serde::Serialize + '_>)
+        });
+
+        #[cfg(feature = "std")]
+        let mut lock = SERDE_HOOK.write().expect("should not be poisoned");
+
+        // The spin RwLock cannot panic
+        #[cfg(all(not(feature = "std"), feature = "hooks"))]
+        let mut lock = SERDE_HOOK.write();
+
+        lock.insert::<T>(hook);
+    }
+
+    /// Returns the hooks that were previously set by [`install_serde_hook`] and
+    /// [`install_serde_hook_with`]
+    ///
+    /// [`install_serde_hook`]: Self::install_serde_hook
+    /// [`install_serde_hook_with`]: Self::install_serde_hook_with
+    pub(crate) fn invoke_serde_hook<T>(closure: impl FnOnce(&SerializeHooks) -> T) -> T {
+        crate::serde::install_builtin_hooks();
+
+        #[cfg(feature = "std")]
+        let hook = SERDE_HOOK.read().expect("should not be poisoned");
+
+        // The spin RwLock cannot panic
+        #[cfg(all(not(feature = "std"), feature = "hooks"))]
+        let hook = SERDE_HOOK.read();
+
+        closure(&hook)
+    }
 }
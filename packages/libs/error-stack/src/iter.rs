@@ -1,16 +1,24 @@
-//! Iterators over [`Frame`]s.
+//! Iterators over [`Frame`]s and extensions for iterators over [`Result`]s.
+//!
+//! [`IteratorExt`] extends iterators over [`Result`]s with the same methods as [`ResultExt`], which
+//! are applied to every item, and [`ResultIteratorExt`] collects all errors of such an iterator
+//! into a single [`Report`].
+//!
+//! [`Result`]: crate::Result
+//! [`ResultExt`]: crate::ResultExt
 
 use alloc::{vec, vec::Vec};
 #[cfg(nightly)]
 use core::marker::PhantomData;
 use core::{
     fmt,
-    fmt::Formatter,
+    fmt::{Debug, Display, Formatter},
     iter::FusedIterator,
+    num::NonZeroUsize,
     slice::{Iter, IterMut},
 };
 
-use crate::Frame;
+use crate::{Context, Frame, Report, Result, ResultExt};
 
 /// Helper function, which is used in both [`Frames`] and [`FramesMut`].
 ///
@@ -267,3 +275,396 @@ where
         fmt.debug_list().entries(self.clone()).finish()
     }
 }
+
+/// Extension trait for iterators over [`Result`]`<T, C>` to collect all errors into a single
+/// [`Report`].
+///
+/// [`Result`]: crate::Result
+pub trait ResultIteratorExt<T, C>: Iterator<Item = Result<T, C>> + Sized {
+    /// Collects all [`Ok`] values into a [`Vec`] or all errors into a single [`Report`].
+    ///
+    /// Unlike [`Iterator::collect`], this consumes the whole iterator, even if an error occurred.
+    /// Every error is added to the returned [`Report`] as a sibling using [`Report::extend_one`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::num::ParseIntError;
+    ///
+    /// use error_stack::{iter::ResultIteratorExt, IntoReport, Report};
+    ///
+    /// fn parse(values: &[&str]) -> Result<Vec<u8>, Report<ParseIntError>> {
+    ///     values
+    ///         .iter()
+    ///         .map(|value| value.parse().into_report())
+    ///         .try_collect_reports()
+    /// }
+    ///
+    /// assert_eq!(parse(&["1", "2", "3"]).expect("all values are valid"), [
+    ///     1, 2, 3
+    /// ]);
+    ///
+    /// let report = parse(&["1", "two", "300"]).expect_err("two values are invalid");
+    /// assert_eq!(report.current_frames().len(), 2);
+    /// ```
+    fn try_collect_reports(self) -> Result<Vec<T>, C>;
+
+    /// Like [`try_collect_reports`], but stops consuming the iterator after `max_errors` errors
+    /// were collected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    ///
+    /// use error_stack::{iter::ResultIteratorExt, IntoReport};
+    ///
+    /// let max_errors = NonZeroUsize::new(2).expect("2 is not zero");
+    /// let report = ["one", "two", "three"]
+    ///     .iter()
+    ///     .map(|value| value.parse::<u8>().into_report())
+    ///     .try_collect_reports_bounded(max_errors)
+    ///     .expect_err("no value is valid");
+    ///
+    /// assert_eq!(report.current_frames().len(), 2);
+    /// ```
+    ///
+    /// [`try_collect_reports`]: Self::try_collect_reports
+    fn try_collect_reports_bounded(self, max_errors: NonZeroUsize) -> Result<Vec<T>, C>;
+}
+
+impl<I, T, C> ResultIteratorExt<T, C> for I
+where
+    I: Iterator<Item = Result<T, C>>,
+{
+    fn try_collect_reports(self) -> Result<Vec<T>, C> {
+        collect_reports(self, None)
+    }
+
+    fn try_collect_reports_bounded(self, max_errors: NonZeroUsize) -> Result<Vec<T>, C> {
+        collect_reports(self, Some(max_errors.get()))
+    }
+}
+
+fn collect_reports<T, C>(
+    iter: impl Iterator<Item = Result<T, C>>,
+    max_errors: Option<usize>,
+) -> Result<Vec<T>, C> {
+    let mut values = Vec::new();
+    let mut report: Option<Report<C>> = None;
+    let mut errors = 0;
+
+    for result in iter {
+        match result {
+            // values are not needed anymore once an error occurred
+            Ok(value) if report.is_none() => values.push(value),
+            Ok(_) => {}
+            Err(error) => {
+                if let Some(report) = &mut report {
+                    report.extend_one(error);
+                } else {
+                    report = Some(error);
+                }
+
+                errors += 1;
+                if max_errors.map_or(false, |max_errors| errors >= max_errors) {
+                    break;
+                }
+            }
+        }
+    }
+
+    report.map_or(Ok(values), Err)
+}
+
+macro_rules! implement_iterator_adaptor {
+    ($iterator:ident, $method:ident, $lazy_method:ident, $bound:ident $(+ $bounds:ident)* $(+ $lifetime:lifetime)*, $output:ty) => {
+        #[doc = concat!("Adaptor returned by [`IteratorExt::", stringify!( $method ), "`].")]
+        pub struct $iterator<I, T> {
+            iterator: I,
+            inner: T,
+        }
+
+        impl<I, T> Iterator for $iterator<I, T>
+        where
+            I: Iterator,
+            I::Item: ResultExt,
+            T: Clone + $bound $(+ $bounds)* $(+ $lifetime)*
+        {
+            type Item = $output;
+
+            #[track_caller]
+            fn next(&mut self) -> Option<Self::Item> {
+                let inner = &self.inner;
+
+                // The value is only cloned if the item is an error, so `Ok` items don't allocate.
+                // Can't use `map` as `#[track_caller]` is unstable on closures
+                match self.iterator.next() {
+                    Some(item) => Some(item.$lazy_method(|| inner.clone())),
+                    None => None,
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.iterator.size_hint()
+            }
+        }
+
+        impl<I, T> FusedIterator for $iterator<I, T>
+        where
+            I: FusedIterator,
+            I::Item: ResultExt,
+            T: Clone + $bound $(+ $bounds)* $(+ $lifetime)*
+        {
+        }
+    };
+}
+
+macro_rules! implement_lazy_iterator_adaptor {
+    ($iterator:ident, $method:ident, $bound:ident $(+ $bounds:ident)* $(+ $lifetime:lifetime)*, $output:ty) => {
+        #[doc = concat!("Adaptor returned by [`IteratorExt::", stringify!( $method ), "`].")]
+        pub struct $iterator<I, F> {
+            iterator: I,
+            inner: F,
+        }
+
+        impl<I, F, T> Iterator for $iterator<I, F>
+        where
+            I: Iterator,
+            I::Item: ResultExt,
+            F: FnMut() -> T,
+            T: $bound $(+ $bounds)* $(+ $lifetime)*
+        {
+            type Item = $output;
+
+            #[track_caller]
+            fn next(&mut self) -> Option<Self::Item> {
+                // Can't use `map` as `#[track_caller]` is unstable on closures
+                match self.iterator.next() {
+                    Some(item) => Some(item.$method(&mut self.inner)),
+                    None => None,
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.iterator.size_hint()
+            }
+        }
+
+        impl<I, F, T> FusedIterator for $iterator<I, F>
+        where
+            I: FusedIterator,
+            I::Item: ResultExt,
+            F: FnMut() -> T,
+            T: $bound $(+ $bounds)* $(+ $lifetime)*
+        {
+        }
+    };
+}
+
+implement_iterator_adaptor!(
+    IteratorWithAttachment,
+    attach,
+    attach_lazy,
+    Send + Sync + 'static,
+    I::Item
+);
+
+implement_lazy_iterator_adaptor!(
+    IteratorWithLazyAttachment,
+    attach_lazy,
+    Send + Sync + 'static,
+    I::Item
+);
+
+implement_iterator_adaptor!(
+    IteratorWithPrintableAttachment,
+    attach_printable,
+    attach_printable_lazy,
+    Display + Debug + Send + Sync + 'static,
+    I::Item
+);
+
+implement_lazy_iterator_adaptor!(
+    IteratorWithLazyPrintableAttachment,
+    attach_printable_lazy,
+    Display + Debug + Send + Sync + 'static,
+    I::Item
+);
+
+implement_iterator_adaptor!(
+    IteratorWithContext,
+    change_context,
+    change_context_lazy,
+    Context,
+    Result<<I::Item as ResultExt>::Ok, T>
+);
+
+implement_lazy_iterator_adaptor!(
+    IteratorWithLazyContext,
+    change_context_lazy,
+    Context,
+    Result<<I::Item as ResultExt>::Ok, T>
+);
+
+/// Extension trait for [`Iterator`]s over [`Result`]s to provide contextual information on
+/// [`Report`]s.
+///
+/// Extends [`Iterator`] with the same methods as [`ResultExt`], which are applied to every item.
+/// Attachments and contexts are only cloned or created if an item is an error.
+///
+/// # Example
+///
+/// ```
+/// use std::num::ParseIntError;
+///
+/// use error_stack::{iter::IteratorExt, IntoReport, Report};
+///
+/// let results = ["1", "two", "3"]
+///     .iter()
+///     .map(|value| value.parse::<u8>().into_report())
+///     .attach_printable("while parsing the input")
+///     .collect::<Vec<Result<u8, Report<ParseIntError>>>>();
+///
+/// assert!(results[0].is_ok());
+/// assert!(results[1].is_err());
+/// assert!(results[2].is_ok());
+/// ```
+///
+/// [`Result`]: crate::Result
+pub trait IteratorExt: Iterator + Sized {
+    /// Adds a new attachment to the [`Report`] of every [`Err`] item of the [`Iterator`].
+    ///
+    /// Applies [`Report::attach`] on the [`Err`] variant, refer to it for more information.
+    #[track_caller]
+    fn attach<A>(self, attachment: A) -> IteratorWithAttachment<Self, A>
+    where
+        A: Clone + Send + Sync + 'static;
+
+    /// Lazily adds a new attachment to the [`Report`] of every [`Err`] item of the [`Iterator`].
+    ///
+    /// Applies [`Report::attach`] on the [`Err`] variant, refer to it for more information.
+    #[track_caller]
+    fn attach_lazy<A, F>(self, attachment: F) -> IteratorWithLazyAttachment<Self, F>
+    where
+        A: Send + Sync + 'static,
+        F: FnMut() -> A;
+
+    /// Adds a new printable attachment to the [`Report`] of every [`Err`] item of the
+    /// [`Iterator`].
+    ///
+    /// Applies [`Report::attach_printable`] on the [`Err`] variant, refer to it for more
+    /// information.
+    #[track_caller]
+    fn attach_printable<A>(self, attachment: A) -> IteratorWithPrintableAttachment<Self, A>
+    where
+        A: Clone + Display + Debug + Send + Sync + 'static;
+
+    /// Lazily adds a new printable attachment to the [`Report`] of every [`Err`] item of the
+    /// [`Iterator`].
+    ///
+    /// Applies [`Report::attach_printable`] on the [`Err`] variant, refer to it for more
+    /// information.
+    #[track_caller]
+    fn attach_printable_lazy<A, F>(
+        self,
+        attachment: F,
+    ) -> IteratorWithLazyPrintableAttachment<Self, F>
+    where
+        A: Display + Debug + Send + Sync + 'static,
+        F: FnMut() -> A;
+
+    /// Changes the [`Context`] of the [`Report`] of every [`Err`] item of the [`Iterator`].
+    ///
+    /// Applies [`Report::change_context`] on the [`Err`] variant, refer to it for more information.
+    #[track_caller]
+    fn change_context<C>(self, context: C) -> IteratorWithContext<Self, C>
+    where
+        C: Clone + Context;
+
+    /// Lazily changes the [`Context`] of the [`Report`] of every [`Err`] item of the [`Iterator`].
+    ///
+    /// Applies [`Report::change_context`] on the [`Err`] variant, refer to it for more information.
+    #[track_caller]
+    fn change_context_lazy<C, F>(self, context: F) -> IteratorWithLazyContext<Self, F>
+    where
+        C: Context,
+        F: FnMut() -> C;
+}
+
+impl<I: Iterator> IteratorExt for I
+where
+    I::Item: ResultExt,
+{
+    #[track_caller]
+    fn attach<A>(self, attachment: A) -> IteratorWithAttachment<Self, A>
+    where
+        A: Clone + Send + Sync + 'static,
+    {
+        IteratorWithAttachment {
+            iterator: self,
+            inner: attachment,
+        }
+    }
+
+    #[track_caller]
+    fn attach_lazy<A, F>(self, attachment: F) -> IteratorWithLazyAttachment<Self, F>
+    where
+        A: Send + Sync + 'static,
+        F: FnMut() -> A,
+    {
+        IteratorWithLazyAttachment {
+            iterator: self,
+            inner: attachment,
+        }
+    }
+
+    #[track_caller]
+    fn attach_printable<A>(self, attachment: A) -> IteratorWithPrintableAttachment<Self, A>
+    where
+        A: Clone + Display + Debug + Send + Sync + 'static,
+    {
+        IteratorWithPrintableAttachment {
+            iterator: self,
+            inner: attachment,
+        }
+    }
+
+    #[track_caller]
+    fn attach_printable_lazy<A, F>(
+        self,
+        attachment: F,
+    ) -> IteratorWithLazyPrintableAttachment<Self, F>
+    where
+        A: Display + Debug + Send + Sync + 'static,
+        F: FnMut() -> A,
+    {
+        IteratorWithLazyPrintableAttachment {
+            iterator: self,
+            inner: attachment,
+        }
+    }
+
+    #[track_caller]
+    fn change_context<C>(self, context: C) -> IteratorWithContext<Self, C>
+    where
+        C: Clone + Context,
+    {
+        IteratorWithContext {
+            iterator: self,
+            inner: context,
+        }
+    }
+
+    #[track_caller]
+    fn change_context_lazy<C, F>(self, context: F) -> IteratorWithLazyContext<Self, F>
+    where
+        C: Context,
+        F: FnMut() -> C,
+    {
+        IteratorWithLazyContext {
+            iterator: self,
+            inner: context,
+        }
+    }
+}
@@ -399,6 +399,51 @@
 //! You can add new hooks with [`Report::install_debug_hook`]. Refer to the module-level
 //! documentation of [`fmt`] for further information.
 //!
+//! ### Output Formats
+//!
+//! Besides the multi-line tree, a [`Report`] can be rendered as a single line for log aggregation
+//! or as a single line of JSON for machine consumption, which does not require the `serde` feature.
+//! The [`Format`] is either selected per call with [`Report::format`] or globally for the
+//! [`Debug`] output with `Report::set_debug_format`.
+//!
+//! ### Diagnostics
+//!
+//! The [`diagnostic`] module contains attachments to point at the input, which caused an error.
+//! A [`SourceSnippet`] is rendered with line numbers and [`Label`]s underlining the offending
+//! spans, while [`Help`] and [`Code`] add a suggestion and an error code to the [`Debug`] output.
+//!
+//! [`SourceSnippet`]: diagnostic::SourceSnippet
+//! [`Label`]: diagnostic::Label
+//! [`Help`]: diagnostic::Help
+//! [`Code`]: diagnostic::Code
+//!
+//! ### Testing
+//!
+//! The [`testing`] module helps to assert on a [`Report`] in tests. [`assert_report_contains!`]
+//! checks its contexts and attachments by type or message, and [`render_normalized`] renders the
+//! [`Debug`] output without locations, backtraces, and memory addresses for snapshot tests.
+//!
+//! [`render_normalized`]: testing::render_normalized
+//!
+//! ### Serde Hooks
+//!
+//! When the `serde` feature is enabled, [`Report`] implements [`Serialize`]. By default, contexts
+//! and printable attachments are serialized using their [`Display`] output, while other
+//! attachments are serialized using their type name. Hooks can be installed with
+//! [`Report::install_serde_hook`] and [`Report::install_serde_hook_with`] to serialize any context
+//! or attachment type as structured data instead.
+//!
+//! [`Serialize`]: serde::Serialize
+//!
+//! ### Tracing Events
+//!
+//! When the `tracing` feature is enabled, [`Report::record_event`] records a [`Report`] as a
+//! structured `tracing` event. Unlike formatting the [`Report`] into the message of an event, the
+//! contexts, printable attachments, and locations are recorded as separate fields.
+//! [`Report::record_frame_events`] additionally records one event per frame. If the `spantrace`
+//! feature is enabled as well, `Report::record_exception` records an exception event following
+//! the `OpenTelemetry` semantic conventions.
+//!
 //! ### Additional Adaptors
 //!
 //! [`ResultExt`] is a convenient wrapper around `Result<_, Report<_>>`. It offers
@@ -407,9 +452,15 @@
 //! an error happens.
 //!
 //! In addition to [`ResultExt`], this crate also comes with [`FutureExt`], which provides the same
-//! functionality for [`Future`]s.
+//! functionality for [`Future`]s, and [`IteratorExt`], which applies it to every item of an
+//! iterator over [`Result`]s. With the `futures` feature, `StreamExt` does the same for `Stream`s.
+//!
+//! To run a fallible operation over a collection and keep every error instead of only the first
+//! one, [`ResultIteratorExt::try_collect_reports`] collects all errors of an iterator into a single
+//! [`Report`].
 //!
 //! [`Future`]: core::future::Future
+//! [`ResultIteratorExt::try_collect_reports`]: iter::ResultIteratorExt::try_collect_reports
 //!
 //! ### Feature Flags
 //!
@@ -419,6 +470,10 @@
 //! `pretty-print` | Provide color[^color] and use of unicode in [`Debug`] output       | enabled
 //! `spantrace`    | Enables automatic capturing of [`SpanTrace`]s                      | disabled
 //! `hooks`        | Enables hooks on `no-std` platforms using spin locks               | disabled
+//! `serde`        | Enables serialization support for [`Report`] and serde hooks       | disabled
+//! `remote`       | Provides `RemoteReport` to send [`Report`]s across processes       | disabled
+//! `tracing`      | Enables recording of [`Report`]s as structured `tracing` events    | disabled
+//! `futures`      | Provides `StreamExt` for `Stream`s of [`Result`]s                  | disabled
 //! `anyhow`       | Provides `into_report` to convert [`anyhow::Error`] to [`Report`]  | disabled
 //! `eyre`         | Provides `into_report` to convert [`eyre::Report`] to [`Report`]   | disabled
 //!
@@ -465,8 +520,12 @@
 
 extern crate alloc;
 
+pub mod diagnostic;
 pub mod future;
 pub mod iter;
+#[cfg(feature = "futures")]
+pub mod stream;
+pub mod testing;
 
 mod compat;
 mod frame;
@@ -483,15 +542,23 @@ pub mod fmt;
 mod fmt;
 #[cfg(any(feature = "std", feature = "hooks"))]
 mod hook;
+#[cfg(feature = "remote")]
+pub mod remote;
 #[cfg(feature = "serde")]
 mod serde;
+#[cfg(feature = "tracing")]
+mod tracing;
 
 #[cfg(all(doc, any(feature = "std", feature = "hooks")))]
 pub use hook::context::HookContext;
 
+#[cfg(feature = "futures")]
+#[doc(inline)]
+pub use self::stream::StreamExt;
 pub use self::{
     compat::IntoReportCompat,
     context::Context,
+    fmt::{Format, FormattedReport},
     frame::{AttachmentKind, Frame, FrameKind},
     macros::*,
     report::Report,
@@ -500,6 +567,7 @@ pub use self::{
 #[doc(inline)]
 pub use self::{
     future::FutureExt,
+    iter::IteratorExt,
     result::{IntoReport, ResultExt},
 };
 
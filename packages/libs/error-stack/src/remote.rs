@@ -0,0 +1,382 @@
+//! Owned representation of a [`Report`], which can be sent across process boundaries.
+//!
+//! A [`RemoteReport`] is created from any [`Report`] and preserves the tree of [`Frame`]s: every
+//! context is captured with its [`Display`] output and, if a serde hook has been installed for its
+//! type, its serialized value. Printable attachments are captured the same way, [`Location`]s are
+//! kept as [`RemoteLocation`]s, and opaque attachments are only kept if a serde hook has been
+//! installed for them.
+//!
+//! Unlike a [`Report`], a [`RemoteReport`] implements [`Deserialize`], so that the receiving side
+//! is able to reconstruct it. It can then be formatted like a live [`Report`] or converted into a
+//! [`Report<RemoteContext>`], which can be used as the source of a new local [`Report`]:
+//!
+//! ```
+//! use std::{fmt, io};
+//!
+//! use error_stack::{remote::RemoteReport, Context, Report};
+//!
+//! #[derive(Debug)]
+//! struct OrchestratorError;
+//!
+//! impl fmt::Display for OrchestratorError {
+//!     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+//!         fmt.write_str("runner failed")
+//!     }
+//! }
+//!
+//! impl Context for OrchestratorError {}
+//!
+//! // on the runner
+//! let report = Report::new(io::Error::from(io::ErrorKind::NotFound))
+//!     .attach_printable("could not read the experiment manifest");
+//! let payload = serde_json::to_string(&RemoteReport::new(&report))?;
+//!
+//! // on the orchestrator
+//! let remote: RemoteReport = serde_json::from_str(&payload)?;
+//! let report = Report::from(remote).change_context(OrchestratorError);
+//!
+//! println!("{report:?}");
+//! # Ok::<(), serde_json::Error>(())
+//! ```
+//!
+//! [`Display`]: core::fmt::Display
+//! [`Location`]: core::panic::Location
+//! [`Deserialize`]: serde::Deserialize
+
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, panic::Location};
+
+use serde::{de, Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use crate::{AttachmentKind, Context, Frame, FrameKind, Report};
+
+/// Owned and deserializable representation of a [`Report`].
+///
+/// See the [module-level documentation] for more information.
+///
+/// [module-level documentation]: self
+#[derive(Clone, Serialize)]
+#[serde(transparent)]
+pub struct RemoteReport {
+    frames: Vec<RemoteFrame>,
+}
+
+impl RemoteReport {
+    /// Captures the frames of `report`.
+    #[must_use]
+    pub fn new<C>(report: &Report<C>) -> Self {
+        let mut frames = Vec::new();
+
+        for frame in report.current_frames() {
+            capture(frame, &mut frames);
+        }
+
+        Self { frames }
+    }
+
+    /// Creates a `RemoteReport` from deserialized frames, which need to form a valid report.
+    fn from_frames(frames: Vec<RemoteFrame>) -> Result<Self, InvalidRemoteReport> {
+        if frames.is_empty() || !frames.iter().all(RemoteFrame::ends_in_context) {
+            return Err(InvalidRemoteReport);
+        }
+
+        Ok(Self { frames })
+    }
+
+    /// Returns the current frames of the report, see [`Report::current_frames`].
+    #[must_use]
+    pub fn current_frames(&self) -> &[RemoteFrame] {
+        &self.frames
+    }
+}
+
+impl<C> From<&Report<C>> for RemoteReport {
+    fn from(report: &Report<C>) -> Self {
+        Self::new(report)
+    }
+}
+
+impl From<RemoteReport> for Report<RemoteContext> {
+    fn from(report: RemoteReport) -> Self {
+        Self::from_frames(
+            report
+                .frames
+                .into_iter()
+                .map(RemoteFrame::into_frame)
+                .collect(),
+        )
+    }
+}
+
+impl fmt::Debug for RemoteReport {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&Report::from(self.clone()), fmt)
+    }
+}
+
+impl fmt::Display for RemoteReport {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&Report::from(self.clone()), fmt)
+    }
+}
+
+impl<'de> Deserialize<'de> for RemoteReport {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let frames = Vec::<RemoteFrame>::deserialize(deserializer)?;
+
+        Self::from_frames(frames).map_err(de::Error::custom)
+    }
+}
+
+/// The deserialized frames do not form a valid report.
+struct InvalidRemoteReport;
+
+impl fmt::Display for InvalidRemoteReport {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("every frame of a remote report has to be followed by a context")
+    }
+}
+
+/// A single context or attachment inside of a [`RemoteReport`], see [`Frame`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteFrame {
+    kind: RemoteFrameKind,
+    sources: Vec<Self>,
+}
+
+impl RemoteFrame {
+    /// Returns the context or attachment held by this frame.
+    #[must_use]
+    pub const fn kind(&self) -> &RemoteFrameKind {
+        &self.kind
+    }
+
+    /// Returns the sources of this frame, see [`Frame::sources`].
+    #[must_use]
+    pub fn sources(&self) -> &[Self] {
+        &self.sources
+    }
+
+    fn ends_in_context(&self) -> bool {
+        match self.kind {
+            RemoteFrameKind::Context(_) => self.sources.iter().all(Self::ends_in_context),
+            _ => !self.sources.is_empty() && self.sources.iter().all(Self::ends_in_context),
+        }
+    }
+
+    fn into_frame(self) -> Frame {
+        let sources = self
+            .sources
+            .into_iter()
+            .map(Self::into_frame)
+            .collect::<Vec<_>>()
+            .into_boxed_slice();
+
+        match self.kind {
+            RemoteFrameKind::Context(context) => Frame::from_context(context, sources),
+            RemoteFrameKind::Attachment(attachment) => {
+                Frame::from_printable_attachment(attachment, sources)
+            }
+            RemoteFrameKind::Location(location) => Frame::from_attachment(location, sources),
+        }
+    }
+}
+
+/// Classification of a [`RemoteFrame`], see [`FrameKind`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RemoteFrameKind {
+    /// A captured [`Context`].
+    Context(RemoteContext),
+    /// A captured printable attachment or an opaque attachment with a serde hook.
+    Attachment(RemoteAttachment),
+    /// A captured [`Location`].
+    ///
+    /// [`Location`]: core::panic::Location
+    Location(RemoteLocation),
+}
+
+/// A [`Context`] captured by a [`RemoteReport`].
+///
+/// Formatting the context prints the [`Display`] output of the original context.
+///
+/// [`Display`]: core::fmt::Display
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteContext {
+    message: String,
+    value: Option<Value>,
+}
+
+impl RemoteContext {
+    /// Returns the [`Display`] output of the original context.
+    ///
+    /// [`Display`]: core::fmt::Display
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the serialized context, if a serde hook was installed for its type.
+    #[must_use]
+    pub const fn value(&self) -> Option<&Value> {
+        self.value.as_ref()
+    }
+}
+
+impl fmt::Display for RemoteContext {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(&self.message)
+    }
+}
+
+impl Context for RemoteContext {}
+
+/// An attachment captured by a [`RemoteReport`].
+///
+/// Remote attachments are always printable: printable attachments print their original
+/// [`Display`] output, opaque attachments print their serialized value.
+///
+/// [`Display`]: core::fmt::Display
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteAttachment {
+    message: Option<String>,
+    value: Option<Value>,
+}
+
+impl RemoteAttachment {
+    /// Returns the [`Display`] output of the original attachment, if it was printable.
+    ///
+    /// [`Display`]: core::fmt::Display
+    #[must_use]
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// Returns the serialized attachment, if a serde hook was installed for its type.
+    #[must_use]
+    pub const fn value(&self) -> Option<&Value> {
+        self.value.as_ref()
+    }
+}
+
+impl fmt::Display for RemoteAttachment {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.message, &self.value) {
+            (Some(message), _) => fmt.write_str(message),
+            (None, Some(Value::String(value))) => fmt.write_str(value),
+            (None, Some(value)) => fmt::Display::fmt(value, fmt),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// A [`Location`] captured by a [`RemoteReport`].
+///
+/// [`Location`]: core::panic::Location
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteLocation {
+    file: String,
+    line: u32,
+    column: u32,
+}
+
+impl RemoteLocation {
+    /// Returns the name of the source file, see [`Location::file`].
+    #[must_use]
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// Returns the line number, see [`Location::line`].
+    #[must_use]
+    pub const fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Returns the column, see [`Location::column`].
+    #[must_use]
+    pub const fn column(&self) -> u32 {
+        self.column
+    }
+}
+
+impl From<&Location<'_>> for RemoteLocation {
+    fn from(location: &Location<'_>) -> Self {
+        Self {
+            file: location.file().to_owned(),
+            line: location.line(),
+            column: location.column(),
+        }
+    }
+}
+
+impl fmt::Display for RemoteLocation {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "hooks"))]
+fn serialize_with_hook(frame: &Frame) -> Option<Value> {
+    Report::invoke_serde_hook(|hooks| hooks.call(frame))
+        .and_then(|value| serde_json::to_value(value).ok())
+}
+
+#[cfg(not(any(feature = "std", feature = "hooks")))]
+const fn serialize_with_hook(_: &Frame) -> Option<Value> {
+    None
+}
+
+/// Captures `frame` and pushes it onto `frames`.
+///
+/// Opaque attachments without a serde hook cannot be captured, in that case their sources are
+/// pushed instead.
+fn capture(frame: &Frame, frames: &mut Vec<RemoteFrame>) {
+    let kind = match frame.kind() {
+        FrameKind::Context(context) => Some(RemoteFrameKind::Context(RemoteContext {
+            message: context.to_string(),
+            value: serialize_with_hook(frame),
+        })),
+        FrameKind::Attachment(AttachmentKind::Printable(attachment)) => {
+            Some(RemoteFrameKind::Attachment(RemoteAttachment {
+                message: Some(attachment.to_string()),
+                value: serialize_with_hook(frame),
+            }))
+        }
+        FrameKind::Attachment(AttachmentKind::Opaque(_)) => frame
+            .downcast_ref::<Location<'static>>()
+            .map(|location| RemoteFrameKind::Location(RemoteLocation::from(location)))
+            .or_else(|| {
+                serialize_with_hook(frame).map(|value| {
+                    RemoteFrameKind::Attachment(RemoteAttachment {
+                        message: None,
+                        value: Some(value),
+                    })
+                })
+            }),
+    };
+
+    match kind {
+        Some(kind) => {
+            let mut sources = Vec::with_capacity(frame.sources().len());
+            for source in frame.sources() {
+                capture(source, &mut sources);
+            }
+
+            frames.push(RemoteFrame { kind, sources });
+        }
+        None => {
+            for source in frame.sources() {
+                capture(source, frames);
+            }
+        }
+    }
+}
@@ -15,6 +15,7 @@ use tracing_error::{SpanTrace, SpanTraceStatus};
 #[cfg(nightly)]
 use crate::iter::{RequestRef, RequestValue};
 use crate::{
+    fmt::{Format, FormattedReport},
     iter::{Frames, FramesMut},
     Context, Frame,
 };
@@ -327,6 +328,15 @@ impl<C> Report<C> {
         report
     }
 
+    /// Creates a report from the given frames without capturing any additional information.
+    #[cfg(feature = "remote")]
+    pub(crate) fn from_frames(frames: Vec<Frame>) -> Self {
+        Self {
+            frames: Box::new(frames),
+            _context: PhantomData,
+        }
+    }
+
     /// Merge two [`Report`]s together
     ///
     /// This function appends the [`current_frames()`] of the other [`Report`] to the
@@ -661,6 +671,26 @@ impl<C> Report<C> {
     {
         crate::error::ReportError::from_ref(self)
     }
+
+    /// Formats this `Report` in the specified [`Format`], regardless of the format set by
+    /// `Report::set_debug_format`.
+    ///
+    /// ```rust
+    /// use std::io::{Error, ErrorKind};
+    ///
+    /// use error_stack::{Format, Report};
+    ///
+    /// let report = Report::new(Error::from(ErrorKind::NotFound))
+    ///     .attach_printable("could not read the experiment manifest");
+    ///
+    /// // a single line for log aggregation
+    /// println!("{}", report.format(Format::Compact));
+    /// // a single line of JSON for machine consumption
+    /// println!("{}", report.format(Format::JsonLines));
+    /// ```
+    pub const fn format(&self, format: Format) -> FormattedReport<'_, C> {
+        FormattedReport::new(self, format)
+    }
 }
 
 #[cfg(any(nightly, feature = "std"))]
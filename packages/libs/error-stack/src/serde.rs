@@ -1,7 +1,10 @@
 //! Implementation of general [`Report`] serialization.
 //!
-//! The value can be of any type, currently only printable attachments and context are supported, in
-//! the near future any values will be supported through the use of hooks.
+//! Contexts and attachments are serialized through hooks, which can be installed for any type using
+//! [`Report::install_serde_hook`] or [`Report::install_serde_hook_with`]. If no hook has been
+//! installed for a frame, contexts and printable attachments fall back to their [`Display`] output
+//! and opaque attachments fall back to their type name. `error-stack` installs hooks for
+//! [`Location`], [`Backtrace`], and [`SpanTrace`] by default.
 //!
 //! The serialized [`Report`] is a list of all current sources with the following output:
 //!
@@ -12,37 +15,90 @@
 //!     "sources": [] // recursive render using `frame.sources()`
 //! }
 //! ```
+//!
+//! [`Display`]: core::fmt::Display
+//! [`Location`]: core::panic::Location
+//! [`Backtrace`]: std::backtrace::Backtrace
+//! [`SpanTrace`]: tracing_error::SpanTrace
 
+#[cfg(any(feature = "std", feature = "hooks"))]
+use alloc::boxed::Box;
 use alloc::{format, vec, vec::Vec};
+#[cfg(any(feature = "std", feature = "hooks"))]
+use core::any::TypeId;
 
+#[cfg(any(feature = "std", feature = "hooks"))]
+pub(crate) use default::install_builtin_hooks;
 use serde::{ser::SerializeMap, Serialize, Serializer};
 
 use crate::{AttachmentKind, Context, Frame, FrameKind, Report};
 
-struct SerializeAttachment<'a>(&'a Frame);
+#[cfg(any(feature = "std", feature = "hooks"))]
+pub(crate) type BoxedSerializeHook =
+    Box<dyn Fn(&Frame) -> Option<Box<dyn erased_serde::Serialize + '_>> + Send + Sync>;
+
+#[cfg(any(feature = "std", feature = "hooks"))]
+pub(crate) fn into_boxed_hook<F>(hook: F) -> BoxedSerializeHook
+where
+    F: Fn(&Frame) -> Option<Box<dyn erased_serde::Serialize + '_>> + Send + Sync + 'static,
+{
+    Box::new(hook)
+}
+
+#[cfg(any(feature = "std", feature = "hooks"))]
+pub(crate) struct SerializeHooks {
+    // Unlike `Debug` hooks only a single hook is called per frame, so the order is irrelevant, but
+    // `Vec` is used for consistency with `Hooks`.
+    pub(crate) inner: Vec<(TypeId, BoxedSerializeHook)>,
+}
+
+#[cfg(any(feature = "std", feature = "hooks"))]
+impl SerializeHooks {
+    pub(crate) fn insert<T: Send + Sync + 'static>(&mut self, hook: BoxedSerializeHook) {
+        let type_id = TypeId::of::<T>();
+
+        // make sure that previous hooks of the same TypeId are deleted.
+        self.inner.retain(|(id, _)| *id != type_id);
+        self.inner.push((type_id, hook));
+    }
 
-impl<'a> Serialize for SerializeAttachment<'a> {
+    pub(crate) fn call<'a>(
+        &self,
+        frame: &'a Frame,
+    ) -> Option<Box<dyn erased_serde::Serialize + 'a>> {
+        let type_id = frame.type_id();
+
+        self.inner
+            .iter()
+            .find(|(id, _)| *id == type_id)
+            .and_then(|(_, hook)| hook(frame))
+    }
+}
+
+struct SerializeFrame<'a>(&'a Frame);
+
+impl<'a> Serialize for SerializeFrame<'a> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
         let Self(frame) = self;
 
-        #[allow(clippy::match_same_arms)]
+        // The lock is released before serializing, so that values are able to serialize reports
+        // themselves.
+        #[cfg(any(feature = "std", feature = "hooks"))]
+        if let Some(value) = Report::invoke_serde_hook(|hooks| hooks.call(frame)) {
+            return value.serialize(serializer);
+        }
+
         match frame.kind() {
-            FrameKind::Context(_) => {
-                // TODO: for now `Context` is unsupported, upcoming PR will fix via hooks
-                // `SerializeAttachmentList` ensures that no context is ever serialized
-                todo!()
-            }
-            FrameKind::Attachment(AttachmentKind::Opaque(_)) => {
-                // TODO: for now opaque attachments are unsupported, upcoming PR will fix that
-                // `SerializeAttachmentList` ensures that no such attachment is added
-                todo!()
-            }
+            FrameKind::Context(context) => format!("{context}").serialize(serializer),
             FrameKind::Attachment(AttachmentKind::Printable(attachment)) => {
                 format!("{attachment}").serialize(serializer)
             }
+            FrameKind::Attachment(AttachmentKind::Opaque(_)) => {
+                frame.type_name().serialize(serializer)
+            }
         }
     }
 }
@@ -54,25 +110,13 @@ impl<'a, 'b> Serialize for SerializeAttachmentList<'a, 'b> {
     where
         S: Serializer,
     {
-        serializer.collect_seq(
-            self.0
-                .iter()
-                .copied()
-                .filter(|attachment| {
-                    // for now opaque attachments are ignored
-                    !matches!(
-                        attachment.kind(),
-                        FrameKind::Attachment(AttachmentKind::Opaque(_))
-                    )
-                })
-                .map(SerializeAttachment),
-        )
+        serializer.collect_seq(self.0.iter().copied().map(SerializeFrame))
     }
 }
 
 struct SerializeContext<'a> {
     attachments: Vec<&'a Frame>,
-    context: &'a dyn Context,
+    context: &'a Frame,
     sources: &'a [Frame],
 }
 
@@ -88,7 +132,7 @@ impl<'a> Serialize for SerializeContext<'a> {
         } = self;
 
         let mut map = serializer.serialize_map(Some(3))?;
-        map.serialize_entry("context", &format!("{context}").as_str())?;
+        map.serialize_entry("context", &SerializeFrame(context))?;
         map.serialize_entry("attachments", &SerializeAttachmentList(&attachments[..]))?;
         map.serialize_entry("sources", &SerializeSources(sources))?;
 
@@ -113,13 +157,13 @@ fn find_next<'a>(head: &[&'a Frame], mut current: &'a Frame) -> Vec<SerializeCon
     attachments.extend(head);
 
     loop {
-        if let FrameKind::Context(context) = current.kind() {
+        if let FrameKind::Context(_) = current.kind() {
             // found the context, return all attachments (reversed)
             attachments.reverse();
 
             return vec![SerializeContext {
                 attachments,
-                context,
+                context: current,
                 sources: current.sources(),
             }];
         } else if current.sources().len() > 1 {
@@ -151,3 +195,47 @@ impl<C: Context> Serialize for Report<C> {
         SerializeSources(self.current_frames()).serialize(serializer)
     }
 }
+
+#[cfg(any(feature = "std", feature = "hooks"))]
+mod default {
+    use alloc::string::ToString;
+    use core::{
+        panic::Location,
+        sync::atomic::{AtomicBool, Ordering},
+    };
+    #[cfg(all(rust_1_65, feature = "std"))]
+    use std::backtrace::Backtrace;
+    #[cfg(feature = "std")]
+    use std::sync::Once;
+
+    #[cfg(all(not(feature = "std"), feature = "hooks"))]
+    use spin::once::Once;
+    #[cfg(feature = "spantrace")]
+    use tracing_error::SpanTrace;
+
+    use crate::Report;
+
+    pub(crate) fn install_builtin_hooks() {
+        static INSTALL_BUILTIN: Once = Once::new();
+
+        // Same as for `Debug` hooks: `install_serde_hook_with` calls `install_builtin_hooks`, this
+        // guard prevents the recursive call into `Once::call_once`.
+        static INSTALL_BUILTIN_RUNNING: AtomicBool = AtomicBool::new(false);
+
+        if INSTALL_BUILTIN.is_completed() || INSTALL_BUILTIN_RUNNING.load(Ordering::Acquire) {
+            return;
+        }
+
+        INSTALL_BUILTIN.call_once(|| {
+            INSTALL_BUILTIN_RUNNING.store(true, Ordering::Release);
+
+            Report::install_serde_hook_with::<Location, _>(ToString::to_string);
+
+            #[cfg(all(feature = "std", rust_1_65))]
+            Report::install_serde_hook_with::<Backtrace, _>(ToString::to_string);
+
+            #[cfg(feature = "spantrace")]
+            Report::install_serde_hook_with::<SpanTrace, _>(ToString::to_string);
+        });
+    }
+}
@@ -0,0 +1,341 @@
+//! Extension for convenient usage of [`Report`]s returned by [`Stream`]s.
+//!
+//! Extends [`Stream`] with the same methods as [`ResultExt`], which are applied to every item when
+//! [`poll_next`]ing. Attachments and contexts are only cloned or created if an item is an error.
+//!
+//! [`Report`]: crate::Report
+//! [`poll_next`]: Stream::poll_next
+
+use core::{
+    fmt::{Debug, Display},
+    pin::Pin,
+    task::{Context as TaskContext, Poll},
+};
+
+use futures_core::{FusedStream, Stream};
+
+use crate::{Context, Result, ResultExt};
+
+macro_rules! implement_stream_adaptor {
+    ($stream:ident, $method:ident, $lazy_method:ident, $bound:ident $(+ $bounds:ident)* $(+ $lifetime:lifetime)*, $output:ty) => {
+        #[doc = concat!("Adaptor returned by [`StreamExt::", stringify!( $method ), "`].")]
+        pub struct $stream<S, T> {
+            stream: S,
+            inner: T,
+        }
+
+        impl<S, T> Stream for $stream<S, T>
+        where
+            S: Stream,
+            S::Item: ResultExt,
+            T: Clone + $bound $(+ $bounds)* $(+ $lifetime)*
+        {
+            type Item = $output;
+
+            #[track_caller]
+            fn poll_next(self: Pin<&mut Self>, cx: &mut TaskContext) -> Poll<Option<Self::Item>> {
+                // SAFETY: `inner` is never pinned, only `stream` is structurally pinned.
+                //         Additionally, `Self` does not implement `Drop`, nor is it
+                //         `#[repr(packed)]`
+                //         See the `pin` module: https://doc.rust-lang.org/core/pin/index.html
+                let (stream, inner) = unsafe {
+                    let Self { stream, inner } = self.get_unchecked_mut();
+                    (Pin::new_unchecked(stream), &*inner)
+                };
+
+                // The value is only cloned if the item is an error, so `Ok` items don't allocate.
+                // Can't use `map` as `#[track_caller]` is unstable on closures
+                match stream.poll_next(cx) {
+                    Poll::Ready(Some(item)) => {
+                        Poll::Ready(Some(item.$lazy_method(|| inner.clone())))
+                    }
+                    Poll::Ready(None) => Poll::Ready(None),
+                    Poll::Pending => Poll::Pending,
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.stream.size_hint()
+            }
+        }
+
+        impl<S, T> FusedStream for $stream<S, T>
+        where
+            S: FusedStream,
+            S::Item: ResultExt,
+            T: Clone + $bound $(+ $bounds)* $(+ $lifetime)*
+        {
+            fn is_terminated(&self) -> bool {
+                self.stream.is_terminated()
+            }
+        }
+    };
+}
+
+macro_rules! implement_lazy_stream_adaptor {
+    ($stream:ident, $method:ident, $bound:ident $(+ $bounds:ident)* $(+ $lifetime:lifetime)*, $output:ty) => {
+        #[doc = concat!("Adaptor returned by [`StreamExt::", stringify!( $method ), "`].")]
+        pub struct $stream<S, F> {
+            stream: S,
+            inner: F,
+        }
+
+        impl<S, F, T> Stream for $stream<S, F>
+        where
+            S: Stream,
+            S::Item: ResultExt,
+            F: FnMut() -> T,
+            T: $bound $(+ $bounds)* $(+ $lifetime)*
+        {
+            type Item = $output;
+
+            #[track_caller]
+            fn poll_next(self: Pin<&mut Self>, cx: &mut TaskContext) -> Poll<Option<Self::Item>> {
+                // SAFETY: `inner` is never pinned, only `stream` is structurally pinned.
+                //         Additionally, `Self` does not implement `Drop`, nor is it
+                //         `#[repr(packed)]`
+                //         See the `pin` module: https://doc.rust-lang.org/core/pin/index.html
+                let (stream, inner) = unsafe {
+                    let Self { stream, inner } = self.get_unchecked_mut();
+                    (Pin::new_unchecked(stream), inner)
+                };
+
+                // Can't use `map` as `#[track_caller]` is unstable on closures
+                match stream.poll_next(cx) {
+                    Poll::Ready(Some(item)) => Poll::Ready(Some(item.$method(inner))),
+                    Poll::Ready(None) => Poll::Ready(None),
+                    Poll::Pending => Poll::Pending,
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.stream.size_hint()
+            }
+        }
+
+        impl<S, F, T> FusedStream for $stream<S, F>
+        where
+            S: FusedStream,
+            S::Item: ResultExt,
+            F: FnMut() -> T,
+            T: $bound $(+ $bounds)* $(+ $lifetime)*
+        {
+            fn is_terminated(&self) -> bool {
+                self.stream.is_terminated()
+            }
+        }
+    };
+}
+
+implement_stream_adaptor!(
+    StreamWithAttachment,
+    attach,
+    attach_lazy,
+    Send + Sync + 'static,
+    S::Item
+);
+
+implement_lazy_stream_adaptor!(
+    StreamWithLazyAttachment,
+    attach_lazy,
+    Send + Sync + 'static,
+    S::Item
+);
+
+implement_stream_adaptor!(
+    StreamWithPrintableAttachment,
+    attach_printable,
+    attach_printable_lazy,
+    Display + Debug + Send + Sync + 'static,
+    S::Item
+);
+
+implement_lazy_stream_adaptor!(
+    StreamWithLazyPrintableAttachment,
+    attach_printable_lazy,
+    Display + Debug + Send + Sync + 'static,
+    S::Item
+);
+
+implement_stream_adaptor!(
+    StreamWithContext,
+    change_context,
+    change_context_lazy,
+    Context,
+    Result<<S::Item as ResultExt>::Ok, T>
+);
+
+implement_lazy_stream_adaptor!(
+    StreamWithLazyContext,
+    change_context_lazy,
+    Context,
+    Result<<S::Item as ResultExt>::Ok, T>
+);
+
+/// Extension trait for [`Stream`] to provide contextual information on [`Report`]s.
+///
+/// [`Report`]: crate::Report
+pub trait StreamExt: Stream + Sized {
+    /// Adds a new attachment to the [`Report`] of every [`Err`] item when [`poll_next`]ing the
+    /// [`Stream`].
+    ///
+    /// Applies [`Report::attach`] on the [`Err`] variant, refer to it for more information.
+    ///
+    /// [`Report`]: crate::Report
+    /// [`Report::attach`]: crate::Report::attach
+    /// [`poll_next`]: Stream::poll_next
+    #[track_caller]
+    fn attach<A>(self, attachment: A) -> StreamWithAttachment<Self, A>
+    where
+        A: Clone + Send + Sync + 'static;
+
+    /// Lazily adds a new attachment to the [`Report`] of every [`Err`] item when [`poll_next`]ing
+    /// the [`Stream`].
+    ///
+    /// Applies [`Report::attach`] on the [`Err`] variant, refer to it for more information.
+    ///
+    /// [`Report`]: crate::Report
+    /// [`Report::attach`]: crate::Report::attach
+    /// [`poll_next`]: Stream::poll_next
+    #[track_caller]
+    fn attach_lazy<A, F>(self, attachment: F) -> StreamWithLazyAttachment<Self, F>
+    where
+        A: Send + Sync + 'static,
+        F: FnMut() -> A;
+
+    /// Adds a new printable attachment to the [`Report`] of every [`Err`] item when
+    /// [`poll_next`]ing the [`Stream`].
+    ///
+    /// Applies [`Report::attach_printable`] on the [`Err`] variant, refer to it for more
+    /// information.
+    ///
+    /// [`Report`]: crate::Report
+    /// [`Report::attach_printable`]: crate::Report::attach_printable
+    /// [`poll_next`]: Stream::poll_next
+    #[track_caller]
+    fn attach_printable<A>(self, attachment: A) -> StreamWithPrintableAttachment<Self, A>
+    where
+        A: Clone + Display + Debug + Send + Sync + 'static;
+
+    /// Lazily adds a new printable attachment to the [`Report`] of every [`Err`] item when
+    /// [`poll_next`]ing the [`Stream`].
+    ///
+    /// Applies [`Report::attach_printable`] on the [`Err`] variant, refer to it for more
+    /// information.
+    ///
+    /// [`Report`]: crate::Report
+    /// [`Report::attach_printable`]: crate::Report::attach_printable
+    /// [`poll_next`]: Stream::poll_next
+    #[track_caller]
+    fn attach_printable_lazy<A, F>(
+        self,
+        attachment: F,
+    ) -> StreamWithLazyPrintableAttachment<Self, F>
+    where
+        A: Display + Debug + Send + Sync + 'static,
+        F: FnMut() -> A;
+
+    /// Changes the [`Context`] of the [`Report`] of every [`Err`] item when [`poll_next`]ing the
+    /// [`Stream`].
+    ///
+    /// Applies [`Report::change_context`] on the [`Err`] variant, refer to it for more information.
+    ///
+    /// [`Report`]: crate::Report
+    /// [`Report::change_context`]: crate::Report::change_context
+    /// [`poll_next`]: Stream::poll_next
+    #[track_caller]
+    fn change_context<C>(self, context: C) -> StreamWithContext<Self, C>
+    where
+        C: Clone + Context;
+
+    /// Lazily changes the [`Context`] of the [`Report`] of every [`Err`] item when [`poll_next`]ing
+    /// the [`Stream`].
+    ///
+    /// Applies [`Report::change_context`] on the [`Err`] variant, refer to it for more information.
+    ///
+    /// [`Report`]: crate::Report
+    /// [`Report::change_context`]: crate::Report::change_context
+    /// [`poll_next`]: Stream::poll_next
+    #[track_caller]
+    fn change_context_lazy<C, F>(self, context: F) -> StreamWithLazyContext<Self, F>
+    where
+        C: Context,
+        F: FnMut() -> C;
+}
+
+impl<S: Stream> StreamExt for S
+where
+    S::Item: ResultExt,
+{
+    #[track_caller]
+    fn attach<A>(self, attachment: A) -> StreamWithAttachment<Self, A>
+    where
+        A: Clone + Send + Sync + 'static,
+    {
+        StreamWithAttachment {
+            stream: self,
+            inner: attachment,
+        }
+    }
+
+    #[track_caller]
+    fn attach_lazy<A, F>(self, attachment: F) -> StreamWithLazyAttachment<Self, F>
+    where
+        A: Send + Sync + 'static,
+        F: FnMut() -> A,
+    {
+        StreamWithLazyAttachment {
+            stream: self,
+            inner: attachment,
+        }
+    }
+
+    #[track_caller]
+    fn attach_printable<A>(self, attachment: A) -> StreamWithPrintableAttachment<Self, A>
+    where
+        A: Clone + Display + Debug + Send + Sync + 'static,
+    {
+        StreamWithPrintableAttachment {
+            stream: self,
+            inner: attachment,
+        }
+    }
+
+    #[track_caller]
+    fn attach_printable_lazy<A, F>(
+        self,
+        attachment: F,
+    ) -> StreamWithLazyPrintableAttachment<Self, F>
+    where
+        A: Display + Debug + Send + Sync + 'static,
+        F: FnMut() -> A,
+    {
+        StreamWithLazyPrintableAttachment {
+            stream: self,
+            inner: attachment,
+        }
+    }
+
+    #[track_caller]
+    fn change_context<C>(self, context: C) -> StreamWithContext<Self, C>
+    where
+        C: Clone + Context,
+    {
+        StreamWithContext {
+            stream: self,
+            inner: context,
+        }
+    }
+
+    #[track_caller]
+    fn change_context_lazy<C, F>(self, context: F) -> StreamWithLazyContext<Self, F>
+    where
+        C: Context,
+        F: FnMut() -> C,
+    {
+        StreamWithLazyContext {
+            stream: self,
+            inner: context,
+        }
+    }
+}
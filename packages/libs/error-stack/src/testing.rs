@@ -0,0 +1,265 @@
+//! Helpers for asserting on [`Report`]s in tests.
+//!
+//! [`assert_report_contains!`] checks the contexts and attachments of a [`Report`], and
+//! [`render_normalized`] renders the [`Debug`] output of a [`Report`] without any value, which
+//! differs between runs or machines, so it can be used in snapshot tests.
+//!
+//! ```rust
+//! use std::io::{Error, ErrorKind};
+//!
+//! use error_stack::{assert_report_contains, testing::render_normalized, Report};
+//!
+//! let report = Report::new(Error::from(ErrorKind::NotFound))
+//!     .attach_printable("could not read the experiment manifest");
+//!
+//! assert_report_contains!(
+//!     report,
+//!     Context = Error,
+//!     attachment = "could not read the experiment manifest",
+//! );
+//!
+//! # #[cfg(feature = "pretty-print")]
+//! assert_eq!(
+//!     render_normalized(&report),
+//!     "entity not found\n╰╴ could not read the experiment manifest"
+//! );
+//! # #[cfg(not(feature = "pretty-print"))]
+//! # assert_eq!(
+//! #     render_normalized(&report),
+//! #     "entity not found\n|- could not read the experiment manifest"
+//! # );
+//! ```
+//!
+//! [`Debug`]: core::fmt::Debug
+
+use alloc::string::{String, ToString};
+
+pub use crate::assert_report_contains;
+use crate::{fmt::FormattedReport, AttachmentKind, FrameKind, Report};
+
+/// Returns if any [`Context`] of the report is of type `T`.
+///
+/// Unlike [`Report::contains`], attachments of type `T` are not considered.
+///
+/// [`Context`]: crate::Context
+#[must_use]
+pub fn contains_context<T: Send + Sync + 'static, C>(report: &Report<C>) -> bool {
+    report
+        .frames()
+        .any(|frame| matches!(frame.kind(), FrameKind::Context(_)) && frame.is::<T>())
+}
+
+/// Returns if any attachment of the report is of type `T`.
+///
+/// Unlike [`Report::contains`], contexts of type `T` are not considered.
+#[must_use]
+pub fn contains_attachment<T: Send + Sync + 'static, C>(report: &Report<C>) -> bool {
+    report
+        .frames()
+        .any(|frame| matches!(frame.kind(), FrameKind::Attachment(_)) && frame.is::<T>())
+}
+
+/// Returns if the [`Display`] output of any [`Context`] of the report is equal to `message`.
+///
+/// [`Context`]: crate::Context
+/// [`Display`]: core::fmt::Display
+#[must_use]
+pub fn contains_context_message<C>(report: &Report<C>, message: &str) -> bool {
+    report.frames().any(|frame| match frame.kind() {
+        FrameKind::Context(context) => context.to_string() == message,
+        FrameKind::Attachment(_) => false,
+    })
+}
+
+/// Returns if the [`Display`] output of any printable attachment of the report is equal to
+/// `message`.
+///
+/// [`Display`]: core::fmt::Display
+#[must_use]
+pub fn contains_attachment_message<C>(report: &Report<C>, message: &str) -> bool {
+    report.frames().any(|frame| match frame.kind() {
+        FrameKind::Attachment(AttachmentKind::Printable(attachment)) => {
+            attachment.to_string() == message
+        }
+        FrameKind::Context(_) | FrameKind::Attachment(AttachmentKind::Opaque(_)) => false,
+    })
+}
+
+/// Removes all ANSI escape sequences, e.g. `\x1b[1m`, from `value`.
+fn strip_ansi(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(char) = chars.next() {
+        if char != '\x1b' {
+            output.push(char);
+            continue;
+        }
+
+        // skip the control sequence until (including) its final byte
+        if chars.next() == Some('[') {
+            for char in chars.by_ref() {
+                if ('@'..='~').contains(&char) {
+                    break;
+                }
+            }
+        }
+    }
+
+    output
+}
+
+/// Replaces all hexadecimal numbers with at least 6 digits and a `0x` prefix with `0x[address]`.
+fn strip_addresses(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(index) = rest.find("0x") {
+        let (head, tail) = rest.split_at(index);
+        output.push_str(head);
+
+        let digits = tail[2..].bytes().take_while(u8::is_ascii_hexdigit).count();
+        // do not replace the suffix of an identifier or another number, like `a0x1234567`
+        let standalone = !head
+            .chars()
+            .next_back()
+            .map_or(false, |char| char.is_ascii_alphanumeric() || char == '_');
+
+        if digits >= 6 && standalone {
+            output.push_str("0x[address]");
+        } else {
+            output.push_str(&tail[..2 + digits]);
+        }
+        rest = &tail[2 + digits..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Renders the [`Debug`] output of a [`Report`], which is stable across runs and machines.
+///
+/// The output is the same as [`Format::Tree`], but:
+///
+/// - [`Location`]s, [`Backtrace`]s, and [`SpanTrace`]s are omitted,
+/// - colors are removed, and
+/// - memory addresses, hexadecimal numbers with at least 6 digits like `0x7ffd5c3e1a2c`, are
+///   replaced by `0x[address]`.
+///
+/// Installed [`Debug`] hooks are still used. Note, that the output depends on the `pretty-print`
+/// feature, which uses unicode symbols instead of ASCII.
+///
+/// [`Debug`]: core::fmt::Debug
+/// [`Format::Tree`]: crate::Format::Tree
+/// [`Location`]: core::panic::Location
+/// [`Backtrace`]: std::backtrace::Backtrace
+/// [`SpanTrace`]: tracing_error::SpanTrace
+#[must_use]
+pub fn render_normalized<C>(report: &Report<C>) -> String {
+    let output = FormattedReport::normalized(report).to_string();
+
+    strip_addresses(&strip_ansi(&output))
+}
+
+/// Asserts that a [`Report`] contains the specified contexts and attachments.
+///
+/// Every check is a `key = value` pair:
+///
+/// - `Context = T` asserts that a context is of type `T`,
+/// - `Attachment = T` asserts that an attachment is of type `T`,
+/// - `context = "..."` asserts that the [`Display`] output of a context is equal to the message,
+///   and
+/// - `attachment = "..."` asserts that the [`Display`] output of a printable attachment is equal to
+///   the message.
+///
+/// On failure, the message includes the normalized output of the [`Report`], see
+/// [`render_normalized`].
+///
+/// [`Report`]: crate::Report
+/// [`Display`]: core::fmt::Display
+/// [`render_normalized`]: crate::testing::render_normalized
+///
+/// # Examples
+///
+/// ```
+/// use std::io::{Error, ErrorKind};
+///
+/// use error_stack::{assert_report_contains, Report};
+///
+/// struct Suggestion;
+///
+/// let report = Report::new(Error::from(ErrorKind::NotFound))
+///     .attach(Suggestion)
+///     .attach_printable("could not read the experiment manifest");
+///
+/// assert_report_contains!(
+///     report,
+///     Context = Error,
+///     Attachment = Suggestion,
+///     context = "entity not found",
+///     attachment = "could not read the experiment manifest",
+/// );
+/// ```
+///
+/// ```should_panic
+/// use std::io::{Error, ErrorKind};
+///
+/// use error_stack::{assert_report_contains, Report};
+///
+/// let report = Report::new(Error::from(ErrorKind::NotFound));
+///
+/// assert_report_contains!(
+///     report,
+///     attachment = "could not read the experiment manifest"
+/// );
+/// ```
+#[macro_export]
+macro_rules! assert_report_contains {
+    (@check $report:ident $(,)?) => {};
+    (@check $report:ident, Context = $context:ty $(, $($rest:tt)*)?) => {
+        if !$crate::testing::contains_context::<$context, _>($report) {
+            ::core::panic!(
+                "report does not contain a context of type `{}`\n\n{}",
+                ::core::any::type_name::<$context>(),
+                $crate::testing::render_normalized($report),
+            );
+        }
+        $crate::assert_report_contains!(@check $report $(, $($rest)*)?);
+    };
+    (@check $report:ident, Attachment = $attachment:ty $(, $($rest:tt)*)?) => {
+        if !$crate::testing::contains_attachment::<$attachment, _>($report) {
+            ::core::panic!(
+                "report does not contain an attachment of type `{}`\n\n{}",
+                ::core::any::type_name::<$attachment>(),
+                $crate::testing::render_normalized($report),
+            );
+        }
+        $crate::assert_report_contains!(@check $report $(, $($rest)*)?);
+    };
+    (@check $report:ident, context = $message:expr $(, $($rest:tt)*)?) => {
+        let message: &str = &$message;
+        if !$crate::testing::contains_context_message($report, message) {
+            ::core::panic!(
+                "report does not contain a context with the message {:?}\n\n{}",
+                message,
+                $crate::testing::render_normalized($report),
+            );
+        }
+        $crate::assert_report_contains!(@check $report $(, $($rest)*)?);
+    };
+    (@check $report:ident, attachment = $message:expr $(, $($rest:tt)*)?) => {
+        let message: &str = &$message;
+        if !$crate::testing::contains_attachment_message($report, message) {
+            ::core::panic!(
+                "report does not contain an attachment with the message {:?}\n\n{}",
+                message,
+                $crate::testing::render_normalized($report),
+            );
+        }
+        $crate::assert_report_contains!(@check $report $(, $($rest)*)?);
+    };
+    ($report:expr $(, $($rest:tt)*)?) => {{
+        let report = &$report;
+        $crate::assert_report_contains!(@check report $(, $($rest)*)?);
+    }};
+}
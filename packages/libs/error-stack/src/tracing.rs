@@ -0,0 +1,210 @@
+//! Recording of [`Report`]s as structured [`tracing`] events.
+//!
+//! [`Report::record_event`] emits a single event with the contexts, attachments, and locations of
+//! a report as separate fields, [`Report::record_frame_events`] additionally emits one event for
+//! every frame, and [`Report::record_exception`] follows the `OpenTelemetry`
+//! semantic conventions.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt::Display, panic::Location};
+#[cfg(all(rust_1_65, feature = "std"))]
+use std::backtrace::Backtrace;
+
+use tracing::Level;
+
+use crate::{AttachmentKind, Context, Frame, FrameKind, Report};
+
+/// Emits an event with a level only known at runtime.
+///
+/// The `tracing` macros require the level to be a constant, so every level needs its own callsite.
+macro_rules! event {
+    ($level:expr, $($arguments:tt)+) => {{
+        let level = $level;
+        if level == Level::ERROR {
+            ::tracing::event!(Level::ERROR, $($arguments)+);
+        } else if level == Level::WARN {
+            ::tracing::event!(Level::WARN, $($arguments)+);
+        } else if level == Level::INFO {
+            ::tracing::event!(Level::INFO, $($arguments)+);
+        } else if level == Level::DEBUG {
+            ::tracing::event!(Level::DEBUG, $($arguments)+);
+        } else {
+            ::tracing::event!(Level::TRACE, $($arguments)+);
+        }
+    }};
+}
+
+/// Creates a span with a level only known at runtime, see [`event!`].
+macro_rules! span {
+    ($level:expr, $($arguments:tt)+) => {{
+        let level = $level;
+        if level == Level::ERROR {
+            ::tracing::span!(Level::ERROR, $($arguments)+)
+        } else if level == Level::WARN {
+            ::tracing::span!(Level::WARN, $($arguments)+)
+        } else if level == Level::INFO {
+            ::tracing::span!(Level::INFO, $($arguments)+)
+        } else if level == Level::DEBUG {
+            ::tracing::span!(Level::DEBUG, $($arguments)+)
+        } else {
+            ::tracing::span!(Level::TRACE, $($arguments)+)
+        }
+    }};
+}
+
+/// The fields of a [`Report`] recorded by [`Report::record_event`].
+struct ReportFields {
+    contexts: Vec<String>,
+    attachments: Vec<String>,
+    locations: Vec<String>,
+    backtrace: bool,
+}
+
+impl ReportFields {
+    fn new<'a>(frames: impl Iterator<Item = &'a Frame>) -> Self {
+        let mut fields = Self {
+            contexts: Vec::new(),
+            attachments: Vec::new(),
+            locations: Vec::new(),
+            backtrace: false,
+        };
+
+        for frame in frames {
+            match frame.kind() {
+                FrameKind::Context(context) => fields.contexts.push(context.to_string()),
+                FrameKind::Attachment(AttachmentKind::Printable(attachment)) => {
+                    fields.attachments.push(attachment.to_string());
+                }
+                FrameKind::Attachment(AttachmentKind::Opaque(_)) => {
+                    if let Some(location) = frame.downcast_ref::<Location<'static>>() {
+                        fields.locations.push(location.to_string());
+                    }
+
+                    #[cfg(all(rust_1_65, feature = "std"))]
+                    if frame.is::<Backtrace>() {
+                        fields.backtrace = true;
+                    }
+                }
+            }
+        }
+
+        fields
+    }
+}
+
+fn record_frame_event(level: Level, kind: &'static str, message: impl Display) {
+    event!(level, kind, "{message}");
+}
+
+impl<C: Context> Report<C> {
+    /// Records this report as a single [`tracing`] event at `level`.
+    ///
+    /// Formatting a report into the message of an event (`tracing::error!("{report:?}")`) turns
+    /// the whole tree into a single opaque string. Instead, this records the following fields,
+    /// which can be processed by any subscriber:
+    ///
+    ///  Field         | Value
+    /// ---------------|--------------------------------------------------------------------------
+    /// `message`      | The [`Display`] output of the current context
+    /// `context`      | The [`Display`] output of the current context
+    /// `contexts`     | The [`Display`] output of every context in the report, outermost first
+    /// `attachments`  | The [`Display`] output of every printable attachment, outermost first
+    /// `locations`    | Every [`Location`] in the report, outermost first
+    /// `backtrace`    | `true` if a [`Backtrace`] has been captured
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # #[cfg(all(feature = "std", not(miri)))] {
+    /// use std::fs;
+    ///
+    /// use error_stack::IntoReport;
+    /// use tracing::Level;
+    ///
+    /// if let Err(report) = fs::read_to_string("config.json").into_report() {
+    ///     report.record_event(Level::WARN);
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`Display`]: core::fmt::Display
+    /// [`Backtrace`]: std::backtrace::Backtrace
+    pub fn record_event(&self, level: Level) {
+        let context = self.current_context();
+        let fields = ReportFields::new(self.frames());
+
+        event!(
+            level,
+            context = %context,
+            contexts = ?fields.contexts,
+            attachments = ?fields.attachments,
+            locations = ?fields.locations,
+            backtrace = fields.backtrace,
+            "{context}"
+        );
+    }
+
+    /// Records this report as a [`tracing`] event at `level` followed by one event for every
+    /// frame.
+    ///
+    /// All events are emitted inside of a span named `report`. In addition to the event emitted
+    /// by [`record_event`], every context, printable attachment, and [`Location`] is recorded as an
+    /// event with the `kind` field set to `"context"`, `"attachment"`, or `"location"`
+    /// respectively and its [`Display`] output as message.
+    ///
+    /// [`record_event`]: Self::record_event
+    /// [`Display`]: core::fmt::Display
+    pub fn record_frame_events(&self, level: Level) {
+        let span = span!(level, "report", context = %self.current_context());
+        let _guard = span.enter();
+
+        self.record_event(level);
+
+        for frame in self.frames() {
+            match frame.kind() {
+                FrameKind::Context(context) => record_frame_event(level, "context", context),
+                FrameKind::Attachment(AttachmentKind::Printable(attachment)) => {
+                    record_frame_event(level, "attachment", attachment);
+                }
+                FrameKind::Attachment(AttachmentKind::Opaque(_)) => {
+                    if let Some(location) = frame.downcast_ref::<Location<'static>>() {
+                        record_frame_event(level, "location", location);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Records this report as an exception event following the [`OpenTelemetry` semantic
+    /// conventions].
+    ///
+    /// The event is emitted at [`Level::ERROR`] with the following fields:
+    ///
+    /// - `exception.type`: The type name of the current context
+    /// - `exception.message`: The [`Display`] output of the current context
+    /// - `exception.stacktrace`: The [`SpanTrace`] of the report if one has been captured
+    ///
+    /// [`OpenTelemetry` semantic conventions]: https://opentelemetry.io/docs/reference/specification/trace/semantic_conventions/exceptions/
+    /// [`Display`]: core::fmt::Display
+    /// [`SpanTrace`]: tracing_error::SpanTrace
+    #[cfg(feature = "spantrace")]
+    pub fn record_exception(&self) {
+        use tracing_error::SpanTrace;
+
+        #[cfg(nightly)]
+        let span_trace = self.request_ref::<SpanTrace>().next();
+        #[cfg(not(nightly))]
+        let span_trace = self.downcast_ref::<SpanTrace>();
+
+        tracing::event!(
+            Level::ERROR,
+            "exception.type" = core::any::type_name::<C>(),
+            "exception.message" = %self.current_context(),
+            "exception.stacktrace" = span_trace.map(tracing::field::display),
+            "exception"
+        );
+    }
+}
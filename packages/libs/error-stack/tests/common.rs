@@ -170,6 +170,15 @@ pub fn create_future() -> impl Future<Output = Result<(), RootError>> {
     futures::future::err(create_report())
 }
 
+pub fn create_iterator() -> impl Iterator<Item = Result<(), RootError>> {
+    [Ok(()), create_error()].into_iter()
+}
+
+#[cfg(feature = "futures")]
+pub fn create_stream() -> impl futures::Stream<Item = Result<(), RootError>> + Unpin {
+    futures::stream::iter(create_iterator())
+}
+
 pub fn capture_ok<E>(closure: impl FnOnce() -> Result<(), E>) {
     closure().expect("expected an OK value, found an error")
 }
@@ -9,6 +9,8 @@ mod common;
 use std::backtrace::Backtrace;
 
 use common::*;
+#[cfg(not(miri))]
+use error_stack::fmt::BacktraceFilter;
 #[cfg(nightly)]
 use error_stack::Report;
 
@@ -58,3 +60,87 @@ fn provided() {
     #[cfg(not(miri))]
     assert_eq!(error_backtrace_string, report_backtrace_string);
 }
+
+#[cfg(not(miri))]
+fn create_nested_report() -> error_stack::Report<ContextA> {
+    create_report().change_context(ContextA(0))
+}
+
+#[test]
+#[cfg(not(miri))]
+fn filter_crate() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+
+    error_stack::Report::install_debug_hook::<Backtrace>(
+        BacktraceFilter::new()
+            .crate_prefix("test_backtrace::")
+            .into_hook(),
+    );
+
+    let output = format!("{:?}", create_nested_report());
+
+    assert!(output.contains("test_backtrace::create_nested_report"));
+    assert!(!output.contains("std::"));
+    assert!(!output.contains("core::"));
+}
+
+#[test]
+#[cfg(not(miri))]
+fn filter_collapse_runtime() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+
+    error_stack::Report::install_debug_hook::<Backtrace>(
+        BacktraceFilter::new().collapse_runtime(true).into_hook(),
+    );
+
+    let output = format!("{:?}", create_nested_report());
+
+    assert!(output.contains("test_backtrace::create_nested_report"));
+    assert!(output.contains("runtime frames hidden"));
+    assert!(!output.contains("core::ops::function"));
+    assert!(!output.contains("test::run_test"));
+}
+
+#[test]
+#[cfg(not(miri))]
+fn filter_between_locations() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+
+    error_stack::Report::install_debug_hook::<Backtrace>(
+        BacktraceFilter::new().between_locations(true).into_hook(),
+    );
+
+    let output = format!("{:?}", create_nested_report());
+    let appendix = output
+        .split_once("backtrace no. 1\n")
+        .expect("backtrace should be rendered")
+        .1;
+
+    assert!(appendix.contains("common::create_report"));
+    assert!(appendix.contains("test_backtrace::create_nested_report"));
+    assert!(!appendix.contains("error_stack::report::Report"));
+    assert!(!appendix.contains("test_backtrace::filter_between_locations"));
+}
+
+#[test]
+#[cfg(not(miri))]
+fn filter_deduplicate() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+
+    error_stack::Report::install_debug_hook::<Backtrace>(
+        BacktraceFilter::new().deduplicate(true).into_hook(),
+    );
+
+    // every report is created with the same call stack
+    let mut reports = (0..3)
+        .map(|_| create_report())
+        .collect::<Vec<_>>()
+        .into_iter();
+    let mut report = reports.next().expect("should have a report");
+    reports.for_each(|other| report.extend_one(other));
+
+    let output = format!("{report:?}");
+
+    assert_eq!(output.matches("backtrace no.").count(), 1);
+    assert_eq!(output.matches("frames (1)").count(), 3);
+}
@@ -5,7 +5,9 @@
 mod common;
 
 use common::*;
-use error_stack::{AttachmentKind, FrameKind, FutureExt, Report, ResultExt};
+#[cfg(feature = "futures")]
+use error_stack::StreamExt;
+use error_stack::{AttachmentKind, FrameKind, FutureExt, IteratorExt, Report, ResultExt};
 
 fn test_messages<E>(report: &Report<E>) {
     assert_eq!(
@@ -63,3 +65,42 @@ fn attach_future() {
     test_messages(&report);
     test_kinds(&report);
 }
+
+#[test]
+fn attach_iterator() {
+    let mut iterator = create_iterator()
+        .change_context(ContextA(0))
+        .attach(AttachmentA)
+        .change_context_lazy(|| ContextB(0))
+        .attach_lazy(|| AttachmentB);
+
+    assert!(iterator.next().expect("Not an item").is_ok());
+    let report = iterator
+        .next()
+        .expect("Not an item")
+        .expect_err("Not an error");
+    test_messages(&report);
+    test_kinds(&report);
+    assert!(iterator.next().is_none());
+}
+
+#[test]
+#[cfg(feature = "futures")]
+fn attach_stream() {
+    let stream = create_stream()
+        .change_context(ContextA(0))
+        .attach(AttachmentA)
+        .change_context_lazy(|| ContextB(0))
+        .attach_lazy(|| AttachmentB);
+
+    let mut iterator = futures::executor::block_on_stream(stream);
+
+    assert!(iterator.next().expect("Not an item").is_ok());
+    let report = iterator
+        .next()
+        .expect("Not an item")
+        .expect_err("Not an error");
+    test_messages(&report);
+    test_kinds(&report);
+    assert!(iterator.next().is_none());
+}
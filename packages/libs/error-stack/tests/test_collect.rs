@@ -0,0 +1,78 @@
+#![cfg_attr(nightly, feature(provide_any))]
+#![cfg_attr(all(nightly, feature = "std"), feature(error_generic_member_access))]
+
+mod common;
+
+use core::num::NonZeroUsize;
+
+use common::*;
+use error_stack::{iter::ResultIteratorExt, Report, Result};
+
+fn results(errors: &[bool]) -> impl Iterator<Item = Result<usize, RootError>> + '_ {
+    errors.iter().enumerate().map(|(index, &error)| {
+        if error {
+            Err(create_report().attach_printable(PrintableC(index as u32)))
+        } else {
+            Ok(index)
+        }
+    })
+}
+
+fn printables(report: &Report<RootError>) -> Vec<u32> {
+    report
+        .frames()
+        .filter_map(|frame| frame.downcast_ref::<PrintableC>())
+        .map(|printable| printable.0)
+        .collect()
+}
+
+#[test]
+fn collect_ok() {
+    let values = results(&[false, false, false])
+        .try_collect_reports()
+        .expect("no error should have occurred");
+
+    assert_eq!(values, [0, 1, 2]);
+}
+
+#[test]
+fn collect_empty() {
+    let values = results(&[])
+        .try_collect_reports()
+        .expect("no error should have occurred");
+
+    assert!(values.is_empty());
+}
+
+#[test]
+fn collect_errors() {
+    let report = results(&[false, true, false, true, true])
+        .try_collect_reports()
+        .expect_err("errors should have occurred");
+
+    assert_eq!(report.current_frames().len(), 3);
+    assert_eq!(printables(&report), [1, 3, 4]);
+}
+
+#[test]
+fn collect_errors_bounded() {
+    let mut consumed = 0;
+
+    let report = results(&[true, false, true, true, false])
+        .inspect(|_| consumed += 1)
+        .try_collect_reports_bounded(NonZeroUsize::new(2).expect("2 is not zero"))
+        .expect_err("errors should have occurred");
+
+    assert_eq!(report.current_frames().len(), 2);
+    assert_eq!(printables(&report), [0, 2]);
+    assert_eq!(consumed, 3);
+}
+
+#[test]
+fn collect_errors_bounded_one() {
+    let report = results(&[true, true])
+        .try_collect_reports_bounded(NonZeroUsize::new(1).expect("1 is not zero"))
+        .expect_err("errors should have occurred");
+
+    assert_eq!(printables(&report), [0]);
+}
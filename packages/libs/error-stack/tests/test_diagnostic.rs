@@ -0,0 +1,169 @@
+#![cfg_attr(nightly, feature(provide_any))]
+// can be considered safe, because we only check the output, which in itself does not use **any**
+// unsafe code.
+#![cfg(not(miri))]
+#![cfg_attr(all(nightly, feature = "std"), feature(error_generic_member_access))]
+
+mod common;
+
+use common::*;
+use error_stack::diagnostic::{Code, Help, Label, SourceSnippet};
+use insta::assert_snapshot;
+
+const MANIFEST: &str = r#"{
+    "name": "warmup",
+    "steps": "ten",
+    "seed": 42,
+	"runs": -1
+}
+"#;
+
+#[cfg(feature = "pretty-print")]
+fn setup_color() {
+    owo_colors::set_override(false);
+}
+
+#[cfg(not(feature = "pretty-print"))]
+fn setup_color() {}
+
+fn prepare() -> impl Drop {
+    std::env::set_var("RUST_LIB_BACKTRACE", "0");
+    setup_color();
+
+    let mut settings = insta::Settings::clone_current();
+    #[cfg(feature = "pretty-print")]
+    settings.set_snapshot_suffix("pretty-print");
+
+    settings.bind_to_scope()
+}
+
+fn find(pattern: &str) -> core::ops::Range<usize> {
+    let start = MANIFEST
+        .find(pattern)
+        .expect("pattern should be in the manifest");
+    start..start + pattern.len()
+}
+
+#[test]
+fn snippet_without_labels() {
+    let snippet = SourceSnippet::new("fn main() {\n\tprintln!();\n}\n");
+
+    assert_eq!(
+        snippet.to_string(),
+        "  |\n1 | fn main() {\n2 |     println!();\n3 | }"
+    );
+}
+
+#[test]
+fn snippet_label() {
+    let snippet = SourceSnippet::new(MANIFEST)
+        .with_origin("experiments.json")
+        .with_label(Label::new(find(r#""ten""#)).with_message("expected a number"));
+
+    assert_eq!(
+        snippet.to_string(),
+        r#" --> experiments.json:3:14
+  |
+3 |     "steps": "ten",
+  |              ^^^^^ expected a number"#
+    );
+}
+
+#[test]
+fn snippet_gap_and_tabs() {
+    let snippet = SourceSnippet::new(MANIFEST)
+        .with_label(Label::new(find("-1")).with_message("must be positive"))
+        .with_label(Label::new(find(r#""name""#)));
+
+    assert_eq!(
+        snippet.to_string(),
+        r#"  |
+2 |     "name": "warmup",
+  |     ^^^^^^
+...
+5 |     "runs": -1
+  |             ^^ must be positive"#
+    );
+}
+
+#[test]
+fn snippet_multiline_label() {
+    let start = MANIFEST
+        .find(r#""seed""#)
+        .expect("seed should be in the manifest");
+    let end = MANIFEST.find("-1").expect("runs should be in the manifest") + 2;
+
+    let snippet = SourceSnippet::new(MANIFEST)
+        .with_origin("experiments.json")
+        .with_label(Label::new(start..end).with_message("conflicting settings"));
+
+    assert_eq!(
+        snippet.to_string(),
+        r#" --> experiments.json:4:5
+  |
+4 |     "seed": 42,
+  |     ^^^^^^^^^^^
+5 |     "runs": -1
+  | ^^^^^^^^^^^^^^ conflicting settings"#
+    );
+}
+
+#[test]
+fn snippet_out_of_bounds() {
+    let snippet = SourceSnippet::new("héllo")
+        .with_label(Label::new(2..100).with_message("truncated"))
+        .with_label(Label::new(200..300).with_message("end of input"));
+
+    assert_eq!(
+        snippet.to_string(),
+        "  |\n1 | héllo\n  |  ^^^^ truncated\n  |      ^ end of input"
+    );
+}
+
+#[test]
+fn accessors() {
+    let label = Label::new(3..5).with_message("here");
+    assert_eq!(label.span(), 3..5);
+    assert_eq!(label.message(), Some("here"));
+    assert_eq!(label.to_string(), "here at 3..5");
+
+    let snippet = SourceSnippet::new("source")
+        .with_origin("origin")
+        .with_label(label.clone());
+    assert_eq!(snippet.source(), "source");
+    assert_eq!(snippet.origin(), Some("origin"));
+    assert_eq!(snippet.labels(), [label]);
+
+    assert_eq!(Code::new("E0042").as_str(), "E0042");
+    assert_eq!(Help::new("try again").message(), "try again");
+}
+
+#[test]
+fn debug() {
+    let _guard = prepare();
+
+    let report = create_report()
+        .attach(Code::new("E0042"))
+        .attach(
+            SourceSnippet::new(MANIFEST)
+                .with_origin("experiments.json")
+                .with_label(Label::new(find(r#""ten""#)).with_message("expected a number")),
+        )
+        .attach_printable(Help::new("the number of steps needs to be an integer"));
+
+    assert_snapshot!(format!("{report:?}"));
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "hooks"))]
+fn hook() {
+    let _guard = prepare();
+
+    error_stack::Report::install_debug_hook::<Help>(|help, context| {
+        context.push_body(format!("try: {}", help.message()));
+    });
+
+    let report = create_report().attach(Help::new("turning it off and on again"));
+
+    assert_snapshot!(format!("{report:?}"));
+}
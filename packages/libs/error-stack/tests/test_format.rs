@@ -0,0 +1,144 @@
+#![cfg_attr(nightly, feature(provide_any))]
+#![cfg_attr(all(nightly, feature = "std"), feature(error_generic_member_access))]
+
+mod common;
+
+use common::*;
+use error_stack::{Format, Report};
+
+fn prepare() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "0");
+}
+
+fn create_multiple_sources() -> Report<ContextA> {
+    let mut report = create_report().attach_printable(PrintableC(1));
+    report.extend_one(create_report().attach_printable(PrintableC(2)));
+
+    report
+        .attach_printable(PrintableC(3))
+        .change_context(ContextA(2))
+        .attach_printable(PrintableC(4))
+}
+
+#[test]
+fn compact() {
+    prepare();
+
+    let report = create_report()
+        .attach_printable(PrintableA(1))
+        .attach(AttachmentA(1))
+        .attach_printable(PrintableB(2))
+        .change_context(ContextA(3))
+        .attach_printable(PrintableB(4));
+
+    assert_eq!(
+        report.format(Format::Compact).to_string(),
+        "context A: printable B; caused by root error: printable A, printable B"
+    );
+}
+
+#[test]
+fn compact_multiple_sources() {
+    prepare();
+
+    let report = create_multiple_sources();
+
+    assert_eq!(
+        report.format(Format::Compact).to_string(),
+        "context A: printable C: 4; caused by [root error: printable C: 1, printable C: 3], [root \
+         error: printable C: 2, printable C: 3]"
+    );
+}
+
+#[test]
+fn compact_multiline() {
+    prepare();
+
+    let report = create_report().attach_printable("first line\nsecond line");
+
+    assert_eq!(
+        report.format(Format::Compact).to_string(),
+        "root error: first line second line"
+    );
+}
+
+#[test]
+fn json_lines() {
+    prepare();
+
+    let report = create_report()
+        .attach_printable(PrintableA(1))
+        .attach(AttachmentA(1))
+        .change_context(ContextA(2));
+
+    assert_eq!(
+        report.format(Format::JsonLines).to_string(),
+        concat!(
+            r#"[{"context":"context A","attachments":["tests/test_format.rs:72:10"],"sources":"#,
+            r#"[{"context":"root error","attachments":["tests/common.rs:4:5","printable A","#,
+            r#""test_format::common::AttachmentA"],"sources":[]}]}]"#
+        )
+    );
+}
+
+#[test]
+fn json_lines_multiple_sources() {
+    prepare();
+
+    let report = create_multiple_sources();
+    let output = report.format(Format::JsonLines).to_string();
+
+    assert!(!output.contains('\n'));
+
+    let value: serde_json::Value = serde_json::from_str(&output).expect("should be valid JSON");
+    assert_eq!(value[0]["context"], "context A");
+    assert_eq!(value[0]["sources"].as_array().map(Vec::len), Some(2));
+    assert_eq!(value[0]["sources"][0]["attachments"][1], "printable C: 1");
+    assert_eq!(value[0]["sources"][1]["attachments"][1], "printable C: 2");
+}
+
+#[test]
+fn json_lines_escape() {
+    prepare();
+
+    let report = create_report().attach_printable("\"quoted\"\n\tback\\slash\u{1}");
+    let output = report.format(Format::JsonLines).to_string();
+
+    assert!(output.contains(r#""\"quoted\"\n\tback\\slash\u0001""#));
+
+    let value: serde_json::Value = serde_json::from_str(&output).expect("should be valid JSON");
+    assert_eq!(value[0]["attachments"][1], "\"quoted\"\n\tback\\slash\u{1}");
+}
+
+#[test]
+fn tree() {
+    prepare();
+
+    let report = create_report().attach_printable(PrintableA(1));
+
+    assert_eq!(
+        report.format(Format::Tree).to_string(),
+        format!("{report:?}")
+    );
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "hooks"))]
+fn set_debug_format() {
+    prepare();
+
+    let report = create_report().attach_printable(PrintableA(1));
+
+    Report::set_debug_format(Format::Compact);
+    assert_eq!(format!("{report:?}"), "root error: printable A");
+    assert_ne!(
+        report.format(Format::Tree).to_string(),
+        format!("{report:?}")
+    );
+
+    Report::set_debug_format(Format::Tree);
+    assert_eq!(
+        report.format(Format::Tree).to_string(),
+        format!("{report:?}")
+    );
+}
@@ -0,0 +1,129 @@
+#![cfg(all(feature = "std", feature = "remote"))]
+// can be considered safe, because we only check the output, which in itself does not use **any**
+// unsafe code.
+#![cfg(not(miri))]
+#![cfg_attr(all(nightly, feature = "std"), feature(error_generic_member_access))]
+#![cfg_attr(nightly, feature(provide_any))]
+
+use error_stack::{
+    remote::{RemoteContext, RemoteFrameKind, RemoteReport},
+    Report,
+};
+use serde::Serialize;
+
+use crate::common::{create_report, ContextA, ContextB, PrintableA, PrintableC, RootError};
+
+mod common;
+
+fn prepare() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "0");
+}
+
+fn roundtrip<C>(report: &Report<C>) -> RemoteReport {
+    let payload = serde_json::to_string(&RemoteReport::new(report)).expect("could not serialize");
+
+    serde_json::from_str(&payload).expect("could not deserialize")
+}
+
+#[test]
+fn debug_matches_report() {
+    prepare();
+
+    let mut report = create_report().attach_printable(PrintableC(1));
+    report.extend_one(create_report().attach_printable(PrintableC(2)));
+
+    let report = report
+        .attach_printable(PrintableA(2))
+        .change_context(ContextA(2))
+        .attach_printable(PrintableC(3));
+
+    let remote = roundtrip(&report);
+
+    assert_eq!(format!("{remote:?}"), format!("{report:?}"));
+    assert_eq!(format!("{remote:#?}"), format!("{report:#?}"));
+    assert_eq!(remote.to_string(), report.to_string());
+}
+
+#[test]
+fn opaque_attachments() {
+    #[derive(Serialize)]
+    struct Suggestion(&'static str);
+
+    struct Opaque;
+
+    prepare();
+
+    Report::install_serde_hook::<Suggestion>();
+
+    let report = create_report()
+        .attach(Opaque)
+        .attach(Suggestion("try again"));
+
+    let remote = roundtrip(&report);
+    let frames = remote.current_frames();
+
+    assert_eq!(frames.len(), 1);
+    let attachment = match frames[0].kind() {
+        RemoteFrameKind::Attachment(attachment) => attachment,
+        kind => panic!("expected an attachment, got {kind:?}"),
+    };
+    assert_eq!(attachment.message(), None);
+    assert_eq!(attachment.value(), Some(&serde_json::json!("try again")));
+
+    // `Opaque` is skipped, as there is no hook installed, the next frame is the location
+    let sources = frames[0].sources();
+    assert_eq!(sources.len(), 1);
+    let location = match sources[0].kind() {
+        RemoteFrameKind::Location(location) => location,
+        kind => panic!("expected a location, got {kind:?}"),
+    };
+    assert_eq!(location.file(), "tests/common.rs");
+    assert_eq!(location.line(), 4);
+}
+
+#[test]
+fn serialized_context() {
+    #[derive(Debug, Serialize)]
+    struct Code(i32);
+
+    prepare();
+
+    Report::install_serde_hook_with::<ContextB, _>(|ContextB(code)| Code(*code));
+
+    let report = create_report().change_context(ContextB(404));
+    let report = Report::from(roundtrip(&report));
+
+    let context: &RemoteContext = report.current_context();
+    assert_eq!(context.message(), "context B");
+    assert_eq!(context.value(), Some(&serde_json::json!(404)));
+}
+
+#[test]
+fn source_of_local_report() {
+    prepare();
+
+    let remote = roundtrip(&create_report());
+    let report = Report::from(remote).change_context(ContextA(0));
+
+    assert!(report.contains::<RemoteContext>());
+    assert!(!report.contains::<RootError>());
+    assert_eq!(
+        report
+            .frames()
+            .filter_map(|frame| frame.downcast_ref::<RemoteContext>())
+            .map(RemoteContext::message)
+            .collect::<Vec<_>>(),
+        ["root error"]
+    );
+}
+
+#[test]
+fn invalid() {
+    assert!(serde_json::from_str::<RemoteReport>("[]").is_err());
+    assert!(
+        serde_json::from_str::<RemoteReport>(
+            r#"[{"kind": {"attachment": {"message": "dangling", "value": null}}, "sources": []}]"#
+        )
+        .is_err()
+    );
+}
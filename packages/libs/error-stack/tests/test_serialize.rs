@@ -7,16 +7,45 @@
 #![cfg_attr(all(nightly, feature = "std"), feature(error_generic_member_access))]
 #![cfg_attr(nightly, feature(provide_any))]
 
+use core::fmt;
+
+use error_stack::{Context, Report};
 use insta::assert_ron_snapshot;
+use serde::Serialize;
 
 use crate::common::{create_report, ContextA, PrintableA, PrintableC};
 
 mod common;
 
+#[derive(Debug, Serialize)]
+struct ConfigError {
+    path: &'static str,
+    line: u32,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("invalid configuration")
+    }
+}
+
+impl Context for ConfigError {}
+
+#[derive(Serialize)]
+struct Suggestion(&'static str);
+
+struct Secret(&'static str);
+
+struct Opaque;
+
 fn prepare() -> impl Drop {
     std::env::set_var("RUST_LIB_BACKTRACE", "0");
 
-    let settings = insta::Settings::clone_current();
+    let mut settings = insta::Settings::clone_current();
+
+    // locations are serialized through the builtin hook, their line and column change with every
+    // edit of the tests
+    settings.add_filter(r#""[^"\s]+\.rs:\d+:\d+""#, r#""[location]""#);
 
     settings.bind_to_scope()
 }
@@ -69,3 +98,40 @@ fn multiple_sources_at_root() {
 
     assert_ron_snapshot!(a);
 }
+
+#[test]
+fn hook_context() {
+    let _guard = prepare();
+
+    Report::install_serde_hook::<ConfigError>();
+
+    let report = create_report().change_context(ConfigError {
+        path: "config.json",
+        line: 42,
+    });
+
+    assert_ron_snapshot!(report);
+}
+
+#[test]
+fn hook_attachment() {
+    let _guard = prepare();
+
+    Report::install_serde_hook::<Suggestion>();
+    Report::install_serde_hook_with::<Secret, _>(|Secret(value)| "*".repeat(value.len()));
+
+    let report = create_report()
+        .attach(Suggestion("try again"))
+        .attach(Secret("hunter2"));
+
+    assert_ron_snapshot!(report);
+}
+
+#[test]
+fn opaque_attachment() {
+    let _guard = prepare();
+
+    let report = create_report().attach(Opaque);
+
+    assert_ron_snapshot!(report);
+}
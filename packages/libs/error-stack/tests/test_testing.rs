@@ -0,0 +1,96 @@
+#![cfg_attr(nightly, feature(provide_any))]
+// can be considered safe, because we only check the output, which in itself does not use **any**
+// unsafe code.
+#![cfg(not(miri))]
+#![cfg_attr(all(nightly, feature = "std"), feature(error_generic_member_access))]
+
+mod common;
+
+use common::*;
+use error_stack::{assert_report_contains, testing::render_normalized, Report};
+use insta::assert_snapshot;
+
+fn prepare() -> impl Drop {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+
+    let mut settings = insta::Settings::clone_current();
+    #[cfg(feature = "pretty-print")]
+    settings.set_snapshot_suffix("pretty-print");
+
+    settings.bind_to_scope()
+}
+
+fn create_nested_report() -> Report<ContextA> {
+    let mut report = create_report().attach_printable(PrintableA(0));
+    report.extend_one(create_report().attach(AttachmentA(1)));
+
+    report
+        .change_context(ContextA(2))
+        .attach_printable(format!("stored at {:p}", &report_address()))
+        .attach_printable(PrintableB(3))
+}
+
+fn report_address() -> u64 {
+    0
+}
+
+#[test]
+fn contains() {
+    let report = create_nested_report();
+
+    assert_report_contains!(report, Context = ContextA);
+    assert_report_contains!(
+        report,
+        Context = RootError,
+        Attachment = AttachmentA,
+        context = "context A",
+        attachment = "printable A",
+        attachment = String::from("printable B"),
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "report does not contain a context of type `test_testing::common::ContextB`"
+)]
+fn contains_context_type() {
+    assert_report_contains!(create_nested_report(), Context = ContextB);
+}
+
+#[test]
+#[should_panic(expected = "report does not contain an attachment of type")]
+fn contains_attachment_type() {
+    // `RootError` is a context, not an attachment
+    assert_report_contains!(create_nested_report(), Attachment = RootError);
+}
+
+#[test]
+#[should_panic(expected = "report does not contain an attachment with the message \"context A\"")]
+fn contains_attachment_message() {
+    assert_report_contains!(create_nested_report(), attachment = "context A");
+}
+
+#[test]
+fn normalized() {
+    let _guard = prepare();
+
+    let report = create_nested_report();
+    let output = render_normalized(&report);
+
+    assert!(!output.contains("tests/"));
+    assert!(!output.contains("backtrace"));
+    assert_snapshot!(output);
+}
+
+#[test]
+#[cfg(feature = "pretty-print")]
+fn normalized_colors() {
+    let _guard = prepare();
+
+    owo_colors::set_override(true);
+    let output = render_normalized(&create_nested_report());
+    owo_colors::set_override(false);
+
+    assert!(!output.contains('\x1b'));
+    assert!(output.contains("context A"));
+}
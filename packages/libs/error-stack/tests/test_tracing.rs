@@ -0,0 +1,156 @@
+#![cfg(all(feature = "std", feature = "tracing"))]
+#![cfg_attr(nightly, feature(provide_any))]
+#![cfg_attr(all(nightly, feature = "std"), feature(error_generic_member_access))]
+
+mod common;
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use common::*;
+use tracing::{
+    field::{Field, Visit},
+    Event, Level, Subscriber,
+};
+use tracing_subscriber::{layer::Context, prelude::*, registry::LookupSpan, Layer, Registry};
+
+#[derive(Debug, Default)]
+struct RecordedEvent {
+    level: Option<Level>,
+    span: Option<String>,
+    fields: BTreeMap<String, String>,
+}
+
+impl Visit for RecordedEvent {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.fields
+            .insert(field.name().to_owned(), format!("{value:?}"));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.fields
+            .insert(field.name().to_owned(), value.to_owned());
+    }
+}
+
+#[derive(Clone, Default)]
+struct RecordingLayer {
+    events: Arc<Mutex<Vec<RecordedEvent>>>,
+}
+
+impl<S> Layer<S> for RecordingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &Event<'_>, context: Context<'_, S>) {
+        let mut recorded = RecordedEvent {
+            level: Some(*event.metadata().level()),
+            span: context.event_span(event).map(|span| span.name().to_owned()),
+            ..RecordedEvent::default()
+        };
+        event.record(&mut recorded);
+
+        self.events.lock().expect("poisoned lock").push(recorded);
+    }
+}
+
+fn record(callback: impl FnOnce()) -> Vec<RecordedEvent> {
+    let layer = RecordingLayer::default();
+    let events = Arc::clone(&layer.events);
+
+    tracing::subscriber::with_default(Registry::default().with(layer), callback);
+
+    let mut events = events.lock().expect("poisoned lock");
+    core::mem::take(&mut *events)
+}
+
+fn prepare() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "0");
+}
+
+#[test]
+fn event() {
+    prepare();
+
+    let report = create_report()
+        .attach_printable(PrintableA(1))
+        .change_context(ContextA(2))
+        .attach_printable(PrintableB(3));
+
+    let events = record(|| report.record_event(Level::WARN));
+
+    assert_eq!(events.len(), 1);
+    let event = &events[0];
+    assert_eq!(event.level, Some(Level::WARN));
+    assert_eq!(event.fields["message"], "context A");
+    assert_eq!(event.fields["context"], "context A");
+    assert_eq!(event.fields["contexts"], r#"["context A", "root error"]"#);
+    assert_eq!(
+        event.fields["attachments"],
+        r#"["printable B", "printable A"]"#
+    );
+    assert_eq!(
+        event.fields["locations"],
+        r#"["tests/test_tracing.rs:80:10", "tests/common.rs:4:5"]"#
+    );
+    assert_eq!(event.fields["backtrace"], "false");
+}
+
+#[test]
+fn frame_events() {
+    prepare();
+
+    let report = create_report()
+        .attach_printable(PrintableA(1))
+        .change_context(ContextA(2));
+
+    let events = record(|| report.record_frame_events(Level::ERROR));
+
+    assert!(events.iter().all(|event| event.level == Some(Level::ERROR)));
+    assert!(
+        events
+            .iter()
+            .all(|event| event.span.as_deref() == Some("report"))
+    );
+
+    let frames = events[1..]
+        .iter()
+        .map(|event| {
+            (
+                event.fields["kind"].as_str(),
+                event.fields["message"].as_str(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(frames, [
+        ("location", "tests/test_tracing.rs:108:10"),
+        ("context", "context A"),
+        ("attachment", "printable A"),
+        ("location", "tests/common.rs:4:5"),
+        ("context", "root error"),
+    ]);
+}
+
+#[test]
+#[cfg(feature = "spantrace")]
+fn exception() {
+    prepare();
+
+    let report = create_report().change_context(ContextA(2));
+
+    let events = record(|| report.record_exception());
+
+    assert_eq!(events.len(), 1);
+    let event = &events[0];
+    assert_eq!(event.level, Some(Level::ERROR));
+    assert_eq!(event.fields["message"], "exception");
+    assert_eq!(
+        event.fields["exception.type"],
+        core::any::type_name::<ContextA>()
+    );
+    assert_eq!(event.fields["exception.message"], "context A");
+}